@@ -52,7 +52,7 @@ fn main() {
 
             example_add => compact(serde_json::json!({
                 "success": true,
-                "data": {"name": "code-review", "installed_to": ".agents/skills/code-review/", "targets": ["claude", "cursor"]}
+                "data": {"name": "code-review", "installed_to": ".agents/skills/code-review/", "targets": ["claude", "cursor"], "dependencies": []}
             })),
 
             example_install_all => compact(serde_json::json!({
@@ -62,7 +62,7 @@ fn main() {
 
             example_remove => compact(serde_json::json!({
                 "success": true,
-                "data": {"removed": ["test-skill"]}
+                "data": {"removed": ["test-skill"], "pruned": []}
             })),
 
             example_skill_list => compact(serde_json::json!({
@@ -84,17 +84,32 @@ fn main() {
                 }
             })),
 
+            example_outdated => compact(serde_json::json!({
+                "success": false,
+                "data": {"skills": [{"name": "code-review", "project": ". (root)", "current": "v1.1.0", "latest": "v1.2.0", "pinned": false, "constraint": "version ^1.1", "outdated": true, "error": null}], "outdated": 1, "failed": 0}
+            })),
+
             example_validate => compact(serde_json::json!({
                 "success": true,
                 "data": {"skills": [{"path": "test-skill/SKILL.md", "name": "test-skill", "findings": [], "errors": 0, "warnings": 0, "infos": 0}], "total_errors": 0, "total_warnings": 0, "total_infos": 0}
             })),
 
+            example_verify => compact(serde_json::json!({
+                "success": true,
+                "data": {"skills": [{"name": "code-review", "project": ". (root)", "ok": true, "modified": [], "missing": [], "extra": [], "problems": []}], "verified": 1, "failed": 0}
+            })),
+
+            example_lock_check => compact(serde_json::json!({
+                "success": false,
+                "data": {"up_to_date": false, "drift": [{"name": "code-review", "project": ". (root)", "problem": "'code-review' is not in Ion.lock"}]}
+            })),
+
             example_config => compact(serde_json::json!({
                 "success": true, "data": {"targets.claude": ".claude/skills", "targets.cursor": ".cursor/skills"}
             })),
 
             example_gc => compact(serde_json::json!({
-                "success": true, "data": {"dry_run": true, "removed": []}
+                "success": true, "data": {"dry_run": true, "removed": [], "snapshots": []}
             })),
 
             example_self_info => compact(serde_json::json!({
@@ -5,6 +5,7 @@ use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
 
+use crate::catalog::Catalog;
 use crate::installer;
 use crate::source::SkillSource;
 use crate::{Error, Result, git};
@@ -37,28 +38,24 @@ pub struct AgentsLockEntry {
     pub updated_at: String, // ISO 8601, stored as plain string
 }
 
-/// Mapping of target names to the agent instructions filename that needs a symlink.
-/// Only targets whose tools don't read AGENTS.md natively need an entry here.
-const AGENT_FILE_SYMLINKS: &[(&str, &str)] = &[("claude", "CLAUDE.md")];
-
-/// For each configured target that has an entry in AGENT_FILE_SYMLINKS,
-/// create a symlink (e.g. CLAUDE.md -> AGENTS.md) if AGENTS.md exists
-/// and the symlink doesn't.
+/// For each configured target whose tool in `catalog` reads its own
+/// instructions file instead of AGENTS.md, create a symlink (e.g.
+/// CLAUDE.md -> AGENTS.md) if AGENTS.md exists and the symlink doesn't.
 ///
 /// Symlinks are only created for targets configured in [options.targets].
 /// If a target filename already exists as a regular file or a symlink
 /// pointing elsewhere, a warning is printed and it is skipped.
-pub fn ensure_agent_symlinks(project_dir: &Path, targets: &BTreeMap<String, String>) -> Result<()> {
+pub fn ensure_agent_symlinks(
+    project_dir: &Path,
+    targets: &BTreeMap<String, String>,
+    catalog: &Catalog,
+) -> Result<()> {
     let agents_md = project_dir.join("AGENTS.md");
     if !agents_md.exists() {
         return Ok(());
     }
 
-    for (target_name, symlink_filename) in AGENT_FILE_SYMLINKS {
-        if !targets.contains_key(*target_name) {
-            continue;
-        }
-
+    for (_, symlink_filename) in catalog.instruction_files(targets) {
         let symlink_path = project_dir.join(symlink_filename);
 
         match std::fs::symlink_metadata(&symlink_path) {
@@ -259,12 +256,14 @@ pub fn fetch_template(
 fn fetch_source_base(source: &SkillSource) -> Result<PathBuf> {
     if source.is_git_based() {
         let url = source.git_url()?;
-        let repo_hash = format!("{:x}", installer::hash_simple(&url));
+        let repo_hash = installer::cache_key(&url);
         let repo_dir = installer::data_dir().join(&repo_hash);
-        git::clone_or_fetch(&url, &repo_dir)?;
+        git::sync_repo(&url, &repo_dir)?;
         if let Some(ref rev) = source.rev {
             git::checkout(&repo_dir, rev)?;
         }
+        // Templates are read from the clone itself, not a snapshot
+        git::full_checkout(&repo_dir)?;
         Ok(repo_dir)
     } else if source.is_path() {
         let path = PathBuf::from(&source.source);
@@ -296,7 +295,7 @@ mod tests {
         let mut targets = BTreeMap::new();
         targets.insert("claude".to_string(), ".claude/skills".to_string());
 
-        ensure_agent_symlinks(project.path(), &targets).unwrap();
+        ensure_agent_symlinks(project.path(), &targets, &Catalog::builtin()).unwrap();
 
         let symlink = project.path().join("CLAUDE.md");
         assert!(symlink.exists(), "CLAUDE.md symlink should exist");
@@ -310,8 +309,23 @@ mod tests {
         let mut targets = BTreeMap::new();
         targets.insert("claude".to_string(), ".claude/skills".to_string());
 
-        ensure_agent_symlinks(project.path(), &targets).unwrap();
+        ensure_agent_symlinks(project.path(), &targets, &Catalog::builtin()).unwrap();
+
+        assert!(!project.path().join("CLAUDE.md").exists());
+    }
+
+    #[test]
+    fn catalog_tools_get_their_instruction_file() {
+        let project = tempfile::tempdir().unwrap();
+        std::fs::write(project.path().join("AGENTS.md"), "# Agents\n").unwrap();
+
+        let mut targets = BTreeMap::new();
+        targets.insert("gemini".to_string(), ".gemini/skills".to_string());
+
+        ensure_agent_symlinks(project.path(), &targets, &Catalog::builtin()).unwrap();
 
+        let link = std::fs::read_link(project.path().join("GEMINI.md")).unwrap();
+        assert_eq!(link, Path::new("AGENTS.md"));
         assert!(!project.path().join("CLAUDE.md").exists());
     }
 
@@ -323,7 +337,7 @@ mod tests {
         let mut targets = BTreeMap::new();
         targets.insert("cursor".to_string(), ".cursor/skills".to_string());
 
-        ensure_agent_symlinks(project.path(), &targets).unwrap();
+        ensure_agent_symlinks(project.path(), &targets, &Catalog::builtin()).unwrap();
 
         assert!(!project.path().join("CLAUDE.md").exists());
     }
@@ -337,7 +351,7 @@ mod tests {
         let mut targets = BTreeMap::new();
         targets.insert("claude".to_string(), ".claude/skills".to_string());
 
-        ensure_agent_symlinks(project.path(), &targets).unwrap();
+        ensure_agent_symlinks(project.path(), &targets, &Catalog::builtin()).unwrap();
 
         let meta = std::fs::symlink_metadata(project.path().join("CLAUDE.md")).unwrap();
         assert!(!meta.is_symlink());
@@ -355,7 +369,7 @@ mod tests {
         let mut targets = BTreeMap::new();
         targets.insert("claude".to_string(), ".claude/skills".to_string());
 
-        ensure_agent_symlinks(project.path(), &targets).unwrap();
+        ensure_agent_symlinks(project.path(), &targets, &Catalog::builtin()).unwrap();
 
         let target = std::fs::read_link(project.path().join("CLAUDE.md")).unwrap();
         assert_eq!(target, std::path::Path::new("OTHER.md"));
@@ -369,8 +383,8 @@ mod tests {
         let mut targets = BTreeMap::new();
         targets.insert("claude".to_string(), ".claude/skills".to_string());
 
-        ensure_agent_symlinks(project.path(), &targets).unwrap();
-        ensure_agent_symlinks(project.path(), &targets).unwrap();
+        ensure_agent_symlinks(project.path(), &targets, &Catalog::builtin()).unwrap();
+        ensure_agent_symlinks(project.path(), &targets, &Catalog::builtin()).unwrap();
 
         let symlink = project.path().join("CLAUDE.md");
         assert!(symlink.symlink_metadata().unwrap().is_symlink());
@@ -500,7 +514,7 @@ mod tests {
         let mut targets = BTreeMap::new();
         targets.insert("claude".to_string(), ".claude/skills".to_string());
 
-        ensure_agent_symlinks(project.path(), &targets).unwrap();
+        ensure_agent_symlinks(project.path(), &targets, &Catalog::builtin()).unwrap();
 
         let meta = std::fs::symlink_metadata(project.path().join("CLAUDE.md")).unwrap();
         assert!(
@@ -522,7 +536,7 @@ mod tests {
         let mut targets = BTreeMap::new();
         targets.insert("claude".to_string(), ".claude/skills".to_string());
 
-        ensure_agent_symlinks(project.path(), &targets).unwrap();
+        ensure_agent_symlinks(project.path(), &targets, &Catalog::builtin()).unwrap();
 
         let meta = std::fs::symlink_metadata(project.path().join("CLAUDE.md")).unwrap();
         assert!(
@@ -539,7 +553,7 @@ mod tests {
         let mut targets = BTreeMap::new();
         targets.insert("claude".to_string(), ".claude/skills".to_string());
 
-        ensure_agent_symlinks(project.path(), &targets).unwrap();
+        ensure_agent_symlinks(project.path(), &targets, &Catalog::builtin()).unwrap();
 
         let gitignore = std::fs::read_to_string(project.path().join(".gitignore")).unwrap();
         assert!(gitignore.contains("CLAUDE.md"));
@@ -556,7 +570,7 @@ mod tests {
         let mut targets = BTreeMap::new();
         targets.insert("claude".to_string(), ".claude/skills".to_string());
 
-        ensure_agent_symlinks(project.path(), &targets).unwrap();
+        ensure_agent_symlinks(project.path(), &targets, &Catalog::builtin()).unwrap();
 
         let gitignore = std::fs::read_to_string(project.path().join(".gitignore")).unwrap();
         assert!(gitignore.contains("CLAUDE.md"));
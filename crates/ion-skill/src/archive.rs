@@ -0,0 +1,405 @@
+//! Archive skill sources — download `.tar.gz`/`.zip` releases, verify their SHA-256 digest, and extract them into the cache.
+
+use std::path::{Path, PathBuf};
+
+use crate::installer::data_dir;
+use crate::source::{SkillSource, SkillSourceKind};
+use crate::{Error, Result, credentials, offline};
+
+/// Directory holding extracted archives, one subdirectory per digest.
+pub fn archives_dir() -> PathBuf {
+    data_dir().join("archives")
+}
+
+/// Download an archive source and return its SHA-256 digest.
+///
+/// The archive is extracted into the cache, so a later [`fetch`] of the
+/// source pinned to this digest does not download it again. If the source
+/// declares a `sha256`, the download must match it.
+pub fn resolve_digest(source: &SkillSource) -> Result<String> {
+    let (expected, _) = archive_fields(source)?;
+    match expected {
+        Some(digest) if content_dir(digest).exists() => Ok(digest.to_string()),
+        _ => download(&source.source, expected),
+    }
+}
+
+/// Fetch an archive source and return its extracted root directory, with
+/// `strip-components` applied.
+///
+/// Archives are cached by digest: a source pinned to a digest that has been
+/// extracted before is served from the cache, even offline.
+pub fn fetch(source: &SkillSource) -> Result<PathBuf> {
+    let (_, strip_components) = archive_fields(source)?;
+    let digest = resolve_digest(source)?;
+    if strip_components == 0 {
+        return Ok(content_dir(&digest));
+    }
+
+    let stripped = archives_dir()
+        .join(&digest)
+        .join(format!("strip-{strip_components}"));
+    if !stripped.exists() {
+        let staging = staging_dir()?;
+        strip_into(&content_dir(&digest), staging.path(), strip_components)?;
+        move_into_place(staging, &stripped)?;
+    }
+    Ok(stripped)
+}
+
+fn archive_fields(source: &SkillSource) -> Result<(Option<&str>, usize)> {
+    match &source.kind {
+        SkillSourceKind::Archive {
+            sha256,
+            strip_components,
+        } => Ok((sha256.as_deref(), *strip_components)),
+        kind => Err(Error::Source(format!(
+            "Source kind {kind:?} is not an archive"
+        ))),
+    }
+}
+
+fn content_dir(digest: &str) -> PathBuf {
+    archives_dir().join(digest).join("content")
+}
+
+/// The extracted directory for an archive digest, if it is already cached.
+pub(crate) fn cached(digest: &str) -> Option<PathBuf> {
+    Some(content_dir(digest)).filter(|dir| dir.exists())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Format {
+    TarGz,
+    Zip,
+}
+
+fn format_of(url: &str) -> Result<Format> {
+    let file = url
+        .split(['?', '#'])
+        .next()
+        .unwrap_or(url)
+        .to_ascii_lowercase();
+    if file.ends_with(".tar.gz") || file.ends_with(".tgz") {
+        Ok(Format::TarGz)
+    } else if file.ends_with(".zip") {
+        Ok(Format::Zip)
+    } else {
+        Err(Error::Source(format!(
+            "Unsupported archive format for {url}: expected .tar.gz, .tgz or .zip"
+        )))
+    }
+}
+
+/// Download `url`, check it against `expected`, and extract it under its digest.
+fn download(url: &str, expected: Option<&str>) -> Result<String> {
+    let format = format_of(url)?;
+    if offline::is_offline() {
+        return Err(Error::Offline(format!(
+            "{url} is not in the local cache; run once without --offline to fetch it"
+        )));
+    }
+
+    log::debug!("downloading archive {url}");
+    let request = credentials::authorize(reqwest::blocking::Client::new().get(url), url)?;
+    let response = request
+        .send()
+        .map_err(|e| Error::Http(format!("Failed to fetch {url}: {e}")))?;
+    if !response.status().is_success() {
+        return Err(Error::Http(format!(
+            "HTTP {} fetching {url}",
+            response.status()
+        )));
+    }
+    let bytes = response
+        .bytes()
+        .map_err(|e| Error::Http(format!("Failed to read response from {url}: {e}")))?;
+
+    store(&bytes, format, expected, url)
+}
+
+/// Check downloaded archive bytes against `expected` and extract them into
+/// the cache under their digest. `url` is only used in error messages.
+pub(crate) fn store(
+    bytes: &[u8],
+    format: Format,
+    expected: Option<&str>,
+    url: &str,
+) -> Result<String> {
+    let digest = sha256_hex(bytes);
+    if let Some(expected) = expected
+        && expected != digest
+    {
+        return Err(Error::Source(format!(
+            "sha256 mismatch for {url}: expected {expected}, downloaded {digest}"
+        )));
+    }
+
+    let dest = content_dir(&digest);
+    if !dest.exists() {
+        let staging = staging_dir()?;
+        extract(bytes, format, staging.path())?;
+        move_into_place(staging, &dest)?;
+    }
+    Ok(digest)
+}
+
+pub(crate) fn sha256_hex(bytes: &[u8]) -> String {
+    use sha2::{Digest, Sha256};
+
+    Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+fn staging_dir() -> Result<tempfile::TempDir> {
+    let dir = archives_dir();
+    std::fs::create_dir_all(&dir).map_err(Error::Io)?;
+    tempfile::Builder::new()
+        .prefix(".staging-")
+        .tempdir_in(&dir)
+        .map_err(Error::Io)
+}
+
+/// Move a fully populated staging directory to `dest`. Another process that
+/// got there first wins; its content is identical.
+fn move_into_place(staging: tempfile::TempDir, dest: &Path) -> Result<()> {
+    if let Some(parent) = dest.parent() {
+        std::fs::create_dir_all(parent).map_err(Error::Io)?;
+    }
+    match std::fs::rename(staging.path(), dest) {
+        Ok(()) => Ok(()),
+        Err(_) if dest.exists() => Ok(()),
+        Err(e) => Err(Error::Io(e)),
+    }
+}
+
+/// Extract an archive into `dest`. Symlinks are skipped, since their
+/// targets may lie outside the archive; this covers archive sources and OCI
+/// layers alike, with or without `strip-components`.
+fn extract(bytes: &[u8], format: Format, dest: &Path) -> Result<()> {
+    let failed =
+        |e: &dyn std::fmt::Display| Error::Source(format!("Failed to extract archive: {e}"));
+    match format {
+        Format::TarGz => {
+            let gz = flate2::read::GzDecoder::new(bytes);
+            let mut archive = tar::Archive::new(gz);
+            for entry in archive.entries().map_err(|e| failed(&e))? {
+                let mut entry = entry.map_err(|e| failed(&e))?;
+                if entry.header().entry_type().is_symlink() {
+                    let path = entry.path().map_err(|e| failed(&e))?;
+                    log::warn!("skipping symlink {} in archive", path.display());
+                    continue;
+                }
+                entry.unpack_in(dest).map_err(|e| failed(&e))?;
+            }
+            Ok(())
+        }
+        Format::Zip => {
+            let mut archive =
+                zip::ZipArchive::new(std::io::Cursor::new(bytes)).map_err(|e| failed(&e))?;
+            for i in 0..archive.len() {
+                let mut file = archive.by_index(i).map_err(|e| failed(&e))?;
+                let Some(rel) = file.enclosed_name() else {
+                    log::warn!("skipping {} in archive: path escapes it", file.name());
+                    continue;
+                };
+                if file.is_symlink() {
+                    log::warn!("skipping symlink {} in archive", rel.display());
+                    continue;
+                }
+                let target = dest.join(rel);
+                if file.is_dir() {
+                    std::fs::create_dir_all(&target).map_err(Error::Io)?;
+                    continue;
+                }
+                if let Some(parent) = target.parent() {
+                    std::fs::create_dir_all(parent).map_err(Error::Io)?;
+                }
+                let mut out = std::fs::File::create(&target).map_err(Error::Io)?;
+                std::io::copy(&mut file, &mut out).map_err(Error::Io)?;
+                #[cfg(unix)]
+                if let Some(mode) = file.unix_mode() {
+                    use std::os::unix::fs::PermissionsExt;
+                    std::fs::set_permissions(
+                        &target,
+                        std::fs::Permissions::from_mode(mode & 0o777),
+                    )
+                    .map_err(Error::Io)?;
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Copy `src` into `dest`, dropping the first `count` components of every
+/// path like `tar --strip-components`. Entries with fewer components are
+/// skipped, and so are symlinks left by older versions of [`extract`].
+fn strip_into(src: &Path, dest: &Path, count: usize) -> Result<()> {
+    for entry in walkdir::WalkDir::new(src).min_depth(1) {
+        let entry = entry.map_err(|e| Error::Io(std::io::Error::other(e)))?;
+        let rel = entry
+            .path()
+            .strip_prefix(src)
+            .map_err(|e| Error::Io(std::io::Error::other(e)))?;
+        let stripped: PathBuf = rel.components().skip(count).collect();
+        if stripped.as_os_str().is_empty() {
+            continue;
+        }
+        let target = dest.join(stripped);
+        if entry.path_is_symlink() {
+            log::warn!("skipping symlink {} in archive", rel.display());
+            continue;
+        }
+        if entry.file_type().is_dir() {
+            std::fs::create_dir_all(&target).map_err(Error::Io)?;
+        } else {
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent).map_err(Error::Io)?;
+            }
+            std::fs::copy(entry.path(), &target).map_err(Error::Io)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tar_gz(entries: &[(&str, &str)]) -> Vec<u8> {
+        let gz = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        let mut builder = tar::Builder::new(gz);
+        for (path, content) in entries {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, path, content.as_bytes())
+                .unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap()
+    }
+
+    #[test]
+    fn format_follows_url_extension() {
+        assert_eq!(
+            format_of("https://example.com/a.tar.gz?x=1").unwrap(),
+            Format::TarGz
+        );
+        assert_eq!(
+            format_of("https://example.com/a.TGZ").unwrap(),
+            Format::TarGz
+        );
+        assert_eq!(format_of("https://example.com/a.zip").unwrap(), Format::Zip);
+        assert!(format_of("https://example.com/a.tar.xz").is_err());
+    }
+
+    #[test]
+    fn extract_and_strip_components() {
+        let bytes = tar_gz(&[
+            ("review-1.0/SKILL.md", "---\nname: review\n---\n"),
+            ("review-1.0/scripts/run.sh", "echo hi\n"),
+            ("README", "top-level files are dropped when stripping"),
+        ]);
+        let raw = tempfile::tempdir().unwrap();
+        extract(&bytes, Format::TarGz, raw.path()).unwrap();
+        assert!(raw.path().join("review-1.0/SKILL.md").is_file());
+
+        let stripped = tempfile::tempdir().unwrap();
+        strip_into(raw.path(), stripped.path(), 1).unwrap();
+        assert!(stripped.path().join("SKILL.md").is_file());
+        assert!(stripped.path().join("scripts/run.sh").is_file());
+        assert!(!stripped.path().join("README").exists());
+    }
+
+    #[test]
+    fn extract_zip() {
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        let options = zip::write::SimpleFileOptions::default();
+        writer.start_file("review/SKILL.md", options).unwrap();
+        std::io::Write::write_all(&mut writer, b"---\nname: review\n---\n").unwrap();
+        let bytes = writer.finish().unwrap().into_inner();
+
+        let raw = tempfile::tempdir().unwrap();
+        extract(&bytes, Format::Zip, raw.path()).unwrap();
+        assert!(raw.path().join("review/SKILL.md").is_file());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn strip_skips_symlinks() {
+        // Archive sources without strip-components and OCI layers are used
+        // as extracted, so the links must not survive extraction itself.
+        let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+            Vec::new(),
+            flate2::Compression::default(),
+        ));
+        let mut header = tar::Header::new_gnu();
+        header.set_size(0);
+        header.set_mode(0o644);
+        header.set_entry_type(tar::EntryType::Symlink);
+        builder
+            .append_link(&mut header, "review/secret", "/etc/passwd")
+            .unwrap();
+        let content = b"---\nname: review\n---\n";
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, "review/SKILL.md", &content[..])
+            .unwrap();
+        let layer = builder.into_inner().unwrap().finish().unwrap();
+
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        let options = zip::write::SimpleFileOptions::default();
+        writer
+            .add_symlink("review/secret", "/etc/passwd", options)
+            .unwrap();
+        writer.start_file("review/SKILL.md", options).unwrap();
+        std::io::Write::write_all(&mut writer, content).unwrap();
+        let zipped = writer.finish().unwrap().into_inner();
+
+        for (bytes, format) in [(layer, Format::TarGz), (zipped, Format::Zip)] {
+            let raw = tempfile::tempdir().unwrap();
+            extract(&bytes, format, raw.path()).unwrap();
+            assert!(raw.path().join("review/SKILL.md").is_file(), "{format:?}");
+            assert!(
+                std::fs::symlink_metadata(raw.path().join("review/secret")).is_err(),
+                "{format:?}"
+            );
+        }
+
+        let outside = tempfile::tempdir().unwrap();
+        std::fs::write(outside.path().join("secret"), "not part of the skill").unwrap();
+        let raw = tempfile::tempdir().unwrap();
+        std::fs::create_dir(raw.path().join("review")).unwrap();
+        std::fs::write(
+            raw.path().join("review/SKILL.md"),
+            "---\nname: review\n---\n",
+        )
+        .unwrap();
+        std::os::unix::fs::symlink(
+            outside.path().join("secret"),
+            raw.path().join("review/secret"),
+        )
+        .unwrap();
+
+        let stripped = tempfile::tempdir().unwrap();
+        strip_into(raw.path(), stripped.path(), 1).unwrap();
+        assert!(stripped.path().join("SKILL.md").is_file());
+        assert!(!stripped.path().join("secret").exists());
+    }
+
+    #[test]
+    fn sha256_is_lowercase_hex() {
+        assert_eq!(
+            sha256_hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
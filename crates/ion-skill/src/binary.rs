@@ -3,6 +3,12 @@
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use crate::{credentials, offline};
+
+/// Releases are looked up through the GitHub API, so a `[credentials]`
+/// entry for `github.com` also covers them.
+const GITHUB_API: &str = "https://api.github.com";
+
 // Re-export types from ionem for backward compatibility.
 // Callers that import `ion_skill::binary::Platform` etc. continue to work.
 pub use ionem::release::{
@@ -18,18 +24,24 @@ fn from_ionem(e: ionem::Error) -> crate::Error {
 }
 
 pub fn fetch_github_release(repo: &str, tag: Option<&str>) -> crate::Result<GitHubRelease> {
-    ionem_fetch_github_release(repo, tag).map_err(from_ionem)
+    offline::ensure_online(&format!("look up GitHub releases of {repo}"))?;
+    let token = credentials::token_for_url(GITHUB_API)?;
+    ionem_fetch_github_release(repo, tag, token.as_deref()).map_err(from_ionem)
 }
 
 pub fn fetch_latest_release_by_tag_prefix(
     repo: &str,
     prefix: &str,
 ) -> crate::Result<GitHubRelease> {
-    ionem_fetch_latest_release_by_tag_prefix(repo, prefix).map_err(from_ionem)
+    offline::ensure_online(&format!("look up GitHub releases of {repo}"))?;
+    let token = credentials::token_for_url(GITHUB_API)?;
+    ionem_fetch_latest_release_by_tag_prefix(repo, prefix, token.as_deref()).map_err(from_ionem)
 }
 
 pub fn download_file(url: &str, dest: &Path) -> crate::Result<()> {
-    ionem_download_file(url, dest).map_err(from_ionem)
+    offline::ensure_online(&format!("download {url}"))?;
+    let token = credentials::token_for_url(url)?;
+    ionem_download_file(url, dest, token.as_deref()).map_err(from_ionem)
 }
 
 pub fn extract_tar_gz(archive_path: &Path, dest_dir: &Path) -> crate::Result<Vec<PathBuf>> {
@@ -318,6 +330,15 @@ pub fn install_binary_from_github(
     skill_dir: &Path,
     asset_pattern: Option<&str>,
 ) -> crate::Result<BinaryInstallResult> {
+    // Offline, a pinned release that is already installed needs no lookup.
+    if offline::is_offline()
+        && let Some(tag) = rev
+        && let Some(result) =
+            check_already_installed(binary_name, parse_version_from_tag(tag), skill_dir)?
+    {
+        return Ok(result);
+    }
+
     let platform = Platform::detect();
     let release = fetch_github_release(repo, rev)?;
     let version = parse_version_from_tag(&release.tag_name).to_string();
@@ -0,0 +1,183 @@
+//! Agent tool catalog — the tools Ion can deploy skills to, shipped in `catalog.toml` and extendable from `[tools]` in the global config.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::GlobalConfig;
+use crate::render::TargetFormat;
+
+/// One agent tool: how to detect it and where it reads skills and instructions.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct ToolDef {
+    /// Display name, e.g. "Claude Code". Defaults to the catalog key.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Paths relative to the project root whose presence means the tool is in use.
+    #[serde(default)]
+    pub markers: Vec<String>,
+    /// Default skills directory for the target. `None` for tools that read
+    /// `.agents/skills` themselves and need no target.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// Instructions file the tool reads instead of AGENTS.md; Ion links it to AGENTS.md.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<String>,
+    /// Format skills are deployed in for the target.
+    #[serde(default)]
+    pub format: TargetFormat,
+}
+
+impl ToolDef {
+    /// Whether any of the tool's markers exists in `project_dir`.
+    pub fn is_detected(&self, project_dir: &Path) -> bool {
+        self.markers
+            .iter()
+            .any(|marker| project_dir.join(marker).symlink_metadata().is_ok())
+    }
+}
+
+#[derive(Deserialize)]
+struct CatalogFile {
+    tools: BTreeMap<String, ToolDef>,
+}
+
+/// The known agent tools, keyed by target name.
+#[derive(Debug, Clone)]
+pub struct Catalog {
+    tools: BTreeMap<String, ToolDef>,
+}
+
+impl Catalog {
+    /// The tools shipped with Ion.
+    pub fn builtin() -> Self {
+        let file: CatalogFile =
+            toml::from_str(include_str!("catalog.toml")).expect("built-in catalog.toml is valid");
+        Catalog { tools: file.tools }
+    }
+
+    /// The built-in tools with `[tools]` from the global config applied on
+    /// top. A configured entry replaces the built-in one of the same name.
+    pub fn load(config: &GlobalConfig) -> Self {
+        let mut catalog = Self::builtin();
+        catalog.tools.extend(config.tools.clone());
+        catalog
+    }
+
+    pub fn get(&self, name: &str) -> Option<&ToolDef> {
+        self.tools.get(name)
+    }
+
+    /// Display name of tool `name`, falling back to the name itself.
+    pub fn label<'a>(&'a self, name: &'a str) -> &'a str {
+        self.get(name)
+            .and_then(|tool| tool.label.as_deref())
+            .unwrap_or(name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &ToolDef)> {
+        self.tools.iter().map(|(name, tool)| (name.as_str(), tool))
+    }
+
+    /// Tools that can be configured as targets, as (name, tool, path).
+    pub fn targets(&self) -> impl Iterator<Item = (&str, &ToolDef, &str)> {
+        self.iter()
+            .filter_map(|(name, tool)| tool.path.as_deref().map(|path| (name, tool, path)))
+    }
+
+    /// Target names whose markers exist in `project_dir`.
+    pub fn detect(&self, project_dir: &Path) -> Vec<&str> {
+        self.targets()
+            .filter(|(_, tool, _)| tool.is_detected(project_dir))
+            .map(|(name, _, _)| name)
+            .collect()
+    }
+
+    /// Instruction files to link to AGENTS.md for the configured `targets`,
+    /// as (target name, filename).
+    pub fn instruction_files<'a>(
+        &'a self,
+        targets: &'a BTreeMap<String, String>,
+    ) -> impl Iterator<Item = (&'a str, &'a str)> {
+        self.iter()
+            .filter(|(name, _)| targets.contains_key(*name))
+            .filter_map(|(name, tool)| tool.instructions.as_deref().map(|file| (name, file)))
+    }
+}
+
+impl FromIterator<(String, ToolDef)> for Catalog {
+    fn from_iter<I: IntoIterator<Item = (String, ToolDef)>>(iter: I) -> Self {
+        Catalog {
+            tools: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_catalog_parses() {
+        let catalog = Catalog::builtin();
+        let claude = catalog.get("claude").unwrap();
+        assert_eq!(claude.path.as_deref(), Some(".claude/skills"));
+        assert_eq!(claude.instructions.as_deref(), Some("CLAUDE.md"));
+        assert_eq!(catalog.label("claude"), "Claude Code");
+        assert_eq!(
+            catalog.get("copilot").unwrap().format,
+            TargetFormat::Copilot
+        );
+        // Codex reads .agents/skills itself, so it is not a target
+        assert!(catalog.get("codex").is_some());
+        assert!(catalog.targets().all(|(name, _, _)| name != "codex"));
+    }
+
+    #[test]
+    fn detect_uses_markers() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join(".gemini")).unwrap();
+        std::fs::write(dir.path().join(".cursorrules"), "").unwrap();
+        std::fs::create_dir(dir.path().join(".codex")).unwrap();
+
+        assert_eq!(Catalog::builtin().detect(dir.path()), ["cursor", "gemini"]);
+    }
+
+    #[test]
+    fn global_config_extends_catalog() {
+        let config: GlobalConfig = toml::from_str(
+            r#"
+[tools.aider]
+markers = [".aider.conf.yml"]
+path = ".aider/skills"
+instructions = "CONVENTIONS.md"
+
+[tools.cursor]
+label = "Cursor"
+markers = [".cursor"]
+path = ".cursor/rules"
+format = "cursor"
+"#,
+        )
+        .unwrap();
+        let catalog = Catalog::load(&config);
+
+        assert_eq!(catalog.label("aider"), "aider");
+        assert_eq!(
+            catalog.get("cursor").unwrap().path.as_deref(),
+            Some(".cursor/rules")
+        );
+
+        let targets = BTreeMap::from([
+            ("aider".to_string(), ".aider/skills".to_string()),
+            ("claude".to_string(), ".claude/skills".to_string()),
+        ]);
+        let files: Vec<_> = catalog.instruction_files(&targets).collect();
+        assert_eq!(
+            files,
+            [("aider", "CONVENTIONS.md"), ("claude", "CLAUDE.md")]
+        );
+    }
+}
@@ -22,6 +22,13 @@ pub struct GlobalConfig {
     pub registries: BTreeMap<String, RegistryConfig>,
     #[serde(default)]
     pub search: SearchConfig,
+    #[serde(default)]
+    pub hosts: BTreeMap<String, HostConfig>,
+    #[serde(default)]
+    pub credentials: BTreeMap<String, CredentialConfig>,
+    /// Agent tools added to or overriding the built-in catalog.
+    #[serde(default)]
+    pub tools: BTreeMap<String, crate::catalog::ToolDef>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -44,6 +51,52 @@ pub struct RegistryConfig {
     pub default: Option<bool>,
 }
 
+/// A named git host, used by `<name>:owner/repo` source shorthands.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct HostConfig {
+    /// Base web URL of the host, e.g. `https://git.example.com`.
+    pub url: String,
+    #[serde(default, skip_serializing_if = "CloneProtocol::is_default")]
+    pub protocol: CloneProtocol,
+}
+
+/// How repositories on a [`HostConfig`] are cloned.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CloneProtocol {
+    #[default]
+    Https,
+    Ssh,
+}
+
+impl CloneProtocol {
+    fn is_default(&self) -> bool {
+        *self == CloneProtocol::Https
+    }
+}
+
+/// Where to read the token for a host, keyed by hostname in `[credentials]`.
+///
+/// Exactly one of `env`, `command` and `file` must be set. Only the location
+/// is stored here; the token itself never lands in a config file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CredentialConfig {
+    /// Username for git's basic auth (defaults to `x-access-token`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    /// Environment variable holding the token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub env: Option<String>,
+    /// Shell command printing the token on stdout.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    /// File containing the token (`~/` is expanded).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct SearchConfig {
@@ -133,6 +186,7 @@ impl GlobalConfig {
                 _ => None,
             },
             "registries" => self.registries.get(field).map(|r| r.url.clone()),
+            "hosts" => self.hosts.get(field).map(|h| h.url.clone()),
             "search" => match field {
                 "agent-command" => self.search.agent_command.clone(),
                 _ => None,
@@ -152,10 +206,10 @@ impl GlobalConfig {
         })?;
 
         match section {
-            "targets" | "sources" | "cache" | "ui" | "registries" | "search" => {}
+            "targets" | "sources" | "cache" | "ui" | "registries" | "search" | "hosts" => {}
             _ => {
                 return Err(Error::Manifest(format!(
-                    "Unknown config section '{section}'. Valid sections: targets, sources, cache, ui, registries, search"
+                    "Unknown config section '{section}'. Valid sections: targets, sources, cache, ui, registries, search, hosts"
                 )));
             }
         }
@@ -184,7 +238,7 @@ impl GlobalConfig {
                 })?;
                 doc[section][field] = toml_edit::value(b);
             }
-            ("registries", _) => {
+            ("registries" | "hosts", _) => {
                 use toml_edit::InlineTable;
                 let mut t = InlineTable::new();
                 t.insert("url", value.into());
@@ -241,6 +295,9 @@ impl GlobalConfig {
         if let Some(ref cmd) = self.search.agent_command {
             entries.push(("search.agent-command".to_string(), cmd.clone()));
         }
+        for (k, v) in &self.hosts {
+            entries.push((format!("hosts.{k}"), v.url.clone()));
+        }
         entries
     }
 }
@@ -565,4 +622,59 @@ agent-command = "claude -p 'search: {query}'"
             Some("claude search {query}".to_string())
         );
     }
+
+    #[test]
+    fn load_hosts_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(
+            &path,
+            r#"
+[hosts.work]
+url = "https://git.example.com"
+protocol = "ssh"
+
+[hosts.gitlab]
+url = "https://gitlab.example.com"
+"#,
+        )
+        .unwrap();
+
+        let config = GlobalConfig::load_from(&path).unwrap();
+        assert_eq!(config.hosts["work"].url, "https://git.example.com");
+        assert_eq!(config.hosts["work"].protocol, CloneProtocol::Ssh);
+        assert_eq!(config.hosts["gitlab"].protocol, CloneProtocol::Https);
+        assert_eq!(
+            config.get_value("hosts.work"),
+            Some("https://git.example.com".to_string())
+        );
+    }
+
+    #[test]
+    fn load_credentials_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(
+            &path,
+            r#"
+[credentials."github.com"]
+env = "GITHUB_TOKEN"
+
+[credentials."git.example.com"]
+username = "deploy"
+command = "pass show ion/example"
+"#,
+        )
+        .unwrap();
+
+        let config = GlobalConfig::load_from(&path).unwrap();
+        assert_eq!(
+            config.credentials["github.com"].env.as_deref(),
+            Some("GITHUB_TOKEN")
+        );
+        let example = &config.credentials["git.example.com"];
+        assert_eq!(example.username.as_deref(), Some("deploy"));
+        assert_eq!(example.command.as_deref(), Some("pass show ion/example"));
+        assert_eq!(example.file, None);
+    }
 }
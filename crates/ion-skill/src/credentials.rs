@@ -0,0 +1,312 @@
+//! Per-host credentials for private skill sources — read tokens from the environment, a command, or a file and attach them to HTTP requests and git operations.
+
+use std::collections::BTreeMap;
+use std::sync::{Mutex, RwLock};
+
+use base64::Engine;
+use reqwest::blocking::RequestBuilder;
+
+use crate::config::CredentialConfig;
+use crate::{Error, Result};
+
+static CONFIGS: RwLock<BTreeMap<String, CredentialConfig>> = RwLock::new(BTreeMap::new());
+
+/// Tokens read so far, so commands and files are read at most once per process.
+/// `None` records a missing environment variable, so it's only reported once.
+static RESOLVED: Mutex<BTreeMap<String, Option<Credential>>> = Mutex::new(BTreeMap::new());
+
+/// Username sent with tokens over git's basic auth when none is configured.
+const DEFAULT_USERNAME: &str = "x-access-token";
+
+/// Register the `[credentials]` table from the global config for the rest of the process.
+pub fn set_credentials(credentials: BTreeMap<String, CredentialConfig>) {
+    *CONFIGS.write().unwrap_or_else(|e| e.into_inner()) = credentials;
+    RESOLVED.lock().unwrap_or_else(|e| e.into_inner()).clear();
+}
+
+/// A token for one host.
+///
+/// `Debug` leaves the token out, so a credential can't leak into logs.
+#[derive(Clone)]
+pub struct Credential {
+    pub username: String,
+    token: String,
+}
+
+impl std::fmt::Debug for Credential {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Credential")
+            .field("username", &self.username)
+            .field("token", &"<redacted>")
+            .finish()
+    }
+}
+
+impl Credential {
+    #[cfg(test)]
+    pub(crate) fn new(username: &str, token: &str) -> Self {
+        Self {
+            username: username.to_string(),
+            token: token.to_string(),
+        }
+    }
+
+    /// The token itself. Never print it.
+    pub(crate) fn token(&self) -> &str {
+        &self.token
+    }
+
+    /// `Authorization` header value for git's HTTP transport.
+    fn basic_header(&self) -> String {
+        let pair = format!("{}:{}", self.username, self.token);
+        format!(
+            "Authorization: Basic {}",
+            base64::engine::general_purpose::STANDARD.encode(pair)
+        )
+    }
+}
+
+/// The credential for `host`, if one is configured.
+///
+/// A `[credentials."example.com"]` entry also covers subdomains such as
+/// `api.example.com`; the most specific entry wins. An entry whose
+/// environment variable is unset is skipped with a warning, so public
+/// sources on that host still work anonymously.
+pub fn for_host(host: &str) -> Result<Option<Credential>> {
+    let host = host.to_ascii_lowercase();
+    let entry = {
+        let configs = CONFIGS.read().unwrap_or_else(|e| e.into_inner());
+        configs
+            .iter()
+            .filter(|(name, _)| {
+                let name = name.to_ascii_lowercase();
+                host == name || host.ends_with(&format!(".{name}"))
+            })
+            .max_by_key(|(name, _)| name.len())
+            .map(|(name, config)| (name.clone(), config.clone()))
+    };
+    let Some((name, config)) = entry else {
+        return Ok(None);
+    };
+
+    let mut resolved = RESOLVED.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(credential) = resolved.get(&name) {
+        return Ok(credential.clone());
+    }
+    let credential = read_token(&name, &config)?.map(|token| Credential {
+        username: config
+            .username
+            .clone()
+            .unwrap_or_else(|| DEFAULT_USERNAME.to_string()),
+        token,
+    });
+    resolved.insert(name, credential.clone());
+    Ok(credential)
+}
+
+/// The credential for the host of `url`, if one is configured.
+///
+/// Only `https://` URLs (and plain `http://` on loopback hosts, for local
+/// servers) get credentials, so tokens never travel in the clear.
+pub fn for_url(url: &str) -> Result<Option<Credential>> {
+    match secure_host(url) {
+        Some(host) => for_host(host),
+        None => Ok(None),
+    }
+}
+
+/// Attach the credential for `url` as a bearer token, if one is configured.
+pub fn authorize(request: RequestBuilder, url: &str) -> Result<RequestBuilder> {
+    Ok(match for_url(url)? {
+        Some(credential) => request.bearer_auth(&credential.token),
+        None => request,
+    })
+}
+
+/// The bearer token for `url`, for requests built outside this crate.
+pub(crate) fn token_for_url(url: &str) -> Result<Option<String>> {
+    Ok(for_url(url)?.map(|credential| credential.token))
+}
+
+/// Authenticate git operations against the host of `url` for the rest of
+/// the process, if a credential is configured for it.
+pub fn register_git(url: &str) -> Result<()> {
+    let Some(host) = secure_host(url) else {
+        return Ok(());
+    };
+    if let Some(credential) = for_host(host)? {
+        // git matches the port too, so keep it but drop any userinfo
+        let (scheme, rest) = url.split_once("://").unwrap_or(("https", url));
+        let authority = rest.split(['/', '?', '#']).next().unwrap_or(host);
+        let authority = authority
+            .rsplit_once('@')
+            .map_or(authority, |(_, authority)| authority);
+        ionem::shell::git::set_http_header(
+            &format!("{scheme}://{authority}/"),
+            &credential.basic_header(),
+        );
+    }
+    Ok(())
+}
+
+/// The host of `url`, without userinfo or port, if credentials may be sent
+/// to it: any `https://` URL, or an `http://` URL on a loopback host.
+pub(crate) fn secure_host(url: &str) -> Option<&str> {
+    let (scheme, rest) = url.split_once("://")?;
+    let authority = rest.split(['/', '?', '#']).next()?;
+    let host = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+    let host = match host.strip_prefix('[') {
+        Some(ipv6) => ipv6.split(']').next()?,
+        None => host.split(':').next()?,
+    };
+    let loopback = matches!(host, "localhost" | "127.0.0.1" | "::1");
+    let allowed = scheme == "https" || (scheme == "http" && loopback);
+    (allowed && !host.is_empty()).then_some(host)
+}
+
+/// Read the token for `host`, or `None` if its environment variable is unset.
+fn read_token(host: &str, config: &CredentialConfig) -> Result<Option<String>> {
+    let token = match (&config.env, &config.command, &config.file) {
+        (Some(var), None, None) => match std::env::var(var) {
+            Ok(token) => token,
+            Err(_) => {
+                log::warn!(
+                    "credential for {host}: environment variable {var} is not set; continuing without it"
+                );
+                return Ok(None);
+            }
+        },
+        (None, Some(command), None) => run_command(host, command)?,
+        (None, None, Some(file)) => {
+            let path = expand_home(file);
+            std::fs::read_to_string(&path).map_err(|e| {
+                Error::Other(format!(
+                    "Credential for {host}: failed to read {}: {e}",
+                    path.display()
+                ))
+            })?
+        }
+        _ => {
+            return Err(Error::Other(format!(
+                "Credential for {host} must set exactly one of `env`, `command` or `file`"
+            )));
+        }
+    };
+    let token = token.trim().to_string();
+    if token.is_empty() {
+        return Err(Error::Other(format!("Credential for {host} is empty")));
+    }
+    Ok(Some(token))
+}
+
+fn run_command(host: &str, command: &str) -> Result<String> {
+    let output = shell(command)
+        .stdin(std::process::Stdio::null())
+        .stderr(std::process::Stdio::inherit())
+        .output()
+        .map_err(|e| Error::Other(format!("Credential for {host}: failed to run command: {e}")))?;
+    if !output.status.success() {
+        return Err(Error::Other(format!(
+            "Credential for {host}: command exited with {}",
+            output.status
+        )));
+    }
+    String::from_utf8(output.stdout).map_err(|_| {
+        Error::Other(format!(
+            "Credential for {host}: command printed invalid UTF-8"
+        ))
+    })
+}
+
+/// `command` run by the platform shell.
+fn shell(command: &str) -> std::process::Command {
+    let (program, flag) = if cfg!(windows) {
+        ("cmd", "/C")
+    } else {
+        ("sh", "-c")
+    };
+    let mut shell = std::process::Command::new(program);
+    shell.args([flag, command]);
+    shell
+}
+
+fn expand_home(path: &str) -> std::path::PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => std::path::PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn secure_host_strips_userinfo_and_port() {
+        assert_eq!(
+            secure_host("https://user@git.example.com:8443/org/repo.git"),
+            Some("git.example.com")
+        );
+        assert_eq!(secure_host("https://example.com"), Some("example.com"));
+        assert_eq!(secure_host("http://127.0.0.1:8080/a"), Some("127.0.0.1"));
+        assert_eq!(secure_host("http://[::1]:8080/a"), Some("::1"));
+        assert_eq!(secure_host("http://example.com/skill.md"), None);
+        assert_eq!(secure_host("git@github.com:org/repo.git"), None);
+    }
+
+    #[test]
+    fn read_token_from_command_and_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("token");
+        std::fs::write(&file, "from-file\n").unwrap();
+
+        let from_file = CredentialConfig {
+            file: Some(file.display().to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            read_token("example.com", &from_file).unwrap().as_deref(),
+            Some("from-file")
+        );
+
+        let from_command = CredentialConfig {
+            command: Some("echo from-command".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            read_token("example.com", &from_command).unwrap().as_deref(),
+            Some("from-command")
+        );
+
+        let unset = CredentialConfig {
+            env: Some("ION_TEST_UNSET_TOKEN_VARIABLE".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(read_token("example.com", &unset).unwrap(), None);
+
+        let both = CredentialConfig {
+            command: Some("echo x".to_string()),
+            file: Some(file.display().to_string()),
+            ..Default::default()
+        };
+        assert!(read_token("example.com", &both).is_err());
+    }
+
+    #[test]
+    fn credential_debug_hides_token() {
+        let credential = Credential {
+            username: "me".to_string(),
+            token: "s3cret".to_string(),
+        };
+        let debug = format!("{credential:?}");
+        assert!(debug.contains("me"));
+        assert!(!debug.contains("s3cret"));
+        assert!(
+            credential
+                .basic_header()
+                .starts_with("Authorization: Basic ")
+        );
+    }
+}
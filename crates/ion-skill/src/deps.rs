@@ -0,0 +1,416 @@
+//! Skill-to-skill dependencies — install what a skill's `requires:` frontmatter lists, transitively.
+//!
+//! Dependencies are recorded in Ion.lock only, with `required_by` naming the
+//! skills that pulled them in. A requirement on a skill that Ion.toml
+//! declares directly is satisfied by that entry and not installed again.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::installer::{InstallValidationOptions, SkillInstaller};
+use crate::lockfile::{LockedSkill, Lockfile, expected_locked_source};
+use crate::skill::{Requirement, SkillMetadata};
+use crate::source::SkillSource;
+use crate::{Error, Result, versions};
+
+/// A dependency installed by [`DependencyResolver`].
+#[derive(Debug, Clone)]
+pub struct ResolvedDependency {
+    pub name: String,
+    pub source: SkillSource,
+    /// Lock entry for the installed dependency, with `required_by` filled in.
+    pub locked: LockedSkill,
+}
+
+/// What a resolved dependency was required as, for conflict messages.
+struct Resolution {
+    source: SkillSource,
+    locked: LockedSkill,
+    /// `(requirer, version requirement)` for every skill that asked for it.
+    requested: Vec<(String, Option<String>)>,
+}
+
+/// Installs the dependencies of one or more skills.
+///
+/// Skills already in the previous Ion.lock from the same source are
+/// reinstalled at their locked commit. A dependency cycle, or two skills
+/// requiring the same name from different sources or with incompatible
+/// versions, is an [`Error::Dependency`].
+pub struct DependencyResolver<'a> {
+    installer: &'a SkillInstaller<'a>,
+    previous: &'a Lockfile,
+    declared: BTreeMap<String, SkillSource>,
+    validation: InstallValidationOptions,
+    locked: bool,
+    resolved: BTreeMap<String, Resolution>,
+}
+
+impl<'a> DependencyResolver<'a> {
+    /// `declared` holds the skills Ion.toml declares directly.
+    pub fn new(
+        installer: &'a SkillInstaller<'a>,
+        previous: &'a Lockfile,
+        declared: BTreeMap<String, SkillSource>,
+    ) -> Self {
+        Self {
+            installer,
+            previous,
+            declared,
+            validation: InstallValidationOptions::default(),
+            locked: false,
+            resolved: BTreeMap::new(),
+        }
+    }
+
+    /// Validation options for installed dependencies. By default, warnings
+    /// fail the install just as they do for `ion add`.
+    pub fn with_validation(mut self, validation: InstallValidationOptions) -> Self {
+        self.validation = validation;
+        self
+    }
+
+    /// Require dependencies already in Ion.lock to match their locked checksum.
+    pub fn with_locked(mut self, locked: bool) -> Self {
+        self.locked = locked;
+        self
+    }
+
+    /// Install everything the deployed skill `name` requires, transitively.
+    pub fn install_for(&mut self, name: &str) -> Result<()> {
+        self.visit(name, &mut vec![name.to_string()])
+    }
+
+    /// The dependencies installed so far, sorted by name.
+    pub fn into_resolved(self) -> Vec<ResolvedDependency> {
+        self.resolved
+            .into_iter()
+            .map(|(name, resolution)| {
+                let mut required_by: Vec<String> = resolution
+                    .requested
+                    .into_iter()
+                    .map(|(requirer, _)| requirer)
+                    .collect();
+                required_by.sort();
+                required_by.dedup();
+                ResolvedDependency {
+                    name,
+                    source: resolution.source,
+                    locked: resolution.locked.with_required_by(required_by),
+                }
+            })
+            .collect()
+    }
+
+    fn visit(&mut self, skill: &str, stack: &mut Vec<String>) -> Result<()> {
+        let from_local = self
+            .declared
+            .get(skill)
+            .or_else(|| self.resolved.get(skill).map(|r| &r.source))
+            .is_some_and(|source| source.is_path() || source.is_local());
+        for requirement in read_requirements(&self.installer.skill_dir(skill))? {
+            let source = requirement.to_source(from_local)?;
+            let name = source.display_name();
+
+            if let Some(start) = stack.iter().position(|s| *s == name) {
+                let mut cycle = stack[start..].to_vec();
+                cycle.push(name);
+                return Err(Error::Dependency(format!(
+                    "dependency cycle: {}",
+                    cycle.join(" -> ")
+                )));
+            }
+
+            if let Some(declared) = self.declared.get(&name) {
+                if !same_source(declared, &source) {
+                    return Err(Error::Dependency(format!(
+                        "'{skill}' requires '{name}' from {}, but Ion.toml installs it from {}",
+                        requirement.source,
+                        expected_locked_source(declared)
+                    )));
+                }
+                continue;
+            }
+
+            if let Some(resolution) = self.resolved.get_mut(&name) {
+                check_compatible(&name, resolution, skill, &requirement, &source)?;
+                resolution
+                    .requested
+                    .push((skill.to_string(), requirement.version.clone()));
+                continue;
+            }
+
+            let locked = self.install(&name, &source)?;
+            let mut requested: Vec<(String, Option<String>)> = self
+                .previous
+                .find(&name)
+                .filter(|l| l.locks_source(&source))
+                .map(|l| l.required_by.iter().map(|r| (r.clone(), None)).collect())
+                .unwrap_or_default();
+            requested.push((skill.to_string(), requirement.version.clone()));
+            self.resolved.insert(
+                name.clone(),
+                Resolution {
+                    source,
+                    locked,
+                    requested,
+                },
+            );
+
+            stack.push(name.clone());
+            self.visit(&name, stack)?;
+            stack.pop();
+        }
+        Ok(())
+    }
+
+    /// Install one dependency, reusing its locked commit when Ion.lock has it.
+    fn install(&self, name: &str, source: &SkillSource) -> Result<LockedSkill> {
+        let previous = self.previous.find(name).filter(|l| {
+            l.locks_source(source)
+                && source
+                    .version
+                    .as_deref()
+                    .is_none_or(|req| satisfies(l, req, source.path.as_deref()))
+        });
+        let install_source = match previous {
+            Some(entry) if self.locked => self.installer.verify_locked(source, entry)?,
+            Some(entry) => entry.pin(source),
+            None => source.clone(),
+        };
+        let installed =
+            self.installer
+                .install_with_options(name, &install_source, self.validation)?;
+        Ok(match previous.and_then(|l| l.tag()) {
+            Some(tag) if installed.tag().is_none() => installed.with_tag(tag),
+            _ => installed,
+        })
+    }
+}
+
+/// The `requires:` list of the skill deployed at `skill_dir`.
+fn read_requirements(skill_dir: &Path) -> Result<Vec<Requirement>> {
+    let skill_md = skill_dir.join("SKILL.md");
+    if !skill_md.exists() {
+        return Ok(Vec::new());
+    }
+    let content = std::fs::read_to_string(&skill_md)?;
+    SkillMetadata::parse_requires(&content)
+}
+
+fn same_source(a: &SkillSource, b: &SkillSource) -> bool {
+    expected_locked_source(a) == expected_locked_source(b) && a.path == b.path
+}
+
+/// Whether a locked dependency satisfies a version requirement, via the tag
+/// it was resolved from or its SKILL.md version.
+fn satisfies(locked: &LockedSkill, req: &str, skill_path: Option<&str>) -> bool {
+    match (locked.tag(), locked.version.as_deref()) {
+        (Some(tag), _) => versions::tag_satisfies(tag, req, skill_path),
+        (None, Some(version)) => versions::version_satisfies(version, req),
+        (None, None) => false,
+    }
+}
+
+/// A second requirement on an already resolved dependency must name the
+/// same source and accept the version that was installed.
+fn check_compatible(
+    name: &str,
+    resolution: &Resolution,
+    skill: &str,
+    requirement: &Requirement,
+    source: &SkillSource,
+) -> Result<()> {
+    let first = resolution
+        .requested
+        .last()
+        .map(|(requirer, _)| requirer.as_str())
+        .unwrap_or_default();
+    if !same_source(&resolution.source, source) {
+        return Err(Error::Dependency(format!(
+            "conflicting requirements for '{name}': '{first}' requires {}, '{skill}' requires {}",
+            expected_locked_source(&resolution.source),
+            expected_locked_source(source)
+        )));
+    }
+    if let Some(ref req) = requirement.version
+        && !satisfies(&resolution.locked, req, source.path.as_deref())
+    {
+        let installed = resolution
+            .locked
+            .tag()
+            .or(resolution.locked.version.as_deref())
+            .unwrap_or("an unversioned commit");
+        let others: Vec<String> = resolution
+            .requested
+            .iter()
+            .map(|(requirer, version)| match version {
+                Some(version) => format!("'{requirer}' requires {version}"),
+                None => format!("'{requirer}' requires any version"),
+            })
+            .collect();
+        return Err(Error::Dependency(format!(
+            "conflicting requirements for '{name}': '{skill}' requires {req}, but {} resolved to {installed}",
+            others.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::ManifestOptions;
+
+    fn write_skill(dir: &Path, name: &str, requires: &[&str]) {
+        std::fs::create_dir_all(dir).unwrap();
+        let requires = if requires.is_empty() {
+            String::new()
+        } else {
+            let items: Vec<String> = requires.iter().map(|r| format!("  - {r}\n")).collect();
+            format!("requires:\n{}", items.concat())
+        };
+        std::fs::write(
+            dir.join("SKILL.md"),
+            format!("---\nname: {name}\ndescription: A {name} skill.\n{requires}---\n\nBody.\n"),
+        )
+        .unwrap();
+    }
+
+    /// Ion.toml declaring the test root `a` as a local path skill.
+    fn local_root() -> BTreeMap<String, SkillSource> {
+        BTreeMap::from([("a".to_string(), SkillSource::infer("./a").unwrap())])
+    }
+
+    #[test]
+    fn installs_requirements_transitively() {
+        let src = tempfile::tempdir().unwrap();
+        let project = tempfile::tempdir().unwrap();
+        let b = src.path().join("b");
+        let c = src.path().join("c");
+        write_skill(&b, "b", &[&c.display().to_string()]);
+        write_skill(&c, "c", &[]);
+        write_skill(
+            &project.path().join(".agents/skills/a"),
+            "a",
+            &[&b.display().to_string()],
+        );
+
+        let options = ManifestOptions::default();
+        let installer = SkillInstaller::new(project.path(), &options);
+        let lockfile = Lockfile::default();
+        let mut resolver = DependencyResolver::new(&installer, &lockfile, local_root());
+        resolver.install_for("a").unwrap();
+
+        let resolved = resolver.into_resolved();
+        let names: Vec<&str> = resolved.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, ["b", "c"]);
+        assert_eq!(resolved[0].locked.required_by, ["a"]);
+        assert_eq!(resolved[1].locked.required_by, ["b"]);
+        assert!(project.path().join(".agents/skills/c/SKILL.md").exists());
+    }
+
+    #[test]
+    fn detects_cycles() {
+        let src = tempfile::tempdir().unwrap();
+        let project = tempfile::tempdir().unwrap();
+        let b = src.path().join("b");
+        let c = src.path().join("c");
+        write_skill(&b, "b", &[&c.display().to_string()]);
+        write_skill(&c, "c", &[&b.display().to_string()]);
+        write_skill(
+            &project.path().join(".agents/skills/a"),
+            "a",
+            &[&b.display().to_string()],
+        );
+
+        let options = ManifestOptions::default();
+        let installer = SkillInstaller::new(project.path(), &options);
+        let lockfile = Lockfile::default();
+        let mut resolver = DependencyResolver::new(&installer, &lockfile, local_root());
+        let err = resolver.install_for("a").unwrap_err();
+        assert!(
+            err.to_string().contains("dependency cycle: b -> c -> b"),
+            "got: {err}"
+        );
+    }
+
+    #[test]
+    fn rejects_conflicting_sources() {
+        let src = tempfile::tempdir().unwrap();
+        let project = tempfile::tempdir().unwrap();
+        let one = src.path().join("one/shared");
+        let two = src.path().join("two/shared");
+        write_skill(&one, "shared", &[]);
+        write_skill(&two, "shared", &[]);
+        let b = src.path().join("b");
+        write_skill(&b, "b", &[&two.display().to_string()]);
+        write_skill(
+            &project.path().join(".agents/skills/a"),
+            "a",
+            &[&one.display().to_string(), &b.display().to_string()],
+        );
+
+        let options = ManifestOptions::default();
+        let installer = SkillInstaller::new(project.path(), &options);
+        let lockfile = Lockfile::default();
+        let mut resolver = DependencyResolver::new(&installer, &lockfile, local_root());
+        let err = resolver.install_for("a").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("conflicting requirements for 'shared'"),
+            "got: {err}"
+        );
+    }
+
+    #[test]
+    fn declared_skills_satisfy_requirements() {
+        let src = tempfile::tempdir().unwrap();
+        let project = tempfile::tempdir().unwrap();
+        let b = src.path().join("b");
+        write_skill(&b, "b", &[]);
+        write_skill(
+            &project.path().join(".agents/skills/a"),
+            "a",
+            &[&b.display().to_string()],
+        );
+
+        let options = ManifestOptions::default();
+        let installer = SkillInstaller::new(project.path(), &options);
+        let lockfile = Lockfile::default();
+        let mut declared = local_root();
+        declared.insert(
+            "b".to_string(),
+            SkillSource::infer(&b.display().to_string()).unwrap(),
+        );
+        let mut resolver = DependencyResolver::new(&installer, &lockfile, declared);
+        resolver.install_for("a").unwrap();
+        assert!(resolver.into_resolved().is_empty());
+        assert!(!project.path().join(".agents/skills/b").exists());
+    }
+
+    #[test]
+    fn fetched_skills_cannot_require_paths() {
+        let src = tempfile::tempdir().unwrap();
+        let project = tempfile::tempdir().unwrap();
+        let b = src.path().join("b");
+        write_skill(&b, "b", &[]);
+        write_skill(
+            &project.path().join(".agents/skills/a"),
+            "a",
+            &[&b.display().to_string()],
+        );
+
+        let options = ManifestOptions::default();
+        let installer = SkillInstaller::new(project.path(), &options);
+        let lockfile = Lockfile::default();
+        let declared =
+            BTreeMap::from([("a".to_string(), SkillSource::infer("org/skills/a").unwrap())]);
+        let mut resolver = DependencyResolver::new(&installer, &lockfile, declared);
+        let err = resolver.install_for("a").unwrap_err();
+        assert!(
+            err.to_string().contains("only local skills may require"),
+            "got: {err}"
+        );
+        assert!(!project.path().join(".agents/skills/b").exists());
+    }
+}
@@ -0,0 +1,516 @@
+//! Project health checks — find half-broken skill installations and repair what can be repaired safely.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::catalog::Catalog;
+use crate::installer::SkillInstaller;
+use crate::lockfile::{LockedSkillKind, Lockfile};
+use crate::manifest::Manifest;
+use crate::prune::{managed_skill_entries, owns_link, skill_dirs};
+use crate::{Error, Result, agents, binary, gitignore};
+
+/// How serious a [`Problem`] is. Errors leave skills unusable; warnings
+/// are leftovers that do no harm yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+/// The check that found a [`Problem`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Check {
+    /// A symlink in the skills directory or a target that points nowhere.
+    DanglingLink,
+    /// An Ion.toml entry that can't be resolved to a source.
+    InvalidEntry,
+    /// A managed `.gitignore` line for a skill the project no longer has.
+    StaleGitignore,
+    /// An Ion.lock entry that Ion.toml no longer declares.
+    StaleLockEntry,
+    /// A locked binary missing from [`binary::bin_dir`].
+    MissingBinary,
+    /// An agent instructions file (e.g. CLAUDE.md) that doesn't link to AGENTS.md.
+    Instructions,
+}
+
+/// Repair for a problem, applied by [`repair`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Fix {
+    /// Re-create the skill's target deployments from its skills-dir entry.
+    Redeploy(String),
+    /// Delete a dangling symlink Ion made that belongs to no skill.
+    RemoveLink(PathBuf),
+    /// Drop the skill's managed `.gitignore` lines.
+    RemoveGitignore(String),
+    /// Drop the skill from Ion.lock along with its deployments.
+    DropLockEntry(String),
+    /// Link the configured agent instructions files to AGENTS.md.
+    LinkInstructions,
+}
+
+/// One finding from [`diagnose`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Problem {
+    pub check: Check,
+    pub severity: Severity,
+    /// The skill the problem concerns, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skill: Option<String>,
+    pub message: String,
+    /// What to do when `--fix` can't repair the problem.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+    #[serde(skip)]
+    fix: Option<Fix>,
+}
+
+impl Problem {
+    fn new(check: Check, severity: Severity, message: String) -> Self {
+        Problem {
+            check,
+            severity,
+            skill: None,
+            message,
+            hint: None,
+            fix: None,
+        }
+    }
+
+    fn skill(mut self, name: &str) -> Self {
+        self.skill = Some(name.to_string());
+        self
+    }
+
+    fn fix(mut self, fix: Fix) -> Self {
+        self.fix = Some(fix);
+        self
+    }
+
+    fn hint(mut self, hint: &str) -> Self {
+        self.hint = Some(hint.to_string());
+        self
+    }
+
+    /// Whether [`repair`] can fix this problem.
+    pub fn is_fixable(&self) -> bool {
+        self.fix.is_some()
+    }
+}
+
+/// Check the project the installer deploys into against its Ion.toml and
+/// Ion.lock. Problems are ordered by check, then by path.
+pub fn diagnose(
+    installer: &SkillInstaller,
+    manifest: &Manifest,
+    lockfile: &Lockfile,
+    catalog: &Catalog,
+) -> Result<Vec<Problem>> {
+    let known: BTreeSet<&str> = manifest
+        .skills
+        .keys()
+        .map(String::as_str)
+        .chain(lockfile.skills.iter().map(|s| s.name.as_str()))
+        .collect();
+
+    let mut problems = dangling_links(installer, &known, lockfile)?;
+    problems.extend(invalid_entries(manifest));
+    problems.extend(stale_gitignore(installer, &known)?);
+    for name in lockfile.stale(&manifest.skills) {
+        problems.push(
+            Problem::new(
+                Check::StaleLockEntry,
+                Severity::Warning,
+                format!("Ion.lock has '{name}', which Ion.toml does not declare"),
+            )
+            .skill(&name)
+            .fix(Fix::DropLockEntry(name.clone())),
+        );
+    }
+    problems.extend(missing_binaries(lockfile));
+    problems.extend(unlinked_instructions(installer, catalog));
+    Ok(problems)
+}
+
+/// Apply the fix for `problem`. Returns `false` when the problem has no
+/// fix. Ion.lock changes are made to `lockfile`; the caller writes it.
+pub fn repair(
+    installer: &SkillInstaller,
+    lockfile: &mut Lockfile,
+    catalog: &Catalog,
+    problem: &Problem,
+) -> Result<bool> {
+    let project_dir = installer.project_dir();
+    match &problem.fix {
+        None => return Ok(false),
+        Some(Fix::Redeploy(name)) => installer.deploy(name, &installer.skill_dir(name))?,
+        Some(Fix::RemoveLink(path)) => {
+            if path.is_symlink() {
+                std::fs::remove_file(path).map_err(Error::Io)?;
+            }
+        }
+        Some(Fix::RemoveGitignore(name)) => gitignore::remove_skill_entries(project_dir, name)?,
+        Some(Fix::DropLockEntry(name)) => {
+            // A local skill's directory is the user's own
+            if let Some(locked) = lockfile.find(name)
+                && !matches!(locked.kind, LockedSkillKind::Local { .. })
+            {
+                installer.uninstall(name)?;
+            }
+            gitignore::remove_skill_entries(project_dir, name)?;
+            lockfile.remove(name);
+        }
+        Some(Fix::LinkInstructions) => {
+            agents::ensure_agent_symlinks(project_dir, &installer.options().targets, catalog)?
+        }
+    }
+    Ok(true)
+}
+
+fn dangling_links(
+    installer: &SkillInstaller,
+    known: &BTreeSet<&str>,
+    lockfile: &Lockfile,
+) -> Result<Vec<Problem>> {
+    let project_dir = installer.project_dir();
+    let skills_dir = installer.options().skills_dir_or_default().to_string();
+    let managed = managed_skill_entries(installer)?;
+    let mut problems = Vec::new();
+    for dir in skill_dirs(installer) {
+        let entries = match std::fs::read_dir(project_dir.join(&dir)) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(Error::Io(e)),
+        };
+        let mut links: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.is_symlink() && !path.exists())
+            .collect();
+        links.sort();
+
+        for link in links {
+            let name = link
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string();
+            let rel = format!("{dir}/{name}");
+            let problem = Problem::new(
+                Check::DanglingLink,
+                Severity::Error,
+                format!("{rel} is a dangling symlink"),
+            );
+            let problem = if !known.contains(name.as_str()) {
+                // Same rule as `ion prune`: only links Ion made are removed
+                if owns_link(&link, &rel, lockfile.find(&name).is_some(), &managed)? {
+                    problem.fix(Fix::RemoveLink(link))
+                } else {
+                    problem.hint("Ion didn't create this link; remove it yourself if it's unused")
+                }
+            } else if dir != skills_dir && installer.skill_dir(&name).exists() {
+                problem.skill(&name).fix(Fix::Redeploy(name.clone()))
+            } else {
+                // The skill itself is gone, e.g. after `ion cache gc`
+                problem
+                    .skill(&name)
+                    .hint("run `ion install` to fetch it again")
+            };
+            problems.push(problem);
+        }
+    }
+    Ok(problems)
+}
+
+/// Ion.toml entries that don't resolve to a source, reported one by one so
+/// the rest of the diagnosis still runs.
+fn invalid_entries(manifest: &Manifest) -> Vec<Problem> {
+    manifest
+        .skills
+        .iter()
+        .filter_map(|(name, entry)| {
+            let err = entry.resolve().err()?;
+            Some(
+                Problem::new(
+                    Check::InvalidEntry,
+                    Severity::Error,
+                    format!("Ion.toml entry '{name}' is invalid: {err}"),
+                )
+                .skill(name)
+                .hint("fix or remove the entry in Ion.toml"),
+            )
+        })
+        .collect()
+}
+
+fn stale_gitignore(installer: &SkillInstaller, known: &BTreeSet<&str>) -> Result<Vec<Problem>> {
+    let mut problems = Vec::new();
+    let mut seen = BTreeSet::new();
+    for (name, entry) in managed_skill_entries(installer)? {
+        if known.contains(name.as_str()) || !seen.insert(name.clone()) {
+            continue;
+        }
+        problems.push(
+            Problem::new(
+                Check::StaleGitignore,
+                Severity::Warning,
+                format!(".gitignore lists {entry}, but '{name}' is not installed"),
+            )
+            .skill(&name)
+            .fix(Fix::RemoveGitignore(name.clone())),
+        );
+    }
+    Ok(problems)
+}
+
+fn missing_binaries(lockfile: &Lockfile) -> Vec<Problem> {
+    lockfile
+        .skills
+        .iter()
+        .filter_map(|locked| match &locked.kind {
+            LockedSkillKind::Binary {
+                binary_name,
+                binary_version: Some(version),
+                dev: false,
+                ..
+            } => {
+                let path = binary::binary_path(binary_name, version);
+                (!path.exists()).then(|| {
+                    Problem::new(
+                        Check::MissingBinary,
+                        Severity::Error,
+                        format!(
+                            "binary {binary_name} {version} is missing from {}",
+                            path.display()
+                        ),
+                    )
+                    .skill(&locked.name)
+                    .hint("run `ion install` to download it again")
+                })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn unlinked_instructions(installer: &SkillInstaller, catalog: &Catalog) -> Vec<Problem> {
+    let project_dir = installer.project_dir();
+    if !project_dir.join("AGENTS.md").exists() {
+        return Vec::new();
+    }
+    let mut problems = Vec::new();
+    for (_, file) in catalog.instruction_files(&installer.options().targets) {
+        let path = project_dir.join(file);
+        let problem =
+            |message: String| Problem::new(Check::Instructions, Severity::Warning, message);
+        match std::fs::symlink_metadata(&path) {
+            Err(_) => problems.push(
+                problem(format!("{file} is missing; it should link to AGENTS.md"))
+                    .fix(Fix::LinkInstructions),
+            ),
+            Ok(meta) if meta.is_symlink() => {
+                let target = std::fs::read_link(&path).unwrap_or_default();
+                if target != Path::new("AGENTS.md") {
+                    problems.push(
+                        problem(format!(
+                            "{file} links to {} instead of AGENTS.md",
+                            target.display()
+                        ))
+                        .hint(&format!("remove {file} and run `ion doctor --fix`")),
+                    );
+                }
+            }
+            Ok(_) => {
+                let content = std::fs::read_to_string(&path).unwrap_or_default();
+                if agents::is_agents_pointer(&content) {
+                    problems.push(
+                        problem(format!("{file} only points at AGENTS.md; it can be a link"))
+                            .fix(Fix::LinkInstructions),
+                    );
+                } else {
+                    problems.push(
+                        problem(format!(
+                            "{file} is a separate file, not a link to AGENTS.md"
+                        ))
+                        .hint("run `ion migrate` to merge it into AGENTS.md"),
+                    );
+                }
+            }
+        }
+    }
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::lockfile::LockedSkill;
+    use crate::manifest::ManifestOptions;
+
+    fn options() -> ManifestOptions {
+        ManifestOptions {
+            targets: BTreeMap::from([("claude".to_string(), ".claude/skills".to_string())]),
+            ..Default::default()
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn dangling_links_are_redeployed_or_removed() {
+        let project = tempfile::tempdir().unwrap();
+        let dir = project.path();
+        std::fs::create_dir_all(dir.join(".agents/skills/review")).unwrap();
+        std::fs::create_dir_all(dir.join(".claude/skills")).unwrap();
+        std::os::unix::fs::symlink("../../gone", dir.join(".claude/skills/review")).unwrap();
+        std::os::unix::fs::symlink("../../gone", dir.join(".claude/skills/old")).unwrap();
+        std::os::unix::fs::symlink("/nowhere", dir.join(".agents/skills/fetched")).unwrap();
+        // Made by hand; Ion leaves it alone
+        std::os::unix::fs::symlink("../../gone", dir.join(".claude/skills/mine")).unwrap();
+        std::fs::write(
+            dir.join(".gitignore"),
+            "# Managed by ion\n.claude/skills/old\n",
+        )
+        .unwrap();
+
+        let manifest = Manifest::parse(
+            "[skills]\nreview = { type = \"local\" }\nfetched = \"org/skills/fetched\"\n",
+        )
+        .unwrap();
+        let options = options();
+        let installer = SkillInstaller::new(dir, &options);
+        let catalog = Catalog::builtin();
+        let mut lockfile = Lockfile::default();
+        let problems = diagnose(&installer, &manifest, &lockfile, &catalog).unwrap();
+
+        let messages: Vec<_> = problems.iter().map(|p| p.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                ".agents/skills/fetched is a dangling symlink",
+                ".claude/skills/mine is a dangling symlink",
+                ".claude/skills/old is a dangling symlink",
+                ".claude/skills/review is a dangling symlink",
+                ".gitignore lists .claude/skills/old, but 'old' is not installed",
+            ]
+        );
+        assert!(!problems[0].is_fixable());
+        assert!(problems[0].hint.is_some());
+        assert!(!problems[1].is_fixable());
+
+        for problem in &problems {
+            repair(&installer, &mut lockfile, &catalog, problem).unwrap();
+        }
+        assert!(!dir.join(".claude/skills/old").is_symlink());
+        assert!(dir.join(".claude/skills/mine").is_symlink());
+        assert!(dir.join(".claude/skills/review").exists());
+    }
+
+    #[test]
+    fn invalid_entries_do_not_stop_the_diagnosis() {
+        let project = tempfile::tempdir().unwrap();
+        let manifest = Manifest::parse("[skills]\nbroken = \"nowhere:team/skills\"\n").unwrap();
+        let mut lockfile = Lockfile::default();
+        lockfile.upsert(LockedSkill::local("leftover"));
+        let options = options();
+        let installer = SkillInstaller::new(project.path(), &options);
+        let problems = diagnose(&installer, &manifest, &lockfile, &Catalog::builtin()).unwrap();
+
+        let checks: Vec<_> = problems
+            .iter()
+            .map(|p| (p.check, p.skill.as_deref().unwrap()))
+            .collect();
+        assert_eq!(
+            checks,
+            [
+                (Check::InvalidEntry, "broken"),
+                (Check::StaleLockEntry, "leftover"),
+            ]
+        );
+        assert_eq!(problems[0].severity, Severity::Error);
+        assert!(!problems[0].is_fixable());
+    }
+
+    #[test]
+    fn stale_gitignore_and_lock_entries_are_dropped() {
+        let project = tempfile::tempdir().unwrap();
+        let dir = project.path();
+        std::fs::write(
+            dir.join(".gitignore"),
+            "# Managed by ion\n.agents/skills/kept\n.claude/skills/kept\n.agents/skills/removed\n.claude/skills/removed\nCLAUDE.md\n",
+        )
+        .unwrap();
+
+        let manifest = Manifest::parse("[skills]\nkept = \"org/skills/kept\"\n").unwrap();
+        let mut lockfile = Lockfile::default();
+        lockfile.upsert(LockedSkill::git(
+            "kept",
+            "https://github.com/org/skills.git",
+            "abc".to_string(),
+            "sha256:x".to_string(),
+        ));
+        lockfile.upsert(LockedSkill::local("leftover"));
+        let options = options();
+        let installer = SkillInstaller::new(dir, &options);
+        let catalog = Catalog::builtin();
+        let problems = diagnose(&installer, &manifest, &lockfile, &catalog).unwrap();
+
+        let checks: Vec<_> = problems
+            .iter()
+            .map(|p| (p.check, p.skill.as_deref().unwrap()))
+            .collect();
+        assert_eq!(
+            checks,
+            [
+                (Check::StaleGitignore, "removed"),
+                (Check::StaleLockEntry, "leftover"),
+            ]
+        );
+
+        for problem in &problems {
+            assert!(repair(&installer, &mut lockfile, &catalog, problem).unwrap());
+        }
+        let content = std::fs::read_to_string(dir.join(".gitignore")).unwrap();
+        assert!(!content.contains("removed"));
+        assert!(content.contains(".claude/skills/kept"));
+        assert!(lockfile.find("leftover").is_none());
+        assert!(lockfile.find("kept").is_some());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn instructions_are_linked_to_agents_md() {
+        let project = tempfile::tempdir().unwrap();
+        let dir = project.path();
+        std::fs::write(dir.join("AGENTS.md"), "# Agents\n").unwrap();
+        let options = options();
+        let installer = SkillInstaller::new(dir, &options);
+        let catalog = Catalog::builtin();
+        let manifest = Manifest::empty();
+        let mut lockfile = Lockfile::default();
+
+        let problems = diagnose(&installer, &manifest, &lockfile, &catalog).unwrap();
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].check, Check::Instructions);
+        repair(&installer, &mut lockfile, &catalog, &problems[0]).unwrap();
+        assert_eq!(
+            std::fs::read_link(dir.join("CLAUDE.md")).unwrap(),
+            Path::new("AGENTS.md")
+        );
+        assert!(
+            diagnose(&installer, &manifest, &lockfile, &catalog)
+                .unwrap()
+                .is_empty()
+        );
+
+        std::fs::remove_file(dir.join("CLAUDE.md")).unwrap();
+        std::fs::write(dir.join("CLAUDE.md"), "Own instructions\n").unwrap();
+        let problems = diagnose(&installer, &manifest, &lockfile, &catalog).unwrap();
+        assert!(!problems[0].is_fixable());
+    }
+}
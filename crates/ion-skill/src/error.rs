@@ -34,6 +34,15 @@ pub enum Error {
     #[error("HTTP error: {0}")]
     Http(String),
 
+    #[error("Lockfile mismatch: {0}")]
+    LockMismatch(String),
+
+    #[error("Offline: {0}")]
+    Offline(String),
+
+    #[error("Dependency error: {0}")]
+    Dependency(String),
+
     #[error("{0}")]
     Other(String),
 
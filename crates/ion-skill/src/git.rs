@@ -2,11 +2,30 @@
 
 use std::path::{Path, PathBuf};
 
-use crate::{Error, Result};
+use crate::source::Channel;
+use crate::{Error, Result, credentials, offline};
 
 /// Clone a git repository to a target directory. If it already exists, fetch updates.
+///
+/// New clones are blob-less and sparse, so file contents are only downloaded
+/// for the paths a snapshot checks out (see [`ensure_snapshot`]).
 pub fn clone_or_fetch(url: &str, target: &Path) -> Result<()> {
-    Ok(ionem::shell::git::clone_or_fetch(url, target)?)
+    credentials::register_git(url)?;
+    Ok(ionem::shell::git::partial_clone_or_fetch(url, target)?)
+}
+
+/// Like [`clone_or_fetch`], but in offline mode uses the existing clone at
+/// `target` as-is and fails if there is none.
+pub fn sync_repo(url: &str, target: &Path) -> Result<()> {
+    if offline::is_offline() {
+        if target.exists() {
+            return Ok(());
+        }
+        return Err(Error::Offline(format!(
+            "{url} is not in the local cache; run once without --offline to fetch it"
+        )));
+    }
+    clone_or_fetch(url, target)
 }
 
 /// Checkout a specific ref (branch, tag, or commit SHA).
@@ -14,6 +33,16 @@ pub fn checkout(repo_path: &Path, rev: &str) -> Result<()> {
     Ok(ionem::shell::git::repo(repo_path).checkout(rev)?)
 }
 
+/// List all tag names in a repository.
+pub fn tags(repo_path: &Path) -> Result<Vec<String>> {
+    Ok(ionem::shell::git::repo(repo_path).tag_list()?)
+}
+
+/// List tag names matching a glob `pattern`, highest version first.
+pub fn tags_matching(repo_path: &Path, pattern: &str) -> Result<Vec<String>> {
+    Ok(ionem::shell::git::repo(repo_path).tag_list_matching(pattern)?)
+}
+
 /// Get the current HEAD commit SHA.
 pub fn head_commit(repo_path: &Path) -> Result<String> {
     Ok(ionem::shell::git::repo(repo_path).head_commit()?)
@@ -63,6 +92,43 @@ fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
     Ok(())
 }
 
+/// List the files under `dir`, relative to it and sorted, skipping `.git`.
+pub fn list_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    collect_files(dir, &mut files)?;
+    let mut files: Vec<PathBuf> = files
+        .into_iter()
+        .filter_map(|f| f.strip_prefix(dir).ok().map(Path::to_path_buf))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// How a file in a checked-out directory differs from its commit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileChange {
+    Modified,
+    Missing,
+    Extra,
+}
+
+/// List files under `dir` (inside a git working tree) that differ from the
+/// checked-out commit, with paths relative to `dir`.
+pub fn changed_files(dir: &Path) -> Result<Vec<(FileChange, String)>> {
+    let entries = ionem::shell::git::repo(dir).status_entries()?;
+    Ok(entries
+        .into_iter()
+        .map(|(code, path)| {
+            let change = match code.as_str() {
+                "??" | "!!" => FileChange::Extra,
+                c if c.contains('D') => FileChange::Missing,
+                _ => FileChange::Modified,
+            };
+            (change, path)
+        })
+        .collect())
+}
+
 /// Get the default branch name for a repo by checking `origin/HEAD` or falling back
 /// to `symbolic-ref HEAD`.
 pub fn default_branch(repo_path: &Path) -> Result<String> {
@@ -75,6 +141,152 @@ pub fn reset_to_remote_head(repo_path: &Path) -> Result<()> {
     Ok(ionem::shell::git::repo(repo_path).reset_to_remote_head()?)
 }
 
+/// Resolve a ref (branch, tag, or commit SHA) to a full commit SHA.
+///
+/// Branch names resolve against the remote-tracking branch first, so a
+/// branch pin follows whatever the last fetch brought in.
+pub fn resolve_commit(repo_path: &Path, rev: &str) -> Result<String> {
+    let repo = ionem::shell::git::repo(repo_path);
+    if let Ok(commit) = repo.rev_parse(&format!("origin/{rev}")) {
+        return Ok(commit);
+    }
+    repo.rev_parse(rev).map_err(|_| {
+        Error::Git(format!(
+            "Revision '{rev}' not found in {}",
+            repo_path.display()
+        ))
+    })
+}
+
+/// Get the commit at the tip of the remote's default branch.
+/// Call this after `clone_or_fetch()`; the working tree is left untouched.
+pub fn remote_head_commit(repo_path: &Path) -> Result<String> {
+    let branch = default_branch(repo_path)?;
+    resolve_commit(repo_path, &branch)
+}
+
+/// Resolve a tracking channel to a commit. For tag channels, also returns
+/// the tag the commit was taken from.
+/// Call this after `clone_or_fetch()`; the working tree is left untouched.
+pub fn channel_commit(repo_path: &Path, channel: &Channel) -> Result<(String, Option<String>)> {
+    let repo = ionem::shell::git::repo(repo_path);
+    let not_found = || Error::Git(format!("{channel} not found in {}", repo_path.display()));
+    match channel {
+        Channel::Branch(branch) => {
+            let commit = repo
+                .rev_parse(&format!("refs/remotes/origin/{branch}"))
+                .map_err(|_| not_found())?;
+            Ok((commit, None))
+        }
+        Channel::Tag(tag) => {
+            let commit = repo
+                .rev_parse(&format!("refs/tags/{tag}"))
+                .map_err(|_| not_found())?;
+            Ok((commit, Some(tag.clone())))
+        }
+        Channel::LatestMatching(pattern) => {
+            let tag = tags_matching(repo_path, pattern)?
+                .into_iter()
+                .next()
+                .ok_or_else(not_found)?;
+            let commit = repo.rev_parse(&format!("refs/tags/{tag}"))?;
+            Ok((commit, Some(tag)))
+        }
+    }
+}
+
+/// Materialize a snapshot of `commit` at `snapshot_dir`.
+///
+/// Snapshots are detached worktrees of the cached clone at `repo_path`. They
+/// are never checked out to another commit, so anything symlinked into one
+/// stays stable. An existing snapshot of the same commit is reused.
+///
+/// With `path` set, the snapshot is a sparse checkout of top-level files plus
+/// `path` (and `skills/<path>`, where skills are looked up as a fallback), so
+/// a partial clone only downloads what that skill needs. Reusing the
+/// snapshot for another path grows the checkout; `None` checks out everything.
+pub fn ensure_snapshot(
+    repo_path: &Path,
+    commit: &str,
+    snapshot_dir: &Path,
+    path: Option<&str>,
+) -> Result<()> {
+    if snapshot_dir.exists() {
+        if head_commit(snapshot_dir).ok().as_deref() == Some(commit) {
+            return checkout_paths(snapshot_dir, path);
+        }
+        // Half-created or orphaned snapshot — rebuild it.
+        std::fs::remove_dir_all(snapshot_dir).map_err(Error::Io)?;
+    }
+
+    let repo = ionem::shell::git::repo(repo_path);
+    repo.worktree_prune()?;
+    if let Some(parent) = snapshot_dir.parent() {
+        std::fs::create_dir_all(parent).map_err(Error::Io)?;
+    }
+    repo.worktree_add_detached(snapshot_dir, commit)?;
+    checkout_paths(snapshot_dir, path)
+}
+
+/// Make sure the worktree at `dir` has `path` checked out, or every file if
+/// `path` is `None`. Worktrees that aren't sparse already have everything.
+fn checkout_paths(dir: &Path, path: Option<&str>) -> Result<()> {
+    let repo = ionem::shell::git::repo(dir);
+    let Some(included) = repo.sparse_checkout_list()? else {
+        return Ok(());
+    };
+    let Some(path) = path.map(|p| p.trim_matches('/')).filter(|p| !p.is_empty()) else {
+        return Ok(repo.sparse_checkout_disable()?);
+    };
+
+    let fallback = format!("skills/{path}");
+    let missing: Vec<&str> = [path, fallback.as_str()]
+        .into_iter()
+        .filter(|dir| !included.iter().any(|i| i == dir))
+        .collect();
+    if missing.is_empty() {
+        return Ok(());
+    }
+    if included.is_empty() {
+        // Fresh worktree: it inherits the clone's top-level-only checkout
+        Ok(repo.sparse_checkout_set(&missing)?)
+    } else {
+        Ok(repo.sparse_checkout_add(&missing)?)
+    }
+}
+
+/// Check out every file in the cached clone at `repo_path`, turning off the
+/// sparse checkout it was created with.
+pub fn full_checkout(repo_path: &Path) -> Result<()> {
+    checkout_paths(repo_path, None)
+}
+
+/// Count the objects in the cached clone at `repo_path` and how many of them
+/// it hasn't downloaded, as `(missing, total)`.
+pub fn object_counts(repo_path: &Path) -> Result<(usize, usize)> {
+    Ok(ionem::shell::git::repo(repo_path).object_counts()?)
+}
+
+/// Reconnect snapshots to their clone after either was moved on disk.
+pub fn repair_snapshots(repo_path: &Path, snapshot_dirs: &[PathBuf]) -> Result<()> {
+    if snapshot_dirs.is_empty() {
+        return Ok(());
+    }
+    let paths: Vec<&Path> = snapshot_dirs.iter().map(PathBuf::as_path).collect();
+    Ok(ionem::shell::git::repo(repo_path).worktree_repair(&paths)?)
+}
+
+/// Delete a snapshot created by [`ensure_snapshot`] and unregister its worktree.
+pub fn remove_snapshot(repo_path: &Path, snapshot_dir: &Path) -> Result<()> {
+    if snapshot_dir.exists() {
+        std::fs::remove_dir_all(snapshot_dir).map_err(Error::Io)?;
+    }
+    if repo_path.join(".git").exists() {
+        ionem::shell::git::repo(repo_path).worktree_prune()?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -109,6 +321,38 @@ mod tests {
         assert!(branch == "main" || branch == "master", "got: {branch}");
     }
 
+    #[test]
+    fn channel_commit_picks_highest_matching_tag() {
+        let tmp = tempfile::tempdir().unwrap();
+        let repo = tmp.path();
+        let git = |args: &[&str]| {
+            std::process::Command::new("git")
+                .args(args)
+                .current_dir(repo)
+                .output()
+                .unwrap();
+        };
+        git(&["init"]);
+        git(&["commit", "--allow-empty", "-m", "one"]);
+        git(&["tag", "v1.10.0"]);
+        git(&["commit", "--allow-empty", "-m", "two"]);
+        git(&["tag", "v1.9.0"]);
+        git(&["tag", "nightly"]);
+
+        let (commit, tag) = channel_commit(repo, &Channel::LatestMatching("v*".into())).unwrap();
+        assert_eq!(tag.as_deref(), Some("v1.10.0"));
+        assert_eq!(commit, resolve_commit(repo, "v1.10.0").unwrap());
+
+        let (_, tag) = channel_commit(repo, &Channel::Tag("nightly".into())).unwrap();
+        assert_eq!(tag.as_deref(), Some("nightly"));
+
+        let err = channel_commit(repo, &Channel::LatestMatching("release-*".into())).unwrap_err();
+        assert!(
+            err.to_string().contains("tag:latest-matching:release-*"),
+            "got: {err}"
+        );
+    }
+
     #[test]
     fn checksum_dir_changes_with_content() {
         let dir = tempfile::tempdir().unwrap();
@@ -153,4 +397,155 @@ mod tests {
 
         assert_ne!(commit1, commit2, "HEAD should have advanced");
     }
+
+    #[test]
+    fn snapshots_stay_on_their_commit() {
+        let tmp = tempfile::tempdir().unwrap();
+
+        let upstream = tmp.path().join("upstream");
+        std::fs::create_dir(&upstream).unwrap();
+        std::process::Command::new("git")
+            .args(["init"])
+            .current_dir(&upstream)
+            .output()
+            .unwrap();
+        std::process::Command::new("git")
+            .args(["commit", "--allow-empty", "-m", "first"])
+            .current_dir(&upstream)
+            .output()
+            .unwrap();
+
+        let clone_dir = tmp.path().join("clone");
+        clone_or_fetch(&upstream.display().to_string(), &clone_dir).unwrap();
+        let commit1 = remote_head_commit(&clone_dir).unwrap();
+        let snap1 = tmp.path().join("store").join(&commit1);
+        ensure_snapshot(&clone_dir, &commit1, &snap1, None).unwrap();
+
+        std::process::Command::new("git")
+            .args(["commit", "--allow-empty", "-m", "second"])
+            .current_dir(&upstream)
+            .output()
+            .unwrap();
+        clone_or_fetch(&upstream.display().to_string(), &clone_dir).unwrap();
+        let commit2 = remote_head_commit(&clone_dir).unwrap();
+        assert_ne!(commit1, commit2, "remote head should have advanced");
+
+        let snap2 = tmp.path().join("store").join(&commit2);
+        ensure_snapshot(&clone_dir, &commit2, &snap2, None).unwrap();
+        // Re-ensuring an existing snapshot is a no-op
+        ensure_snapshot(&clone_dir, &commit1, &snap1, None).unwrap();
+
+        assert_eq!(head_commit(&snap1).unwrap(), commit1);
+        assert_eq!(head_commit(&snap2).unwrap(), commit2);
+        assert_eq!(resolve_commit(&clone_dir, &commit1).unwrap(), commit1);
+
+        remove_snapshot(&clone_dir, &snap1).unwrap();
+        assert!(!snap1.exists());
+        assert!(snap2.exists());
+    }
+
+    #[test]
+    fn sparse_snapshots_grow_on_demand() {
+        let tmp = tempfile::tempdir().unwrap();
+
+        let upstream = tmp.path().join("upstream");
+        for dir in ["skills/alpha", "skills/beta", "gamma"] {
+            std::fs::create_dir_all(upstream.join(dir)).unwrap();
+            std::fs::write(upstream.join(dir).join("SKILL.md"), dir).unwrap();
+        }
+        std::fs::write(upstream.join("README.md"), "readme").unwrap();
+        for args in [
+            &["init"][..],
+            &["config", "uploadpack.allowFilter", "true"],
+            &["add", "."],
+            &["commit", "-m", "init"],
+        ] {
+            std::process::Command::new("git")
+                .args(args)
+                .current_dir(&upstream)
+                .output()
+                .unwrap();
+        }
+
+        let clone_dir = tmp.path().join("clone");
+        let url = format!("file://{}", upstream.display());
+        clone_or_fetch(&url, &clone_dir).unwrap();
+        let (missing, total) = object_counts(&clone_dir).unwrap();
+        assert!(missing > 0 && missing < total, "{missing} of {total}");
+
+        let commit = remote_head_commit(&clone_dir).unwrap();
+        let snap = tmp.path().join("store").join(&commit);
+        ensure_snapshot(&clone_dir, &commit, &snap, Some("alpha")).unwrap();
+        assert!(snap.join("README.md").exists());
+        assert!(snap.join("skills/alpha/SKILL.md").exists());
+        assert!(!snap.join("skills/beta").exists());
+        assert!(!snap.join("gamma").exists());
+
+        // Another skill from the same commit grows the checkout
+        ensure_snapshot(&clone_dir, &commit, &snap, Some("gamma")).unwrap();
+        assert!(snap.join("skills/alpha/SKILL.md").exists());
+        assert!(snap.join("gamma/SKILL.md").exists());
+        assert!(!snap.join("skills/beta").exists());
+        assert!(changed_files(&snap).unwrap().is_empty());
+
+        // No path means the whole tree
+        ensure_snapshot(&clone_dir, &commit, &snap, None).unwrap();
+        assert!(snap.join("skills/beta/SKILL.md").exists());
+    }
+
+    #[test]
+    fn resolve_commit_unknown_rev_errors() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::process::Command::new("git")
+            .args(["init"])
+            .current_dir(tmp.path())
+            .output()
+            .unwrap();
+        std::process::Command::new("git")
+            .args(["commit", "--allow-empty", "-m", "init"])
+            .current_dir(tmp.path())
+            .output()
+            .unwrap();
+        let err = resolve_commit(tmp.path(), "no-such-branch").unwrap_err();
+        assert!(err.to_string().contains("no-such-branch"), "got: {err}");
+    }
+
+    #[test]
+    fn changed_files_reports_paths_relative_to_dir() {
+        let tmp = tempfile::tempdir().unwrap();
+        let repo = tmp.path();
+        let skill = repo.join("skills/demo");
+        std::fs::create_dir_all(&skill).unwrap();
+        std::fs::write(skill.join("SKILL.md"), "hello").unwrap();
+        std::fs::write(skill.join("notes.md"), "notes").unwrap();
+        std::fs::write(repo.join("README.md"), "readme").unwrap();
+        for args in [
+            &["init"][..],
+            &["add", "."][..],
+            &["commit", "-m", "init"][..],
+        ] {
+            std::process::Command::new("git")
+                .args(args)
+                .current_dir(repo)
+                .output()
+                .unwrap();
+        }
+        assert!(changed_files(&skill).unwrap().is_empty());
+
+        std::fs::write(skill.join("SKILL.md"), "tampered").unwrap();
+        std::fs::remove_file(skill.join("notes.md")).unwrap();
+        std::fs::write(skill.join("extra.sh"), "echo hi").unwrap();
+        std::fs::write(repo.join("README.md"), "outside the skill").unwrap();
+
+        let mut changes = changed_files(&skill).unwrap();
+        changes.sort_by(|a, b| a.1.cmp(&b.1));
+        assert_eq!(
+            changes,
+            vec![
+                (FileChange::Modified, "SKILL.md".to_string()),
+                (FileChange::Extra, "extra.sh".to_string()),
+                (FileChange::Missing, "notes.md".to_string()),
+            ]
+        );
+    }
 }
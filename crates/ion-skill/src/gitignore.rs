@@ -49,17 +49,23 @@ pub fn add_skill_entries(
     target_paths: &[&str],
     skills_dir: &str,
 ) -> Result<()> {
-    let gitignore_path = project_dir.join(".gitignore");
-    let mut content = std::fs::read_to_string(&gitignore_path).unwrap_or_default();
-
-    let mut entries_to_add = vec![format!("{skills_dir}/{skill_name}")];
+    let mut entries = vec![format!("{skills_dir}/{skill_name}")];
     for target in target_paths {
-        entries_to_add.push(format!("{target}/{skill_name}"));
+        entries.push(format!("{target}/{skill_name}"));
     }
+    add_entries(project_dir, &entries)
+}
+
+/// Add entries to the managed section of `.gitignore`, such as the paths
+/// from [`SkillInstaller::gitignore_entries`](crate::installer::SkillInstaller::gitignore_entries).
+/// Idempotent — won't duplicate existing entries.
+pub fn add_entries(project_dir: &Path, entries: &[String]) -> Result<()> {
+    let gitignore_path = project_dir.join(".gitignore");
+    let mut content = std::fs::read_to_string(&gitignore_path).unwrap_or_default();
 
     // Filter out entries that already exist
     let existing_lines: Vec<&str> = content.lines().map(|l| l.trim()).collect();
-    let new_entries: Vec<&String> = entries_to_add
+    let new_entries: Vec<&String> = entries
         .iter()
         .filter(|e| !existing_lines.contains(&e.as_str()))
         .collect();
@@ -115,8 +121,31 @@ pub fn ensure_agent_file_ignored(project_dir: &Path, filename: &str) -> Result<(
     Ok(())
 }
 
+/// Entries in the "# Managed by ion" sections of `.gitignore`, in file order.
+/// A section runs until the next comment line.
+pub fn managed_entries(project_dir: &Path) -> Result<Vec<String>> {
+    let content = match std::fs::read_to_string(project_dir.join(".gitignore")) {
+        Ok(c) => c,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(Error::Io(e)),
+    };
+
+    let mut entries = Vec::new();
+    let mut managed = false;
+    for line in content.lines().map(str::trim) {
+        if line.starts_with('#') {
+            managed = line == "# Managed by ion";
+        } else if managed && !line.is_empty() {
+            entries.push(line.to_string());
+        }
+    }
+    Ok(entries)
+}
+
 /// Remove all gitignore entries for a specific skill.
-/// Removes any line ending with `/<name>` under the managed section.
+/// Removes any line ending with `/<name>`, or with `/<name>.<ext>` for files
+/// rendered for a target. Skill names can't contain dots, so these never
+/// belong to another skill.
 /// Cleans up the "# Managed by ion" header if no managed entries remain.
 pub fn remove_skill_entries(project_dir: &Path, skill_name: &str) -> Result<()> {
     let gitignore_path = project_dir.join(".gitignore");
@@ -127,15 +156,51 @@ pub fn remove_skill_entries(project_dir: &Path, skill_name: &str) -> Result<()>
     };
 
     let skill_suffix = format!("/{skill_name}");
+    let rendered_prefix = format!("/{skill_name}.");
+    let filtered: Vec<&str> = content
+        .lines()
+        .filter(|line| {
+            let trimmed = line.trim();
+            let rendered = trimmed
+                .rfind(&rendered_prefix)
+                .is_some_and(|i| !trimmed[i + 1..].contains('/'));
+            !trimmed.ends_with(&skill_suffix) && !rendered
+        })
+        .collect();
+
+    write_filtered(&gitignore_path, &filtered)
+}
+
+/// Remove exactly these lines from the "# Managed by ion" sections of
+/// `.gitignore`, leaving the user's own lines alone even if they match.
+/// Cleans up the header if no managed entries remain.
+pub fn remove_entries(project_dir: &Path, entries: &[String]) -> Result<()> {
+    let gitignore_path = project_dir.join(".gitignore");
+    let content = match std::fs::read_to_string(&gitignore_path) {
+        Ok(c) => c,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(Error::Io(e)),
+    };
+
+    let mut managed = false;
     let filtered: Vec<&str> = content
         .lines()
         .filter(|line| {
             let trimmed = line.trim();
-            !trimmed.ends_with(&skill_suffix)
+            if trimmed.starts_with('#') {
+                managed = trimmed == "# Managed by ion";
+                return true;
+            }
+            !managed || !entries.iter().any(|e| e == trimmed)
         })
         .collect();
 
-    // Clean up empty managed section
+    write_filtered(&gitignore_path, &filtered)
+}
+
+/// Write the remaining `.gitignore` lines, dropping any "# Managed by ion"
+/// header left without entries.
+fn write_filtered(gitignore_path: &Path, filtered: &[&str]) -> Result<()> {
     let mut result: Vec<&str> = Vec::new();
     for (i, line) in filtered.iter().enumerate() {
         if line.trim() == "# Managed by ion" {
@@ -160,7 +225,7 @@ pub fn remove_skill_entries(project_dir: &Path, skill_name: &str) -> Result<()>
         output.push('\n');
     }
 
-    std::fs::write(&gitignore_path, &output).map_err(Error::Io)?;
+    std::fs::write(gitignore_path, &output).map_err(Error::Io)?;
     Ok(())
 }
 
@@ -189,6 +254,42 @@ mod tests {
         assert_eq!(missing, vec![".agents/", ".claude/"]);
     }
 
+    #[test]
+    fn managed_entries_skip_user_lines() {
+        let project = tempfile::tempdir().unwrap();
+        std::fs::write(
+            project.path().join(".gitignore"),
+            "target/\n\n# Managed by ion\n.agents/skills/a\n.claude/skills/a\n\n# Mine\nnotes/\n",
+        )
+        .unwrap();
+
+        let entries = managed_entries(project.path()).unwrap();
+
+        assert_eq!(entries, vec![".agents/skills/a", ".claude/skills/a"]);
+    }
+
+    #[test]
+    fn remove_entries_only_touches_managed_lines() {
+        let project = tempfile::tempdir().unwrap();
+        std::fs::write(
+            project.path().join(".gitignore"),
+            ".claude/skills/old\n\n# Managed by ion\n.agents/skills/old\n.claude/skills/old\n",
+        )
+        .unwrap();
+
+        remove_entries(
+            project.path(),
+            &[
+                ".agents/skills/old".to_string(),
+                ".claude/skills/old".to_string(),
+            ],
+        )
+        .unwrap();
+
+        let content = std::fs::read_to_string(project.path().join(".gitignore")).unwrap();
+        assert_eq!(content, ".claude/skills/old\n");
+    }
+
     #[test]
     fn append_creates_gitignore() {
         let project = tempfile::tempdir().unwrap();
@@ -297,6 +398,23 @@ mod tests {
         assert!(content.contains("writing-plans"));
     }
 
+    #[test]
+    fn remove_skill_gitignore_entries_includes_rendered_files() {
+        let project = tempfile::tempdir().unwrap();
+        let entries = [
+            ".agents/skills/review".to_string(),
+            ".cursor/rules/review.mdc".to_string(),
+            ".cursor/rules/review-extra.mdc".to_string(),
+        ];
+        add_entries(project.path(), &entries).unwrap();
+
+        remove_skill_entries(project.path(), "review").unwrap();
+
+        let content = std::fs::read_to_string(project.path().join(".gitignore")).unwrap();
+        assert!(!content.contains("rules/review.mdc"));
+        assert!(content.contains(".cursor/rules/review-extra.mdc"));
+    }
+
     #[test]
     fn remove_skill_gitignore_entries_noop_if_not_present() {
         let project = tempfile::tempdir().unwrap();
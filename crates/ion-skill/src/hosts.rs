@@ -0,0 +1,130 @@
+//! Named git hosts — expand `gitlab:group/repo` style source shorthands into clone and web URLs.
+
+use std::collections::BTreeMap;
+use std::sync::RwLock;
+
+use crate::config::{CloneProtocol, HostConfig};
+use crate::{Error, Result};
+
+static HOSTS: RwLock<BTreeMap<String, HostConfig>> = RwLock::new(BTreeMap::new());
+
+/// Hosts usable as shorthand prefixes without any configuration.
+const BUILTIN_HOSTS: &[(&str, &str)] = &[
+    ("gitlab", "https://gitlab.com"),
+    ("bitbucket", "https://bitbucket.org"),
+    ("gitea", "https://gitea.com"),
+];
+
+/// Register the `[hosts]` table from the global config for the rest of the process.
+///
+/// Configured hosts take precedence over built-in hosts of the same name, so
+/// `[hosts.gitlab]` can point the `gitlab:` prefix at a self-hosted instance.
+pub fn set_hosts(hosts: BTreeMap<String, HostConfig>) {
+    *HOSTS.write().unwrap_or_else(|e| e.into_inner()) = hosts;
+}
+
+/// Look up a host by name, checking configured hosts before built-in ones.
+pub fn lookup(name: &str) -> Option<HostConfig> {
+    if let Some(host) = HOSTS.read().unwrap_or_else(|e| e.into_inner()).get(name) {
+        return Some(host.clone());
+    }
+    BUILTIN_HOSTS
+        .iter()
+        .find(|(builtin, _)| *builtin == name)
+        .map(|(_, url)| HostConfig {
+            url: url.to_string(),
+            protocol: CloneProtocol::Https,
+        })
+}
+
+/// Like [`lookup`], but fails with a hint when the host is unknown.
+pub fn require(name: &str) -> Result<HostConfig> {
+    lookup(name).ok_or_else(|| {
+        Error::Source(format!(
+            "Unknown git host '{name}'. Add a [hosts.{name}] entry with a `url` to ~/.config/ion/config.toml"
+        ))
+    })
+}
+
+/// Split a `<host>:<repo>` shorthand into the host name and the rest.
+///
+/// Returns `None` for strings that aren't shaped like a shorthand, such as
+/// URLs (`https://…`), SCP-style remotes (`git@host:…`), Windows paths
+/// (`C:\skills`, `C:/skills`), and paths. Callers still have to check that
+/// the name is a known host.
+pub fn split_shorthand(source: &str) -> Option<(&str, &str)> {
+    let (name, rest) = source.split_once(':')?;
+    // A single letter is a drive prefix, never a host name.
+    let valid_name = name.len() > 1
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    (valid_name && !rest.is_empty() && !rest.starts_with('/') && !rest.contains('\\'))
+        .then_some((name, rest))
+}
+
+impl HostConfig {
+    /// Clone URL for `repo` (`owner/repo`, or `group/subgroup/repo`) on this host.
+    pub fn clone_url(&self, repo: &str) -> String {
+        let base = self.url.trim_end_matches('/');
+        match self.protocol {
+            CloneProtocol::Https => format!("{base}/{repo}.git"),
+            CloneProtocol::Ssh => {
+                let authority = base.split_once("://").map_or(base, |(_, rest)| rest);
+                let host = authority.split('/').next().unwrap_or(authority);
+                format!("git@{host}:{repo}.git")
+            }
+        }
+    }
+
+    /// Web page for `repo` on this host.
+    pub fn web_url(&self, repo: &str) -> String {
+        format!("{}/{repo}", self.url.trim_end_matches('/'))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_shorthand_recognizes_prefixes() {
+        assert_eq!(
+            split_shorthand("gitlab:group/sub/repo"),
+            Some(("gitlab", "group/sub/repo"))
+        );
+        assert_eq!(split_shorthand("https://gitlab.com/a/b"), None);
+        assert_eq!(split_shorthand("git@gitlab.com:a/b.git"), None);
+        assert_eq!(split_shorthand("owner/repo"), None);
+        assert_eq!(split_shorthand("gitlab:"), None);
+        assert_eq!(split_shorthand("C:\\skills\\x"), None);
+        assert_eq!(split_shorthand("C:skills/x"), None);
+        assert_eq!(split_shorthand("skills:v2\\x"), None);
+    }
+
+    #[test]
+    fn builtin_hosts_clone_over_https() {
+        let host = lookup("bitbucket").unwrap();
+        assert_eq!(
+            host.clone_url("team/skills"),
+            "https://bitbucket.org/team/skills.git"
+        );
+        assert!(lookup("nowhere").is_none());
+    }
+
+    #[test]
+    fn ssh_clone_url_uses_host_of_base_url() {
+        let host = HostConfig {
+            url: "https://git.example.com/".to_string(),
+            protocol: CloneProtocol::Ssh,
+        };
+        assert_eq!(
+            host.clone_url("team/skills"),
+            "git@git.example.com:team/skills.git"
+        );
+        assert_eq!(
+            host.web_url("team/skills"),
+            "https://git.example.com/team/skills"
+        );
+    }
+}
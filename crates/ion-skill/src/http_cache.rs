@@ -0,0 +1,433 @@
+//! Cached HTTP skills — revalidate single-file skills with ETag/Last-Modified and refuse content that no longer matches the lock.
+//!
+//! Each URL has an entry directory holding one immutable copy per accepted
+//! body, at `<checksum>/<name>/SKILL.md`, plus the validators of the current
+//! copy. Projects link to the copy they locked, so accepting new content in
+//! one project never changes what another project has deployed.
+
+use std::path::{Path, PathBuf};
+
+use reqwest::StatusCode;
+use reqwest::header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use serde::{Deserialize, Serialize};
+
+use crate::installer::{cache_key, create_skill_symlink, data_dir, legacy_cache_key};
+use crate::registry::move_dir;
+use crate::source::SkillSource;
+use crate::{Error, Result, credentials, git, offline};
+
+/// File in an entry directory holding the validators of its last download.
+const VALIDATORS_FILE: &str = "http-cache.json";
+
+/// Validators from the response that produced the current SKILL.md.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Validators {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    etag: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    last_modified: Option<String>,
+    /// Checksum of the content they describe, naming the directory it is
+    /// stored in.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    checksum: Option<String>,
+}
+
+impl Validators {
+    fn load(entry_dir: &Path) -> Self {
+        std::fs::read_to_string(entry_dir.join(VALIDATORS_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self, entry_dir: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| Error::Other(format!("Failed to serialize HTTP cache entry: {e}")))?;
+        std::fs::write(entry_dir.join(VALIDATORS_FILE), content).map_err(Error::Io)
+    }
+}
+
+/// What the server returned for a revalidation request.
+enum Response {
+    /// The cached copy in this directory is still current (`304 Not Modified`).
+    NotModified(PathBuf),
+    /// New content, along with the validators to store with it.
+    Modified {
+        body: String,
+        validators: Validators,
+    },
+}
+
+/// The cache directory holding the SKILL.md an HTTP skill's URL served
+/// last, or `None` if it was never downloaded.
+pub fn cached_dir(source: &SkillSource) -> Result<Option<PathBuf>> {
+    let entry_dir = entry_dir(source)?;
+    Ok(current_dir(&entry_dir, &source.display_name()))
+}
+
+/// The directory holding everything cached for an HTTP skill's URL.
+fn entry_dir(source: &SkillSource) -> Result<PathBuf> {
+    let url = source.http_skill_url()?;
+    let root = data_dir();
+    migrate_legacy_entry(&root, &url)?;
+    Ok(root.join(cache_key(&url)))
+}
+
+/// The immutable directory holding content with `checksum`.
+fn content_dir(entry_dir: &Path, checksum: &str, name: &str) -> PathBuf {
+    let key = checksum.strip_prefix("sha256:").unwrap_or(checksum);
+    entry_dir.join(key).join(name)
+}
+
+/// The directory of the content the validators describe.
+///
+/// Older versions of Ion kept a single copy at `<entry>/<name>`, which is
+/// copied into its checksum directory here. The old copy stays in place so
+/// project links into it keep resolving until the skill is next deployed.
+fn current_dir(entry_dir: &Path, name: &str) -> Option<PathBuf> {
+    let mut validators = Validators::load(entry_dir);
+    if let Some(checksum) = &validators.checksum {
+        let dir = content_dir(entry_dir, checksum, name);
+        return dir.join("SKILL.md").is_file().then_some(dir);
+    }
+
+    let legacy = entry_dir.join(name).join("SKILL.md");
+    let body = std::fs::read_to_string(legacy).ok()?;
+    match write_content(entry_dir, name, &body) {
+        Ok((dir, checksum)) => {
+            validators.checksum = Some(checksum);
+            if let Err(e) = validators.save(entry_dir) {
+                log::warn!("Failed to record HTTP cache entry: {e}");
+            }
+            Some(dir)
+        }
+        Err(e) => {
+            log::warn!("Failed to migrate HTTP cache entry: {e}");
+            None
+        }
+    }
+}
+
+/// Move an entry cached under the key older versions of Ion used to its
+/// current key, leaving a symlink at the old location so project links
+/// into it keep resolving until the skill is next deployed.
+fn migrate_legacy_entry(root: &Path, url: &str) -> Result<()> {
+    let legacy = root.join(legacy_cache_key(url));
+    let current = root.join(cache_key(url));
+    if !legacy.is_dir() || legacy.is_symlink() || current.exists() {
+        return Ok(());
+    }
+    log::debug!("moving HTTP cache for {url} to {}", current.display());
+    move_dir(&legacy, &current)?;
+    create_skill_symlink(&current, &legacy)
+}
+
+/// Fetch an HTTP skill into the cache and return its directory.
+///
+/// The current copy is revalidated with `If-None-Match`/`If-Modified-Since`
+/// and kept if the server reports it unchanged. New content is stored next
+/// to it and becomes current, unless `expected` is set and the new
+/// content's checksum differs from it: then `Error::LockMismatch` is
+/// returned and the cache is left alone.
+pub fn fetch(source: &SkillSource, expected: Option<&str>) -> Result<PathBuf> {
+    let url = source.http_skill_url()?;
+    let name = source.display_name();
+    let entry_dir = entry_dir(source)?;
+    let current = current_dir(&entry_dir, &name);
+
+    if offline::is_offline() {
+        // A copy with the expected checksum is what the lock wants, even
+        // if another project has since accepted newer content.
+        let locked = expected
+            .map(|checksum| content_dir(&entry_dir, checksum, &name))
+            .filter(|dir| dir.join("SKILL.md").is_file());
+        if let Some(dir) = locked.or(current) {
+            return Ok(dir);
+        }
+        return Err(Error::Offline(format!(
+            "{url} is not in the local cache; run once without --offline to fetch it"
+        )));
+    }
+
+    log::debug!(
+        "fetching HTTP skill from {url} into {}",
+        entry_dir.display()
+    );
+
+    let (body, validators) = match request(&url, &entry_dir, current)? {
+        Response::NotModified(dir) => return Ok(dir),
+        Response::Modified { body, validators } => (body, validators),
+    };
+
+    if let Some(expected) = expected {
+        let actual = checksum_of(&body)?;
+        if actual != expected {
+            return Err(Error::LockMismatch(format!(
+                "{url} no longer serves the locked content (expected {expected}, found {actual})"
+            )));
+        }
+    }
+
+    store(&entry_dir, &name, &body, validators)
+}
+
+/// Store `body` in its checksum directory and make it the current copy,
+/// with the validators of the response it came from.
+fn store(entry_dir: &Path, name: &str, body: &str, mut validators: Validators) -> Result<PathBuf> {
+    let (dir, checksum) = write_content(entry_dir, name, body)?;
+    validators.checksum = Some(checksum);
+    validators.save(entry_dir)?;
+    Ok(dir)
+}
+
+/// Write `body` as the SKILL.md of its checksum directory, unless it is
+/// already there, and return the directory and checksum.
+fn write_content(entry_dir: &Path, name: &str, body: &str) -> Result<(PathBuf, String)> {
+    let checksum = checksum_of(body)?;
+    let dir = content_dir(entry_dir, &checksum, name);
+    if !dir.join("SKILL.md").is_file() {
+        std::fs::create_dir_all(&dir).map_err(Error::Io)?;
+        std::fs::write(dir.join("SKILL.md"), body).map_err(Error::Io)?;
+    }
+    Ok((dir, checksum))
+}
+
+/// Download what the server currently serves without touching the cache.
+///
+/// Returns the cached directory if the server reports it unchanged,
+/// otherwise a temporary directory holding the new SKILL.md. Call
+/// [`Candidate::accept`] to move new content into the cache.
+pub fn candidate(source: &SkillSource) -> Result<Candidate> {
+    let url = source.http_skill_url()?;
+    let name = source.display_name();
+    let entry_dir = entry_dir(source)?;
+    let current = current_dir(&entry_dir, &name);
+
+    offline::ensure_online(&format!("check {url}"))?;
+    match request(&url, &entry_dir, current)? {
+        Response::NotModified(dir) => Ok(Candidate::Cached(dir)),
+        Response::Modified { body, validators } => {
+            let dir = tempfile::tempdir().map_err(Error::Io)?;
+            std::fs::write(dir.path().join("SKILL.md"), &body).map_err(Error::Io)?;
+            Ok(Candidate::Downloaded(Download {
+                dir,
+                entry_dir,
+                name,
+                body,
+                validators,
+            }))
+        }
+    }
+}
+
+/// The content an HTTP skill's server currently serves.
+pub enum Candidate {
+    /// Unchanged since the cached copy was downloaded.
+    Cached(PathBuf),
+    /// New content, held in a temporary directory.
+    Downloaded(Download),
+}
+
+/// New content for an HTTP skill that isn't in the cache yet.
+pub struct Download {
+    dir: tempfile::TempDir,
+    entry_dir: PathBuf,
+    name: String,
+    body: String,
+    validators: Validators,
+}
+
+impl Candidate {
+    pub fn dir(&self) -> &Path {
+        match self {
+            Candidate::Cached(dir) => dir,
+            Candidate::Downloaded(download) => download.dir.path(),
+        }
+    }
+
+    /// Store this content in the cache as the current copy, along with the
+    /// validators it was served with, and return its directory. Copies
+    /// other projects link to are left alone, and nothing is downloaded
+    /// again.
+    pub fn accept(self) -> Result<PathBuf> {
+        match self {
+            Candidate::Cached(dir) => Ok(dir),
+            Candidate::Downloaded(download) => store(
+                &download.entry_dir,
+                &download.name,
+                &download.body,
+                download.validators,
+            ),
+        }
+    }
+}
+
+/// Request `url`, revalidating the `cached` copy if there is one.
+fn request(url: &str, entry_dir: &Path, cached: Option<PathBuf>) -> Result<Response> {
+    let mut request = credentials::authorize(reqwest::blocking::Client::new().get(url), url)?;
+    if cached.is_some() {
+        let validators = Validators::load(entry_dir);
+        if let Some(ref etag) = validators.etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        if let Some(ref last_modified) = validators.last_modified {
+            request = request.header(IF_MODIFIED_SINCE, last_modified);
+        }
+    }
+
+    let response = request
+        .send()
+        .map_err(|e| Error::Http(format!("Failed to fetch {url}: {e}")))?;
+
+    if let Some(dir) = cached
+        && response.status() == StatusCode::NOT_MODIFIED
+    {
+        log::debug!("{url} not modified since it was cached");
+        return Ok(Response::NotModified(dir));
+    }
+    if !response.status().is_success() {
+        return Err(Error::Http(format!(
+            "HTTP {} fetching {url}",
+            response.status()
+        )));
+    }
+
+    let header = |name| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string)
+    };
+    let validators = Validators {
+        etag: header(ETAG),
+        last_modified: header(LAST_MODIFIED),
+        checksum: None,
+    };
+    let body = response
+        .text()
+        .map_err(|e| Error::Http(format!("Failed to read response from {url}: {e}")))?;
+
+    Ok(Response::Modified { body, validators })
+}
+
+/// The checksum a skill directory holding only `body` as its SKILL.md has.
+fn checksum_of(body: &str) -> Result<String> {
+    let dir = tempfile::tempdir().map_err(Error::Io)?;
+    std::fs::write(dir.path().join("SKILL.md"), body).map_err(Error::Io)?;
+    git::checksum_dir(dir.path())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validators_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(Validators::load(dir.path()).etag.is_none());
+
+        Validators {
+            etag: Some("\"abc\"".to_string()),
+            ..Default::default()
+        }
+        .save(dir.path())
+        .unwrap();
+        let loaded = Validators::load(dir.path());
+        assert_eq!(loaded.etag.as_deref(), Some("\"abc\""));
+        assert!(loaded.last_modified.is_none());
+    }
+
+    #[test]
+    fn accepting_a_download_stores_body_and_validators() {
+        let cache = tempfile::tempdir().unwrap();
+        let entry_dir = cache.path().join("entry");
+        let download = |body: &str, etag: &str| {
+            let dir = tempfile::tempdir().unwrap();
+            std::fs::write(dir.path().join("SKILL.md"), body).unwrap();
+            Candidate::Downloaded(Download {
+                dir,
+                entry_dir: entry_dir.clone(),
+                name: "skill".to_string(),
+                body: body.to_string(),
+                validators: Validators {
+                    etag: Some(etag.to_string()),
+                    ..Default::default()
+                },
+            })
+        };
+
+        let old = download("old", "\"v1\"").accept().unwrap();
+        let new = download("new", "\"v2\"").accept().unwrap();
+        assert_eq!(
+            new,
+            content_dir(&entry_dir, &checksum_of("new").unwrap(), "skill")
+        );
+        assert_eq!(current_dir(&entry_dir, "skill"), Some(new.clone()));
+        assert_eq!(
+            std::fs::read_to_string(new.join("SKILL.md")).unwrap(),
+            "new"
+        );
+        // The copy accepted before stays where links into it expect it
+        assert_eq!(
+            std::fs::read_to_string(old.join("SKILL.md")).unwrap(),
+            "old"
+        );
+        let validators = Validators::load(&entry_dir);
+        assert_eq!(validators.etag.as_deref(), Some("\"v2\""));
+    }
+
+    #[test]
+    fn single_copy_entries_move_to_a_checksum_dir() {
+        let entry = tempfile::tempdir().unwrap();
+        std::fs::create_dir(entry.path().join("skill")).unwrap();
+        std::fs::write(entry.path().join("skill/SKILL.md"), "cached").unwrap();
+
+        let dir = current_dir(entry.path(), "skill").unwrap();
+        assert_eq!(
+            dir,
+            content_dir(entry.path(), &checksum_of("cached").unwrap(), "skill")
+        );
+        assert_eq!(
+            std::fs::read_to_string(dir.join("SKILL.md")).unwrap(),
+            "cached"
+        );
+        assert!(entry.path().join("skill/SKILL.md").is_file());
+        assert_eq!(current_dir(entry.path(), "skill"), Some(dir));
+    }
+
+    #[test]
+    fn legacy_entries_move_to_the_current_key() {
+        let root = tempfile::tempdir().unwrap();
+        let url = "https://example.com/skills/review/skill.md";
+        let legacy = root.path().join(legacy_cache_key(url)).join("review");
+        std::fs::create_dir_all(&legacy).unwrap();
+        std::fs::write(legacy.join("SKILL.md"), "cached").unwrap();
+
+        migrate_legacy_entry(root.path(), url).unwrap();
+        let current = root.path().join(cache_key(url)).join("review");
+        assert_eq!(
+            std::fs::read_to_string(current.join("SKILL.md")).unwrap(),
+            "cached"
+        );
+        // Links into the old location still resolve
+        assert!(root.path().join(legacy_cache_key(url)).is_symlink());
+        assert_eq!(
+            std::fs::read_to_string(legacy.join("SKILL.md")).unwrap(),
+            "cached"
+        );
+        // Running it again is a no-op
+        migrate_legacy_entry(root.path(), url).unwrap();
+        assert!(current.join("SKILL.md").is_file());
+    }
+
+    #[test]
+    fn checksum_of_matches_checksum_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("SKILL.md"), "hello").unwrap();
+        assert_eq!(
+            checksum_of("hello").unwrap(),
+            git::checksum_dir(dir.path()).unwrap()
+        );
+    }
+}
@@ -2,13 +2,14 @@
 
 use std::path::{Path, PathBuf};
 
-use crate::lockfile::LockedSkill;
-use crate::manifest::ManifestOptions;
+use crate::lockfile::{LockedSkill, Lockfile};
+use crate::manifest::{DeployMode, ManifestOptions};
+use crate::render::{TargetFormat, TargetRenderer};
 use crate::skill::SkillMetadata;
 use crate::source::{SkillSource, SkillSourceKind};
 use crate::validate;
 use crate::validate::discovery::discover_skill_files;
-use crate::{Error, Result, git};
+use crate::{Error, Result, archive, git, http_cache, oci, offline, versions};
 
 /// Resolve the platform data directory, respecting `XDG_DATA_HOME` on all platforms.
 ///
@@ -28,6 +29,7 @@ pub fn builtin_skills_dir() -> PathBuf {
 
 /// Where ion stores cloned repositories persistently.
 pub fn data_dir() -> PathBuf {
+    crate::registry::ensure_cache_keys_migrated();
     let dir = platform_data_dir().join("ion").join("repos");
 
     // One-time migration from old cache location
@@ -44,10 +46,25 @@ pub fn data_dir() -> PathBuf {
     dir
 }
 
+/// Where ion stores immutable per-commit snapshots of cloned repositories.
+///
+/// Layout: `{store_dir}/{repo_hash}/{commit}`, where `repo_hash` matches the
+/// clone under [`data_dir`]. Deployed skills symlink into these snapshots.
+pub fn store_dir() -> PathBuf {
+    crate::registry::ensure_cache_keys_migrated();
+    platform_data_dir().join("ion").join("store")
+}
+
+/// The snapshot directory for `commit` of the repo cached under `repo_hash`.
+pub fn snapshot_dir(repo_hash: &str, commit: &str) -> PathBuf {
+    store_dir().join(repo_hash).join(commit)
+}
+
 /// Manages skill installation and uninstallation for a project.
 pub struct SkillInstaller<'a> {
     project_dir: &'a Path,
     options: &'a ManifestOptions,
+    lockfile_path: Option<&'a Path>,
 }
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -61,9 +78,18 @@ impl<'a> SkillInstaller<'a> {
         Self {
             project_dir,
             options,
+            lockfile_path: None,
         }
     }
 
+    /// Read the files previously rendered for a skill from the Ion.lock at
+    /// `path`, so they can be replaced and removed even after the targets
+    /// change.
+    pub fn with_lockfile(mut self, path: &'a Path) -> Self {
+        self.lockfile_path = Some(path);
+        self
+    }
+
     pub fn project_dir(&self) -> &Path {
         self.project_dir
     }
@@ -99,8 +125,9 @@ impl<'a> SkillInstaller<'a> {
             return Ok(validate::ValidationReport::from_findings(Vec::new()));
         }
 
-        let skill_dir = self.fetch(source)?;
-        let (meta, body) = self.validate_spec(&skill_dir, source)?;
+        let (source, _) = resolve_rev(source)?;
+        let skill_dir = self.fetch(&source)?;
+        let (meta, body) = self.validate_spec(&skill_dir, &source)?;
         let report = validate::validate_skill_dir(&skill_dir, &meta, &body);
 
         if report.error_count > 0 {
@@ -121,8 +148,9 @@ impl<'a> SkillInstaller<'a> {
             return self.install_binary(name, source);
         }
 
-        let skill_dir = self.fetch(source)?;
-        let (meta, body) = self.validate_spec(&skill_dir, source)?;
+        let (source, tag) = resolve_rev(source)?;
+        let skill_dir = self.fetch(&source)?;
+        let (meta, body) = self.validate_spec(&skill_dir, &source)?;
 
         if !validation.skip_validation {
             let report = validate::validate_skill_dir(&skill_dir, &meta, &body);
@@ -136,7 +164,93 @@ impl<'a> SkillInstaller<'a> {
         }
 
         self.deploy(name, &skill_dir)?;
-        self.build_locked_entry(name, source, &meta, &skill_dir)
+        let locked = self.build_locked_entry(name, &source, &meta, &skill_dir)?;
+        Ok(match tag {
+            Some(tag) => locked.with_tag(tag),
+            None => locked,
+        })
+    }
+
+    /// Resolve a skill to its Ion.lock entry without deploying it.
+    ///
+    /// Channels and version requirements are resolved to a commit as
+    /// [`install`](Self::install) would, and the fetched content is hashed,
+    /// but nothing under the project directory is touched. Binary skills
+    /// are only locked by installing them.
+    pub fn lock(&self, name: &str, source: &SkillSource) -> Result<LockedSkill> {
+        if source.is_binary() {
+            return Err(Error::Source(format!(
+                "binary skill '{name}' can only be locked by installing it with `ion add`"
+            )));
+        }
+
+        let (source, tag) = resolve_rev(source)?;
+        let skill_dir = self.fetch(&source)?;
+        let (meta, _) = self.validate_spec(&skill_dir, &source)?;
+        let locked = self.build_locked_entry(name, &source, &meta, &skill_dir)?;
+        Ok(match tag {
+            Some(tag) => locked.with_tag(tag),
+            None => locked,
+        })
+    }
+
+    /// Fetch a skill exactly as recorded in Ion.lock and verify its content.
+    ///
+    /// Git sources are checked out at the locked commit; a commit that no
+    /// longer exists upstream is an error. The fetched directory's checksum
+    /// must match the locked checksum, otherwise `Error::LockMismatch` is
+    /// returned. Returns the source pinned to the locked commit, which
+    /// callers should use for the subsequent install.
+    pub fn verify_locked(&self, source: &SkillSource, locked: &LockedSkill) -> Result<SkillSource> {
+        let pinned = locked.pin(source);
+
+        // Binary skills are verified against `binary_checksum` after install;
+        // local and path skills are the user's own files and may change freely.
+        if !(pinned.is_git_based() || pinned.is_http() || pinned.is_archive() || pinned.is_oci()) {
+            return Ok(pinned);
+        }
+
+        let expected = locked.checksum().filter(|c| !c.is_empty());
+        // HTTP skills can't be pinned, so new content is refused before it
+        // replaces the cached copy; `ion update` is how it gets accepted.
+        let accept_hint = |msg: String| {
+            if pinned.is_http() {
+                format!(
+                    "{msg}; run `ion update {}` to accept the new content",
+                    locked.name
+                )
+            } else {
+                msg
+            }
+        };
+
+        let fetched = if pinned.is_http() {
+            http_cache::fetch(&pinned, expected)
+        } else {
+            self.fetch(&pinned)
+        };
+        let skill_dir = fetched.map_err(|e| match (locked.commit(), e) {
+            (Some(commit), Error::Git(msg)) => Error::LockMismatch(format!(
+                "'{}' is locked to commit {commit}, which could not be checked out: {msg}",
+                locked.name
+            )),
+            (_, Error::LockMismatch(msg)) => {
+                Error::LockMismatch(accept_hint(format!("'{}': {msg}", locked.name)))
+            }
+            (_, e) => e,
+        })?;
+
+        if let Some(expected) = expected {
+            let actual = git::checksum_dir(&skill_dir)?;
+            if actual != expected {
+                return Err(Error::LockMismatch(accept_hint(format!(
+                    "'{}' content does not match Ion.lock (expected {expected}, found {actual})",
+                    locked.name
+                ))));
+            }
+        }
+
+        Ok(pinned)
     }
 
     /// Fetch a source and discover all skills within it.
@@ -165,13 +279,11 @@ impl<'a> SkillInstaller<'a> {
 
     pub fn uninstall(&self, name: &str) -> Result<()> {
         let agents_dir = self.skill_dir(name);
-        if agents_dir.is_symlink() {
-            std::fs::remove_file(&agents_dir).map_err(Error::Io)?;
-        } else if agents_dir.exists() {
-            std::fs::remove_dir_all(&agents_dir).map_err(Error::Io)?;
-        }
+        // Targets that exclude the skill are compared against the skills-dir
+        // entry, so clean them up before it goes away.
+        self.clean_excluded_targets(name, &agents_dir)?;
 
-        for target_path in self.options.targets.values() {
+        for target_path in self.skill_targets(name) {
             let target_dir = self.project_dir.join(target_path).join(name);
             if target_dir.is_symlink() {
                 std::fs::remove_file(&target_dir).map_err(Error::Io)?;
@@ -180,6 +292,54 @@ impl<'a> SkillInstaller<'a> {
             }
         }
 
+        if agents_dir.is_symlink() {
+            std::fs::remove_file(&agents_dir).map_err(Error::Io)?;
+        } else if agents_dir.exists() {
+            std::fs::remove_dir_all(&agents_dir).map_err(Error::Io)?;
+        }
+
+        self.remove_rendered(name)
+    }
+
+    /// Remove Ion's deployments of `name` from the skill-directory targets
+    /// that exclude it: links to the skill, and copies or hardlinks that the
+    /// managed `.gitignore` section lists or whose content still matches
+    /// `skill_dir`. Anything else at that path is the user's and stays.
+    fn clean_excluded_targets(&self, name: &str, skill_dir: &Path) -> Result<()> {
+        let agents_target = self.skill_dir(name);
+        let mut managed = None;
+        let mut checksum = None;
+        for (target, target_path) in &self.options.targets {
+            let path = self.project_dir.join(target_path).join(name);
+            if path == agents_target
+                || path == skill_dir
+                || self.options.format_for(target) != TargetFormat::Skill
+                || self.options.deploys_to(target, name)
+            {
+                continue;
+            }
+            if path.is_symlink() {
+                if links_to(&path, &agents_target) || links_to(&path, skill_dir) {
+                    std::fs::remove_file(&path).map_err(Error::Io)?;
+                }
+                continue;
+            }
+            if !path.is_dir() {
+                continue;
+            }
+            let managed = match &managed {
+                Some(entries) => entries,
+                None => managed.insert(crate::gitignore::managed_entries(self.project_dir)?),
+            };
+            let rel = relative_file(target_path, Path::new(name));
+            let ours = managed.contains(&rel) || {
+                let expected = checksum.get_or_insert_with(|| git::checksum_dir(skill_dir).ok());
+                expected.is_some() && git::checksum_dir(&path).ok() == *expected
+            };
+            if ours {
+                std::fs::remove_dir_all(&path).map_err(Error::Io)?;
+            }
+        }
         Ok(())
     }
 
@@ -204,7 +364,7 @@ impl<'a> SkillInstaller<'a> {
 
         if let Some(ref required_version) = source.version {
             let actual_version = meta.version().unwrap_or("(none)");
-            if actual_version != required_version {
+            if !versions::version_satisfies(actual_version, required_version) {
                 return Err(Error::InvalidSkill(format!(
                     "Version mismatch: expected {required_version}, found {actual_version}"
                 )));
@@ -214,44 +374,254 @@ impl<'a> SkillInstaller<'a> {
         Ok((meta, body))
     }
 
-    /// Check whether a skill is fully deployed: its canonical directory exists
-    /// and all configured target symlinks are in place and resolve to valid paths.
+    /// Check whether a skill is fully deployed: its canonical directory exists,
+    /// all configured target symlinks are in place and resolve to valid paths,
+    /// and every rendered file exists.
     pub fn is_deployed(&self, name: &str) -> bool {
         let skill_dir = self.skill_dir(name);
         if !skill_dir.exists() {
             return false;
         }
-        for target_path in self.options.targets.values() {
+        for target_path in self.skill_targets(name) {
             let target_dir = self.project_dir.join(target_path).join(name);
             if !target_dir.exists() {
                 return false;
             }
         }
-        true
+        self.rendered_files(name)
+            .iter()
+            .all(|file| self.project_dir.join(file).is_file())
     }
 
+    /// Deploy `skill_dir` into the skills directory and every target, using
+    /// the configured deploy mode for each. Copies and hardlinks whose
+    /// checksum still matches the skill are left untouched.
     pub fn deploy(&self, name: &str, skill_dir: &Path) -> Result<()> {
         let agents_target = self.skill_dir(name);
 
-        // Only create the .agents/skills/ symlink if skill_dir is a different location.
+        // Only deploy into .agents/skills/ if skill_dir is a different location.
         // Binary skills write directly into .agents/skills/{name}, so symlinking
         // it to itself would create a circular symlink.
+        //
+        // Hardlinks share inodes with their source, so they are never made
+        // from Ion's shared cache: editing a hardlinked file would change
+        // the cached skill for every project. A cached skill that any target
+        // hardlinks gets a per-project copy in the skills directory instead,
+        // and the hardlinks are made from that copy.
+        let hardlinks_cache =
+            skill_dir.starts_with(platform_data_dir().join("ion")) && self.hardlinks(name);
+        let link_source = if hardlinks_cache && skill_dir != agents_target {
+            agents_target.as_path()
+        } else {
+            skill_dir
+        };
         if skill_dir != agents_target {
-            create_skill_symlink(skill_dir, &agents_target)?;
+            let mode = match self.options.deploy_mode() {
+                _ if hardlinks_cache => DeployMode::Copy,
+                mode => mode,
+            };
+            deploy_skill_dir(skill_dir, &agents_target, mode)?;
         }
 
-        let canonical = self.skill_dir(name);
-        for target_path in self.options.targets.values() {
+        // Drop what was deployed before a target excluded this skill
+        self.clean_excluded_targets(name, skill_dir)?;
+
+        for (target, target_path) in &self.options.targets {
             let target_skill_dir = self.project_dir.join(target_path).join(name);
-            if target_skill_dir == agents_target {
+            if target_skill_dir == agents_target
+                || self.options.format_for(target) != TargetFormat::Skill
+                || !self.options.deploys_to(target, name)
+            {
                 continue;
             }
-            create_skill_symlink(&canonical, &target_skill_dir)?;
+            // Symlinks go through the canonical directory; copies are taken
+            // from the skill itself so they never depend on another link.
+            match self.options.deploy_mode_for(target) {
+                DeployMode::Symlink => create_skill_symlink(&agents_target, &target_skill_dir)?,
+                DeployMode::Hardlink => {
+                    deploy_skill_dir(link_source, &target_skill_dir, DeployMode::Hardlink)?
+                }
+                DeployMode::Copy => {
+                    deploy_skill_dir(skill_dir, &target_skill_dir, DeployMode::Copy)?
+                }
+            }
         }
 
+        self.render_targets(name, skill_dir)
+    }
+
+    /// Whether the skills directory or any target receiving `name` as a
+    /// skill directory uses hardlinks.
+    fn hardlinks(&self, name: &str) -> bool {
+        self.options.deploy_mode() == DeployMode::Hardlink
+            || self
+                .options
+                .targets
+                .keys()
+                .filter(|target| self.options.format_for(target) == TargetFormat::Skill)
+                .filter(|target| self.options.deploys_to(target, name))
+                .any(|target| self.options.deploy_mode_for(target) == DeployMode::Hardlink)
+    }
+
+    /// Write the files of every target with a [`TargetFormat`] renderer,
+    /// leaving files whose contents are already current untouched.
+    ///
+    /// A file Ion didn't write — one that neither Ion.lock nor the managed
+    /// `.gitignore` section records — is never overwritten. Files recorded
+    /// for targets that no longer receive the skill are removed.
+    fn render_targets(&self, name: &str, skill_dir: &Path) -> Result<()> {
+        let recorded = self.recorded_rendered(name);
+        let mut skill = None;
+        for (_, target_path, renderer) in self.renderers(name) {
+            let (meta, body) = match &skill {
+                Some(parsed) => parsed,
+                None => skill.insert(SkillMetadata::from_file(&skill_dir.join("SKILL.md"))?),
+            };
+            for file in renderer.render(name, meta, body) {
+                let rel = relative_file(target_path, &file.path);
+                let path = self.project_dir.join(&rel);
+                if path.is_symlink() {
+                    std::fs::remove_file(&path).map_err(Error::Io)?;
+                } else if path.exists() {
+                    if std::fs::read_to_string(&path).ok().as_deref() == Some(&file.contents) {
+                        continue;
+                    }
+                    if !self.owns_rendered(&rel, &recorded)? {
+                        return Err(Error::Other(format!(
+                            "{rel} already exists and was not written by Ion; move it away to render '{name}' there"
+                        )));
+                    }
+                }
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent).map_err(Error::Io)?;
+                }
+                std::fs::write(&path, &file.contents).map_err(Error::Io)?;
+            }
+        }
+
+        let current = self.rendered_files(name);
+        for rel in recorded.iter().filter(|rel| !current.contains(rel)) {
+            remove_file_if_present(&self.project_dir.join(rel))?;
+        }
         Ok(())
     }
 
+    /// Remove the files rendered for `name`: those Ion.lock records, and
+    /// those the current targets would render if Ion wrote them.
+    fn remove_rendered(&self, name: &str) -> Result<()> {
+        let recorded = self.recorded_rendered(name);
+        for rel in self.rendered_files(name) {
+            if !recorded.contains(&rel) && self.owns_rendered(&rel, &recorded)? {
+                remove_file_if_present(&self.project_dir.join(&rel))?;
+            }
+        }
+        for rel in &recorded {
+            remove_file_if_present(&self.project_dir.join(rel))?;
+        }
+        Ok(())
+    }
+
+    /// Files Ion.lock records as rendered for `name`. Empty without a
+    /// lockfile (see [`with_lockfile`](Self::with_lockfile)).
+    fn recorded_rendered(&self, name: &str) -> Vec<String> {
+        self.lockfile_path
+            .and_then(|path| Lockfile::from_file(path).ok())
+            .and_then(|lockfile| lockfile.find(name).map(|locked| locked.rendered.clone()))
+            .unwrap_or_default()
+    }
+
+    /// Whether Ion wrote the rendered file `rel`: Ion.lock records it, or
+    /// the managed `.gitignore` section lists it.
+    fn owns_rendered(&self, rel: &str, recorded: &[String]) -> Result<bool> {
+        if recorded.iter().any(|r| r == rel) {
+            return Ok(true);
+        }
+        Ok(crate::gitignore::managed_entries(self.project_dir)?
+            .iter()
+            .any(|entry| entry == rel))
+    }
+
+    /// Paths of the targets that receive `name` as a skill directory.
+    fn skill_targets<'s>(&'s self, name: &'s str) -> impl Iterator<Item = &'s str> {
+        self.options
+            .targets
+            .iter()
+            .filter(move |(target, _)| {
+                self.options.format_for(target) == TargetFormat::Skill
+                    && self.options.deploys_to(target, name)
+            })
+            .map(|(_, path)| path.as_str())
+    }
+
+    /// Targets that receive `name` through a renderer, as (target name, path,
+    /// renderer).
+    fn renderers<'s>(
+        &'s self,
+        name: &'s str,
+    ) -> impl Iterator<Item = (&'s str, &'s str, &'static dyn TargetRenderer)> {
+        self.options
+            .targets
+            .iter()
+            .filter(move |(target, _)| self.options.deploys_to(target, name))
+            .filter_map(|(target, path)| {
+                self.options
+                    .format_for(target)
+                    .renderer()
+                    .map(|renderer| (target.as_str(), path.as_str(), renderer))
+            })
+    }
+
+    /// Paths that deploying `name` creates, relative to the project
+    /// directory: the skills-dir entry, each target's skill directory and
+    /// every rendered file. These are what `.gitignore` should list.
+    pub fn gitignore_entries(&self, name: &str) -> Vec<String> {
+        let mut entries = vec![format!("{}/{name}", self.options.skills_dir_or_default())];
+        for target_path in self.skill_targets(name) {
+            entries.push(format!("{target_path}/{name}"));
+        }
+        entries.extend(self.rendered_files(name));
+        entries
+    }
+
+    /// Files rendered for `name`, relative to the project directory, as
+    /// recorded in Ion.lock.
+    pub fn rendered_files(&self, name: &str) -> Vec<String> {
+        let mut files: Vec<String> = self
+            .renderers(name)
+            .flat_map(|(_, target_path, renderer)| {
+                renderer
+                    .paths(name)
+                    .into_iter()
+                    .map(move |path| relative_file(target_path, &path))
+            })
+            .collect();
+        files.sort();
+        files
+    }
+
+    /// Remove the deployments of a skill that lives at `skill_dir`, leaving
+    /// that directory itself in place. Used for local skills, whose files
+    /// belong to the user.
+    pub fn undeploy(&self, name: &str, skill_dir: &Path) -> Result<()> {
+        self.clean_excluded_targets(name, skill_dir)?;
+        let agents_target = self.skill_dir(name);
+        if agents_target != skill_dir {
+            remove_deployment(&agents_target, self.options.deploy_mode())?;
+        }
+        for (target, target_path) in &self.options.targets {
+            let target_skill_dir = self.project_dir.join(target_path).join(name);
+            if target_skill_dir == agents_target
+                || target_skill_dir == skill_dir
+                || self.options.format_for(target) != TargetFormat::Skill
+                || !self.options.deploys_to(target, name)
+            {
+                continue;
+            }
+            remove_deployment(&target_skill_dir, self.options.deploy_mode_for(target))?;
+        }
+        self.remove_rendered(name)
+    }
+
     fn install_binary(&self, name: &str, source: &SkillSource) -> Result<LockedSkill> {
         use crate::binary;
 
@@ -341,7 +711,7 @@ impl<'a> SkillInstaller<'a> {
         if let Some(version) = meta.version() {
             locked = locked.with_version(version);
         }
-        Ok(locked)
+        Ok(locked.with_rendered(self.rendered_files(name)))
     }
 
     /// Install a local binary skill by building in release mode.
@@ -382,7 +752,7 @@ impl<'a> SkillInstaller<'a> {
         if let Some(version) = meta.version() {
             locked = locked.with_version(version);
         }
-        Ok(locked)
+        Ok(locked.with_rendered(self.rendered_files(name)))
     }
 
     /// Set up a dev-mode local binary skill (no release build).
@@ -421,7 +791,7 @@ impl<'a> SkillInstaller<'a> {
         if let Some(version) = meta.version() {
             locked = locked.with_version(version);
         }
-        Ok(locked)
+        Ok(locked.with_rendered(self.rendered_files(name)))
     }
 
     fn build_locked_entry(
@@ -441,7 +811,11 @@ impl<'a> SkillInstaller<'a> {
                 let repo_dir = find_repo_root(skill_dir);
                 let commit = git::head_commit(&repo_dir).ok().unwrap_or_default();
                 let checksum = git::checksum_dir(skill_dir).ok().unwrap_or_default();
-                LockedSkill::git(name, &git_url, commit, checksum)
+                let locked = LockedSkill::git(name, &git_url, commit, checksum);
+                match source.channel.clone() {
+                    Some(channel) => locked.with_channel(channel),
+                    None => locked,
+                }
             }
             SkillSourceKind::Path => {
                 let mut l = LockedSkill::path(name, &git_url);
@@ -457,6 +831,26 @@ impl<'a> SkillInstaller<'a> {
                 }
                 l
             }
+            SkillSourceKind::Archive { sha256, .. } => {
+                let sha256 = sha256.clone().ok_or_else(|| {
+                    Error::Source(format!("archive skill '{name}' was not pinned to a digest"))
+                })?;
+                let mut l = LockedSkill::archive(name, &git_url, sha256);
+                if let Ok(checksum) = git::checksum_dir(skill_dir) {
+                    l = l.with_checksum(checksum);
+                }
+                l
+            }
+            SkillSourceKind::Oci => {
+                let digest = source.rev.clone().ok_or_else(|| {
+                    Error::Source(format!("OCI skill '{name}' was not pinned to a digest"))
+                })?;
+                let mut l = LockedSkill::oci(name, &git_url, digest);
+                if let Ok(checksum) = git::checksum_dir(skill_dir) {
+                    l = l.with_checksum(checksum);
+                }
+                l
+            }
             SkillSourceKind::Local { .. } => {
                 let mut l = LockedSkill::local(name).with_source(&git_url);
                 if let Ok(checksum) = git::checksum_dir(skill_dir) {
@@ -477,15 +871,87 @@ impl<'a> SkillInstaller<'a> {
             locked = locked.with_version(version);
         }
 
-        Ok(locked)
+        Ok(locked.with_rendered(self.rendered_files(name)))
+    }
+}
+
+/// Cache key for a source URL, used to name directories under [`data_dir`]
+/// and [`store_dir`] and to key entries in the global registry.
+///
+/// This is the hex SHA-256 of the URL, so keys stay the same across Rust
+/// toolchains and platforms.
+pub fn cache_key(url: &str) -> String {
+    use sha2::{Digest, Sha256};
+
+    Sha256::digest(url.as_bytes())
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// The cache key older versions of Ion used: a `DefaultHasher` hash of the
+/// URL. Only used to find caches that still need migrating.
+pub(crate) fn legacy_cache_key(url: &str) -> String {
+    use std::hash::{DefaultHasher, Hash, Hasher};
+
+    let mut hasher = DefaultHasher::new();
+    url.hash(&mut hasher);
+    format!("{:x}", hasher.finish())
+}
+
+/// Resolve a git source's channel or `version` requirement to a commit.
+///
+/// Returns the source pinned to that commit, along with the tag it was taken
+/// from, if any. A version requirement resolved to a tag is dropped from the
+/// returned source, since the tag satisfies it. Sources with an explicit
+/// `rev` or neither a channel nor a requirement, and repos without version
+/// tags, are returned unchanged; a requirement is then checked against the
+/// SKILL.md `version` field instead.
+///
+/// Archive sources are downloaded and pinned to their SHA-256 digest, which
+/// must match the declared `sha256` if there is one. OCI sources are pinned
+/// to the digest of the manifest their tag points at.
+pub fn resolve_rev(source: &SkillSource) -> Result<(SkillSource, Option<String>)> {
+    if source.is_archive() {
+        let digest = archive::resolve_digest(source)?;
+        return Ok((source.clone().with_sha256(digest), None));
+    }
+    if source.is_oci() {
+        let digest = oci::resolve_digest(source)?;
+        return Ok((source.clone().with_rev(digest), None));
+    }
+
+    if !source.is_git_based()
+        || source.rev.is_some()
+        || (source.channel.is_none() && source.version.is_none())
+    {
+        return Ok((source.clone(), None));
+    }
+
+    let url = source.git_url()?;
+    let repo_dir = data_dir().join(cache_key(&url));
+    git::sync_repo(&url, &repo_dir)?;
+
+    if let Some(ref channel) = source.channel {
+        let (commit, tag) = git::channel_commit(&repo_dir, channel)?;
+        return Ok((source.clone().with_rev(commit), tag));
+    }
+
+    let req = source.version.as_deref().unwrap_or_default();
+    match versions::resolve(&repo_dir, req, source.path.as_deref())? {
+        Some(resolved) => {
+            let mut pinned = source.clone().with_rev(resolved.commit);
+            pinned.version = None;
+            Ok((pinned, Some(resolved.tag)))
+        }
+        None => Ok((source.clone(), None)),
     }
 }
 
 /// Compute the cache directory for a git-based source (does not clone or check existence).
 pub fn repo_dir_for_source(source: &SkillSource) -> Result<PathBuf> {
     let url = source.git_url()?;
-    let repo_hash = format!("{:x}", hash_simple(&url));
-    Ok(data_dir().join(&repo_hash))
+    Ok(data_dir().join(cache_key(&url)))
 }
 
 /// Return the cached clone directory for a git-based source, if it exists.
@@ -509,22 +975,43 @@ fn absolutize(path: PathBuf) -> Result<PathBuf> {
     }
 }
 
-/// Fetch a source to its cached repo directory (for git sources) or local path.
+/// Fetch a source to its cached snapshot directory (for git sources) or local path.
 /// Does NOT resolve the skill path within the repo.
+///
+/// Git sources resolve `rev` (or the remote default branch) to a commit and
+/// return the immutable snapshot of that commit from [`store_dir`].
 fn fetch_skill_base(source: &SkillSource) -> Result<PathBuf> {
     match &source.kind {
         SkillSourceKind::Github | SkillSourceKind::Git => {
             let url = source.git_url()?;
-            let repo_hash = format!("{:x}", hash_simple(&url));
-            let repo_dir = data_dir().join(&repo_hash);
+            let hash = cache_key(&url);
+            let repo_dir = data_dir().join(&hash);
 
-            git::clone_or_fetch(&url, &repo_dir)?;
+            git::sync_repo(&url, &repo_dir)?;
 
-            if let Some(ref rev) = source.rev {
-                git::checkout(&repo_dir, rev)?;
+            let commit = match (&source.rev, &source.channel) {
+                (Some(rev), _) => git::resolve_commit(&repo_dir, rev),
+                (None, Some(channel)) => git::channel_commit(&repo_dir, channel).map(|(c, _)| c),
+                (None, None) => git::remote_head_commit(&repo_dir),
             }
+            .map_err(|e| {
+                if offline::is_offline() {
+                    let wanted = match (&source.rev, &source.channel) {
+                        (Some(rev), _) => rev.clone(),
+                        (None, Some(channel)) => channel.to_string(),
+                        (None, None) => "the default branch".to_string(),
+                    };
+                    Error::Offline(format!(
+                        "the cached clone of {url} does not contain {wanted}"
+                    ))
+                } else {
+                    e
+                }
+            })?;
+            let snapshot = snapshot_dir(&hash, &commit);
+            git::ensure_snapshot(&repo_dir, &commit, &snapshot, source.path.as_deref())?;
 
-            Ok(repo_dir)
+            Ok(snapshot)
         }
         SkillSourceKind::Path => {
             let path = PathBuf::from(&source.source);
@@ -539,7 +1026,9 @@ fn fetch_skill_base(source: &SkillSource) -> Result<PathBuf> {
             // source stays relative; only the fetched path is absolutized.
             Ok(absolutize(path)?)
         }
-        SkillSourceKind::Http => fetch_http_skill(source),
+        SkillSourceKind::Http => http_cache::fetch(source, None),
+        SkillSourceKind::Archive { .. } => archive::fetch(source),
+        SkillSourceKind::Oci => oci::fetch(source),
         SkillSourceKind::Binary { .. } => Err(Error::Source(
             "Binary source uses dedicated installer".to_string(),
         )),
@@ -556,39 +1045,6 @@ fn fetch_skill_base(source: &SkillSource) -> Result<PathBuf> {
     }
 }
 
-/// Fetch a single SKILL.md file from an HTTP URL.
-/// Creates a skill directory in the cache containing just the downloaded SKILL.md.
-fn fetch_http_skill(source: &SkillSource) -> Result<PathBuf> {
-    let url = source.http_skill_url()?;
-    let url_hash = format!("{:x}", hash_simple(&url));
-    let skill_name = source.display_name();
-    let skill_dir = data_dir().join(&url_hash).join(&skill_name);
-
-    log::debug!(
-        "fetching HTTP skill from {url} into {}",
-        skill_dir.display()
-    );
-
-    let response = reqwest::blocking::get(&url)
-        .map_err(|e| Error::Http(format!("Failed to fetch {url}: {e}")))?;
-
-    if !response.status().is_success() {
-        return Err(Error::Http(format!(
-            "HTTP {} fetching {url}",
-            response.status()
-        )));
-    }
-
-    let body = response
-        .text()
-        .map_err(|e| Error::Http(format!("Failed to read response from {url}: {e}")))?;
-
-    std::fs::create_dir_all(&skill_dir).map_err(Error::Io)?;
-    std::fs::write(skill_dir.join("SKILL.md"), &body).map_err(Error::Io)?;
-
-    Ok(skill_dir)
-}
-
 /// Resolve the skill directory within a repo, handling subdirectory skills.
 /// Tries `repo_dir/path` first, then falls back to `repo_dir/skills/path`.
 pub fn resolve_skill_dir(repo_dir: &Path, path: Option<&str>) -> Result<PathBuf> {
@@ -617,7 +1073,7 @@ fn fetch_skill(source: &SkillSource) -> Result<PathBuf> {
 }
 
 /// Create a relative symlink from `link` pointing to `original`.
-fn create_skill_symlink(original: &Path, link: &Path) -> Result<()> {
+pub(crate) fn create_skill_symlink(original: &Path, link: &Path) -> Result<()> {
     // Remove existing file/dir/symlink at the link location
     if link.is_symlink() {
         std::fs::remove_file(link).map_err(Error::Io)?;
@@ -649,6 +1105,86 @@ fn create_skill_symlink(original: &Path, link: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Whether `link` is a symlink that resolves to `original`.
+fn links_to(link: &Path, original: &Path) -> bool {
+    if !link.is_symlink() {
+        return false;
+    }
+    match (std::fs::canonicalize(link), std::fs::canonicalize(original)) {
+        (Ok(resolved), Ok(original)) => resolved == original,
+        _ => false,
+    }
+}
+
+/// `file` inside `target_path`, relative to the project directory, with
+/// `/` separators as Ion.lock and `.gitignore` record it.
+fn relative_file(target_path: &str, file: &Path) -> String {
+    Path::new(target_path)
+        .join(file)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+fn remove_file_if_present(path: &Path) -> Result<()> {
+    if path.is_symlink() || path.is_file() {
+        std::fs::remove_file(path).map_err(Error::Io)?;
+    }
+    Ok(())
+}
+
+/// Place `original` at `dest` using `mode`. Copies and hardlinks are only
+/// rewritten when their checksum no longer matches `original`.
+pub(crate) fn deploy_skill_dir(original: &Path, dest: &Path, mode: DeployMode) -> Result<()> {
+    if mode == DeployMode::Symlink {
+        return create_skill_symlink(original, dest);
+    }
+    if !dest.is_symlink()
+        && dest.is_dir()
+        && git::checksum_dir(dest).ok() == Some(git::checksum_dir(original)?)
+    {
+        return Ok(());
+    }
+
+    if dest.is_symlink() {
+        std::fs::remove_file(dest).map_err(Error::Io)?;
+    } else if dest.exists() {
+        std::fs::remove_dir_all(dest).map_err(Error::Io)?;
+    }
+    copy_skill_files(original, dest, mode == DeployMode::Hardlink)
+}
+
+/// Copy the files of a skill (skipping `.git`), hardlinking them when
+/// `hardlink` is set and falling back to a copy where that fails, e.g.
+/// across filesystems.
+fn copy_skill_files(from: &Path, to: &Path, hardlink: bool) -> Result<()> {
+    std::fs::create_dir_all(to).map_err(Error::Io)?;
+    for entry in std::fs::read_dir(from).map_err(Error::Io)? {
+        let entry = entry.map_err(Error::Io)?;
+        let path = entry.path();
+        if entry.file_name() == ".git" {
+            continue;
+        }
+        let dest = to.join(entry.file_name());
+        if path.is_dir() {
+            copy_skill_files(&path, &dest, hardlink)?;
+        } else if !hardlink || std::fs::hard_link(&path, &dest).is_err() {
+            std::fs::copy(&path, &dest).map_err(Error::Io)?;
+        }
+    }
+    Ok(())
+}
+
+/// Remove a deployment at `path`: always a symlink, and a real directory
+/// only when `mode` says Ion put a copy there.
+fn remove_deployment(path: &Path, mode: DeployMode) -> Result<()> {
+    if path.is_symlink() {
+        std::fs::remove_file(path).map_err(Error::Io)?;
+    } else if mode != DeployMode::Symlink && path.is_dir() {
+        std::fs::remove_dir_all(path).map_err(Error::Io)?;
+    }
+    Ok(())
+}
+
 fn find_repo_root(path: &Path) -> PathBuf {
     let mut current = path.to_path_buf();
     loop {
@@ -661,13 +1197,6 @@ fn find_repo_root(path: &Path) -> PathBuf {
     }
 }
 
-pub fn hash_simple(s: &str) -> u64 {
-    use std::hash::{DefaultHasher, Hash, Hasher};
-    let mut hasher = DefaultHasher::new();
-    s.hash(&mut hasher);
-    hasher.finish()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -680,6 +1209,7 @@ mod tests {
         ManifestOptions {
             targets: std::collections::BTreeMap::new(),
             skills_dir: None,
+            ..Default::default()
         }
     }
 
@@ -689,6 +1219,7 @@ mod tests {
         ManifestOptions {
             targets,
             skills_dir: None,
+            ..Default::default()
         }
     }
 
@@ -792,6 +1323,7 @@ mod tests {
         let options = ManifestOptions {
             targets,
             skills_dir: None,
+            ..Default::default()
         };
 
         let installer = SkillInstaller::new(project.path(), &options);
@@ -818,6 +1350,7 @@ mod tests {
         let options = ManifestOptions {
             targets,
             skills_dir: None,
+            ..Default::default()
         };
 
         let installer = SkillInstaller::new(project.path(), &options);
@@ -838,6 +1371,254 @@ mod tests {
         assert!(target.join("SKILL.md").exists());
     }
 
+    #[test]
+    fn copy_deploy_refreshes_stale_copies() {
+        let skill_src = tempfile::tempdir().unwrap();
+        std::fs::write(skill_src.path().join("SKILL.md"), "one").unwrap();
+        std::fs::create_dir_all(skill_src.path().join(".git")).unwrap();
+
+        let project = tempfile::tempdir().unwrap();
+        let mut options = options_with_targets();
+        options.deploy = Some(DeployMode::Copy);
+        options
+            .target_deploy
+            .insert("claude".to_string(), DeployMode::Hardlink);
+        let installer = SkillInstaller::new(project.path(), &options);
+        installer.deploy("demo", skill_src.path()).unwrap();
+
+        let canonical = project.path().join(".agents/skills/demo");
+        let target = project.path().join(".claude/skills/demo");
+        assert!(!canonical.is_symlink() && !target.is_symlink());
+        assert!(!canonical.join(".git").exists());
+        assert_eq!(
+            std::fs::read_to_string(target.join("SKILL.md")).unwrap(),
+            "one"
+        );
+
+        // A copy that no longer matches the skill is replaced on redeploy
+        std::fs::write(canonical.join("SKILL.md"), "local edit").unwrap();
+        std::fs::write(skill_src.path().join("SKILL.md"), "two").unwrap();
+        installer.deploy("demo", skill_src.path()).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(canonical.join("SKILL.md")).unwrap(),
+            "two"
+        );
+        assert_eq!(
+            std::fs::read_to_string(target.join("SKILL.md")).unwrap(),
+            "two"
+        );
+
+        installer.undeploy("demo", skill_src.path()).unwrap();
+        assert!(!canonical.exists() && !target.exists());
+        assert!(skill_src.path().join("SKILL.md").exists());
+    }
+
+    #[test]
+    fn rendering_targets_get_native_files() {
+        let skill_src = tempfile::tempdir().unwrap();
+        std::fs::write(
+            skill_src.path().join("SKILL.md"),
+            "---\nname: demo\ndescription: Demo skill.\n---\n\nBody.\n",
+        )
+        .unwrap();
+
+        let project = tempfile::tempdir().unwrap();
+        let mut options = options_with_targets();
+        options
+            .targets
+            .insert("cursor".to_string(), ".cursor/rules".to_string());
+        options
+            .target_format
+            .insert("cursor".to_string(), TargetFormat::Cursor);
+        let installer = SkillInstaller::new(project.path(), &options);
+        installer.deploy("demo", skill_src.path()).unwrap();
+
+        let rule = project.path().join(".cursor/rules/demo.mdc");
+        assert!(std::fs::read_to_string(&rule).unwrap().ends_with("Body.\n"));
+        assert!(!project.path().join(".cursor/rules/demo").exists());
+        assert!(project.path().join(".claude/skills/demo").is_symlink());
+        assert!(installer.is_deployed("demo"));
+        assert_eq!(
+            installer.rendered_files("demo"),
+            vec![".cursor/rules/demo.mdc"]
+        );
+        assert_eq!(
+            installer.gitignore_entries("demo"),
+            vec![
+                ".agents/skills/demo",
+                ".claude/skills/demo",
+                ".cursor/rules/demo.mdc"
+            ]
+        );
+
+        // Ion.lock records the rendered file, so uninstall removes it
+        let lock_path = project.path().join("Ion.lock");
+        let mut lockfile = Lockfile::default();
+        lockfile.upsert(LockedSkill::local("demo").with_rendered(installer.rendered_files("demo")));
+        lockfile.write_to(&lock_path).unwrap();
+        let installer = installer.with_lockfile(&lock_path);
+        installer.uninstall("demo").unwrap();
+        assert!(!rule.exists());
+        assert!(!installer.is_deployed("demo"));
+    }
+
+    #[test]
+    fn rendering_leaves_files_ion_did_not_write() {
+        let skill_src = tempfile::tempdir().unwrap();
+        std::fs::write(
+            skill_src.path().join("SKILL.md"),
+            "---\nname: demo\ndescription: Demo skill.\n---\n\nBody.\n",
+        )
+        .unwrap();
+
+        let project = tempfile::tempdir().unwrap();
+        let lock_path = project.path().join("Ion.lock");
+        let mut options = empty_options();
+        options
+            .targets
+            .insert("cursor".to_string(), ".cursor/rules".to_string());
+        options
+            .target_format
+            .insert("cursor".to_string(), TargetFormat::Cursor);
+        let rule = project.path().join(".cursor/rules/demo.mdc");
+        std::fs::create_dir_all(rule.parent().unwrap()).unwrap();
+        std::fs::write(&rule, "my own rule").unwrap();
+
+        let installer = SkillInstaller::new(project.path(), &options).with_lockfile(&lock_path);
+        let err = installer.deploy("demo", skill_src.path()).unwrap_err();
+        assert!(err.to_string().contains("not written by Ion"), "{err}");
+        installer.uninstall("demo").unwrap();
+        assert_eq!(std::fs::read_to_string(&rule).unwrap(), "my own rule");
+
+        // Once Ion.lock records the file, it is Ion's to replace
+        let mut lockfile = Lockfile::default();
+        lockfile.upsert(LockedSkill::local("demo").with_rendered(installer.rendered_files("demo")));
+        lockfile.write_to(&lock_path).unwrap();
+        installer.deploy("demo", skill_src.path()).unwrap();
+        assert!(std::fs::read_to_string(&rule).unwrap().ends_with("Body.\n"));
+
+        // Dropping the target removes the recorded file on the next deploy
+        options.targets.remove("cursor");
+        SkillInstaller::new(project.path(), &options)
+            .with_lockfile(&lock_path)
+            .deploy("demo", skill_src.path())
+            .unwrap();
+        assert!(!rule.exists());
+    }
+
+    #[test]
+    fn targets_only_receive_selected_skills() {
+        let project = tempfile::tempdir().unwrap();
+        let skill_src = tempfile::tempdir().unwrap();
+        std::fs::write(
+            skill_src.path().join("SKILL.md"),
+            "---\nname: review\ndescription: Review.\n---\n\nBody.\n",
+        )
+        .unwrap();
+
+        let mut options = options_with_targets();
+        options
+            .targets
+            .insert("cursor".to_string(), ".cursor/skills".to_string());
+        let installer = SkillInstaller::new(project.path(), &options);
+        installer.deploy("review", skill_src.path()).unwrap();
+        let cursor_link = project.path().join(".cursor/skills/review");
+        assert!(cursor_link.is_symlink());
+
+        // Excluding the skill drops the link on the next deploy
+        options.target_filters.insert(
+            "cursor".to_string(),
+            crate::manifest::TargetFilter {
+                include: vec![],
+                exclude: vec!["review".to_string()],
+            },
+        );
+        let installer = SkillInstaller::new(project.path(), &options);
+        installer.deploy("review", skill_src.path()).unwrap();
+        assert!(!cursor_link.exists() && !cursor_link.is_symlink());
+        assert!(project.path().join(".claude/skills/review").is_symlink());
+        assert!(installer.is_deployed("review"));
+        assert_eq!(
+            installer.gitignore_entries("review"),
+            [".agents/skills/review", ".claude/skills/review"]
+        );
+
+        // Uninstalling leaves an unmanaged directory of the same name alone
+        std::fs::create_dir_all(&cursor_link).unwrap();
+        installer.uninstall("review").unwrap();
+        assert!(cursor_link.is_dir());
+        assert!(!project.path().join(".claude/skills/review").exists());
+    }
+
+    #[test]
+    fn excluded_targets_lose_copies_ion_made() {
+        let project = tempfile::tempdir().unwrap();
+        let skill_src = tempfile::tempdir().unwrap();
+        std::fs::write(
+            skill_src.path().join("SKILL.md"),
+            "---\nname: review\ndescription: Review.\n---\n\nBody.\n",
+        )
+        .unwrap();
+
+        let mut options = options_with_targets();
+        options.deploy = Some(DeployMode::Copy);
+        options
+            .targets
+            .insert("cursor".to_string(), ".cursor/skills".to_string());
+        SkillInstaller::new(project.path(), &options)
+            .deploy("review", skill_src.path())
+            .unwrap();
+        let cursor_copy = project.path().join(".cursor/skills/review");
+        assert!(cursor_copy.join("SKILL.md").is_file());
+
+        let exclude = |options: &mut ManifestOptions, target: &str| {
+            options.target_filters.insert(
+                target.to_string(),
+                crate::manifest::TargetFilter {
+                    include: vec![],
+                    exclude: vec!["review".to_string()],
+                },
+            );
+        };
+        exclude(&mut options, "cursor");
+        SkillInstaller::new(project.path(), &options)
+            .deploy("review", skill_src.path())
+            .unwrap();
+        assert!(!cursor_copy.exists());
+
+        // Uninstall cleans targets that exclude the skill too
+        let claude_copy = project.path().join(".claude/skills/review");
+        assert!(claude_copy.join("SKILL.md").is_file());
+        exclude(&mut options, "claude");
+        SkillInstaller::new(project.path(), &options)
+            .uninstall("review")
+            .unwrap();
+        assert!(!claude_copy.exists());
+        assert!(!project.path().join(".agents/skills/review").exists());
+    }
+
+    #[test]
+    fn copy_deploy_replaces_existing_symlinks() {
+        let skill_src = tempfile::tempdir().unwrap();
+        std::fs::write(skill_src.path().join("SKILL.md"), "one").unwrap();
+
+        let project = tempfile::tempdir().unwrap();
+        let options = options_with_targets();
+        SkillInstaller::new(project.path(), &options)
+            .deploy("demo", skill_src.path())
+            .unwrap();
+        let target = project.path().join(".claude/skills/demo");
+        assert!(target.is_symlink());
+
+        let mut copy_options = options_with_targets();
+        copy_options.deploy = Some(DeployMode::Copy);
+        SkillInstaller::new(project.path(), &copy_options)
+            .deploy("demo", skill_src.path())
+            .unwrap();
+        assert!(!target.is_symlink());
+        assert!(target.join("SKILL.md").is_file());
+    }
+
     #[test]
     fn install_local_skill() {
         let skill_src = tempfile::tempdir().unwrap();
@@ -859,6 +1640,30 @@ mod tests {
         assert!(agents_skill.join("SKILL.md").exists());
     }
 
+    #[test]
+    fn lock_records_checksum_without_deploying() {
+        let skill_src = tempfile::tempdir().unwrap();
+        std::fs::write(
+            skill_src.path().join("SKILL.md"),
+            "---\nname: lock-test\ndescription: A lock test skill.\nmetadata:\n  version: 1.0.0\n---\n\nBody.\n",
+        )
+        .unwrap();
+
+        let project = tempfile::tempdir().unwrap();
+        let source = SkillSource::from_path(&skill_src.path().display().to_string());
+        let options = options_with_targets();
+
+        let installer = SkillInstaller::new(project.path(), &options);
+        let locked = installer.lock("lock-test", &source).unwrap();
+        assert_eq!(
+            locked.checksum(),
+            Some(git::checksum_dir(skill_src.path()).unwrap().as_str())
+        );
+        assert_eq!(locked.version.as_deref(), Some("1.0.0"));
+        assert!(!project.path().join(".agents").exists());
+        assert!(!project.path().join(".claude").exists());
+    }
+
     #[test]
     fn install_blocks_on_validation_errors() {
         let skill_src = tempfile::tempdir().unwrap();
@@ -961,6 +1766,7 @@ mod tests {
         let options = ManifestOptions {
             targets,
             skills_dir: None,
+            ..Default::default()
         };
 
         let installer = SkillInstaller::new(project.path(), &options);
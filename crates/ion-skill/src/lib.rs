@@ -1,17 +1,28 @@
 //! Core library for Ion skill management — installation, validation, search, and configuration.
 
 pub mod agents;
+pub mod archive;
 pub mod binary;
+pub mod catalog;
 pub mod config;
+pub mod credentials;
+pub mod deps;
+pub mod doctor;
 pub mod error;
 pub mod git;
 pub mod gitignore;
+pub mod hosts;
+pub mod http_cache;
 pub mod installer;
 pub mod lockfile;
 pub mod manifest;
 pub mod manifest_writer;
 pub mod migrate;
+pub mod oci;
+pub mod offline;
+pub mod prune;
 pub mod registry;
+pub mod render;
 pub mod search;
 pub mod skill;
 pub mod source;
@@ -19,6 +30,8 @@ pub mod templates;
 pub mod tool_permission;
 pub mod update;
 pub mod validate;
+pub mod verify;
+pub mod versions;
 pub mod workspace;
 
 // Re-export ionem's self_update for backward compatibility.
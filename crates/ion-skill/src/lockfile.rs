@@ -1,10 +1,13 @@
-//! Ion.lock types — track installed skills with pinned versions and checksums across Git, binary, and local sources.
+//! Ion.lock types — track installed skills with pinned versions and checksums across Git, archive, OCI, binary, and local sources.
 
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::Path;
 
 use serde::{Deserialize, Serialize};
 
-use crate::{Error, Result};
+use crate::manifest::SkillEntry;
+use crate::source::{Channel, SkillSource, SkillSourceKind};
+use crate::{Error, Result, versions};
 
 // ---------------------------------------------------------------------------
 // Public API types
@@ -15,6 +18,12 @@ pub enum LockedSkillKind {
     Git {
         commit: String,
         checksum: String,
+        /// Tag the commit was resolved from, for skills with a `version`
+        /// requirement or a tag channel.
+        tag: Option<String>,
+        /// Branch or tag channel that produced the commit; `None` means the
+        /// default branch, a `rev` or a `version` requirement.
+        channel: Option<Channel>,
     },
     Binary {
         binary_name: String,
@@ -28,6 +37,16 @@ pub enum LockedSkillKind {
     Http {
         checksum: Option<String>,
     },
+    Archive {
+        /// SHA-256 digest of the downloaded archive.
+        sha256: String,
+        checksum: Option<String>,
+    },
+    Oci {
+        /// Digest of the artifact's manifest (`sha256:…`).
+        digest: String,
+        checksum: Option<String>,
+    },
     Path {
         checksum: Option<String>,
     },
@@ -40,6 +59,12 @@ pub struct LockedSkill {
     pub path: Option<String>,
     pub version: Option<String>,
     pub kind: LockedSkillKind,
+    /// Skills whose `requires:` pulled this one in. Empty for skills
+    /// declared directly in Ion.toml.
+    pub required_by: Vec<String>,
+    /// Files rendered for targets with a native format, relative to the
+    /// project directory.
+    pub rendered: Vec<String>,
 }
 
 // ---------------------------------------------------------------------------
@@ -58,7 +83,14 @@ impl LockedSkill {
             source: source.into(),
             path: None,
             version: None,
-            kind: LockedSkillKind::Git { commit, checksum },
+            kind: LockedSkillKind::Git {
+                commit,
+                checksum,
+                tag: None,
+                channel: None,
+            },
+            required_by: Vec::new(),
+            rendered: Vec::new(),
         }
     }
 
@@ -80,6 +112,8 @@ impl LockedSkill {
                 binary_checksum,
                 dev: false,
             },
+            required_by: Vec::new(),
+            rendered: Vec::new(),
         }
     }
 
@@ -90,6 +124,8 @@ impl LockedSkill {
             path: None,
             version: None,
             kind: LockedSkillKind::Local { checksum: None },
+            required_by: Vec::new(),
+            rendered: Vec::new(),
         }
     }
 
@@ -100,6 +136,46 @@ impl LockedSkill {
             path: None,
             version: None,
             kind: LockedSkillKind::Http { checksum: None },
+            required_by: Vec::new(),
+            rendered: Vec::new(),
+        }
+    }
+
+    pub fn archive(
+        name: impl Into<String>,
+        source: impl Into<String>,
+        sha256: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            source: source.into(),
+            path: None,
+            version: None,
+            kind: LockedSkillKind::Archive {
+                sha256: sha256.into(),
+                checksum: None,
+            },
+            required_by: Vec::new(),
+            rendered: Vec::new(),
+        }
+    }
+
+    pub fn oci(
+        name: impl Into<String>,
+        source: impl Into<String>,
+        digest: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            source: source.into(),
+            path: None,
+            version: None,
+            kind: LockedSkillKind::Oci {
+                digest: digest.into(),
+                checksum: None,
+            },
+            required_by: Vec::new(),
+            rendered: Vec::new(),
         }
     }
 
@@ -110,6 +186,8 @@ impl LockedSkill {
             path: None,
             version: None,
             kind: LockedSkillKind::Path { checksum: None },
+            required_by: Vec::new(),
+            rendered: Vec::new(),
         }
     }
 
@@ -132,12 +210,38 @@ impl LockedSkill {
         match &mut self.kind {
             LockedSkillKind::Local { checksum: c }
             | LockedSkillKind::Http { checksum: c }
+            | LockedSkillKind::Archive { checksum: c, .. }
+            | LockedSkillKind::Oci { checksum: c, .. }
             | LockedSkillKind::Path { checksum: c } => *c = Some(checksum.into()),
             _ => {}
         }
         self
     }
 
+    pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
+        if let LockedSkillKind::Git { tag: t, .. } = &mut self.kind {
+            *t = Some(tag.into());
+        }
+        self
+    }
+
+    pub fn with_channel(mut self, channel: Channel) -> Self {
+        if let LockedSkillKind::Git { channel: c, .. } = &mut self.kind {
+            *c = Some(channel);
+        }
+        self
+    }
+
+    pub fn with_required_by(mut self, required_by: Vec<String>) -> Self {
+        self.required_by = required_by;
+        self
+    }
+
+    pub fn with_rendered(mut self, rendered: Vec<String>) -> Self {
+        self.rendered = rendered;
+        self
+    }
+
     pub fn with_dev(mut self) -> Self {
         match &mut self.kind {
             LockedSkillKind::Binary { dev, .. } => *dev = true,
@@ -181,17 +285,87 @@ impl LockedSkill {
         }
     }
 
+    pub fn tag(&self) -> Option<&str> {
+        match &self.kind {
+            LockedSkillKind::Git { tag, .. } => tag.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn channel(&self) -> Option<&Channel> {
+        match &self.kind {
+            LockedSkillKind::Git { channel, .. } => channel.as_ref(),
+            _ => None,
+        }
+    }
+
     pub fn checksum(&self) -> Option<&str> {
         match &self.kind {
             LockedSkillKind::Git { checksum, .. } => Some(checksum),
             LockedSkillKind::Local { checksum }
             | LockedSkillKind::Http { checksum }
+            | LockedSkillKind::Archive { checksum, .. }
+            | LockedSkillKind::Oci { checksum, .. }
             | LockedSkillKind::Path { checksum } => checksum.as_deref(),
             LockedSkillKind::Binary {
                 binary_checksum, ..
             } => binary_checksum.as_deref(),
         }
     }
+
+    pub fn sha256(&self) -> Option<&str> {
+        match &self.kind {
+            LockedSkillKind::Archive { sha256, .. } => Some(sha256),
+            _ => None,
+        }
+    }
+
+    /// The manifest digest an OCI skill is locked to.
+    pub fn digest(&self) -> Option<&str> {
+        match &self.kind {
+            LockedSkillKind::Oci { digest, .. } => Some(digest),
+            _ => None,
+        }
+    }
+
+    /// Whether this entry was installed from `source` (same repo or location
+    /// and skill path).
+    pub fn locks_source(&self, source: &SkillSource) -> bool {
+        self.source == expected_locked_source(source)
+            && (source.is_local() || self.path == source.path)
+    }
+
+    /// Pin a git-based source to the commit recorded in this entry, an
+    /// archive source without a `sha256` to the locked digest, or an OCI
+    /// source without a `rev` to the locked manifest digest.
+    ///
+    /// Other sources, or entries without a recorded commit, are returned
+    /// unchanged. A version requirement that was resolved to a tag is
+    /// dropped, since the pinned commit already satisfies it.
+    pub fn pin(&self, source: &SkillSource) -> SkillSource {
+        if source.is_archive() {
+            return match self.sha256() {
+                Some(digest) if source.sha256().is_none() => source.clone().with_sha256(digest),
+                _ => source.clone(),
+            };
+        }
+        if source.is_oci() {
+            return match self.digest() {
+                Some(digest) if source.rev.is_none() => source.clone().with_rev(digest),
+                _ => source.clone(),
+            };
+        }
+        match self.commit() {
+            Some(commit) if source.is_git_based() && !commit.is_empty() => {
+                let mut pinned = source.clone().with_rev(commit);
+                if self.tag().is_some() && self.channel().is_none() {
+                    pinned.version = None;
+                }
+                pinned
+            }
+            _ => source.clone(),
+        }
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -244,6 +418,8 @@ impl From<LegacyLockedSkill> for LockedSkill {
             LockedSkillKind::Git {
                 commit: old.commit.unwrap_or_default(),
                 checksum: old.checksum.unwrap_or_default(),
+                tag: None,
+                channel: None,
             }
         };
         LockedSkill {
@@ -252,6 +428,8 @@ impl From<LegacyLockedSkill> for LockedSkill {
             path: old.path,
             version: old.version,
             kind,
+            required_by: Vec::new(),
+            rendered: Vec::new(),
         }
     }
 }
@@ -274,6 +452,14 @@ struct RawLockedSkill {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     checksum: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    sha256: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    digest: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tag: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    channel: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     binary_name: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     binary_version: Option<String>,
@@ -281,6 +467,10 @@ struct RawLockedSkill {
     binary_checksum: Option<String>,
     #[serde(default, skip_serializing_if = "is_false")]
     dev: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    required_by: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    rendered: Vec<String>,
 }
 
 fn is_false(v: &bool) -> bool {
@@ -295,6 +485,8 @@ impl TryFrom<RawLockedSkill> for LockedSkill {
             "git" => LockedSkillKind::Git {
                 commit: raw.commit.ok_or("git skill missing 'commit' field")?,
                 checksum: raw.checksum.ok_or("git skill missing 'checksum' field")?,
+                tag: raw.tag,
+                channel: raw.channel.map(|c| c.parse()).transpose()?,
             },
             "binary" => LockedSkillKind::Binary {
                 binary_name: raw
@@ -310,6 +502,14 @@ impl TryFrom<RawLockedSkill> for LockedSkill {
             "http" => LockedSkillKind::Http {
                 checksum: raw.checksum,
             },
+            "archive" => LockedSkillKind::Archive {
+                sha256: raw.sha256.ok_or("archive skill missing 'sha256' field")?,
+                checksum: raw.checksum,
+            },
+            "oci" => LockedSkillKind::Oci {
+                digest: raw.digest.ok_or("oci skill missing 'digest' field")?,
+                checksum: raw.checksum,
+            },
             "path" => LockedSkillKind::Path {
                 checksum: raw.checksum,
             },
@@ -325,17 +525,23 @@ impl TryFrom<RawLockedSkill> for LockedSkill {
             path: raw.path,
             version: raw.version,
             kind,
+            required_by: raw.required_by,
+            rendered: raw.rendered,
         })
     }
 }
 
 impl From<LockedSkill> for RawLockedSkill {
     fn from(skill: LockedSkill) -> Self {
+        let tag = skill.tag().map(str::to_string);
+        let channel = skill.channel().map(|c| c.to_string());
+        let sha256 = skill.sha256().map(str::to_string);
+        let digest = skill.digest().map(str::to_string);
         let (kind_str, commit, checksum, binary_name, binary_version, binary_checksum, dev) =
             match skill.kind {
-                LockedSkillKind::Git { commit, checksum } => {
-                    ("git", Some(commit), Some(checksum), None, None, None, false)
-                }
+                LockedSkillKind::Git {
+                    commit, checksum, ..
+                } => ("git", Some(commit), Some(checksum), None, None, None, false),
                 LockedSkillKind::Binary {
                     binary_name,
                     binary_version,
@@ -356,6 +562,12 @@ impl From<LockedSkill> for RawLockedSkill {
                 LockedSkillKind::Http { checksum } => {
                     ("http", None, checksum, None, None, None, false)
                 }
+                LockedSkillKind::Archive { checksum, .. } => {
+                    ("archive", None, checksum, None, None, None, false)
+                }
+                LockedSkillKind::Oci { checksum, .. } => {
+                    ("oci", None, checksum, None, None, None, false)
+                }
                 LockedSkillKind::Path { checksum } => {
                     ("path", None, checksum, None, None, None, false)
                 }
@@ -368,10 +580,16 @@ impl From<LockedSkill> for RawLockedSkill {
             version: skill.version,
             commit,
             checksum,
+            sha256,
+            digest,
+            tag,
+            channel,
             binary_name,
             binary_version,
             binary_checksum,
             dev,
+            required_by: skill.required_by,
+            rendered: skill.rendered,
         }
     }
 }
@@ -474,6 +692,269 @@ impl Lockfile {
     pub fn remove(&mut self, name: &str) {
         self.skills.retain(|s| s.name != name);
     }
+
+    /// Compare this lockfile against the skills declared in Ion.toml.
+    ///
+    /// Returns every disagreement found; an empty list means the lock is
+    /// up to date with the manifest.
+    pub fn drift(&self, skills: &BTreeMap<String, SkillEntry>) -> Result<Vec<LockDrift>> {
+        let mut drift = Vec::new();
+
+        for (name, entry) in skills {
+            let source = entry.resolve()?;
+            let Some(locked) = self.find(name) else {
+                drift.push(LockDrift::Missing { name: name.clone() });
+                continue;
+            };
+
+            let expected = expected_locked_source(&source);
+            if locked.source != expected {
+                drift.push(LockDrift::SourceMismatch {
+                    name: name.clone(),
+                    expected,
+                    locked: locked.source.clone(),
+                });
+                continue;
+            }
+
+            // Local skills never record a path in the lock.
+            if !source.is_local() && locked.path != source.path {
+                drift.push(LockDrift::PathMismatch {
+                    name: name.clone(),
+                    expected: source.path.clone(),
+                    locked: locked.path.clone(),
+                });
+                continue;
+            }
+
+            if let Some(expected) = source.sha256()
+                && locked.sha256() != Some(expected)
+            {
+                drift.push(LockDrift::DigestMismatch {
+                    name: name.clone(),
+                    expected: expected.to_string(),
+                    locked: locked.sha256().map(str::to_string),
+                });
+                continue;
+            }
+
+            if source.is_oci()
+                && let Some(expected) = crate::oci::pinned_digest(&source)
+                && locked.digest() != Some(expected.as_str())
+            {
+                drift.push(LockDrift::DigestMismatch {
+                    name: name.clone(),
+                    expected,
+                    locked: locked.digest().map(str::to_string),
+                });
+                continue;
+            }
+
+            if source.rev.is_none() && source.channel.as_ref() != locked.channel() {
+                drift.push(LockDrift::ChannelMismatch {
+                    name: name.clone(),
+                    expected: source.channel.clone(),
+                    locked: locked.channel().cloned(),
+                });
+                continue;
+            }
+
+            if source.rev.is_none()
+                && source.channel.is_none()
+                && let (Some(required), Some(tag)) = (&source.version, locked.tag())
+                && !versions::tag_satisfies(tag, required, source.path.as_deref())
+            {
+                drift.push(LockDrift::VersionMismatch {
+                    name: name.clone(),
+                    required: required.clone(),
+                    locked: tag.to_string(),
+                });
+            }
+        }
+
+        drift.extend(
+            self.stale(skills)
+                .into_iter()
+                .map(|name| LockDrift::Stale { name }),
+        );
+
+        Ok(drift)
+    }
+
+    /// Entries Ion.toml no longer declares, directly or as a dependency of a
+    /// declared skill. Unlike [`drift`](Self::drift), this never resolves
+    /// the manifest entries, so it can't fail.
+    pub fn stale(&self, skills: &BTreeMap<String, SkillEntry>) -> Vec<String> {
+        let orphans = self.orphans(skills);
+        self.skills
+            .iter()
+            .filter(|locked| {
+                !skills.contains_key(&locked.name)
+                    && (locked.required_by.is_empty() || orphans.contains(&locked.name))
+            })
+            .map(|locked| locked.name.clone())
+            .collect()
+    }
+
+    /// Dependencies that nothing declared in Ion.toml still needs.
+    ///
+    /// A dependency is kept while any skill in its `required_by` list is
+    /// declared in `skills` or is itself a kept dependency.
+    pub fn orphans(&self, skills: &BTreeMap<String, SkillEntry>) -> Vec<String> {
+        let mut live: BTreeSet<&str> = skills.keys().map(String::as_str).collect();
+        loop {
+            let before = live.len();
+            for locked in &self.skills {
+                if locked.required_by.iter().any(|r| live.contains(r.as_str())) {
+                    live.insert(&locked.name);
+                }
+            }
+            if live.len() == before {
+                break;
+            }
+        }
+        self.skills
+            .iter()
+            .filter(|l| !l.required_by.is_empty() && !live.contains(l.name.as_str()))
+            .map(|l| l.name.clone())
+            .collect()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Manifest/lock drift
+// ---------------------------------------------------------------------------
+
+/// A disagreement between an Ion.toml entry and Ion.lock.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LockDrift {
+    /// Declared in Ion.toml but has no Ion.lock entry.
+    Missing { name: String },
+    /// Locked in Ion.lock but no longer declared in Ion.toml.
+    Stale { name: String },
+    /// Locked from a different source than Ion.toml declares.
+    SourceMismatch {
+        name: String,
+        expected: String,
+        locked: String,
+    },
+    /// Locked with a different skill path than Ion.toml declares.
+    PathMismatch {
+        name: String,
+        expected: Option<String>,
+        locked: Option<String>,
+    },
+    /// Locked from a different branch or tag channel than Ion.toml declares.
+    ChannelMismatch {
+        name: String,
+        expected: Option<Channel>,
+        locked: Option<Channel>,
+    },
+    /// Locked to a different digest than Ion.toml pins: an archive's `sha256`,
+    /// or an OCI skill's `rev` or `@sha256:` reference.
+    DigestMismatch {
+        name: String,
+        expected: String,
+        locked: Option<String>,
+    },
+    /// Locked to a tag outside the version requirement Ion.toml declares.
+    VersionMismatch {
+        name: String,
+        required: String,
+        locked: String,
+    },
+}
+
+impl LockDrift {
+    pub fn name(&self) -> &str {
+        match self {
+            LockDrift::Missing { name }
+            | LockDrift::Stale { name }
+            | LockDrift::SourceMismatch { name, .. }
+            | LockDrift::PathMismatch { name, .. }
+            | LockDrift::ChannelMismatch { name, .. }
+            | LockDrift::DigestMismatch { name, .. }
+            | LockDrift::VersionMismatch { name, .. } => name,
+        }
+    }
+}
+
+impl std::fmt::Display for LockDrift {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LockDrift::Missing { name } => write!(f, "'{name}' is not in Ion.lock"),
+            LockDrift::Stale { name } => write!(f, "'{name}' is locked but not in Ion.toml"),
+            LockDrift::SourceMismatch {
+                name,
+                expected,
+                locked,
+            } => write!(
+                f,
+                "'{name}' source changed: Ion.toml has {expected}, Ion.lock has {locked}"
+            ),
+            LockDrift::PathMismatch {
+                name,
+                expected,
+                locked,
+            } => write!(
+                f,
+                "'{name}' path changed: Ion.toml has {}, Ion.lock has {}",
+                expected.as_deref().unwrap_or("(none)"),
+                locked.as_deref().unwrap_or("(none)")
+            ),
+            LockDrift::ChannelMismatch {
+                name,
+                expected,
+                locked,
+            } => {
+                let describe = |channel: &Option<Channel>| {
+                    channel
+                        .as_ref()
+                        .map_or_else(|| "the default branch".to_string(), |c| c.to_string())
+                };
+                write!(
+                    f,
+                    "'{name}' channel changed: Ion.toml follows {}, Ion.lock follows {}",
+                    describe(expected),
+                    describe(locked)
+                )
+            }
+            LockDrift::DigestMismatch {
+                name,
+                expected,
+                locked,
+            } => write!(
+                f,
+                "'{name}' digest changed: Ion.toml has {expected}, Ion.lock has {}",
+                locked.as_deref().unwrap_or("(none)")
+            ),
+            LockDrift::VersionMismatch {
+                name,
+                required,
+                locked,
+            } => write!(
+                f,
+                "'{name}' is locked to {locked}, which does not satisfy version {required}"
+            ),
+        }
+    }
+}
+
+/// The `source` string the installer records in Ion.lock for a given source.
+pub(crate) fn expected_locked_source(source: &SkillSource) -> String {
+    match &source.kind {
+        SkillSourceKind::Binary { .. }
+            if !source.is_local_path()
+                && !source.source.starts_with("http://")
+                && !source.source.starts_with("https://") =>
+        {
+            format!("https://github.com/{}.git", source.source)
+        }
+        _ => source
+            .git_url()
+            .ok()
+            .unwrap_or_else(|| source.source.clone()),
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -693,10 +1174,16 @@ kind = "local"
             version: None,
             commit: None,
             checksum: None,
+            sha256: None,
+            digest: None,
+            tag: None,
+            channel: None,
             binary_name: None,
             binary_version: None,
             binary_checksum: None,
             dev: false,
+            required_by: Vec::new(),
+            rendered: Vec::new(),
         };
         let err = LockedSkill::try_from(raw).unwrap_err();
         assert!(err.contains("unknown locked skill kind 'unknown'"));
@@ -712,10 +1199,16 @@ kind = "local"
             version: None,
             commit: None,
             checksum: Some("sha256:abc".into()),
+            sha256: None,
+            digest: None,
+            tag: None,
+            channel: None,
             binary_name: None,
             binary_version: None,
             binary_checksum: None,
             dev: false,
+            required_by: Vec::new(),
+            rendered: Vec::new(),
         };
         let err = LockedSkill::try_from(raw).unwrap_err();
         assert!(err.contains("missing 'commit'"));
@@ -855,4 +1348,374 @@ updated-at = "2026-01-01T00:00:00Z"
         let agents = lockfile.agents.as_ref().unwrap();
         assert_eq!(agents.template, "org/templates");
     }
+
+    fn manifest_skills(toml_src: &str) -> BTreeMap<String, SkillEntry> {
+        crate::manifest::Manifest::parse(toml_src).unwrap().skills
+    }
+
+    #[test]
+    fn drift_empty_when_in_sync() {
+        let skills = manifest_skills(
+            r#"
+[skills]
+brainstorming = "obra/superpowers/brainstorming"
+"#,
+        );
+        let mut lockfile = Lockfile::default();
+        lockfile.upsert(
+            LockedSkill::git(
+                "brainstorming",
+                "https://github.com/obra/superpowers.git",
+                "abc".into(),
+                "sha256:def".into(),
+            )
+            .with_path("brainstorming"),
+        );
+        assert!(lockfile.drift(&skills).unwrap().is_empty());
+    }
+
+    #[test]
+    fn drift_reports_missing_and_stale() {
+        let skills = manifest_skills(
+            r#"
+[skills]
+new-skill = "org/repo/new-skill"
+"#,
+        );
+        let mut lockfile = Lockfile::default();
+        lockfile.upsert(LockedSkill::git(
+            "old-skill",
+            "https://github.com/org/repo.git",
+            "abc".into(),
+            "sha256:def".into(),
+        ));
+        let drift = lockfile.drift(&skills).unwrap();
+        assert_eq!(
+            drift,
+            vec![
+                LockDrift::Missing {
+                    name: "new-skill".into()
+                },
+                LockDrift::Stale {
+                    name: "old-skill".into()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn drift_reports_source_and_path_changes() {
+        let skills = manifest_skills(
+            r#"
+[skills]
+moved = "org/other/moved"
+renamed = "org/repo/new-path"
+"#,
+        );
+        let mut lockfile = Lockfile::default();
+        lockfile.upsert(
+            LockedSkill::git(
+                "moved",
+                "https://github.com/org/repo.git",
+                "abc".into(),
+                "sha256:def".into(),
+            )
+            .with_path("moved"),
+        );
+        lockfile.upsert(
+            LockedSkill::git(
+                "renamed",
+                "https://github.com/org/repo.git",
+                "abc".into(),
+                "sha256:def".into(),
+            )
+            .with_path("old-path"),
+        );
+        let drift = lockfile.drift(&skills).unwrap();
+        assert_eq!(drift.len(), 2);
+        assert!(matches!(&drift[0], LockDrift::SourceMismatch { name, .. } if name == "moved"));
+        assert!(matches!(&drift[1], LockDrift::PathMismatch { name, .. } if name == "renamed"));
+    }
+
+    #[test]
+    fn drift_matches_binary_and_local_sources() {
+        let skills = manifest_skills(
+            r#"
+[skills]
+mytool = { type = "binary", source = "owner/mytool", binary = "mytool" }
+mine = { type = "local" }
+"#,
+        );
+        let mut lockfile = Lockfile::default();
+        lockfile.upsert(LockedSkill::binary(
+            "mytool",
+            "https://github.com/owner/mytool.git",
+            "mytool",
+            Some("1.0.0".into()),
+            None,
+        ));
+        lockfile.upsert(LockedSkill::local("mine"));
+        assert!(lockfile.drift(&skills).unwrap().is_empty());
+    }
+
+    #[test]
+    fn pin_sets_rev_for_git_sources_only() {
+        let locked = LockedSkill::git("s", "u", "abc123".into(), "sha256:def".into());
+        let pinned = locked.pin(&SkillSource::github("org/repo"));
+        assert_eq!(pinned.rev.as_deref(), Some("abc123"));
+
+        let pinned = locked.pin(&SkillSource::path("./skills/s"));
+        assert_eq!(pinned.rev, None);
+
+        let unpinned = LockedSkill::git("s", "u", String::new(), String::new());
+        assert_eq!(unpinned.pin(&SkillSource::github("org/repo")).rev, None);
+    }
+
+    #[test]
+    fn pin_drops_version_resolved_to_tag() {
+        let mut source = SkillSource::github("org/repo");
+        source.version = Some("^1".into());
+
+        let locked = LockedSkill::git("s", "u", "abc123".into(), "sha256:def".into());
+        assert_eq!(locked.pin(&source).version.as_deref(), Some("^1"));
+
+        let tagged = locked.with_tag("v1.2.0");
+        let pinned = tagged.pin(&source);
+        assert_eq!(pinned.rev.as_deref(), Some("abc123"));
+        assert_eq!(pinned.version, None);
+    }
+
+    #[test]
+    fn roundtrip_tag() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Ion.lock");
+
+        let mut lockfile = Lockfile::default();
+        lockfile.upsert(
+            LockedSkill::git("s", "u", "abc123".into(), "sha256:def".into()).with_tag("v1.2.0"),
+        );
+        lockfile.write_to(&path).unwrap();
+        assert!(
+            std::fs::read_to_string(&path)
+                .unwrap()
+                .contains("tag = \"v1.2.0\"")
+        );
+
+        let loaded = Lockfile::from_file(&path).unwrap();
+        assert_eq!(loaded.skills[0].tag(), Some("v1.2.0"));
+    }
+
+    #[test]
+    fn roundtrip_channel() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Ion.lock");
+
+        let mut lockfile = Lockfile::default();
+        lockfile.upsert(
+            LockedSkill::git("s", "u", "abc123".into(), "sha256:def".into())
+                .with_tag("v1.2.0")
+                .with_channel(Channel::LatestMatching("v*".into())),
+        );
+        lockfile.write_to(&path).unwrap();
+        assert!(
+            std::fs::read_to_string(&path)
+                .unwrap()
+                .contains("channel = \"tag:latest-matching:v*\"")
+        );
+
+        let loaded = Lockfile::from_file(&path).unwrap();
+        assert_eq!(loaded.skills[0], lockfile.skills[0]);
+    }
+
+    #[test]
+    fn roundtrip_required_by() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Ion.lock");
+
+        let mut lockfile = Lockfile::default();
+        lockfile.upsert(
+            LockedSkill::git("dep", "u", "abc123".into(), "sha256:def".into())
+                .with_required_by(vec!["a".into(), "b".into()]),
+        );
+        lockfile.write_to(&path).unwrap();
+        assert!(
+            std::fs::read_to_string(&path)
+                .unwrap()
+                .contains("required_by = [")
+        );
+
+        let loaded = Lockfile::from_file(&path).unwrap();
+        assert_eq!(loaded.skills[0], lockfile.skills[0]);
+    }
+
+    #[test]
+    fn roundtrip_archive_and_digest_drift() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Ion.lock");
+
+        let mut lockfile = Lockfile::default();
+        lockfile.upsert(
+            LockedSkill::archive("review", "https://example.com/review.tar.gz", "abc123")
+                .with_checksum("sha256:def"),
+        );
+        lockfile.write_to(&path).unwrap();
+        let loaded = Lockfile::from_file(&path).unwrap();
+        assert_eq!(loaded.skills[0], lockfile.skills[0]);
+        assert_eq!(loaded.skills[0].sha256(), Some("abc123"));
+
+        let skills = manifest_skills(
+            r#"
+[skills]
+review = { type = "archive", source = "https://example.com/review.tar.gz", sha256 = "abc123" }
+"#,
+        );
+        assert!(loaded.drift(&skills).unwrap().is_empty());
+
+        let skills = manifest_skills(
+            r#"
+[skills]
+review = { type = "archive", source = "https://example.com/review.tar.gz", sha256 = "fff000" }
+"#,
+        );
+        assert!(matches!(
+            loaded.drift(&skills).unwrap()[..],
+            [LockDrift::DigestMismatch { .. }]
+        ));
+
+        // The locked digest pins a source that doesn't declare one.
+        let source = SkillSource::archive("https://example.com/review.tar.gz");
+        assert_eq!(loaded.skills[0].pin(&source).sha256(), Some("abc123"));
+    }
+
+    #[test]
+    fn roundtrip_oci_and_digest_drift() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Ion.lock");
+        let digest = format!("sha256:{}", "a".repeat(64));
+        let other = format!("sha256:{}", "b".repeat(64));
+
+        let mut lockfile = Lockfile::default();
+        lockfile.upsert(
+            LockedSkill::oci("review", "oci://ghcr.io/acme/review:1.0", &digest)
+                .with_checksum("sha256:def"),
+        );
+        lockfile.write_to(&path).unwrap();
+        let loaded = Lockfile::from_file(&path).unwrap();
+        assert_eq!(loaded.skills[0], lockfile.skills[0]);
+        assert_eq!(loaded.skills[0].digest(), Some(digest.as_str()));
+
+        let skills = manifest_skills(
+            r#"
+[skills]
+review = "oci://ghcr.io/acme/review:1.0"
+"#,
+        );
+        assert!(loaded.drift(&skills).unwrap().is_empty());
+
+        let skills = manifest_skills(&format!(
+            "[skills]\nreview = {{ type = \"oci\", source = \"oci://ghcr.io/acme/review:1.0\", rev = \"{other}\" }}\n"
+        ));
+        assert!(matches!(
+            loaded.drift(&skills).unwrap()[..],
+            [LockDrift::DigestMismatch { .. }]
+        ));
+
+        // The locked digest pins a source that follows a tag.
+        let source = SkillSource::oci("oci://ghcr.io/acme/review:1.0");
+        assert_eq!(loaded.skills[0].pin(&source).rev, Some(digest));
+    }
+
+    #[test]
+    fn orphans_follow_required_by_chains() {
+        let skills = manifest_skills(
+            r#"
+[skills]
+a = "org/repo/a"
+"#,
+        );
+        let dep = |name: &str, by: &str| {
+            LockedSkill::git(name, "u", "abc".into(), "sha256:def".into())
+                .with_required_by(vec![by.to_string()])
+        };
+        let mut lockfile = Lockfile::default();
+        lockfile.upsert(dep("b", "a"));
+        lockfile.upsert(dep("c", "b"));
+        lockfile.upsert(dep("x", "gone"));
+        lockfile.upsert(dep("y", "x"));
+
+        assert_eq!(lockfile.orphans(&skills), ["x", "y"]);
+
+        // Live dependencies aren't stale, orphaned ones are
+        let stale: Vec<String> = lockfile
+            .drift(&skills)
+            .unwrap()
+            .into_iter()
+            .filter(|d| matches!(d, LockDrift::Stale { .. }))
+            .map(|d| d.name().to_string())
+            .collect();
+        assert_eq!(stale, ["x", "y"]);
+    }
+
+    #[test]
+    fn drift_reports_channel_changes() {
+        let skills = manifest_skills(
+            r#"
+[skills]
+s = { source = "org/repo/s", branch = "stable" }
+"#,
+        );
+        let mut lockfile = Lockfile::default();
+        lockfile.upsert(
+            LockedSkill::git(
+                "s",
+                "https://github.com/org/repo.git",
+                "abc".into(),
+                "sha256:def".into(),
+            )
+            .with_path("s"),
+        );
+        let drift = lockfile.drift(&skills).unwrap();
+        assert_eq!(drift.len(), 1);
+        assert_eq!(
+            drift[0].to_string(),
+            "'s' channel changed: Ion.toml follows branch:stable, Ion.lock follows the default branch"
+        );
+
+        lockfile.upsert(
+            lockfile.skills[0]
+                .clone()
+                .with_channel(Channel::Branch("stable".into())),
+        );
+        assert!(lockfile.drift(&skills).unwrap().is_empty());
+    }
+
+    #[test]
+    fn drift_reports_tag_outside_version_requirement() {
+        let skills = manifest_skills(
+            r#"
+[skills]
+s = { source = "org/repo/s", version = "^2" }
+"#,
+        );
+        let mut lockfile = Lockfile::default();
+        lockfile.upsert(
+            LockedSkill::git(
+                "s",
+                "https://github.com/org/repo.git",
+                "abc".into(),
+                "sha256:def".into(),
+            )
+            .with_path("s")
+            .with_tag("v1.2.0"),
+        );
+        let drift = lockfile.drift(&skills).unwrap();
+        assert_eq!(
+            drift,
+            vec![LockDrift::VersionMismatch {
+                name: "s".into(),
+                required: "^2".into(),
+                locked: "v1.2.0".into(),
+            }]
+        );
+    }
 }
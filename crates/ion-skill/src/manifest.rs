@@ -5,12 +5,15 @@ use std::path::Path;
 
 use serde::{Deserialize, Serialize};
 
-use crate::source::{SkillSource, SourceType};
+use crate::render::TargetFormat;
+use crate::source::{Channel, SkillSource, SourceType};
 use crate::{Error, Result};
 
 /// Default directory where skills are installed within a project.
 pub const DEFAULT_SKILLS_DIR: &str = ".agents/skills";
 
+// Entries are parsed once per manifest load, so the size of `Full` is not worth boxing.
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum SkillEntry {
@@ -24,6 +27,12 @@ pub enum SkillEntry {
         version: Option<String>,
         #[serde(default)]
         rev: Option<String>,
+        /// Branch to follow instead of the default branch.
+        #[serde(default)]
+        branch: Option<String>,
+        /// Tag to follow, or `latest-matching:<glob>` for the newest matching tag.
+        #[serde(default)]
+        tag: Option<String>,
         #[serde(default)]
         path: Option<String>,
         #[serde(default)]
@@ -34,6 +43,12 @@ pub enum SkillEntry {
         forked_from: Option<String>,
         #[serde(default)]
         dev: Option<bool>,
+        /// Expected SHA-256 digest of an archive source.
+        #[serde(default)]
+        sha256: Option<String>,
+        /// Leading path components to drop when extracting an archive.
+        #[serde(default, alias = "strip-components")]
+        strip_components: Option<usize>,
     },
 }
 
@@ -47,12 +62,23 @@ impl SkillEntry {
                 source,
                 version,
                 rev,
+                branch,
+                tag,
                 path,
                 binary,
                 asset_pattern,
                 forked_from,
                 dev,
+                sha256,
+                strip_components,
             } => {
+                let pins = [rev.is_some(), branch.is_some(), tag.is_some()];
+                if pins.iter().filter(|set| **set).count() > 1 {
+                    return Err(Error::Manifest(
+                        "only one of 'rev', 'branch' and 'tag' may be set".to_string(),
+                    ));
+                }
+
                 let mut resolved = match source_type {
                     Some(SourceType::Local) => {
                         let mut s =
@@ -83,6 +109,23 @@ impl SkillEntry {
                 if let Some(r) = rev {
                     resolved.rev = Some(r.clone());
                 }
+                if let Some(b) = branch {
+                    resolved.channel = Some(Channel::Branch(b.clone()));
+                }
+                if let Some(t) = tag {
+                    resolved.channel = Some(Channel::tag(t));
+                }
+                if resolved.channel.is_some() && !resolved.is_git_based() {
+                    return Err(Error::Manifest(
+                        "'branch' and 'tag' are only supported for git sources".to_string(),
+                    ));
+                }
+                if (sha256.is_some() || strip_components.is_some()) && !resolved.is_archive() {
+                    return Err(Error::Manifest(
+                        "'sha256' and 'strip-components' are only supported for archive sources"
+                            .to_string(),
+                    ));
+                }
                 if path.is_some() {
                     resolved.path = path.clone();
                 }
@@ -99,19 +142,172 @@ impl SkillEntry {
                 if dev.unwrap_or(false) {
                     resolved = resolved.with_dev(true);
                 }
+                if let Some(digest) = sha256 {
+                    resolved = resolved.with_sha256(digest.as_str());
+                }
+                if let Some(count) = strip_components {
+                    resolved = resolved.with_strip_components(*count);
+                }
                 Ok(resolved)
             }
         }
     }
 }
 
+/// How a skill is placed into the skills directory and each target directory.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DeployMode {
+    /// Relative symlinks into the global cache (the default).
+    #[default]
+    Symlink,
+    /// Independent copies, refreshed when their checksum goes stale.
+    Copy,
+    /// Hardlinked files, falling back to copies across filesystems.
+    Hardlink,
+}
+
+impl DeployMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeployMode::Symlink => "symlink",
+            DeployMode::Copy => "copy",
+            DeployMode::Hardlink => "hardlink",
+        }
+    }
+}
+
+impl std::fmt::Display for DeployMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for DeployMode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "symlink" => Ok(DeployMode::Symlink),
+            "copy" => Ok(DeployMode::Copy),
+            "hardlink" => Ok(DeployMode::Hardlink),
+            _ => Err(Error::Manifest(format!(
+                "Invalid deploy mode '{s}': expected 'symlink', 'copy' or 'hardlink'"
+            ))),
+        }
+    }
+}
+
+/// Which skills a target receives, from the `include` and `exclude` lists
+/// of a table-form `[options.targets.<name>]` entry.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TargetFilter {
+    /// Only these skills are deployed to the target; empty means all.
+    pub include: Vec<String>,
+    /// Skills never deployed to the target.
+    pub exclude: Vec<String>,
+}
+
+impl TargetFilter {
+    /// Whether skill `name` passes this filter.
+    pub fn allows(&self, name: &str) -> bool {
+        (self.include.is_empty() || self.include.iter().any(|s| s == name))
+            && !self.exclude.iter().any(|s| s == name)
+    }
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
-#[serde(rename_all = "kebab-case")]
+#[serde(rename_all = "kebab-case", from = "RawManifestOptions")]
 pub struct ManifestOptions {
     #[serde(default)]
     pub targets: BTreeMap<String, String>,
+    /// Skill selection for targets declared in table form, keyed by target name.
+    #[serde(skip)]
+    pub target_filters: BTreeMap<String, TargetFilter>,
     #[serde(default)]
     pub skills_dir: Option<String>,
+    /// How skills are deployed; defaults to symlinks.
+    #[serde(default)]
+    pub deploy: Option<DeployMode>,
+    /// Per-target overrides of `deploy`, keyed by target name.
+    #[serde(default)]
+    pub target_deploy: BTreeMap<String, DeployMode>,
+    /// Targets that receive skills rendered into a tool's native format
+    /// instead of skill directories, keyed by target name.
+    #[serde(default)]
+    pub target_format: BTreeMap<String, TargetFormat>,
+}
+
+/// A `[options.targets]` value: either a bare path or a table that also
+/// selects skills and overrides the format. Deploy modes are set per target
+/// under `[options.target-deploy]` only.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawTarget {
+    Path(String),
+    Table(RawTargetTable),
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawTargetTable {
+    path: String,
+    #[serde(default)]
+    format: Option<TargetFormat>,
+    #[serde(default)]
+    include: Vec<String>,
+    #[serde(default)]
+    exclude: Vec<String>,
+}
+
+/// Serde bridge for [`ManifestOptions`] that splits table-form targets into
+/// the flat per-target maps.
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct RawManifestOptions {
+    #[serde(default)]
+    targets: BTreeMap<String, RawTarget>,
+    #[serde(default)]
+    skills_dir: Option<String>,
+    #[serde(default)]
+    deploy: Option<DeployMode>,
+    #[serde(default)]
+    target_deploy: BTreeMap<String, DeployMode>,
+    #[serde(default)]
+    target_format: BTreeMap<String, TargetFormat>,
+}
+
+impl From<RawManifestOptions> for ManifestOptions {
+    fn from(raw: RawManifestOptions) -> Self {
+        let mut options = ManifestOptions {
+            targets: BTreeMap::new(),
+            target_filters: BTreeMap::new(),
+            skills_dir: raw.skills_dir,
+            deploy: raw.deploy,
+            target_deploy: raw.target_deploy,
+            target_format: raw.target_format,
+        };
+        for (name, target) in raw.targets {
+            let path = match target {
+                RawTarget::Path(path) => path,
+                RawTarget::Table(table) => {
+                    if let Some(format) = table.format {
+                        options.target_format.insert(name.clone(), format);
+                    }
+                    if !table.include.is_empty() || !table.exclude.is_empty() {
+                        let filter = TargetFilter {
+                            include: table.include,
+                            exclude: table.exclude,
+                        };
+                        options.target_filters.insert(name.clone(), filter);
+                    }
+                    table.path
+                }
+            };
+            options.targets.insert(name, path);
+        }
+        options
+    }
 }
 
 impl ManifestOptions {
@@ -121,13 +317,53 @@ impl ManifestOptions {
         if key == "skills-dir" {
             return self.skills_dir.clone();
         }
+        if key == "deploy" {
+            return self.deploy.map(|mode| mode.to_string());
+        }
         let (section, field) = key.split_once('.')?;
         match section {
             "targets" => self.targets.get(field).cloned(),
+            "target-deploy" => self.target_deploy.get(field).map(|mode| mode.to_string()),
+            "target-format" => self.target_format.get(field).map(|f| f.to_string()),
             _ => None,
         }
     }
 
+    /// The deploy mode for the skills directory itself.
+    pub fn deploy_mode(&self) -> DeployMode {
+        self.deploy.unwrap_or_default()
+    }
+
+    /// The deploy mode for `target`, honouring `[options.target-deploy]`.
+    pub fn deploy_mode_for(&self, target: &str) -> DeployMode {
+        self.target_deploy
+            .get(target)
+            .copied()
+            .unwrap_or_else(|| self.deploy_mode())
+    }
+
+    /// Whether skill `name` is deployed to `target`, honouring the target's
+    /// `include` and `exclude` lists.
+    pub fn deploys_to(&self, target: &str, name: &str) -> bool {
+        self.target_filters
+            .get(target)
+            .is_none_or(|filter| filter.allows(name))
+    }
+
+    /// Names of the targets skill `name` is deployed to.
+    pub fn targets_for(&self, name: &str) -> Vec<&str> {
+        self.targets
+            .keys()
+            .filter(|target| self.deploys_to(target, name))
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// The format skills are deployed in for `target`.
+    pub fn format_for(&self, target: &str) -> TargetFormat {
+        self.target_format.get(target).copied().unwrap_or_default()
+    }
+
     /// Returns the configured skills directory, or the default `.agents/skills`.
     pub fn skills_dir_or_default(&self) -> &str {
         self.skills_dir.as_deref().unwrap_or(DEFAULT_SKILLS_DIR)
@@ -143,6 +379,19 @@ impl ManifestOptions {
         if let Some(dir) = &self.skills_dir {
             values.push(("skills-dir".to_string(), dir.clone()));
         }
+        if let Some(mode) = self.deploy {
+            values.push(("deploy".to_string(), mode.to_string()));
+        }
+        values.extend(
+            self.target_deploy
+                .iter()
+                .map(|(k, v)| (format!("target-deploy.{k}"), v.to_string())),
+        );
+        values.extend(
+            self.target_format
+                .iter()
+                .map(|(k, v)| (format!("target-format.{k}"), v.to_string())),
+        );
         values
     }
 }
@@ -276,6 +525,25 @@ mod tests {
         assert_eq!(source.path.as_deref(), Some("my-skill"));
     }
 
+    #[test]
+    fn parse_archive_entry() {
+        let toml_str = "[skills]\nreview = { type = \"archive\", source = \"https://example.com/review.tar.gz\", sha256 = \"ABCDEF\", strip-components = 1, path = \"skills/review\" }\n";
+        let manifest = Manifest::parse(toml_str).unwrap();
+        let source = manifest.skills["review"].resolve().unwrap();
+        assert_eq!(
+            source.kind,
+            SkillSourceKind::Archive {
+                sha256: Some("abcdef".to_string()),
+                strip_components: 1,
+            }
+        );
+        assert_eq!(source.path.as_deref(), Some("skills/review"));
+
+        let toml_str = "[skills]\nreview = { type = \"git\", source = \"https://gitlab.com/org/skills.git\", sha256 = \"abcdef\" }\n";
+        let manifest = Manifest::parse(toml_str).unwrap();
+        assert!(manifest.skills["review"].resolve().is_err());
+    }
+
     #[test]
     fn parse_local_path_entry() {
         let toml_str =
@@ -302,6 +570,54 @@ mod tests {
         assert_eq!(manifest.options.targets["cursor"], ".cursor/skills");
     }
 
+    #[test]
+    fn parse_table_targets_with_selection() {
+        let toml_str = r#"[skills]
+
+[options.targets]
+claude = ".claude/skills"
+
+[options.targets.cursor]
+path = ".cursor/rules"
+exclude = ["shell-admin"]
+format = "cursor"
+
+[options.targets.copilot]
+path = ".github/instructions"
+include = ["review"]
+
+[options.target-deploy]
+cursor = "copy"
+"#;
+        let options = Manifest::parse(toml_str).unwrap().options;
+        assert_eq!(options.targets["cursor"], ".cursor/rules");
+        assert_eq!(options.format_for("cursor"), TargetFormat::Cursor);
+        assert_eq!(options.deploy_mode_for("cursor"), DeployMode::Copy);
+
+        assert!(options.deploys_to("claude", "shell-admin"));
+        assert!(!options.deploys_to("cursor", "shell-admin"));
+        assert!(options.deploys_to("cursor", "review"));
+        assert!(!options.deploys_to("copilot", "shell-admin"));
+        assert_eq!(
+            options.targets_for("review"),
+            ["claude", "copilot", "cursor"]
+        );
+        assert_eq!(options.targets_for("shell-admin"), ["claude"]);
+
+        let typo = "[skills]
+
+[options.targets.cursor]
+path = \".cursor/skills\"\nexclde = [\"x\"]\n";
+        assert!(Manifest::parse(typo).is_err());
+
+        // The deploy mode lives in [options.target-deploy] only
+        let table_deploy = "[skills]
+
+[options.targets.cursor]
+path = \".cursor/skills\"\ndeploy = \"copy\"\n";
+        assert!(Manifest::parse(table_deploy).is_err());
+    }
+
     #[test]
     fn parse_empty_manifest() {
         let manifest = Manifest::parse("[skills]\n").unwrap();
@@ -329,6 +645,31 @@ mod tests {
         assert_eq!(source.version.as_deref(), Some("1.0"));
     }
 
+    #[test]
+    fn parse_channel_entries() {
+        let toml_str = r#"
+[skills]
+a = { source = "org/repo/a", branch = "stable" }
+b = { source = "org/repo/b", tag = "latest" }
+c = { source = "org/repo/c", tag = "latest-matching:v*" }
+"#;
+        let manifest = Manifest::parse(toml_str).unwrap();
+        let channel = |name: &str| manifest.skills[name].resolve().unwrap().channel;
+        assert_eq!(channel("a"), Some(Channel::Branch("stable".into())));
+        assert_eq!(channel("b"), Some(Channel::Tag("latest".into())));
+        assert_eq!(channel("c"), Some(Channel::LatestMatching("v*".into())));
+    }
+
+    #[test]
+    fn rejects_conflicting_channel_and_rev() {
+        let toml_str = "[skills]
+my-skill = { source = \"org/repo\", branch = \"stable\", rev = \"v1\" }
+";
+        let manifest = Manifest::parse(toml_str).unwrap();
+        let err = manifest.skills["my-skill"].resolve().unwrap_err();
+        assert!(err.to_string().contains("only one of"), "got: {err}");
+    }
+
     #[test]
     fn get_project_value() {
         let toml_str = "[skills]\n\n[options.targets]\nclaude = \".claude/skills\"\n";
@@ -439,6 +780,7 @@ path = "templates/AGENTS.md"
         let opts = ManifestOptions {
             targets: std::collections::BTreeMap::new(),
             skills_dir: None,
+            ..Default::default()
         };
         assert_eq!(opts.skills_dir_or_default(), ".agents/skills");
     }
@@ -448,6 +790,7 @@ path = "templates/AGENTS.md"
         let opts = ManifestOptions {
             targets: std::collections::BTreeMap::new(),
             skills_dir: Some("custom/skills".to_string()),
+            ..Default::default()
         };
         assert_eq!(opts.skills_dir_or_default(), "custom/skills");
     }
@@ -2,8 +2,10 @@
 
 use std::path::Path;
 
-use toml_edit::{DocumentMut, Item, Table, value};
+use toml_edit::{DocumentMut, Item, Table, TableLike, value};
 
+use crate::manifest::DeployMode;
+use crate::render::TargetFormat;
 use crate::source::{SkillSource, SkillSourceKind};
 use crate::{Error, Result};
 
@@ -72,17 +74,51 @@ pub fn write_targets(
         .as_table_mut()
         .ok_or_else(|| Error::Manifest("[options] is not a table".to_string()))?;
 
-    options["targets"] = Item::Table(Table::new());
-    let targets_table = options["targets"].as_table_mut().unwrap();
-    for (k, v) in targets {
-        targets_table[k.as_str()] = value(v.as_str());
-    }
+    replace_targets(
+        options,
+        targets.iter().map(|(k, v)| (k.as_str(), v.as_str())),
+    );
 
     let result = doc.to_string();
     std::fs::write(manifest_path, &result).map_err(Error::Io)?;
     Ok(result)
 }
 
+/// Replace `[options.targets]` in `options` with `targets`, keeping the skill
+/// selection and overrides of table-form entries that are still present.
+pub fn replace_targets<'a>(
+    options: &mut Table,
+    targets: impl IntoIterator<Item = (&'a str, &'a str)>,
+) {
+    let previous = options.remove("targets");
+    let mut table = Table::new();
+    for (name, path) in targets {
+        let existing = previous
+            .as_ref()
+            .and_then(|item| item.as_table_like())
+            .and_then(|old| old.get(name))
+            .filter(|item| item.is_table_like());
+        table[name] = existing.cloned().unwrap_or_else(|| value(path));
+        set_target_path(&mut table, name, path);
+    }
+    options["targets"] = Item::Table(table);
+}
+
+/// Set the path of target `name`, in place when it is declared as a table.
+fn set_target_path(targets: &mut dyn TableLike, name: &str, path: &str) {
+    match targets
+        .get_mut(name)
+        .and_then(|item| item.as_table_like_mut())
+    {
+        Some(target) => {
+            target.insert("path", value(path));
+        }
+        None => {
+            targets.insert(name, value(path));
+        }
+    }
+}
+
 /// Write a skills-dir value to an Ion.toml file's [options] section.
 /// Creates the file with a [skills] section if it doesn't exist.
 /// Preserves all existing content.
@@ -144,8 +180,8 @@ pub fn write_agents_config(
 /// Set a value in the [options] section of Ion.toml.
 ///
 /// Handles nested sub-sections like `targets.claude` by writing to
-/// `[options.targets]`, and direct option keys like `options.skills-dir`
-/// by writing to `[options]`.
+/// `[options.targets]` (likewise `target-deploy` and `target-format`), and
+/// direct option keys like `options.skills-dir` by writing to `[options]`.
 pub fn set_option(manifest_path: &Path, key: &str, val: &str) -> Result<String> {
     let (section, field) = key.split_once('.').ok_or_else(|| {
         Error::Manifest(format!(
@@ -168,19 +204,34 @@ pub fn set_option(manifest_path: &Path, key: &str, val: &str) -> Result<String>
         .as_table_mut()
         .ok_or_else(|| Error::Manifest("[options] is not a table".to_string()))?;
 
+    // Reject unknown modes and formats before they make Ion.toml unparseable
+    if section == "target-deploy" || (section == "options" && field == "deploy") {
+        val.parse::<DeployMode>()?;
+    }
+    if section == "target-format" {
+        val.parse::<TargetFormat>()?;
+    }
+
     match section {
-        "targets" => {
+        "targets" | "target-deploy" | "target-format" => {
             if !options.contains_key(section) {
                 options[section] = Item::Table(Table::new());
             }
-            options[section][field] = value(val);
+            let table = options[section]
+                .as_table_like_mut()
+                .ok_or_else(|| Error::Manifest(format!("[options.{section}] is not a table")))?;
+            if section == "targets" {
+                set_target_path(table, field, val);
+            } else {
+                table.insert(field, value(val));
+            }
         }
         "options" => {
             options[field] = value(val);
         }
         _ => {
             return Err(Error::Manifest(format!(
-                "Project config only supports 'targets' and 'options' sections, got '{section}'"
+                "Project config only supports 'targets', 'target-deploy', 'target-format' and 'options' sections, got '{section}'"
             )));
         }
     }
@@ -204,9 +255,12 @@ pub fn delete_option(manifest_path: &Path, key: &str) -> Result<String> {
     // Validate the section up front, independent of whether [options] exists —
     // otherwise an unknown section on a manifest with no [options] table would
     // be silently accepted as a no-op.
-    if section != "targets" && section != "options" {
+    if !matches!(
+        section,
+        "targets" | "target-deploy" | "target-format" | "options"
+    ) {
         return Err(Error::Manifest(format!(
-            "Project config only supports 'targets' and 'options' sections, got '{section}'"
+            "Project config only supports 'targets', 'target-deploy', 'target-format' and 'options' sections, got '{section}'"
         )));
     }
 
@@ -215,7 +269,7 @@ pub fn delete_option(manifest_path: &Path, key: &str) -> Result<String> {
 
     if let Some(options) = doc.get_mut("options").and_then(|item| item.as_table_mut()) {
         match section {
-            "targets" => {
+            "targets" | "target-deploy" | "target-format" => {
                 if let Some(targets) = options.get_mut(section).and_then(|t| t.as_table_mut()) {
                     targets.remove(field);
                 }
@@ -312,15 +366,25 @@ fn skill_to_toml(source: &SkillSource) -> Item {
         SkillSourceKind::Local { forked_from } => forked_from.as_deref(),
         _ => None,
     };
+    let strip_components = match &source.kind {
+        SkillSourceKind::Archive {
+            strip_components, ..
+        } => *strip_components,
+        _ => 0,
+    };
 
     let needs_table = source.rev.is_some()
+        || source.channel.is_some()
         || source.version.is_some()
         || source.path.is_some()
         || binary_name.is_some()
         || asset_pattern.is_some()
         || forked_from.is_some()
         || dev
-        || source.is_local();
+        || source.is_local()
+        || source.is_archive()
+        // `skills/foo` would read back as a GitHub shorthand
+        || (source.is_path() && !SkillSource::infer(&source.source).is_ok_and(|s| s.is_path()));
 
     if !needs_table {
         let display = if source.is_github() {
@@ -344,6 +408,12 @@ fn skill_to_toml(source: &SkillSource) -> Item {
         SkillSourceKind::Http => {
             table.insert("type", "http".into());
         }
+        SkillSourceKind::Archive { .. } => {
+            table.insert("type", "archive".into());
+        }
+        SkillSourceKind::Oci => {
+            table.insert("type", "oci".into());
+        }
         SkillSourceKind::Path => {
             table.insert("type", "path".into());
         }
@@ -374,6 +444,10 @@ fn skill_to_toml(source: &SkillSource) -> Item {
     if let Some(ref r) = source.rev {
         table.insert("rev", r.as_str().into());
     }
+    if let Some(ref channel) = source.channel {
+        let (key, value) = channel.manifest_field();
+        table.insert(key, value.into());
+    }
     if let Some(ref p) = source.path
         && !source.is_github()
     {
@@ -391,6 +465,12 @@ fn skill_to_toml(source: &SkillSource) -> Item {
     if dev {
         table.insert("dev", true.into());
     }
+    if let Some(digest) = source.sha256() {
+        table.insert("sha256", digest.into());
+    }
+    if strip_components > 0 {
+        table.insert("strip-components", (strip_components as i64).into());
+    }
 
     value(table)
 }
@@ -398,6 +478,7 @@ fn skill_to_toml(source: &SkillSource) -> Item {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::source::Channel;
 
     #[test]
     fn add_skill_to_empty_manifest() {
@@ -430,6 +511,35 @@ mod tests {
         assert!(result.contains("v1.0"));
     }
 
+    #[test]
+    fn add_skill_with_channel() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Ion.toml");
+        std::fs::write(&path, "[skills]\n").unwrap();
+
+        let source = SkillSource::infer("org/my-skill")
+            .unwrap()
+            .with_channel(Channel::LatestMatching("v*".into()));
+
+        let result = add_skill(&path, "my-skill", &source).unwrap();
+        assert!(result.contains("tag = \"latest-matching:v*\""), "{result}");
+    }
+
+    #[test]
+    fn relative_path_skill_keeps_its_type() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Ion.toml");
+        std::fs::write(&path, "[skills]\n").unwrap();
+
+        add_skill(&path, "mine", &SkillSource::from_path("skills/mine")).unwrap();
+        let result = add_skill(&path, "other", &SkillSource::from_path("./skills/other")).unwrap();
+        assert!(
+            result.contains("mine = { type = \"path\", source = \"skills/mine\" }"),
+            "{result}"
+        );
+        assert!(result.contains("other = \"./skills/other\""), "{result}");
+    }
+
     #[test]
     fn remove_skill_from_manifest() {
         let dir = tempfile::tempdir().unwrap();
@@ -626,6 +736,53 @@ mod tests {
         assert!(result.contains("skills-dir = \"my-skills\""));
     }
 
+    #[test]
+    fn set_option_deploy_modes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Ion.toml");
+        std::fs::write(&path, "[skills]\n").unwrap();
+
+        set_option(&path, "options.deploy", "copy").unwrap();
+        let result = set_option(&path, "target-deploy.claude", "symlink").unwrap();
+        assert!(result.contains("deploy = \"copy\""));
+        assert!(result.contains("[options.target-deploy]"));
+        assert!(set_option(&path, "options.deploy", "junction").is_err());
+
+        let manifest = crate::manifest::Manifest::parse(&result).unwrap();
+        assert_eq!(manifest.options.deploy_mode(), DeployMode::Copy);
+        assert_eq!(
+            manifest.options.deploy_mode_for("claude"),
+            DeployMode::Symlink
+        );
+        assert_eq!(manifest.options.deploy_mode_for("cursor"), DeployMode::Copy);
+    }
+
+    #[test]
+    fn set_option_keeps_table_targets() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Ion.toml");
+        std::fs::write(
+            &path,
+            "[skills]\n\n[options.targets.cursor]\npath = \".cursor/skills\"\nexclude = [\"x\"]\n",
+        )
+        .unwrap();
+
+        let result = set_option(&path, "targets.cursor", ".cursor/other").unwrap();
+        let options = crate::manifest::Manifest::parse(&result).unwrap().options;
+        assert_eq!(options.targets["cursor"], ".cursor/other");
+        assert!(!options.deploys_to("cursor", "x"));
+
+        let targets = std::collections::BTreeMap::from([
+            ("claude".to_string(), ".claude/skills".to_string()),
+            ("cursor".to_string(), ".cursor/skills".to_string()),
+        ]);
+        let result = write_targets(&path, &targets).unwrap();
+        let options = crate::manifest::Manifest::parse(&result).unwrap().options;
+        assert_eq!(options.targets, targets);
+        assert!(!options.deploys_to("cursor", "x"));
+        assert!(options.deploys_to("claude", "x"));
+    }
+
     #[test]
     fn set_option_preserves_existing_content() {
         let dir = tempfile::tempdir().unwrap();
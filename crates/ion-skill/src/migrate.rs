@@ -324,7 +324,7 @@ pub fn move_skill_to_local(
     // If the skill is already in .agents/skills/, no move needed
     if skill.installed_path == agents_dir {
         // Just create symlinks to target dirs
-        for target_path in options.targets.values() {
+        for target_path in targets_for(options, &skill.name) {
             let target_dir = project_dir.join(target_path).join(&skill.name);
             if target_dir == skill.installed_path {
                 continue;
@@ -351,7 +351,7 @@ pub fn move_skill_to_local(
     create_local_symlink(&agents_dir, &skill.installed_path)?;
 
     // Create symlinks to all target dirs
-    for target_path in options.targets.values() {
+    for target_path in targets_for(options, &skill.name) {
         let target_dir = project_dir.join(target_path).join(&skill.name);
         if target_dir == skill.installed_path || target_dir == agents_dir {
             continue;
@@ -362,6 +362,18 @@ pub fn move_skill_to_local(
     Ok(())
 }
 
+/// Paths of the targets that receive skill `name`.
+fn targets_for<'a>(
+    options: &'a ManifestOptions,
+    name: &'a str,
+) -> impl Iterator<Item = &'a String> {
+    options
+        .targets
+        .iter()
+        .filter(move |(target, _)| options.deploys_to(target, name))
+        .map(|(_, path)| path)
+}
+
 /// Create a relative symlink from `link` pointing to `original`.
 fn create_local_symlink(original: &Path, link: &Path) -> Result<()> {
     if link.is_symlink() {
@@ -0,0 +1,715 @@
+//! OCI registry skill sources — pull skills stored as OCI artifacts and push skill directories to a registry.
+//!
+//! A skill artifact is an OCI image manifest with one `tar+gzip` layer holding
+//! the skill directory and a JSON config blob holding the SKILL.md frontmatter.
+
+use std::path::{Path, PathBuf};
+
+use reqwest::StatusCode;
+use reqwest::blocking::{Client, RequestBuilder, Response};
+use reqwest::header::{ACCEPT, CONTENT_TYPE, LOCATION, WWW_AUTHENTICATE};
+use serde::{Deserialize, Serialize};
+
+use crate::archive::{self, Format};
+use crate::credentials::{self, Credential};
+use crate::installer::data_dir;
+use crate::skill::SkillMetadata;
+use crate::source::{SkillSource, SkillSourceKind};
+use crate::{Error, Result, git, offline};
+
+/// Media type of the OCI image manifest that wraps a skill.
+pub const MANIFEST_MEDIA_TYPE: &str = "application/vnd.oci.image.manifest.v1+json";
+/// Media type of the config blob holding the SKILL.md frontmatter as JSON.
+pub const CONFIG_MEDIA_TYPE: &str = "application/vnd.ion.skill.config.v1+json";
+/// Media type of the layer holding the skill directory.
+pub const LAYER_MEDIA_TYPE: &str = "application/vnd.ion.skill.layer.v1.tar+gzip";
+
+const TITLE_ANNOTATION: &str = "org.opencontainers.image.title";
+const VERSION_ANNOTATION: &str = "org.opencontainers.image.version";
+const DESCRIPTION_ANNOTATION: &str = "org.opencontainers.image.description";
+
+/// A parsed `oci://registry/namespace/skill[:tag|@sha256:…]` reference.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reference {
+    /// Registry host, with port if any (`ghcr.io`, `localhost:5000`).
+    pub registry: String,
+    /// Repository path within the registry (`team/review`).
+    pub repository: String,
+    /// Tag or manifest digest; tags default to `latest`.
+    pub reference: String,
+}
+
+impl Reference {
+    pub fn parse(source: &str) -> Result<Self> {
+        let invalid = || {
+            Error::Source(format!(
+                "Invalid OCI reference '{source}': expected oci://registry/namespace/skill[:tag|@sha256:digest]"
+            ))
+        };
+        let rest = source.strip_prefix("oci://").ok_or_else(invalid)?;
+        let (registry, path) = rest.split_once('/').ok_or_else(invalid)?;
+
+        let (repository, reference) = match path.split_once('@') {
+            Some((repository, digest)) => {
+                if !is_digest(digest) {
+                    return Err(invalid());
+                }
+                (repository, digest.to_string())
+            }
+            None => match path.rsplit_once(':') {
+                Some((repository, tag)) if !tag.is_empty() && !tag.contains('/') => {
+                    (repository, tag.to_string())
+                }
+                Some(_) => return Err(invalid()),
+                None => (path, "latest".to_string()),
+            },
+        };
+
+        if registry.is_empty() || repository.is_empty() || repository.ends_with('/') {
+            return Err(invalid());
+        }
+        Ok(Self {
+            registry: registry.to_string(),
+            repository: repository.to_string(),
+            reference,
+        })
+    }
+
+    /// Whether this reference names a manifest digest rather than a tag.
+    pub fn is_digest(&self) -> bool {
+        is_digest(&self.reference)
+    }
+
+    /// The same repository at a manifest digest.
+    pub fn with_digest(&self, digest: &str) -> Self {
+        Self {
+            reference: digest.to_string(),
+            ..self.clone()
+        }
+    }
+
+    /// The skill name this reference suggests: the last repository segment.
+    pub fn name(&self) -> &str {
+        self.repository
+            .rsplit('/')
+            .next()
+            .unwrap_or(&self.repository)
+    }
+
+    /// Registries on the loopback interface are reached over plain HTTP,
+    /// everything else over HTTPS.
+    fn base_url(&self) -> String {
+        let host = self
+            .registry
+            .rsplit_once(':')
+            .filter(|(_, port)| port.chars().all(|c| c.is_ascii_digit()))
+            .map_or(self.registry.as_str(), |(host, _)| host);
+        let scheme = if matches!(host, "localhost" | "127.0.0.1" | "[::1]") {
+            "http"
+        } else {
+            "https"
+        };
+        format!("{scheme}://{}/v2/{}", self.registry, self.repository)
+    }
+}
+
+impl std::fmt::Display for Reference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let sep = if self.is_digest() { '@' } else { ':' };
+        write!(
+            f,
+            "oci://{}/{}{sep}{}",
+            self.registry, self.repository, self.reference
+        )
+    }
+}
+
+fn is_digest(value: &str) -> bool {
+    value
+        .strip_prefix("sha256:")
+        .is_some_and(|hex| hex.len() == 64 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Manifest {
+    schema_version: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    media_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    artifact_type: Option<String>,
+    config: Descriptor,
+    layers: Vec<Descriptor>,
+    #[serde(default, skip_serializing_if = "serde_json::Map::is_empty")]
+    annotations: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Descriptor {
+    media_type: String,
+    digest: String,
+    size: u64,
+    #[serde(default, skip_serializing_if = "serde_json::Map::is_empty")]
+    annotations: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Descriptor {
+    fn new(media_type: &str, blob: &[u8]) -> Self {
+        Self {
+            media_type: media_type.to_string(),
+            digest: digest_of(blob),
+            size: blob.len() as u64,
+            annotations: serde_json::Map::new(),
+        }
+    }
+}
+
+fn digest_of(bytes: &[u8]) -> String {
+    format!("sha256:{}", archive::sha256_hex(bytes))
+}
+
+/// Directory holding manifests pulled from registries, by digest.
+fn manifests_dir() -> PathBuf {
+    data_dir().join("oci").join("manifests")
+}
+
+fn reference_of(source: &SkillSource) -> Result<Reference> {
+    match source.kind {
+        SkillSourceKind::Oci => Reference::parse(&source.source),
+        ref kind => Err(Error::Source(format!(
+            "Source kind {kind:?} is not an OCI reference"
+        ))),
+    }
+}
+
+/// The manifest digest an OCI source is pinned to in Ion.toml, via `rev` or
+/// an `@sha256:` reference.
+pub fn pinned_digest(source: &SkillSource) -> Option<String> {
+    source.rev.clone().or_else(|| {
+        Reference::parse(&source.source)
+            .ok()
+            .filter(Reference::is_digest)
+            .map(|reference| reference.reference)
+    })
+}
+
+/// Resolve an OCI source to the digest of its manifest.
+///
+/// Sources pinned with `rev` or an `@sha256:` reference resolve without
+/// touching the network; tags are looked up in the registry.
+pub fn resolve_digest(source: &SkillSource) -> Result<String> {
+    let reference = reference_of(source)?;
+    if let Some(ref rev) = source.rev {
+        if !is_digest(rev) {
+            return Err(Error::Source(format!(
+                "OCI skills can only be pinned to a manifest digest (sha256:…), got '{rev}'"
+            )));
+        }
+        return Ok(rev.clone());
+    }
+    if reference.is_digest() {
+        return Ok(reference.reference);
+    }
+    offline::ensure_online(&format!("resolve {reference}"))?;
+    let (digest, _) = RegistryClient::new(&reference)?.pull_manifest(&reference)?;
+    Ok(digest)
+}
+
+/// Fetch an OCI source and return its extracted skill directory.
+///
+/// Layers are extracted into the archive cache under their digest, so an
+/// artifact pulled once is available offline when pinned to its digest.
+pub fn fetch(source: &SkillSource) -> Result<PathBuf> {
+    let reference = reference_of(source)?;
+    let digest = resolve_digest(source)?;
+    let manifest = match load_cached_manifest(&digest) {
+        Some(manifest) => manifest,
+        None => {
+            offline::ensure_online(&format!("pull {reference}"))?;
+            RegistryClient::new(&reference)?
+                .pull_manifest(&reference.with_digest(&digest))?
+                .1
+        }
+    };
+
+    let layer = skill_layer(&manifest, &reference)?;
+    let layer_hex = layer
+        .digest
+        .strip_prefix("sha256:")
+        .ok_or_else(|| Error::Source(format!("Unsupported layer digest {}", layer.digest)))?;
+    if let Some(dir) = archive::cached(layer_hex) {
+        return Ok(dir);
+    }
+
+    offline::ensure_online(&format!("pull {reference}"))?;
+    log::debug!("pulling layer {} of {reference}", layer.digest);
+    let bytes = RegistryClient::new(&reference)?.get_blob(&reference, &layer.digest)?;
+    let stored = archive::store(
+        &bytes,
+        Format::TarGz,
+        Some(layer_hex),
+        &reference.to_string(),
+    )?;
+    archive::cached(&stored)
+        .ok_or_else(|| Error::Source(format!("Failed to extract layer of {reference}")))
+}
+
+fn load_cached_manifest(digest: &str) -> Option<Manifest> {
+    let hex = digest.strip_prefix("sha256:")?;
+    let content = std::fs::read(manifests_dir().join(format!("{hex}.json"))).ok()?;
+    serde_json::from_slice(&content).ok()
+}
+
+fn skill_layer<'a>(manifest: &'a Manifest, reference: &Reference) -> Result<&'a Descriptor> {
+    manifest
+        .layers
+        .iter()
+        .find(|layer| layer.media_type == LAYER_MEDIA_TYPE)
+        .or(match manifest.layers.as_slice() {
+            [only] if only.media_type.ends_with("tar+gzip") => Some(only),
+            _ => None,
+        })
+        .ok_or_else(|| Error::Source(format!("{reference} is not a skill artifact")))
+}
+
+/// Package a skill directory as an OCI artifact and push it to `reference`.
+///
+/// The skill must pass validation. Returns the digest of the pushed manifest.
+pub fn push(skill_dir: &Path, reference: &Reference) -> Result<String> {
+    if reference.is_digest() {
+        return Err(Error::Source(format!(
+            "Cannot push to a digest reference ({reference}); use a tag"
+        )));
+    }
+    let skill_md = skill_dir.join("SKILL.md");
+    let content = std::fs::read_to_string(&skill_md)
+        .map_err(|e| Error::InvalidSkill(format!("Cannot read {}: {e}", skill_md.display())))?;
+    let (meta, body) = SkillMetadata::parse(&content)?;
+    let report = crate::validate::validate_skill_dir(skill_dir, &meta, &body);
+    if report.error_count > 0 {
+        return Err(Error::validation_failed(report));
+    }
+    offline::ensure_online(&format!("push {reference}"))?;
+
+    let config = serde_json::to_vec(&SkillMetadata::frontmatter_json(&content)?)
+        .map_err(|e| Error::Other(format!("Failed to serialize skill config: {e}")))?;
+    let layer = pack_layer(skill_dir)?;
+
+    let mut layer_desc = Descriptor::new(LAYER_MEDIA_TYPE, &layer);
+    layer_desc.annotations.insert(
+        TITLE_ANNOTATION.into(),
+        format!("{}.tar.gz", meta.name).into(),
+    );
+    let mut annotations = serde_json::Map::new();
+    annotations.insert(TITLE_ANNOTATION.into(), meta.name.clone().into());
+    annotations.insert(
+        DESCRIPTION_ANNOTATION.into(),
+        meta.description.clone().into(),
+    );
+    if let Some(version) = meta.version() {
+        annotations.insert(VERSION_ANNOTATION.into(), version.into());
+    }
+    let manifest = Manifest {
+        schema_version: 2,
+        media_type: Some(MANIFEST_MEDIA_TYPE.to_string()),
+        artifact_type: Some(CONFIG_MEDIA_TYPE.to_string()),
+        config: Descriptor::new(CONFIG_MEDIA_TYPE, &config),
+        layers: vec![layer_desc],
+        annotations,
+    };
+    let manifest = serde_json::to_vec(&manifest)
+        .map_err(|e| Error::Other(format!("Failed to serialize OCI manifest: {e}")))?;
+
+    let mut client = RegistryClient::new(reference)?;
+    client.put_blob(reference, &config)?;
+    client.put_blob(reference, &layer)?;
+    client.put_manifest(reference, &manifest)?;
+    Ok(digest_of(&manifest))
+}
+
+/// Pack a skill directory into a reproducible `.tar.gz`: sorted entries,
+/// zeroed timestamps and owners, and only the executable bit kept.
+fn pack_layer(skill_dir: &Path) -> Result<Vec<u8>> {
+    let gz = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    let mut builder = tar::Builder::new(gz);
+    for rel in git::list_files(skill_dir)? {
+        let path = skill_dir.join(&rel);
+        let data = std::fs::read(&path).map_err(Error::Io)?;
+        let mut header = tar::Header::new_gnu();
+        header.set_size(data.len() as u64);
+        header.set_mode(if is_executable(&path) { 0o755 } else { 0o644 });
+        header.set_mtime(0);
+        header.set_uid(0);
+        header.set_gid(0);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, &rel, data.as_slice())
+            .map_err(Error::Io)?;
+    }
+    builder
+        .into_inner()
+        .and_then(|gz| gz.finish())
+        .map_err(Error::Io)
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(path).is_ok_and(|m| m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(_path: &Path) -> bool {
+    false
+}
+
+/// Minimal client for the OCI distribution API. Answers bearer challenges
+/// with a token from the registry's auth service (anonymous, or using the
+/// `[credentials]` entry for the registry host) and basic challenges with
+/// that credential directly.
+struct RegistryClient {
+    http: Client,
+    credential: Option<Credential>,
+    auth: Option<Auth>,
+}
+
+/// How requests to the registry are authenticated once it has challenged us.
+enum Auth {
+    /// A token and the `scope` it was issued for.
+    Bearer {
+        token: String,
+        scope: Option<String>,
+    },
+    Basic,
+}
+
+impl RegistryClient {
+    fn new(reference: &Reference) -> Result<Self> {
+        let host = reference
+            .registry
+            .rsplit_once(':')
+            .map_or(reference.registry.as_str(), |(host, _)| host);
+        Ok(Self {
+            http: Client::new(),
+            credential: credentials::for_host(host)?,
+            auth: None,
+        })
+    }
+
+    fn authorize(&self, request: RequestBuilder) -> RequestBuilder {
+        match (&self.auth, &self.credential) {
+            (Some(Auth::Bearer { token, .. }), _) => request.bearer_auth(token),
+            (Some(Auth::Basic), Some(credential)) => {
+                request.basic_auth(&credential.username, Some(credential.token()))
+            }
+            _ => request,
+        }
+    }
+
+    /// Send a request, answering a `401` challenge once.
+    ///
+    /// A bearer token only covers the scope it was issued for, so a push
+    /// holding a `pull` token is challenged again for `pull,push`; that
+    /// challenge gets a new token too.
+    fn send(&mut self, build: impl Fn(&Client) -> RequestBuilder) -> Result<Response> {
+        let response = self
+            .authorize(build(&self.http))
+            .send()
+            .map_err(|e| Error::Http(format!("Registry request failed: {e}")))?;
+        if response.status() != StatusCode::UNAUTHORIZED {
+            return Ok(response);
+        }
+        let Some(challenge) = response
+            .headers()
+            .get(WWW_AUTHENTICATE)
+            .and_then(|v| v.to_str().ok())
+        else {
+            return Ok(response);
+        };
+        if let Some(challenge) = challenge.strip_prefix("Bearer ") {
+            let params = parse_challenge(challenge);
+            let scope = params
+                .iter()
+                .find(|(key, _)| key == "scope")
+                .map(|(_, value)| value.clone());
+            let rechallenged = match &self.auth {
+                None => true,
+                Some(Auth::Bearer { scope: held, .. }) => {
+                    *held != scope
+                        || params
+                            .iter()
+                            .any(|(key, value)| key == "error" && value == "insufficient_scope")
+                }
+                Some(Auth::Basic) => false,
+            };
+            if !rechallenged {
+                return Ok(response);
+            }
+            self.auth = Some(Auth::Bearer {
+                token: self.fetch_token(&params)?,
+                scope,
+            });
+        } else if challenge.starts_with("Basic") && self.credential.is_some() && self.auth.is_none()
+        {
+            self.auth = Some(Auth::Basic);
+        } else {
+            return Ok(response);
+        }
+        self.authorize(build(&self.http))
+            .send()
+            .map_err(|e| Error::Http(format!("Registry request failed: {e}")))
+    }
+
+    fn fetch_token(&self, params: &[(String, String)]) -> Result<String> {
+        let realm = params
+            .iter()
+            .find(|(key, _)| key == "realm")
+            .map(|(_, value)| value.clone())
+            .ok_or_else(|| Error::Http("Registry auth challenge has no realm".to_string()))?;
+        let query: Vec<_> = params.iter().filter(|(key, _)| key != "realm").collect();
+
+        #[derive(Deserialize)]
+        struct Token {
+            token: Option<String>,
+            access_token: Option<String>,
+        }
+        let mut request = self.http.get(&realm).query(&query);
+        if let Some(credential) = self.realm_credential(&realm) {
+            request = request.basic_auth(&credential.username, Some(credential.token()));
+        }
+        let response = request
+            .send()
+            .map_err(|e| Error::Http(format!("Failed to fetch registry token: {e}")))?;
+        if !response.status().is_success() {
+            return Err(Error::Http(format!(
+                "HTTP {} fetching registry token from {realm}",
+                response.status()
+            )));
+        }
+        let token: Token = response
+            .json()
+            .map_err(|e| Error::Http(format!("Invalid registry token response: {e}")))?;
+        token
+            .token
+            .or(token.access_token)
+            .ok_or_else(|| Error::Http("Registry token response has no token".to_string()))
+    }
+
+    /// The credential to send to the token service at `realm`. Never over
+    /// plain `http://` to a remote host, where it would travel in the clear.
+    fn realm_credential(&self, realm: &str) -> Option<&Credential> {
+        self.credential
+            .as_ref()
+            .filter(|_| credentials::secure_host(realm).is_some())
+    }
+
+    /// Fetch a manifest, verify it against its digest and cache it.
+    fn pull_manifest(&mut self, reference: &Reference) -> Result<(String, Manifest)> {
+        let url = format!("{}/manifests/{}", reference.base_url(), reference.reference);
+        log::debug!("fetching OCI manifest {url}");
+        let response = self.send(|http| http.get(&url).header(ACCEPT, MANIFEST_MEDIA_TYPE))?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Err(Error::Source(format!(
+                "{reference} not found in the registry"
+            )));
+        }
+        let body = check(response, &url)?
+            .bytes()
+            .map_err(|e| Error::Http(format!("Failed to read response from {url}: {e}")))?;
+
+        let digest = digest_of(&body);
+        if reference.is_digest() && digest != reference.reference {
+            return Err(Error::Source(format!(
+                "Manifest digest mismatch for {reference}: registry served {digest}"
+            )));
+        }
+        let manifest: Manifest = serde_json::from_slice(&body)
+            .map_err(|e| Error::Source(format!("Invalid OCI manifest for {reference}: {e}")))?;
+
+        let dir = manifests_dir();
+        std::fs::create_dir_all(&dir).map_err(Error::Io)?;
+        let hex = digest.trim_start_matches("sha256:");
+        std::fs::write(dir.join(format!("{hex}.json")), &body).map_err(Error::Io)?;
+        Ok((digest, manifest))
+    }
+
+    fn get_blob(&mut self, reference: &Reference, digest: &str) -> Result<Vec<u8>> {
+        let url = format!("{}/blobs/{digest}", reference.base_url());
+        let response = self.send(|http| http.get(&url))?;
+        let bytes = check(response, &url)?
+            .bytes()
+            .map_err(|e| Error::Http(format!("Failed to read response from {url}: {e}")))?;
+        Ok(bytes.to_vec())
+    }
+
+    /// Upload a blob unless the registry already has it.
+    fn put_blob(&mut self, reference: &Reference, blob: &[u8]) -> Result<()> {
+        let digest = digest_of(blob);
+        let url = format!("{}/blobs/{digest}", reference.base_url());
+        if self.send(|http| http.head(&url))?.status().is_success() {
+            log::debug!("blob {digest} already present in {}", reference.registry);
+            return Ok(());
+        }
+
+        let start = format!("{}/blobs/uploads/", reference.base_url());
+        let response = check(self.send(|http| http.post(&start))?, &start)?;
+        let location = response
+            .headers()
+            .get(LOCATION)
+            .and_then(|v| v.to_str().ok())
+            .ok_or_else(|| Error::Http(format!("Registry gave no upload location for {start}")))?;
+        let mut upload = response
+            .url()
+            .join(location)
+            .map_err(|e| Error::Http(format!("Invalid upload location '{location}': {e}")))?;
+        upload.query_pairs_mut().append_pair("digest", &digest);
+
+        let upload = upload.to_string();
+        check(
+            self.send(|http| {
+                http.put(&upload)
+                    .header(CONTENT_TYPE, "application/octet-stream")
+                    .body(blob.to_vec())
+            })?,
+            &upload,
+        )?;
+        Ok(())
+    }
+
+    fn put_manifest(&mut self, reference: &Reference, manifest: &[u8]) -> Result<()> {
+        let url = format!("{}/manifests/{}", reference.base_url(), reference.reference);
+        check(
+            self.send(|http| {
+                http.put(&url)
+                    .header(CONTENT_TYPE, MANIFEST_MEDIA_TYPE)
+                    .body(manifest.to_vec())
+            })?,
+            &url,
+        )?;
+        Ok(())
+    }
+}
+
+fn check(response: Response, url: &str) -> Result<Response> {
+    if response.status().is_success() {
+        return Ok(response);
+    }
+    let status = response.status();
+    let body = response.text().unwrap_or_default();
+    Err(Error::Http(format!(
+        "HTTP {status} from {url}{}",
+        if body.trim().is_empty() {
+            String::new()
+        } else {
+            format!(": {}", body.trim())
+        }
+    )))
+}
+
+/// Parse `key="value",key2="value2"` from a `WWW-Authenticate` header.
+fn parse_challenge(challenge: &str) -> Vec<(String, String)> {
+    let mut params = Vec::new();
+    let mut rest = challenge.trim();
+    while let Some((key, after)) = rest.split_once('=') {
+        let key = key.trim().trim_start_matches(',').trim().to_string();
+        let (value, remaining) = match after.strip_prefix('"') {
+            Some(quoted) => match quoted.split_once('"') {
+                Some((value, remaining)) => (value.to_string(), remaining),
+                None => (quoted.to_string(), ""),
+            },
+            None => match after.split_once(',') {
+                Some((value, remaining)) => (value.trim().to_string(), remaining),
+                None => (after.trim().to_string(), ""),
+            },
+        };
+        params.push((key, value));
+        rest = remaining.trim_start_matches(',').trim();
+    }
+    params
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_tag_and_digest_references() {
+        let r = Reference::parse("oci://localhost:5000/team/review:1.2").unwrap();
+        assert_eq!(r.registry, "localhost:5000");
+        assert_eq!(r.repository, "team/review");
+        assert_eq!(r.reference, "1.2");
+        assert_eq!(r.name(), "review");
+        assert_eq!(r.base_url(), "http://localhost:5000/v2/team/review");
+
+        let r = Reference::parse("oci://ghcr.io/acme/skills/review").unwrap();
+        assert_eq!(r.repository, "acme/skills/review");
+        assert_eq!(r.reference, "latest");
+        assert_eq!(r.base_url(), "https://ghcr.io/v2/acme/skills/review");
+
+        let digest = format!("sha256:{}", "a".repeat(64));
+        let r = Reference::parse(&format!("oci://ghcr.io/acme/review@{digest}")).unwrap();
+        assert!(r.is_digest());
+        assert_eq!(r.to_string(), format!("oci://ghcr.io/acme/review@{digest}"));
+
+        assert!(Reference::parse("oci://ghcr.io").is_err());
+        assert!(Reference::parse("oci://ghcr.io/acme/review@sha256:abc").is_err());
+        assert!(Reference::parse("oci://ghcr.io/acme/review:").is_err());
+    }
+
+    #[test]
+    fn pack_layer_is_reproducible() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("SKILL.md"), "---\nname: s\n---\n").unwrap();
+        std::fs::create_dir(dir.path().join("scripts")).unwrap();
+        std::fs::write(dir.path().join("scripts/run.sh"), "echo hi\n").unwrap();
+
+        let first = pack_layer(dir.path()).unwrap();
+        std::fs::write(dir.path().join("SKILL.md"), "---\nname: s\n---\n").unwrap();
+        assert_eq!(first, pack_layer(dir.path()).unwrap());
+
+        let out = tempfile::tempdir().unwrap();
+        tar::Archive::new(flate2::read::GzDecoder::new(first.as_slice()))
+            .unpack(out.path())
+            .unwrap();
+        assert!(out.path().join("scripts/run.sh").is_file());
+    }
+
+    #[test]
+    fn credentials_only_go_to_secure_token_services() {
+        let client = RegistryClient {
+            http: Client::new(),
+            credential: Some(Credential::new("me", "s3cret")),
+            auth: None,
+        };
+        assert!(client.realm_credential("https://ghcr.io/token").is_some());
+        assert!(
+            client
+                .realm_credential("http://127.0.0.1:5000/token")
+                .is_some()
+        );
+        assert!(
+            client
+                .realm_credential("http://auth.example.com/token")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn parse_bearer_challenge() {
+        let params = parse_challenge(
+            r#"realm="https://ghcr.io/token",service="ghcr.io",scope="repository:acme/review:pull""#,
+        );
+        assert_eq!(
+            params,
+            vec![
+                ("realm".to_string(), "https://ghcr.io/token".to_string()),
+                ("service".to_string(), "ghcr.io".to_string()),
+                (
+                    "scope".to_string(),
+                    "repository:acme/review:pull".to_string()
+                ),
+            ]
+        );
+    }
+}
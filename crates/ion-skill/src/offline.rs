@@ -0,0 +1,30 @@
+//! Process-wide offline mode — serve everything from local caches and never touch the network.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::{Error, Result};
+
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+/// Enable or disable offline mode for the rest of the process.
+pub fn set_offline(offline: bool) {
+    OFFLINE.store(offline, Ordering::Relaxed);
+    ionem::shell::git::set_network_blocked(offline);
+}
+
+/// Whether offline mode is enabled.
+pub fn is_offline() -> bool {
+    OFFLINE.load(Ordering::Relaxed)
+}
+
+/// Fail with [`Error::Offline`] if offline mode is enabled.
+///
+/// `what` names the network access that was skipped, e.g. "download https://…".
+pub fn ensure_online(what: &str) -> Result<()> {
+    if is_offline() {
+        return Err(Error::Offline(format!(
+            "cannot {what} without network access"
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,326 @@
+//! Orphan pruning — remove the links, copies and `.gitignore` lines left behind by skills Ion.toml no longer declares.
+
+use std::collections::BTreeSet;
+use std::path::{Component, Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::installer::{SkillInstaller, builtin_skills_dir};
+use crate::lockfile::{LockedSkillKind, Lockfile};
+use crate::manifest::Manifest;
+use crate::render::TargetFormat;
+use crate::{Error, Result, git, gitignore};
+
+/// What an [`Orphan`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OrphanKind {
+    /// A symlink Ion created in the skills directory or a target.
+    Link,
+    /// A directory Ion copied or hardlinked into the skills directory or a
+    /// target.
+    Copy,
+    /// A line in the "# Managed by ion" section of `.gitignore`.
+    Gitignore,
+}
+
+/// A deployment of a skill that is no longer declared.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Orphan {
+    pub skill: String,
+    pub kind: OrphanKind,
+    /// The link, directory or `.gitignore` line, relative to the project
+    /// directory.
+    pub path: String,
+}
+
+/// Skills the project still wants deployed: everything Ion.toml declares,
+/// plus the locked dependencies those skills still require.
+pub fn declared_skills(manifest: &Manifest, lockfile: &Lockfile) -> BTreeSet<String> {
+    let orphans = lockfile.orphans(&manifest.skills);
+    manifest
+        .skills
+        .keys()
+        .cloned()
+        .chain(
+            lockfile
+                .skills
+                .iter()
+                .filter(|l| !l.required_by.is_empty() && !orphans.contains(&l.name))
+                .map(|l| l.name.clone()),
+        )
+        .collect()
+}
+
+/// Find the links, copies and `.gitignore` lines for skills outside
+/// `declared`.
+///
+/// A link counts as Ion's when Ion.lock records the skill, when it points
+/// into Ion's data directory, or when `.gitignore` lists it as managed. A
+/// directory counts as Ion's when `.gitignore` lists it as managed, or when
+/// its content matches the checksum Ion.lock records for the skill. Other
+/// directories, local skills and links the user made by hand are never
+/// reported, even when they point into the skills directory. A `.gitignore`
+/// line is only reported once nothing but an orphaned link or copy is left
+/// at its path, so pruning never exposes files to git.
+pub fn find_orphans(
+    installer: &SkillInstaller,
+    declared: &BTreeSet<String>,
+    lockfile: &Lockfile,
+) -> Result<Vec<Orphan>> {
+    let project_dir = installer.project_dir();
+    let managed = managed_skill_entries(installer)?;
+
+    let mut orphans = Vec::new();
+    for dir in skill_dirs(installer) {
+        let entries = match std::fs::read_dir(project_dir.join(&dir)) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(Error::Io(e)),
+        };
+        let mut links: Vec<(String, PathBuf)> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| {
+                (
+                    entry.file_name().to_string_lossy().to_string(),
+                    entry.path(),
+                )
+            })
+            .filter(|(name, path)| (path.is_symlink() || path.is_dir()) && !declared.contains(name))
+            .collect();
+        links.sort();
+
+        for (name, link) in links {
+            let rel = format!("{dir}/{name}");
+            let locked = lockfile.find(&name);
+            let kind = if link.is_symlink() {
+                if !owns_link(&link, &rel, locked.is_some(), &managed)? {
+                    continue;
+                }
+                OrphanKind::Link
+            } else {
+                let checksum = locked
+                    .filter(|l| !matches!(l.kind, LockedSkillKind::Local { .. }))
+                    .and_then(|l| l.checksum());
+                if !owns_copy(&link, &rel, checksum, &managed) {
+                    continue;
+                }
+                OrphanKind::Copy
+            };
+            orphans.push(Orphan {
+                skill: name,
+                kind,
+                path: rel,
+            });
+        }
+    }
+
+    for (name, entry) in managed {
+        if declared.contains(&name) {
+            continue;
+        }
+        let path = project_dir.join(&entry);
+        let pruned = orphans
+            .iter()
+            .any(|o| o.kind != OrphanKind::Gitignore && o.path == entry);
+        if path.symlink_metadata().is_err() || pruned {
+            orphans.push(Orphan {
+                skill: name,
+                kind: OrphanKind::Gitignore,
+                path: entry,
+            });
+        }
+    }
+    Ok(orphans)
+}
+
+/// Whether Ion made the symlink `link`, found at `rel` in the project:
+/// the skill it is named after is `locked`, the link points into Ion's data
+/// directory, or `managed` (see [`managed_skill_entries`]) lists it.
+pub(crate) fn owns_link(
+    link: &Path,
+    rel: &str,
+    locked: bool,
+    managed: &[(String, String)],
+) -> Result<bool> {
+    if locked || managed.iter().any(|(_, entry)| entry == rel) {
+        return Ok(true);
+    }
+    let target = std::fs::read_link(link).map_err(Error::Io)?;
+    let target = normalize(&link.parent().unwrap_or(link).join(target));
+    let data_root = builtin_skills_dir()
+        .parent()
+        .map(normalize)
+        .unwrap_or_default();
+    Ok(target.starts_with(&data_root))
+}
+
+/// Whether Ion deployed the directory `dir`, found at `rel` in the project,
+/// as a copy or hardlinks: `managed` lists it, or its content matches the
+/// `checksum` locked for the skill it is named after. The same rule decides
+/// which copies `ion add` removes from targets that exclude a skill.
+fn owns_copy(dir: &Path, rel: &str, checksum: Option<&str>, managed: &[(String, String)]) -> bool {
+    managed.iter().any(|(_, entry)| entry == rel)
+        || checksum.is_some_and(|checksum| git::checksum_dir(dir).ok().as_deref() == Some(checksum))
+}
+
+/// Remove `orphans` from disk and `.gitignore`.
+pub fn remove_orphans(installer: &SkillInstaller, orphans: &[Orphan]) -> Result<()> {
+    let project_dir = installer.project_dir();
+    let mut entries = Vec::new();
+    for orphan in orphans {
+        match orphan.kind {
+            OrphanKind::Link => {
+                let link = project_dir.join(&orphan.path);
+                if link.is_symlink() {
+                    std::fs::remove_file(&link).map_err(Error::Io)?;
+                }
+            }
+            OrphanKind::Copy => {
+                let dir = project_dir.join(&orphan.path);
+                if dir.is_dir() && !dir.is_symlink() {
+                    std::fs::remove_dir_all(&dir).map_err(Error::Io)?;
+                }
+            }
+            OrphanKind::Gitignore => entries.push(orphan.path.clone()),
+        }
+    }
+    if !entries.is_empty() {
+        gitignore::remove_entries(project_dir, &entries)?;
+    }
+    Ok(())
+}
+
+/// Directories Ion deploys skill directories into: the skills directory
+/// and every target that takes skills as directories, relative to the
+/// project.
+pub(crate) fn skill_dirs(installer: &SkillInstaller) -> Vec<String> {
+    let options = installer.options();
+    let mut dirs = vec![options.skills_dir_or_default().to_string()];
+    for (target, path) in &options.targets {
+        if options.format_for(target) == TargetFormat::Skill && !dirs.contains(path) {
+            dirs.push(path.clone());
+        }
+    }
+    dirs
+}
+
+/// Managed `.gitignore` lines that name a skill's deployment, as (skill
+/// name, line): entries directly under a skill directory or a rendering
+/// target. Lines such as `CLAUDE.md` are skipped.
+pub(crate) fn managed_skill_entries(installer: &SkillInstaller) -> Result<Vec<(String, String)>> {
+    let dirs = skill_dirs(installer);
+    let options = installer.options();
+    let rendered = |parent: &str| {
+        options
+            .targets
+            .iter()
+            .any(|(target, path)| path == parent && options.format_for(target).renderer().is_some())
+    };
+    Ok(gitignore::managed_entries(installer.project_dir())?
+        .into_iter()
+        .filter_map(|entry| {
+            let (parent, leaf) = entry.rsplit_once('/')?;
+            if !dirs.iter().any(|d| d == parent) && !rendered(parent) {
+                return None;
+            }
+            // Rendered files are named `<skill>.<ext>`; skill names have no dots
+            let name = leaf.split('.').next().unwrap_or(leaf).to_string();
+            Some((name, entry))
+        })
+        .collect())
+}
+
+/// Resolve `.` and `..` components without touching the filesystem, so
+/// dangling links can be compared too.
+fn normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::ParentDir => {
+                out.pop();
+            }
+            Component::CurDir => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::manifest::ManifestOptions;
+
+    #[cfg(unix)]
+    #[test]
+    fn prunes_only_managed_links_of_undeclared_skills() {
+        use std::os::unix::fs::symlink;
+
+        let project = tempfile::tempdir().unwrap();
+        let dir = project.path();
+        let elsewhere = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.join(".agents/skills/kept")).unwrap();
+        std::fs::create_dir_all(dir.join(".agents/skills/users-own")).unwrap();
+        // A copy Ion deployed in copy mode
+        std::fs::create_dir_all(dir.join(".claude/skills/copied")).unwrap();
+        std::fs::write(dir.join(".claude/skills/copied/SKILL.md"), "copied").unwrap();
+        symlink("../../.agents/skills/kept", dir.join(".claude/skills/kept")).unwrap();
+        symlink(
+            "../../.agents/skills/dropped",
+            dir.join(".claude/skills/dropped"),
+        )
+        .unwrap();
+        symlink(elsewhere.path(), dir.join(".claude/skills/personal")).unwrap();
+        // Made by hand, pointing at a skills-dir entry Ion doesn't manage
+        symlink(
+            "../../.agents/skills/users-own",
+            dir.join(".claude/skills/users-own"),
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join(".gitignore"),
+            "# Managed by ion\n.agents/skills/kept\n.claude/skills/kept\n.agents/skills/dropped\n.claude/skills/dropped\n.claude/skills/copied\n",
+        )
+        .unwrap();
+
+        let manifest = Manifest::parse("[skills]\nkept = \"org/skills/kept\"\n").unwrap();
+        let declared = declared_skills(&manifest, &Lockfile::default());
+        let options = ManifestOptions {
+            targets: BTreeMap::from([("claude".to_string(), ".claude/skills".to_string())]),
+            ..Default::default()
+        };
+        let installer = SkillInstaller::new(dir, &options);
+
+        let orphans = find_orphans(&installer, &declared, &Lockfile::default()).unwrap();
+        let paths: Vec<_> = orphans.iter().map(|o| (o.kind, o.path.as_str())).collect();
+        assert_eq!(
+            paths,
+            [
+                (OrphanKind::Copy, ".claude/skills/copied"),
+                (OrphanKind::Link, ".claude/skills/dropped"),
+                (OrphanKind::Gitignore, ".agents/skills/dropped"),
+                (OrphanKind::Gitignore, ".claude/skills/dropped"),
+                (OrphanKind::Gitignore, ".claude/skills/copied"),
+            ]
+        );
+
+        remove_orphans(&installer, &orphans).unwrap();
+        assert!(!dir.join(".claude/skills/dropped").is_symlink());
+        assert!(!dir.join(".claude/skills/copied").exists());
+        assert!(dir.join(".agents/skills/users-own").is_dir());
+        assert!(dir.join(".claude/skills/personal").is_symlink());
+        assert!(dir.join(".claude/skills/users-own").is_symlink());
+        assert!(dir.join(".claude/skills/kept").exists());
+        let content = std::fs::read_to_string(dir.join(".gitignore")).unwrap();
+        assert!(!content.contains("dropped"));
+        assert!(!content.contains("copied"));
+        assert!(
+            find_orphans(&installer, &declared, &Lockfile::default())
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
@@ -1,11 +1,16 @@
 //! Global registry of skill repositories — tracks which projects use which remote repos and cleans up stale entries.
 
-use std::collections::BTreeMap;
-use std::path::{Path, PathBuf};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use serde::{Deserialize, Serialize};
 
-use crate::{Error, Result};
+use crate::installer::{cache_key, create_skill_symlink, data_dir, store_dir};
+use crate::lockfile::Lockfile;
+use crate::manifest::Manifest;
+use crate::search::SearchCache;
+use crate::{Error, Result, git};
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct RepoEntry {
@@ -28,6 +33,7 @@ impl Registry {
 
     /// Load the global registry. Returns empty registry if file doesn't exist.
     pub fn load() -> Result<Self> {
+        ensure_cache_keys_migrated();
         match Self::registry_path() {
             Some(path) => Self::load_from(&path),
             None => Ok(Self::default()),
@@ -95,6 +101,190 @@ impl Registry {
         });
         removed
     }
+
+    /// Commits still locked by a registered project, keyed by repo hash.
+    ///
+    /// Reads each project's Ion.lock and collects the commits of skills locked
+    /// from the repo's URL. Snapshots of any other commit can be deleted.
+    /// Repos with a project whose lockfile cannot be read are omitted, so
+    /// callers never prune snapshots they cannot account for.
+    pub fn live_commits(&self) -> BTreeMap<String, BTreeSet<String>> {
+        let mut live = BTreeMap::new();
+        'repos: for (hash, entry) in &self.repos {
+            let mut commits = BTreeSet::new();
+            for project in &entry.projects {
+                let Ok(lockfile) = Lockfile::from_file(&Path::new(project).join("Ion.lock")) else {
+                    continue 'repos;
+                };
+                commits.extend(
+                    lockfile
+                        .skills
+                        .iter()
+                        .filter(|s| s.source == entry.url)
+                        .filter_map(|s| s.commit())
+                        .map(str::to_string),
+                );
+            }
+            live.insert(hash.clone(), commits);
+        }
+        live
+    }
+
+    /// Re-key entries whose key is not the current [`cache_key`] of their URL.
+    ///
+    /// Moves each repo's clone under `repos_dir` and snapshots under
+    /// `store_dir` to the new key, reconnects the snapshot worktrees, and
+    /// rewrites registered projects' symlinks that pointed into the old
+    /// directories. Returns whether anything changed.
+    pub fn migrate_keys(&mut self, repos_dir: &Path, store_dir: &Path) -> Result<bool> {
+        let legacy: Vec<(String, RepoEntry)> = self
+            .repos
+            .iter()
+            .filter(|(key, entry)| **key != cache_key(&entry.url))
+            .map(|(key, entry)| (key.clone(), entry.clone()))
+            .collect();
+
+        for (old_key, entry) in &legacy {
+            let new_key = cache_key(&entry.url);
+            let moves = [
+                (repos_dir.join(old_key), repos_dir.join(&new_key)),
+                (store_dir.join(old_key), store_dir.join(&new_key)),
+            ];
+            for (from, to) in &moves {
+                move_dir(from, to)?;
+            }
+
+            let (new_repo, new_store) = (&moves[0].1, &moves[1].1);
+            if new_repo.exists() && new_store.exists() {
+                let snapshots: Vec<PathBuf> = std::fs::read_dir(new_store)
+                    .map_err(Error::Io)?
+                    .flatten()
+                    .map(|e| e.path())
+                    .filter(|p| p.is_dir())
+                    .collect();
+                // A broken snapshot is rebuilt on next use, so don't abort.
+                if let Err(e) = git::repair_snapshots(new_repo, &snapshots) {
+                    log::warn!("Failed to repair snapshots of {}: {e}", entry.url);
+                }
+            }
+
+            for project in &entry.projects {
+                relink_project(Path::new(project), &moves)?;
+            }
+
+            self.repos.remove(old_key);
+            let merged = self.repos.entry(new_key).or_insert_with(|| RepoEntry {
+                url: entry.url.clone(),
+                projects: Vec::new(),
+            });
+            for project in &entry.projects {
+                if !merged.projects.contains(project) {
+                    merged.projects.push(project.clone());
+                }
+            }
+            merged.projects.sort();
+        }
+
+        Ok(!legacy.is_empty())
+    }
+}
+
+/// Run [`migrate_cache_keys`] the first time a process touches the cache or
+/// the registry. Later calls, including those made by the migration itself,
+/// return immediately.
+pub(crate) fn ensure_cache_keys_migrated() {
+    static STARTED: AtomicBool = AtomicBool::new(false);
+    if STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    if let Err(e) = migrate_cache_keys() {
+        log::warn!("Failed to migrate cache keys: {e}");
+    }
+}
+
+/// One-time migration from the `DefaultHasher`-based cache keys used by
+/// older versions of Ion to SHA-256 keys.
+///
+/// A no-op once the registry only contains current keys. Runs lazily through
+/// [`data_dir`], [`store_dir`] and [`Registry::load`]; HTTP skill caches,
+/// which the registry doesn't track, are migrated by
+/// [`http_cache::cached_dir`](crate::http_cache::cached_dir).
+pub fn migrate_cache_keys() -> Result<()> {
+    let Some(path) = Registry::registry_path() else {
+        return Ok(());
+    };
+    if !path.exists() {
+        return Ok(());
+    }
+
+    let mut registry = Registry::load_from(&path)?;
+    if registry.migrate_keys(&data_dir(), &store_dir())? {
+        registry.save_to(&path)?;
+        if let Some(cache) = SearchCache::new() {
+            cache.prune_legacy();
+        }
+    }
+    Ok(())
+}
+
+/// Move a cache directory to its new key. If the destination already exists
+/// the old copy is redundant and is deleted instead.
+pub(crate) fn move_dir(from: &Path, to: &Path) -> Result<()> {
+    if !from.exists() {
+        return Ok(());
+    }
+    if to.exists() {
+        return std::fs::remove_dir_all(from).map_err(Error::Io);
+    }
+    if let Some(parent) = to.parent() {
+        std::fs::create_dir_all(parent).map_err(Error::Io)?;
+    }
+    std::fs::rename(from, to).map_err(Error::Io)
+}
+
+/// Point a project's skill symlinks at the moved cache directories.
+///
+/// `moves` pairs each old directory with its new location. Only symlinks in
+/// the project's skills directory and target directories are considered.
+fn relink_project(project: &Path, moves: &[(PathBuf, PathBuf)]) -> Result<()> {
+    let options = Manifest::from_file(&project.join("Ion.toml"))
+        .map(|m| m.options)
+        .unwrap_or_default();
+
+    let mut dirs = vec![project.join(options.skills_dir_or_default())];
+    dirs.extend(options.targets.values().map(|t| project.join(t)));
+
+    for dir in dirs {
+        for entry in std::fs::read_dir(&dir).into_iter().flatten().flatten() {
+            let link = entry.path();
+            let Ok(target) = std::fs::read_link(&link) else {
+                continue;
+            };
+            let resolved = normalize(&dir.join(target));
+            for (from, to) in moves {
+                if let Ok(rest) = resolved.strip_prefix(normalize(from)) {
+                    create_skill_symlink(&to.join(rest), &link)?;
+                    break;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Lexically resolve `.` and `..` components (the target may not exist).
+fn normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::ParentDir => {
+                out.pop();
+            }
+            Component::CurDir => {}
+            other => out.push(other),
+        }
+    }
+    out
 }
 
 #[cfg(test)]
@@ -176,6 +366,118 @@ mod tests {
         assert_eq!(registry.repos["abc123"].projects.len(), 1);
     }
 
+    #[test]
+    fn live_commits_reads_project_lockfiles() {
+        let project = tempfile::tempdir().unwrap();
+        let mut lockfile = Lockfile::default();
+        lockfile.upsert(crate::lockfile::LockedSkill::git(
+            "a",
+            "https://github.com/org/repo.git",
+            "c1".into(),
+            "sha256:x".into(),
+        ));
+        lockfile.upsert(crate::lockfile::LockedSkill::git(
+            "b",
+            "https://github.com/org/other.git",
+            "c2".into(),
+            "sha256:y".into(),
+        ));
+        lockfile.write_to(&project.path().join("Ion.lock")).unwrap();
+
+        let mut registry = Registry::default();
+        registry.register(
+            "abc123",
+            "https://github.com/org/repo.git",
+            &project.path().display().to_string(),
+        );
+
+        let live = registry.live_commits();
+        assert_eq!(
+            live["abc123"],
+            BTreeSet::from(["c1".to_string()]),
+            "only commits locked from this repo are live"
+        );
+    }
+
+    #[test]
+    fn live_commits_skips_repos_with_unreadable_locks() {
+        let project = tempfile::tempdir().unwrap();
+        std::fs::write(project.path().join("Ion.lock"), "not = [valid").unwrap();
+
+        let mut registry = Registry::default();
+        registry.register(
+            "abc123",
+            "https://github.com/org/repo.git",
+            &project.path().display().to_string(),
+        );
+
+        assert!(registry.live_commits().is_empty());
+    }
+
+    #[test]
+    fn migrate_keys_moves_cache_and_relinks_projects() {
+        let tmp = tempfile::tempdir().unwrap();
+        let repos_dir = tmp.path().join("repos");
+        let store = tmp.path().join("store");
+
+        // Upstream repo with one skill
+        let upstream = tmp.path().join("upstream");
+        std::fs::create_dir_all(upstream.join("skill")).unwrap();
+        std::fs::write(upstream.join("skill/SKILL.md"), "hello").unwrap();
+        for args in [
+            &["init"][..],
+            &["add", "."][..],
+            &["commit", "-m", "init"][..],
+        ] {
+            std::process::Command::new("git")
+                .args(args)
+                .current_dir(&upstream)
+                .output()
+                .unwrap();
+        }
+        let url = upstream.display().to_string();
+
+        // Cache laid out under a legacy key
+        let old_key = "1a2b3c4d5e6f7a8b";
+        let old_repo = repos_dir.join(old_key);
+        git::clone_or_fetch(&url, &old_repo).unwrap();
+        let commit = git::remote_head_commit(&old_repo).unwrap();
+        let old_snapshot = store.join(old_key).join(&commit);
+        git::ensure_snapshot(&old_repo, &commit, &old_snapshot, None).unwrap();
+
+        // Project symlinked into the legacy snapshot
+        let project = tmp.path().join("project");
+        let link = project.join(".agents/skills/skill");
+        create_skill_symlink(&old_snapshot.join("skill"), &link).unwrap();
+
+        let mut registry = Registry::default();
+        registry.register(old_key, &url, &project.display().to_string());
+
+        assert!(registry.migrate_keys(&repos_dir, &store).unwrap());
+
+        let new_key = cache_key(&url);
+        assert_eq!(registry.repos.keys().collect::<Vec<_>>(), vec![&new_key]);
+        assert!(!old_repo.exists());
+        let new_snapshot = store.join(&new_key).join(&commit);
+        assert_eq!(git::head_commit(&new_snapshot).unwrap(), commit);
+        assert_eq!(
+            std::fs::read_to_string(link.join("SKILL.md")).unwrap(),
+            "hello"
+        );
+        assert!(
+            normalize(
+                &link
+                    .parent()
+                    .unwrap()
+                    .join(std::fs::read_link(&link).unwrap())
+            )
+            .starts_with(normalize(&store.join(&new_key)))
+        );
+
+        // Second run is a no-op
+        assert!(!registry.migrate_keys(&repos_dir, &store).unwrap());
+    }
+
     #[test]
     fn roundtrip_save_load() {
         let dir = tempfile::tempdir().unwrap();
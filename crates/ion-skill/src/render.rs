@@ -0,0 +1,238 @@
+//! Per-target renderers — translate a skill's SKILL.md into the native rule or instruction format of tools that don't read skill directories.
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::skill::SkillMetadata;
+use crate::{Error, Result};
+
+/// How a target receives skills.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TargetFormat {
+    /// The skill directory itself, deployed as configured by `deploy` (the default).
+    #[default]
+    Skill,
+    /// Cursor `.mdc` rules, for `.cursor/rules`.
+    Cursor,
+    /// Windsurf rules, for `.windsurf/rules`.
+    Windsurf,
+    /// GitHub Copilot `.instructions.md` files, for `.github/instructions`.
+    Copilot,
+}
+
+impl TargetFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TargetFormat::Skill => "skill",
+            TargetFormat::Cursor => "cursor",
+            TargetFormat::Windsurf => "windsurf",
+            TargetFormat::Copilot => "copilot",
+        }
+    }
+
+    /// The renderer for this format, or `None` for plain skill directories.
+    pub fn renderer(&self) -> Option<&'static dyn TargetRenderer> {
+        match self {
+            TargetFormat::Skill => None,
+            TargetFormat::Cursor => Some(&CursorRules),
+            TargetFormat::Windsurf => Some(&WindsurfRules),
+            TargetFormat::Copilot => Some(&CopilotInstructions),
+        }
+    }
+}
+
+impl std::fmt::Display for TargetFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for TargetFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "skill" => Ok(TargetFormat::Skill),
+            "cursor" => Ok(TargetFormat::Cursor),
+            "windsurf" => Ok(TargetFormat::Windsurf),
+            "copilot" => Ok(TargetFormat::Copilot),
+            _ => Err(Error::Manifest(format!(
+                "Invalid target format '{s}': expected 'skill', 'cursor', 'windsurf' or 'copilot'"
+            ))),
+        }
+    }
+}
+
+/// A file generated for a target, relative to the target directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderedFile {
+    pub path: PathBuf,
+    pub contents: String,
+}
+
+/// Translates a skill into the files a tool reads natively.
+///
+/// SKILL.md `metadata` can carry hints for renderers: `globs` (comma
+/// separated file patterns the rule applies to) and `always-apply`
+/// (`"true"` to include the rule in every request).
+pub trait TargetRenderer: Sync {
+    /// Paths of the files rendered for skill `name`, relative to the target
+    /// directory. Used to remove them without re-reading the skill.
+    fn paths(&self, name: &str) -> Vec<PathBuf>;
+
+    /// Render skill `name` from its frontmatter and body.
+    fn render(&self, name: &str, meta: &SkillMetadata, body: &str) -> Vec<RenderedFile>;
+}
+
+/// Cursor project rules: `<name>.mdc` with `description`, `globs` and `alwaysApply`.
+pub struct CursorRules;
+
+impl TargetRenderer for CursorRules {
+    fn paths(&self, name: &str) -> Vec<PathBuf> {
+        vec![PathBuf::from(format!("{name}.mdc"))]
+    }
+
+    fn render(&self, name: &str, meta: &SkillMetadata, body: &str) -> Vec<RenderedFile> {
+        // Cursor reads `globs` verbatim, so it is deliberately left unquoted
+        let frontmatter = format!(
+            "description: {}\nglobs: {}\nalwaysApply: {}\n",
+            quoted(&meta.description),
+            hint(meta, "globs").unwrap_or_default(),
+            always_apply(meta)
+        );
+        single_file(self.paths(name), &frontmatter, body)
+    }
+}
+
+/// Windsurf workspace rules: `<name>.md` with a `trigger` derived from the hints.
+pub struct WindsurfRules;
+
+impl TargetRenderer for WindsurfRules {
+    fn paths(&self, name: &str) -> Vec<PathBuf> {
+        vec![PathBuf::from(format!("{name}.md"))]
+    }
+
+    fn render(&self, name: &str, meta: &SkillMetadata, body: &str) -> Vec<RenderedFile> {
+        let mut frontmatter = match (always_apply(meta), hint(meta, "globs")) {
+            (true, _) => "trigger: always_on\n".to_string(),
+            (false, Some(globs)) => format!("trigger: glob\nglobs: {}\n", quoted(globs)),
+            (false, None) => "trigger: model_decision\n".to_string(),
+        };
+        frontmatter.push_str(&format!("description: {}\n", quoted(&meta.description)));
+        single_file(self.paths(name), &frontmatter, body)
+    }
+}
+
+/// GitHub Copilot custom instructions: `<name>.instructions.md` with `applyTo`.
+pub struct CopilotInstructions;
+
+impl TargetRenderer for CopilotInstructions {
+    fn paths(&self, name: &str) -> Vec<PathBuf> {
+        vec![PathBuf::from(format!("{name}.instructions.md"))]
+    }
+
+    fn render(&self, name: &str, meta: &SkillMetadata, body: &str) -> Vec<RenderedFile> {
+        let mut frontmatter = format!("description: {}\n", quoted(&meta.description));
+        let apply_to = match (always_apply(meta), hint(meta, "globs")) {
+            (true, _) => Some("**"),
+            (false, globs) => globs,
+        };
+        if let Some(apply_to) = apply_to {
+            frontmatter.push_str(&format!("applyTo: {}\n", quoted(apply_to)));
+        }
+        single_file(self.paths(name), &frontmatter, body)
+    }
+}
+
+fn single_file(paths: Vec<PathBuf>, frontmatter: &str, body: &str) -> Vec<RenderedFile> {
+    paths
+        .into_iter()
+        .map(|path| RenderedFile {
+            path,
+            contents: format!("---\n{frontmatter}---\n\n{}\n", body.trim()),
+        })
+        .collect()
+}
+
+fn hint<'a>(meta: &'a SkillMetadata, key: &str) -> Option<&'a str> {
+    meta.metadata
+        .as_ref()?
+        .get(key)
+        .map(|v| v.trim())
+        .filter(|v| !v.is_empty())
+}
+
+fn always_apply(meta: &SkillMetadata) -> bool {
+    hint(meta, "always-apply") == Some("true")
+}
+
+/// A YAML double-quoted scalar; JSON string escaping is a subset of it.
+fn quoted(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_else(|_| format!("\"{value}\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn skill(extra: &str) -> (SkillMetadata, String) {
+        SkillMetadata::parse(&format!(
+            "---\nname: review\ndescription: \"Review code: carefully\"\n{extra}---\n\n# Review\n\nBe thorough.\n"
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn cursor_rule_carries_globs_unquoted() {
+        let (meta, body) = skill("metadata:\n  globs: \"*.rs,*.toml\"\n");
+        let files = CursorRules.render("review", &meta, &body);
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, PathBuf::from("review.mdc"));
+        assert_eq!(
+            files[0].contents,
+            "---\ndescription: \"Review code: carefully\"\nglobs: *.rs,*.toml\nalwaysApply: false\n---\n\n# Review\n\nBe thorough.\n"
+        );
+    }
+
+    #[test]
+    fn windsurf_trigger_follows_hints() {
+        let (meta, body) = skill("");
+        let contents = &WindsurfRules.render("review", &meta, &body)[0].contents;
+        assert!(contents.starts_with("---\ntrigger: model_decision\n"));
+
+        let (meta, body) = skill("metadata:\n  always-apply: \"true\"\n");
+        let contents = &WindsurfRules.render("review", &meta, &body)[0].contents;
+        assert!(contents.starts_with("---\ntrigger: always_on\n"));
+    }
+
+    #[test]
+    fn copilot_instructions_apply_to_globs() {
+        let (meta, body) = skill("metadata:\n  globs: \"**/*.rs\"\n");
+        let files = CopilotInstructions.render("review", &meta, &body);
+        assert_eq!(files[0].path, PathBuf::from("review.instructions.md"));
+        assert!(files[0].contents.contains("applyTo: \"**/*.rs\"\n"));
+
+        // The rendered frontmatter parses back as YAML
+        let yaml = files[0].contents.split("---\n").nth(1).unwrap();
+        let parsed: serde_yaml::Value = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(parsed["description"], "Review code: carefully");
+    }
+
+    #[test]
+    fn parse_target_format() {
+        assert_eq!(
+            "cursor".parse::<TargetFormat>().unwrap(),
+            TargetFormat::Cursor
+        );
+        assert!(
+            "skill"
+                .parse::<TargetFormat>()
+                .unwrap()
+                .renderer()
+                .is_none()
+        );
+        assert!("vscode".parse::<TargetFormat>().is_err());
+    }
+}
@@ -4,7 +4,7 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use serde::{Deserialize, Serialize};
 
 use super::SearchResult;
-use crate::installer::hash_simple;
+use crate::installer::cache_key;
 
 /// Cached search results for a single (source, query) pair.
 #[derive(Serialize, Deserialize)]
@@ -80,10 +80,25 @@ impl SearchCache {
         }
     }
 
+    /// Delete entries written under the old 16-hex-digit key scheme.
+    pub fn prune_legacy(&self) {
+        for entry in std::fs::read_dir(&self.cache_dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+        {
+            let path = entry.path();
+            let is_legacy = path.extension().is_some_and(|e| e == "json")
+                && path.file_stem().is_some_and(|s| s.len() == 16);
+            if is_legacy {
+                let _ = std::fs::remove_file(&path);
+            }
+        }
+    }
+
     fn cache_path(&self, source_name: &str, query: &str) -> PathBuf {
         let key = format!("{source_name}:{query}");
-        let hash = hash_simple(&key);
-        self.cache_dir.join(format!("{hash:016x}.json"))
+        self.cache_dir.join(format!("{}.json", cache_key(&key)))
     }
 }
 
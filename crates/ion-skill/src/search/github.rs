@@ -389,8 +389,11 @@ fn select_with_diversity(
 /// Enrich search results by fetching SKILL.md descriptions and star counts.
 /// Works for both GitHub and skills.sh results (skills.sh skills are GitHub-hosted).
 /// For skills.sh results, falls back to scraping the skills.sh detail page when
-/// the GitHub repo is unavailable (deleted or private).
+/// the GitHub repo is unavailable (deleted or private). Does nothing offline.
 pub fn enrich_results(results: &mut [SearchResult]) {
+    if crate::offline::is_offline() {
+        return;
+    }
     let handles: Vec<_> = results
         .iter()
         .enumerate()
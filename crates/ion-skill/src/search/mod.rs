@@ -209,11 +209,12 @@ pub fn group_by_owner_repo(results: &[SearchResult]) -> Vec<(String, Vec<usize>)
 
 /// Perform an HTTP GET and return the response body as a string.
 fn http_get(url: &str, timeout_secs: u64, label: &str) -> crate::Result<String> {
-    let response = reqwest::blocking::Client::builder()
+    let request = reqwest::blocking::Client::builder()
         .timeout(std::time::Duration::from_secs(timeout_secs))
         .build()
         .map_err(|e| crate::Error::Http(format!("{label}: {e}")))?
-        .get(url)
+        .get(url);
+    let response = crate::credentials::authorize(request, url)?
         .send()
         .map_err(|e| crate::Error::Http(format!("{label}: {e}")))?
         .error_for_status()
@@ -230,12 +231,13 @@ fn http_get_with_query(
     timeout_secs: u64,
     label: &str,
 ) -> crate::Result<String> {
-    let response = reqwest::blocking::Client::builder()
+    let request = reqwest::blocking::Client::builder()
         .timeout(std::time::Duration::from_secs(timeout_secs))
         .build()
         .map_err(|e| crate::Error::Http(format!("{label}: {e}")))?
         .get(url)
-        .query(query)
+        .query(query);
+    let response = crate::credentials::authorize(request, url)?
         .send()
         .map_err(|e| crate::Error::Http(format!("{label}: {e}")))?
         .error_for_status()
@@ -251,6 +253,9 @@ fn http_get_with_query(
 /// When `cache` is provided, each source checks the cache before making a
 /// network call and writes results back on a miss. The "agent" source is
 /// never cached because its output is dynamic.
+///
+/// In offline mode cached results are used regardless of age and sources
+/// without cached results are skipped.
 pub fn parallel_search(
     sources: Vec<Box<dyn SearchSource + Send>>,
     query: &str,
@@ -260,6 +265,8 @@ pub fn parallel_search(
 ) -> Vec<SearchResult> {
     log::debug!("parallel: spawning {} search threads", sources.len());
     let query = query.to_string();
+    let offline = crate::offline::is_offline();
+    let max_age_secs = if offline { u64::MAX } else { max_age_secs };
 
     // Pre-resolve cache hits on the main thread (cache is not Send).
     let source_cache: Vec<_> = sources
@@ -288,6 +295,13 @@ pub fn parallel_search(
                     );
                     return (source.name().to_string(), results, false);
                 }
+                if offline {
+                    log::debug!(
+                        "parallel: '{}' has no cached results, skipping",
+                        source.name()
+                    );
+                    return (source.name().to_string(), vec![], false);
+                }
 
                 log::debug!("parallel: thread searching '{}'", source.name());
                 match source.search(&q, limit) {
@@ -5,6 +5,7 @@ use std::path::Path;
 
 use serde::Deserialize;
 
+use crate::source::SkillSource;
 use crate::{Error, Result};
 
 /// Parsed SKILL.md frontmatter.
@@ -20,25 +21,92 @@ pub struct SkillMetadata {
     pub metadata: Option<HashMap<String, String>>,
     #[serde(default, rename = "allowed-tools")]
     pub allowed_tools: Option<String>,
+    /// Other skills this one expects to be installed alongside it.
+    #[serde(default)]
+    pub requires: Vec<Requirement>,
 }
 
-impl SkillMetadata {
-    /// Parse SKILL.md content (frontmatter + body).
-    pub fn parse(content: &str) -> Result<(Self, String)> {
-        let content = content.trim_start();
-        if !content.starts_with("---") {
-            return Err(Error::InvalidSkill(
-                "SKILL.md must start with YAML frontmatter (---)".to_string(),
-            ));
+/// An entry in the `requires:` frontmatter list.
+///
+/// Written either as a bare source (`- obra/superpowers/brainstorming`) or
+/// as a mapping with a version requirement
+/// (`- { source: obra/superpowers/brainstorming, version: "^1.2" }`).
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(from = "RawRequirement")]
+pub struct Requirement {
+    pub source: String,
+    pub version: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawRequirement {
+    Source(String),
+    Full {
+        source: String,
+        #[serde(default)]
+        version: Option<String>,
+    },
+}
+
+impl From<RawRequirement> for Requirement {
+    fn from(raw: RawRequirement) -> Self {
+        match raw {
+            RawRequirement::Source(source) => Requirement {
+                source,
+                version: None,
+            },
+            RawRequirement::Full { source, version } => Requirement { source, version },
         }
+    }
+}
 
-        let after_first = &content[3..];
-        let end = after_first
-            .find("\n---")
-            .ok_or_else(|| Error::InvalidSkill("No closing --- for frontmatter".to_string()))?;
+impl Requirement {
+    /// The source to install for this requirement.
+    ///
+    /// Only a skill that is itself a local path (`from_local`) may require
+    /// another by path; a fetched skill could otherwise pull in any
+    /// directory on the user's machine.
+    pub fn to_source(&self, from_local: bool) -> Result<SkillSource> {
+        let mut source = SkillSource::infer(&self.source)?;
+        if source.is_path() && !from_local {
+            return Err(Error::Dependency(format!(
+                "'{}' is a local path; only local skills may require skills by path",
+                self.source
+            )));
+        }
+        if let Some(ref version) = self.version {
+            source.version = Some(version.clone());
+        }
+        Ok(source)
+    }
+}
+
+/// Split SKILL.md content into its YAML frontmatter and body.
+fn split_frontmatter(content: &str) -> Result<(&str, &str)> {
+    let content = content.trim_start();
+    if !content.starts_with("---") {
+        return Err(Error::InvalidSkill(
+            "SKILL.md must start with YAML frontmatter (---)".to_string(),
+        ));
+    }
 
-        let yaml = &after_first[..end];
-        let body = after_first[end + 4..].trim_start_matches('\n').to_string();
+    let after_first = &content[3..];
+    let end = after_first
+        .find("\n---")
+        .ok_or_else(|| Error::InvalidSkill("No closing --- for frontmatter".to_string()))?;
+
+    Ok((
+        &after_first[..end],
+        after_first[end + 4..].trim_start_matches('\n'),
+    ))
+}
+
+impl SkillMetadata {
+    /// Parse SKILL.md content (frontmatter + body).
+    pub fn parse(content: &str) -> Result<(Self, String)> {
+        let (yaml, body) = split_frontmatter(content)?;
+        let body = body.to_string();
 
         let meta: SkillMetadata = serde_yaml::from_str(yaml).map_err(Error::YamlParse)?;
 
@@ -59,6 +127,26 @@ impl SkillMetadata {
         Self::parse(&content)
     }
 
+    /// Read only the `requires:` list from SKILL.md content, without
+    /// enforcing the rest of the frontmatter schema.
+    pub fn parse_requires(content: &str) -> Result<Vec<Requirement>> {
+        #[derive(Deserialize)]
+        struct RequiresOnly {
+            #[serde(default)]
+            requires: Vec<Requirement>,
+        }
+
+        let (yaml, _) = split_frontmatter(content)?;
+        let parsed: RequiresOnly = serde_yaml::from_str(yaml).map_err(Error::YamlParse)?;
+        Ok(parsed.requires)
+    }
+
+    /// Read the whole frontmatter as JSON, including fields Ion doesn't model.
+    pub fn frontmatter_json(content: &str) -> Result<serde_json::Value> {
+        let (yaml, _) = split_frontmatter(content)?;
+        serde_yaml::from_str(yaml).map_err(Error::YamlParse)
+    }
+
     /// Get the version from metadata, if present.
     pub fn version(&self) -> Option<&str> {
         self.metadata
@@ -149,4 +237,36 @@ mod tests {
         assert!(SkillMetadata::validate_name("").is_err());
         assert!(SkillMetadata::validate_name(&"a".repeat(65)).is_err());
     }
+
+    #[test]
+    fn parse_requires() {
+        let content = "---\nname: my-skill\ndescription: Needs others.\nrequires:\n  - obra/superpowers/brainstorming\n  - source: org/skills/review\n    version: \"^1.2\"\n---\n\nBody.\n";
+        let (meta, _) = SkillMetadata::parse(content).unwrap();
+        assert_eq!(
+            meta.requires,
+            vec![
+                Requirement {
+                    source: "obra/superpowers/brainstorming".to_string(),
+                    version: None,
+                },
+                Requirement {
+                    source: "org/skills/review".to_string(),
+                    version: Some("^1.2".to_string()),
+                },
+            ]
+        );
+        let source = meta.requires[1].to_source(false).unwrap();
+        assert_eq!(source.display_name(), "review");
+        assert_eq!(source.version.as_deref(), Some("^1.2"));
+
+        let by_path = Requirement {
+            source: "./skills/review".to_string(),
+            version: None,
+        };
+        assert!(by_path.to_source(true).unwrap().is_path());
+        assert!(matches!(
+            by_path.to_source(false),
+            Err(Error::Dependency(_))
+        ));
+    }
 }
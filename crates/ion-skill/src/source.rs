@@ -1,8 +1,8 @@
-//! Skill source abstraction — represent and resolve GitHub, Git, HTTP, path, binary, and local skill origins.
+//! Skill source abstraction — represent and resolve GitHub, Git, HTTP, archive, OCI, path, binary, and local skill origins.
 
 use serde::{Deserialize, Serialize};
 
-use crate::{Error, Result};
+use crate::{Error, Result, hosts};
 
 /// The type of source a skill is fetched from.
 ///
@@ -13,6 +13,8 @@ pub enum SourceType {
     Github,
     Git,
     Http,
+    Archive,
+    Oci,
     Path,
     Binary,
     Local,
@@ -24,6 +26,16 @@ pub enum SkillSourceKind {
     Github,
     Git,
     Http,
+    /// A `.tar.gz`, `.tgz` or `.zip` archive downloaded over HTTP.
+    Archive {
+        /// Expected SHA-256 digest of the archive, as lowercase hex.
+        sha256: Option<String>,
+        /// Leading path components dropped from every archive entry.
+        strip_components: usize,
+    },
+    /// An artifact in an OCI registry (`oci://registry/ns/skill:tag`).
+    /// `rev` pins it to a manifest digest.
+    Oci,
     Path,
     Binary {
         binary_name: String,
@@ -37,6 +49,58 @@ pub enum SkillSourceKind {
     },
 }
 
+/// An upstream ref that a git skill follows when it isn't pinned to a `rev`.
+///
+/// Written in Ion.lock as `branch:<name>` or `tag:<value>`, mirroring the
+/// Ion.toml field it came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Channel {
+    /// The tip of a branch (`branch = "stable"`).
+    Branch(String),
+    /// A single tag, which upstream may move (`tag = "latest"`).
+    Tag(String),
+    /// The highest-versioned tag matching a glob (`tag = "latest-matching:v*"`).
+    LatestMatching(String),
+}
+
+impl Channel {
+    /// Build the channel for an Ion.toml `tag` value.
+    pub fn tag(value: &str) -> Self {
+        match value.strip_prefix("latest-matching:") {
+            Some(pattern) => Channel::LatestMatching(pattern.to_string()),
+            None => Channel::Tag(value.to_string()),
+        }
+    }
+
+    /// The Ion.toml key (`branch` or `tag`) and value for this channel.
+    pub fn manifest_field(&self) -> (&'static str, String) {
+        match self {
+            Channel::Branch(branch) => ("branch", branch.clone()),
+            Channel::Tag(tag) => ("tag", tag.clone()),
+            Channel::LatestMatching(pattern) => ("tag", format!("latest-matching:{pattern}")),
+        }
+    }
+}
+
+impl std::fmt::Display for Channel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (key, value) = self.manifest_field();
+        write!(f, "{key}:{value}")
+    }
+}
+
+impl std::str::FromStr for Channel {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.split_once(':') {
+            Some(("branch", branch)) if !branch.is_empty() => Ok(Channel::Branch(branch.into())),
+            Some(("tag", tag)) if !tag.is_empty() => Ok(Channel::tag(tag)),
+            _ => Err(format!("invalid channel '{s}'")),
+        }
+    }
+}
+
 /// A fully resolved skill source.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SkillSource {
@@ -46,8 +110,11 @@ pub struct SkillSource {
     pub path: Option<String>,
     /// Pinned revision (git commit, tag, or branch).
     pub rev: Option<String>,
-    /// Required SKILL.md version.
+    /// Required version: a semver requirement resolved against the repo's
+    /// tags for git sources, otherwise checked against the SKILL.md version.
     pub version: Option<String>,
+    /// Branch or tag followed when `rev` is unset (git sources only).
+    pub channel: Option<Channel>,
     /// Source-type-specific data.
     pub kind: SkillSourceKind,
 }
@@ -62,6 +129,7 @@ impl SkillSource {
             path: None,
             rev: None,
             version: None,
+            channel: None,
         }
     }
 
@@ -72,6 +140,7 @@ impl SkillSource {
             path: None,
             rev: None,
             version: None,
+            channel: None,
         }
     }
 
@@ -82,6 +151,32 @@ impl SkillSource {
             path: None,
             rev: None,
             version: None,
+            channel: None,
+        }
+    }
+
+    pub fn archive(source: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+            kind: SkillSourceKind::Archive {
+                sha256: None,
+                strip_components: 0,
+            },
+            path: None,
+            rev: None,
+            version: None,
+            channel: None,
+        }
+    }
+
+    pub fn oci(source: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+            kind: SkillSourceKind::Oci,
+            path: None,
+            rev: None,
+            version: None,
+            channel: None,
         }
     }
 
@@ -92,6 +187,7 @@ impl SkillSource {
             path: None,
             rev: None,
             version: None,
+            channel: None,
         }
     }
 
@@ -102,6 +198,7 @@ impl SkillSource {
             path: None,
             rev: None,
             version: None,
+            channel: None,
         }
     }
 
@@ -117,6 +214,7 @@ impl SkillSource {
             path: None,
             rev: None,
             version: None,
+            channel: None,
         }
     }
 
@@ -132,6 +230,11 @@ impl SkillSource {
             SourceType::Github => SkillSourceKind::Github,
             SourceType::Git => SkillSourceKind::Git,
             SourceType::Http => SkillSourceKind::Http,
+            SourceType::Archive => SkillSourceKind::Archive {
+                sha256: None,
+                strip_components: 0,
+            },
+            SourceType::Oci => SkillSourceKind::Oci,
             SourceType::Path => SkillSourceKind::Path,
             SourceType::Binary => SkillSourceKind::Binary {
                 binary_name: String::new(),
@@ -146,6 +249,7 @@ impl SkillSource {
             path: None,
             rev: None,
             version: None,
+            channel: None,
             kind,
         }
     }
@@ -174,6 +278,11 @@ impl SkillSource {
         self
     }
 
+    pub fn with_channel(mut self, channel: Channel) -> Self {
+        self.channel = Some(channel);
+        self
+    }
+
     pub fn with_binary(mut self, binary: impl Into<String>) -> Self {
         let binary_name = binary.into();
         match &mut self.kind {
@@ -211,6 +320,24 @@ impl SkillSource {
         self
     }
 
+    pub fn with_sha256(mut self, digest: impl Into<String>) -> Self {
+        if let SkillSourceKind::Archive { ref mut sha256, .. } = self.kind {
+            *sha256 = Some(digest.into().to_ascii_lowercase());
+        }
+        self
+    }
+
+    pub fn with_strip_components(mut self, count: usize) -> Self {
+        if let SkillSourceKind::Archive {
+            ref mut strip_components,
+            ..
+        } = self.kind
+        {
+            *strip_components = count;
+        }
+        self
+    }
+
     pub fn with_forked_from(mut self, forked_from: impl Into<String>) -> Self {
         if let SkillSourceKind::Local {
             forked_from: ref mut ff,
@@ -254,6 +381,22 @@ impl SkillSource {
         matches!(self.kind, SkillSourceKind::Http)
     }
 
+    pub fn is_archive(&self) -> bool {
+        matches!(self.kind, SkillSourceKind::Archive { .. })
+    }
+
+    pub fn is_oci(&self) -> bool {
+        matches!(self.kind, SkillSourceKind::Oci)
+    }
+
+    /// The expected SHA-256 digest of an archive source, if pinned.
+    pub fn sha256(&self) -> Option<&str> {
+        match &self.kind {
+            SkillSourceKind::Archive { sha256, .. } => sha256.as_deref(),
+            _ => None,
+        }
+    }
+
     /// Returns true if this source points to a local filesystem path
     /// (either a Path source or a Binary source with a local project).
     pub fn is_local_path(&self) -> bool {
@@ -308,6 +451,23 @@ impl SkillSource {
                 .to_string();
         }
 
+        if self.is_archive() {
+            // e.g. "https://example.com/releases/review-1.2.0.tar.gz" → "review-1.2.0"
+            let file = self.source.split(['?', '#']).next().unwrap_or(&self.source);
+            let file = file.rsplit('/').next().unwrap_or(file);
+            return [".tar.gz", ".tgz", ".zip"]
+                .iter()
+                .find_map(|ext| file.strip_suffix(ext))
+                .unwrap_or(file)
+                .to_string();
+        }
+
+        if self.is_oci()
+            && let Ok(reference) = crate::oci::Reference::parse(&self.source)
+        {
+            return reference.name().to_string();
+        }
+
         self.source
             .trim_end_matches(".git")
             .rsplit('/')
@@ -322,6 +482,9 @@ impl SkillSource {
         if source.starts_with('/') || source.starts_with("./") || source.starts_with("../") {
             return Ok(Self::path(source));
         }
+        if is_windows_path(source) {
+            return Ok(Self::path(source));
+        }
 
         // URLs
         if source.starts_with("https://") || source.starts_with("http://") {
@@ -334,6 +497,11 @@ impl SkillSource {
             }
         }
 
+        // OCI registry references
+        if source.starts_with("oci://") {
+            return Ok(Self::oci(source));
+        }
+
         // Other git remote forms: self-hosted servers (GitLab, Bitbucket, Gitea,
         // internal git hosts) and local bare repos aren't always reachable over
         // https://. Recognize the standard git transports and SCP-like syntax
@@ -347,8 +515,44 @@ impl SkillSource {
             return Ok(Self::git(source));
         }
 
+        // Named hosts: `gitlab:group/sub/repo//skill-path`, `github:owner/repo`,
+        // `git:<url>`, `archive:<url>`, or any `[hosts.<name>]` from the global config. Hosts like GitLab
+        // allow nested groups, so `//` separates the repo from the skill path.
+        if let Some((host, rest)) = hosts::split_shorthand(source)
+            && (matches!(host, "github" | "git" | "archive") || hosts::lookup(host).is_some())
+        {
+            match host {
+                "github" => return Self::infer(&rest.replacen("//", "/", 1)),
+                "git" => return Ok(Self::git(rest)),
+                "archive" => return Ok(Self::archive(rest)),
+                _ => {}
+            }
+            let (repo, path) = match rest.split_once("//") {
+                Some((repo, path)) => (repo, Some(path.trim_matches('/'))),
+                None => (rest, None),
+            };
+            let repo = repo.trim_end_matches('/');
+            if !repo.contains('/') {
+                return Err(Error::Source(format!(
+                    "Expected {host}:owner/repo, got: {source}"
+                )));
+            }
+            let git = Self::git(format!("{host}:{repo}"));
+            return Ok(match path {
+                Some(path) if !path.is_empty() => git.with_path(path),
+                _ => git,
+            });
+        }
+
         // Shorthand: owner/repo or owner/repo/skill-path
         let segments: Vec<&str> = source.split('/').collect();
+        if let Some((host, _)) = segments[0].split_once(':') {
+            // GitHub owners can't contain a colon, so this is most likely a
+            // host that isn't configured, or a relative path.
+            return Err(Error::Source(format!(
+                "Unknown git host '{host}' in: {source}. Add a [hosts.{host}] entry with a `url` to ~/.config/ion/config.toml, or prefix a local path with ./"
+            )));
+        }
         match segments.len() {
             2 => Ok(Self::github(source)),
             3.. => Ok(Self::github(format!("{}/{}", segments[0], segments[1]))
@@ -390,13 +594,44 @@ impl SkillSource {
                 }
                 Ok(format!("https://github.com/{}.git", self.source))
             }
-            SkillSourceKind::Git => Ok(self.source.clone()),
+            SkillSourceKind::Git => match hosts::split_shorthand(&self.source) {
+                Some((host, repo)) => Ok(hosts::require(host)?.clone_url(repo)),
+                None => Ok(self.source.clone()),
+            },
             _ => Err(Error::Source(format!(
                 "Source kind {:?} has no git URL",
                 self.kind
             ))),
         }
     }
+
+    /// Web page for this source's repository, if it has one.
+    pub fn web_url(&self) -> Option<String> {
+        match &self.kind {
+            SkillSourceKind::Github if !self.source.starts_with("https://") => {
+                Some(format!("https://github.com/{}", self.source))
+            }
+            SkillSourceKind::Git => match hosts::split_shorthand(&self.source) {
+                Some((host, repo)) => hosts::lookup(host).map(|h| h.web_url(repo)),
+                None => self
+                    .source
+                    .starts_with("https://")
+                    .then(|| self.source.trim_end_matches(".git").to_string()),
+            },
+            SkillSourceKind::Github | SkillSourceKind::Http | SkillSourceKind::Archive { .. } => {
+                Some(self.source.trim_end_matches(".git").to_string())
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Whether `source` is a Windows path: `C:\skills`, `C:/skills`, `.\skills`,
+/// `..\skills` or a `\\server\share` UNC path.
+fn is_windows_path(source: &str) -> bool {
+    let bytes = source.as_bytes();
+    let drive = bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':';
+    drive || source.starts_with(".\\") || source.starts_with("..\\") || source.starts_with("\\\\")
 }
 
 #[cfg(test)]
@@ -502,6 +737,97 @@ mod tests {
         assert!(matches!(s.kind, SkillSourceKind::Git));
     }
 
+    #[test]
+    fn infer_builtin_host_shorthand() {
+        let s = SkillSource::infer("gitlab:group/sub/repo//skills/review").unwrap();
+        assert!(matches!(s.kind, SkillSourceKind::Git));
+        assert_eq!(s.source, "gitlab:group/sub/repo");
+        assert_eq!(s.path.as_deref(), Some("skills/review"));
+        assert_eq!(
+            s.git_url().unwrap(),
+            "https://gitlab.com/group/sub/repo.git"
+        );
+        assert_eq!(
+            s.web_url().as_deref(),
+            Some("https://gitlab.com/group/sub/repo")
+        );
+        assert_eq!(s.display_name(), "review");
+
+        let s = SkillSource::infer("gitea:team/skills").unwrap();
+        assert_eq!(s.git_url().unwrap(), "https://gitea.com/team/skills.git");
+        assert_eq!(s.display_name(), "skills");
+    }
+
+    #[test]
+    fn infer_github_prefix() {
+        let s = SkillSource::infer("github:obra/superpowers//brainstorming").unwrap();
+        assert!(s.is_github());
+        assert_eq!(s.source, "obra/superpowers");
+        assert_eq!(s.path.as_deref(), Some("brainstorming"));
+
+        let s = SkillSource::infer("git:https://gitlab.com/owner/repo.git").unwrap();
+        assert_eq!(s.git_url().unwrap(), "https://gitlab.com/owner/repo.git");
+    }
+
+    #[test]
+    fn infer_archive_prefix() {
+        let s = SkillSource::infer("archive:https://example.com/releases/review-1.2.0.tar.gz")
+            .unwrap()
+            .with_sha256("ABC123")
+            .with_strip_components(1);
+        assert!(s.is_archive());
+        assert_eq!(s.source, "https://example.com/releases/review-1.2.0.tar.gz");
+        assert_eq!(s.sha256(), Some("abc123"));
+        assert_eq!(s.display_name(), "review-1.2.0");
+        assert!(s.is_remote_installable());
+        assert!(s.git_url().is_err());
+    }
+
+    #[test]
+    fn infer_oci_reference() {
+        let s = SkillSource::infer("oci://ghcr.io/acme/skills/review:1.2").unwrap();
+        assert!(s.is_oci());
+        assert_eq!(s.source, "oci://ghcr.io/acme/skills/review:1.2");
+        assert_eq!(s.display_name(), "review");
+        assert!(s.is_remote_installable());
+    }
+
+    #[test]
+    fn infer_unknown_host_is_error() {
+        let err = SkillSource::infer("nowhere:team/skills").unwrap_err();
+        assert!(err.to_string().contains("[hosts.nowhere]"));
+        assert!(SkillSource::infer("gitlab:repo").is_err());
+    }
+
+    #[test]
+    fn infer_windows_paths() {
+        for path in [
+            r"C:\skills\x",
+            "C:/skills/x",
+            r".\skills\x",
+            r"\\server\share\x",
+        ] {
+            let s = SkillSource::infer(path).unwrap();
+            assert_eq!(s.kind, SkillSourceKind::Path, "{path}");
+            assert_eq!(s.source, path);
+        }
+    }
+
+    #[test]
+    fn infer_colon_without_known_host_is_not_shorthand() {
+        // `skills:v2` names no host, so it isn't expanded as a shorthand and
+        // the error points at the `./` prefix for relative paths.
+        for source in ["skills:v2/review", r"skills:v2\review"] {
+            let err = SkillSource::infer(source).unwrap_err();
+            assert!(
+                err.to_string().contains("prefix a local path with ./"),
+                "{err}"
+            );
+        }
+        let s = SkillSource::infer("./skills:v2/review").unwrap();
+        assert_eq!(s.kind, SkillSourceKind::Path);
+    }
+
     #[test]
     fn git_url_github_shorthand() {
         let s = SkillSource::infer("org/repo").unwrap();
@@ -1,24 +1,23 @@
 //! Updater for binary skills — check and download newer releases from GitHub.
 
-use crate::binary;
 use crate::installer::SkillInstaller;
 use crate::lockfile::LockedSkill;
 use crate::source::SkillSource;
+use crate::{binary, offline};
 
-use super::{UpdateInfo, Updater};
+use super::{UpdateCheck, UpdateInfo, Updater};
 
 /// Updater for binary skills installed from GitHub Releases.
 pub struct BinaryUpdater;
 
 impl Updater for BinaryUpdater {
-    fn check(
-        &self,
-        skill: &LockedSkill,
-        source: &SkillSource,
-    ) -> crate::Result<Option<UpdateInfo>> {
+    fn check(&self, skill: &LockedSkill, source: &SkillSource) -> crate::Result<UpdateCheck> {
         if source.source.starts_with("http://") || source.source.starts_with("https://") {
             // URL-based binary sources don't support automatic update checking
-            return Ok(None);
+            return Ok(UpdateCheck::UpToDate);
+        }
+        if offline::is_offline() {
+            return Ok(UpdateCheck::Unknown);
         }
 
         let release = binary::fetch_github_release(&source.source, source.rev.as_deref())?;
@@ -27,10 +26,10 @@ impl Updater for BinaryUpdater {
         let current_version = skill.binary_version().unwrap_or("unknown").to_string();
 
         if current_version == latest_version {
-            return Ok(None);
+            return Ok(UpdateCheck::UpToDate);
         }
 
-        Ok(Some(UpdateInfo {
+        Ok(UpdateCheck::Available(UpdateInfo {
             old_version: current_version,
             new_version: latest_version,
         }))
@@ -96,7 +95,7 @@ impl Updater for BinaryUpdater {
             locked = locked.with_version(version);
         }
 
-        Ok(locked)
+        Ok(locked.with_rendered(installer.rendered_files(&skill.name)))
     }
 }
 
@@ -1,58 +1,85 @@
-//! Updater for Git-sourced skills — fetch the latest commit from the default branch and redeploy.
+//! Updater for Git-sourced skills — fetch the latest commit on the skill's branch or tag channel, or
+//! the highest tag within its version requirement, and redeploy.
 
-use crate::installer::{SkillInstaller, data_dir, hash_simple, resolve_skill_dir};
+use std::path::{Path, PathBuf};
+
+use crate::installer::{SkillInstaller, cache_key, data_dir, resolve_skill_dir, snapshot_dir};
 use crate::lockfile::LockedSkill;
 use crate::skill::SkillMetadata;
 use crate::source::SkillSource;
-use crate::{Error, git, validate};
+use crate::{Error, git, offline, validate, versions};
 
-use super::{UpdateInfo, Updater};
+use super::{UpdateCheck, UpdateInfo, Updater};
 
 /// Updater for Git and GitHub-sourced skills.
 pub struct GitUpdater;
 
-impl Updater for GitUpdater {
-    fn check(
+/// The commit an update would move a skill to, snapshotted but not deployed.
+#[derive(Debug, Clone)]
+pub struct Candidate {
+    pub commit: String,
+    /// Tag the commit was resolved from, if any.
+    pub tag: Option<String>,
+    /// The skill directory within the commit's snapshot.
+    pub skill_dir: PathBuf,
+}
+
+impl GitUpdater {
+    /// Fetch the source and snapshot the commit an update would move to.
+    /// Nothing is deployed; `apply` deploys the same commit.
+    pub fn candidate(&self, source: &SkillSource) -> crate::Result<Candidate> {
+        let url = source.git_url()?;
+        let hash = cache_key(&url);
+        let repo_dir = data_dir().join(&hash);
+
+        git::sync_repo(&url, &repo_dir)?;
+        let (commit, tag) = latest(&repo_dir, source)?;
+        let snapshot = snapshot_dir(&hash, &commit);
+        git::ensure_snapshot(&repo_dir, &commit, &snapshot, source.path.as_deref())?;
+
+        let skill_dir = resolve_skill_dir(&snapshot, source.path.as_deref())?;
+        Ok(Candidate {
+            commit,
+            tag,
+            skill_dir,
+        })
+    }
+
+    /// The skill directory at the commit recorded in `skill`, snapshotted
+    /// from the cached clone. Returns `None` if no commit is locked.
+    pub fn locked_dir(
         &self,
         skill: &LockedSkill,
         source: &SkillSource,
-    ) -> crate::Result<Option<UpdateInfo>> {
-        let url = source.git_url()?;
-        let repo_hash = format!("{:x}", hash_simple(&url));
-        let repo_dir = data_dir().join(&repo_hash);
-
-        git::clone_or_fetch(&url, &repo_dir)?;
-        git::reset_to_remote_head(&repo_dir)?;
-
-        let new_commit = git::head_commit(&repo_dir)?;
-        let old_commit = skill.commit().unwrap_or_default().to_string();
-
-        if new_commit == old_commit {
+    ) -> crate::Result<Option<PathBuf>> {
+        let Some(commit) = skill.commit().filter(|c| !c.is_empty()) else {
             return Ok(None);
-        }
-
-        Ok(Some(UpdateInfo {
-            old_version: short_sha(&old_commit),
-            new_version: short_sha(&new_commit),
-        }))
+        };
+        let hash = cache_key(&source.git_url()?);
+        let snapshot = snapshot_dir(&hash, commit);
+        git::ensure_snapshot(
+            &data_dir().join(&hash),
+            commit,
+            &snapshot,
+            source.path.as_deref(),
+        )?;
+        resolve_skill_dir(&snapshot, source.path.as_deref()).map(Some)
     }
 
-    fn apply(
+    /// Deploy a commit returned by [`candidate`](Self::candidate) and return
+    /// the updated lock entry.
+    pub fn apply_candidate(
         &self,
         skill: &LockedSkill,
         source: &SkillSource,
+        candidate: Candidate,
         installer: &SkillInstaller,
     ) -> crate::Result<LockedSkill> {
-        let url = source.git_url()?;
-        let repo_hash = format!("{:x}", hash_simple(&url));
-        let repo_dir = data_dir().join(&repo_hash);
-
-        // Fetch and advance to latest
-        git::clone_or_fetch(&url, &repo_dir)?;
-        git::reset_to_remote_head(&repo_dir)?;
-
-        // Resolve the skill directory within the repo
-        let skill_dir = resolve_skill_dir(&repo_dir, source.path.as_deref())?;
+        let Candidate {
+            commit,
+            tag,
+            skill_dir,
+        } = candidate;
 
         // Validate SKILL.md
         let skill_md = skill_dir.join("SKILL.md");
@@ -73,7 +100,6 @@ impl Updater for GitUpdater {
         installer.deploy(&skill.name, &skill_dir)?;
 
         // Build updated lock entry
-        let commit = git::head_commit(&repo_dir).ok();
         let checksum = git::checksum_dir(&skill_dir).ok();
         let git_url = source
             .git_url()
@@ -83,7 +109,7 @@ impl Updater for GitUpdater {
         let mut locked = LockedSkill::git(
             skill.name.clone(),
             git_url,
-            commit.unwrap_or_default(),
+            commit,
             checksum.unwrap_or_default(),
         );
         if let Some(path) = source.path.clone() {
@@ -92,10 +118,70 @@ impl Updater for GitUpdater {
         if let Some(version) = meta.version() {
             locked = locked.with_version(version);
         }
-        Ok(locked)
+        if let Some(tag) = tag {
+            locked = locked.with_tag(tag);
+        }
+        if let Some(channel) = source.channel.clone() {
+            locked = locked.with_channel(channel);
+        }
+        Ok(locked.with_rendered(installer.rendered_files(&skill.name)))
     }
 }
 
+impl Updater for GitUpdater {
+    fn check(&self, skill: &LockedSkill, source: &SkillSource) -> crate::Result<UpdateCheck> {
+        if offline::is_offline() {
+            return Ok(UpdateCheck::Unknown);
+        }
+
+        let url = source.git_url()?;
+        let repo_dir = data_dir().join(cache_key(&url));
+
+        git::clone_or_fetch(&url, &repo_dir)?;
+
+        let (new_commit, new_tag) = latest(&repo_dir, source)?;
+        let old_commit = skill.commit().unwrap_or_default().to_string();
+
+        if new_commit == old_commit {
+            return Ok(UpdateCheck::UpToDate);
+        }
+
+        Ok(UpdateCheck::Available(UpdateInfo {
+            old_version: skill
+                .tag()
+                .map(str::to_string)
+                .unwrap_or_else(|| short_sha(&old_commit)),
+            new_version: new_tag.unwrap_or_else(|| short_sha(&new_commit)),
+        }))
+    }
+
+    fn apply(
+        &self,
+        skill: &LockedSkill,
+        source: &SkillSource,
+        installer: &SkillInstaller,
+    ) -> crate::Result<LockedSkill> {
+        // Fetch and snapshot the latest commit
+        let candidate = self.candidate(source)?;
+        self.apply_candidate(skill, source, candidate, installer)
+    }
+}
+
+/// The commit an update moves to: the head of the source's branch or tag
+/// channel, the highest tag satisfying its version requirement, or the
+/// remote default branch if it has neither or the repo has no version tags.
+fn latest(repo_dir: &Path, source: &SkillSource) -> crate::Result<(String, Option<String>)> {
+    if let Some(ref channel) = source.channel {
+        return git::channel_commit(repo_dir, channel);
+    }
+    if let Some(req) = source.version.as_deref()
+        && let Some(resolved) = versions::resolve(repo_dir, req, source.path.as_deref())?
+    {
+        return Ok((resolved.commit, Some(resolved.tag)));
+    }
+    Ok((git::remote_head_commit(repo_dir)?, None))
+}
+
 /// Return a short (7-char) prefix of a SHA, or the full string if shorter.
 fn short_sha(sha: &str) -> String {
     sha.get(..7).unwrap_or(sha).to_string()
@@ -199,8 +285,9 @@ mod tests {
 
         let updater = GitUpdater;
         let result = updater.check(&locked, &source).unwrap();
-        assert!(result.is_some(), "should detect an update");
-        let info = result.unwrap();
+        let UpdateCheck::Available(info) = result else {
+            panic!("should detect an update, got {result:?}");
+        };
         assert_ne!(info.old_version, info.new_version);
     }
 
@@ -214,8 +301,7 @@ mod tests {
 
         // Clone via the same hashing mechanism the updater uses
         let url = source.git_url().unwrap();
-        let repo_hash = format!("{:x}", hash_simple(&url));
-        let repo_dir = data_dir().join(&repo_hash);
+        let repo_dir = data_dir().join(cache_key(&url));
         git::clone_or_fetch(&url, &repo_dir).unwrap();
         let current_commit = git::head_commit(&repo_dir).unwrap();
 
@@ -228,6 +314,9 @@ mod tests {
 
         let updater = GitUpdater;
         let result = updater.check(&locked, &source).unwrap();
-        assert!(result.is_none(), "should be up to date");
+        assert!(
+            matches!(result, UpdateCheck::UpToDate),
+            "should be up to date"
+        );
     }
 }
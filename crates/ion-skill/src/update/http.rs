@@ -0,0 +1,78 @@
+//! Updater for HTTP-sourced skills — accept whatever SKILL.md the URL serves now, replacing the
+//! content recorded in Ion.lock.
+
+use crate::http_cache;
+use crate::installer::SkillInstaller;
+use crate::lockfile::LockedSkill;
+use crate::skill::SkillMetadata;
+use crate::source::SkillSource;
+use crate::{Error, git, offline, validate};
+
+use super::{UpdateCheck, UpdateInfo, Updater, short_digest};
+
+/// Updater for single-file skills served over HTTP.
+///
+/// HTTP skills have no versions, so an update is available whenever the
+/// served content no longer matches the checksum in Ion.lock.
+pub struct HttpUpdater;
+
+impl Updater for HttpUpdater {
+    fn check(&self, skill: &LockedSkill, source: &SkillSource) -> crate::Result<UpdateCheck> {
+        if offline::is_offline() {
+            return Ok(UpdateCheck::Unknown);
+        }
+
+        let candidate = http_cache::candidate(source)?;
+        let new_checksum = git::checksum_dir(candidate.dir())?;
+        let old_checksum = skill.checksum().unwrap_or_default();
+        if new_checksum == old_checksum {
+            return Ok(UpdateCheck::UpToDate);
+        }
+
+        Ok(UpdateCheck::Available(UpdateInfo {
+            old_version: short_digest(old_checksum),
+            new_version: short_digest(&new_checksum),
+        }))
+    }
+
+    fn apply(
+        &self,
+        skill: &LockedSkill,
+        source: &SkillSource,
+        installer: &SkillInstaller,
+    ) -> crate::Result<LockedSkill> {
+        let candidate = http_cache::candidate(source)?;
+        self.apply_candidate(skill, source, candidate, installer)
+    }
+}
+
+impl HttpUpdater {
+    /// Deploy content returned by [`http_cache::candidate`] and return the
+    /// updated lock entry.
+    pub fn apply_candidate(
+        &self,
+        skill: &LockedSkill,
+        source: &SkillSource,
+        candidate: http_cache::Candidate,
+        installer: &SkillInstaller,
+    ) -> crate::Result<LockedSkill> {
+        // Validate before the new content replaces the cached copy
+        let skill_md = candidate.dir().join("SKILL.md");
+        let (meta, body) = SkillMetadata::from_file(&skill_md)?;
+        let report = validate::validate_skill_dir(candidate.dir(), &meta, &body);
+        if report.error_count > 0 {
+            return Err(Error::validation_failed(report));
+        }
+        let checksum = git::checksum_dir(candidate.dir())?;
+
+        let skill_dir = candidate.accept()?;
+        installer.deploy(&skill.name, &skill_dir)?;
+
+        let mut locked = LockedSkill::http(skill.name.clone(), source.source.clone())
+            .with_checksum(git::checksum_dir(&skill_dir).unwrap_or(checksum));
+        if let Some(version) = meta.version() {
+            locked = locked.with_version(version);
+        }
+        Ok(locked.with_rendered(installer.rendered_files(&skill.name)))
+    }
+}
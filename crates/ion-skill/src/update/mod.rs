@@ -1,14 +1,18 @@
-//! Skill update infrastructure — check for newer versions and apply updates across Git and binary sources.
+//! Skill update infrastructure — check for newer versions and apply updates across Git, binary, HTTP and OCI sources.
 
 pub mod binary;
 pub mod git;
+pub mod http;
+pub mod oci;
+
+use std::path::{Path, PathBuf};
 
 use crate::installer::SkillInstaller;
 use crate::lockfile::LockedSkill;
 use crate::source::SkillSource;
 
 /// Information about an available update.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct UpdateInfo {
     /// Human-readable description of the old version (e.g., commit SHA prefix or version string).
     pub old_version: String,
@@ -16,11 +20,21 @@ pub struct UpdateInfo {
     pub new_version: String,
 }
 
+/// Outcome of an update check.
+#[derive(Debug)]
+pub enum UpdateCheck {
+    /// The installed version is the latest.
+    UpToDate,
+    /// A newer version is available.
+    Available(UpdateInfo),
+    /// The latest version can't be determined without the network (offline mode).
+    Unknown,
+}
+
 /// Trait for source-type-specific update logic.
 pub trait Updater {
-    /// Check if an update is available. Returns `Some(UpdateInfo)` if yes, `None` if up to date.
-    fn check(&self, skill: &LockedSkill, source: &SkillSource)
-    -> crate::Result<Option<UpdateInfo>>;
+    /// Check if an update is available.
+    fn check(&self, skill: &LockedSkill, source: &SkillSource) -> crate::Result<UpdateCheck>;
 
     /// Apply the update: fetch new version, validate, deploy, return updated lock entry.
     fn apply(
@@ -30,3 +44,66 @@ pub trait Updater {
         installer: &SkillInstaller,
     ) -> crate::Result<LockedSkill>;
 }
+
+/// An update resolved ahead of time, e.g. to preview it. [`apply_candidate`]
+/// deploys exactly this version, not whatever the source serves by then.
+pub enum Candidate {
+    Git(git::Candidate),
+    Http(crate::http_cache::Candidate),
+    Oci {
+        digest: String,
+        /// The skill directory within the pulled manifest.
+        skill_dir: PathBuf,
+    },
+}
+
+impl Candidate {
+    /// The candidate's skill directory.
+    pub fn skill_dir(&self) -> &Path {
+        match self {
+            Candidate::Git(candidate) => &candidate.skill_dir,
+            Candidate::Http(candidate) => candidate.dir(),
+            Candidate::Oci { skill_dir, .. } => skill_dir,
+        }
+    }
+}
+
+/// Deploy a candidate and return the updated lock entry.
+pub fn apply_candidate(
+    skill: &LockedSkill,
+    source: &SkillSource,
+    candidate: Candidate,
+    installer: &SkillInstaller,
+) -> crate::Result<LockedSkill> {
+    match candidate {
+        Candidate::Git(candidate) => {
+            git::GitUpdater.apply_candidate(skill, source, candidate, installer)
+        }
+        Candidate::Http(candidate) => {
+            http::HttpUpdater.apply_candidate(skill, source, candidate, installer)
+        }
+        Candidate::Oci { digest, .. } => {
+            oci::OciUpdater.apply_digest(skill, source, digest, installer)
+        }
+    }
+}
+
+/// A `sha256:` checksum or digest shortened for display, e.g. `sha256:1a2b3c4d`.
+pub fn short_digest(digest: &str) -> String {
+    if digest.is_empty() {
+        return "(none)".to_string();
+    }
+    let hex = digest.strip_prefix("sha256:").unwrap_or(digest);
+    format!("sha256:{}", hex.get(..8).unwrap_or(hex))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_digest_truncates() {
+        assert_eq!(short_digest("sha256:0123456789abcdef"), "sha256:01234567");
+        assert_eq!(short_digest(""), "(none)");
+    }
+}
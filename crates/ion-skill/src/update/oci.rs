@@ -0,0 +1,85 @@
+//! Updater for OCI-sourced skills — re-resolve the skill's tag in its registry and redeploy when it
+//! points at a new manifest.
+
+use crate::installer::{SkillInstaller, resolve_skill_dir};
+use crate::lockfile::LockedSkill;
+use crate::skill::SkillMetadata;
+use crate::source::SkillSource;
+use crate::{Error, git, oci, offline, validate};
+
+use super::{UpdateCheck, UpdateInfo, Updater, short_digest};
+
+/// Updater for skills pulled from an OCI registry by tag.
+pub struct OciUpdater;
+
+impl Updater for OciUpdater {
+    fn check(&self, skill: &LockedSkill, source: &SkillSource) -> crate::Result<UpdateCheck> {
+        if offline::is_offline() {
+            return Ok(UpdateCheck::Unknown);
+        }
+
+        let new_digest = oci::resolve_digest(source)?;
+        let old_digest = skill.digest().unwrap_or_default();
+        if new_digest == old_digest {
+            return Ok(UpdateCheck::UpToDate);
+        }
+
+        Ok(UpdateCheck::Available(UpdateInfo {
+            old_version: short_digest(old_digest),
+            new_version: short_digest(&new_digest),
+        }))
+    }
+
+    fn apply(
+        &self,
+        skill: &LockedSkill,
+        source: &SkillSource,
+        installer: &SkillInstaller,
+    ) -> crate::Result<LockedSkill> {
+        // Resolve the tag once so the deployed content and the lock agree
+        let digest = oci::resolve_digest(source)?;
+        self.apply_digest(skill, source, digest, installer)
+    }
+}
+
+impl OciUpdater {
+    /// Deploy the manifest `digest`, as resolved from the skill's tag, and
+    /// return the updated lock entry.
+    pub fn apply_digest(
+        &self,
+        skill: &LockedSkill,
+        source: &SkillSource,
+        digest: String,
+        installer: &SkillInstaller,
+    ) -> crate::Result<LockedSkill> {
+        let base = oci::fetch(&source.clone().with_rev(&digest))?;
+        let skill_dir = resolve_skill_dir(&base, source.path.as_deref())?;
+
+        let skill_md = skill_dir.join("SKILL.md");
+        if !skill_md.exists() {
+            return Err(Error::InvalidSkill(format!(
+                "No SKILL.md found at {}",
+                skill_md.display()
+            )));
+        }
+        let (meta, body) = SkillMetadata::from_file(&skill_md)?;
+        let report = validate::validate_skill_dir(&skill_dir, &meta, &body);
+        if report.error_count > 0 {
+            return Err(Error::validation_failed(report));
+        }
+
+        installer.deploy(&skill.name, &skill_dir)?;
+
+        let mut locked = LockedSkill::oci(skill.name.clone(), source.source.clone(), digest);
+        if let Ok(checksum) = git::checksum_dir(&skill_dir) {
+            locked = locked.with_checksum(checksum);
+        }
+        if let Some(path) = source.path.clone() {
+            locked = locked.with_path(path);
+        }
+        if let Some(version) = meta.version() {
+            locked = locked.with_version(version);
+        }
+        Ok(locked.with_rendered(installer.rendered_files(&skill.name)))
+    }
+}
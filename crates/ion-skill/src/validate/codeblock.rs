@@ -85,6 +85,7 @@ mod tests {
             compatibility: None,
             metadata: Some(HashMap::new()),
             allowed_tools: None,
+            requires: Vec::new(),
         }
     }
 
@@ -181,6 +181,7 @@ mod tests {
             compatibility: None,
             metadata: Some(HashMap::new()),
             allowed_tools: None,
+            requires: Vec::new(),
         }
     }
 
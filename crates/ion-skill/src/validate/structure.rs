@@ -114,6 +114,7 @@ mod tests {
             compatibility: None,
             metadata: Some(HashMap::new()),
             allowed_tools: value.map(ToString::to_string),
+            requires: Vec::new(),
         }
     }
 
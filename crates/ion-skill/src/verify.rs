@@ -0,0 +1,354 @@
+//! Integrity checks — compare deployed skills against the checksums and links recorded in Ion.lock.
+
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::binary;
+use crate::git::{self, FileChange};
+use crate::installer::{SkillInstaller, store_dir};
+use crate::lockfile::{LockedSkill, LockedSkillKind};
+use crate::manifest::DeployMode;
+use crate::render::TargetRenderer;
+use crate::skill::SkillMetadata;
+
+/// Verification result for a single locked skill.
+///
+/// File paths are relative to the deployed skill directory. `problems`
+/// holds findings that aren't tied to one file, such as a missing
+/// deployment, a checksum mismatch or a broken target link.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SkillVerification {
+    pub name: String,
+    pub modified: Vec<String>,
+    pub missing: Vec<String>,
+    pub extra: Vec<String>,
+    pub problems: Vec<String>,
+}
+
+impl SkillVerification {
+    /// Whether the deployed skill matches Ion.lock.
+    pub fn is_ok(&self) -> bool {
+        self.modified.is_empty()
+            && self.missing.is_empty()
+            && self.extra.is_empty()
+            && self.problems.is_empty()
+    }
+}
+
+/// Verify a locked skill's deployment in the installer's project.
+///
+/// Checks that the deployed directory hashes to the locked checksum, that
+/// binaries still match `binary_checksum`, and that every target link
+/// resolves to (or every target copy matches) the canonical skill directory
+/// and every rendered target file is current. Local and path skills are
+/// the user's own files, so only their links are checked.
+pub fn verify_skill(installer: &SkillInstaller, locked: &LockedSkill) -> SkillVerification {
+    let mut report = SkillVerification {
+        name: locked.name.clone(),
+        ..Default::default()
+    };
+    let deployed = installer.skill_dir(&locked.name);
+
+    if !deployed.exists() {
+        report
+            .problems
+            .push(format!("not deployed at {}", deployed.display()));
+    } else {
+        match &locked.kind {
+            LockedSkillKind::Git { checksum, .. } => {
+                verify_content(&mut report, &deployed, Some(checksum.as_str()));
+            }
+            LockedSkillKind::Http { checksum }
+            | LockedSkillKind::Archive { checksum, .. }
+            | LockedSkillKind::Oci { checksum, .. } => {
+                verify_content(&mut report, &deployed, checksum.as_deref());
+            }
+            LockedSkillKind::Binary {
+                binary_name,
+                binary_version: Some(version),
+                binary_checksum: Some(expected),
+                dev: false,
+            } => verify_binary(&mut report, binary_name, version, expected),
+            _ => {}
+        }
+    }
+
+    verify_targets(&mut report, installer, &deployed);
+
+    report.modified.sort();
+    report.missing.sort();
+    report.extra.sort();
+    report
+}
+
+/// Compare the deployed directory against the locked checksum. When the
+/// directory lives in a snapshot under [`store_dir`], report the individual
+/// files that differ from the snapshot's commit.
+fn verify_content(report: &mut SkillVerification, deployed: &Path, expected: Option<&str>) {
+    let Some(expected) = expected.filter(|c| !c.is_empty()) else {
+        return;
+    };
+    let actual = match git::checksum_dir(deployed) {
+        Ok(actual) => actual,
+        Err(e) => {
+            report.problems.push(format!("could not be read: {e}"));
+            return;
+        }
+    };
+    if actual == expected {
+        return;
+    }
+
+    let store = std::fs::canonicalize(store_dir()).ok();
+    let changes = std::fs::canonicalize(deployed)
+        .ok()
+        .filter(|dir| store.is_some_and(|store| dir.starts_with(store)))
+        .and_then(|dir| git::changed_files(&dir).ok())
+        .unwrap_or_default();
+    if changes.is_empty() {
+        report.problems.push(format!(
+            "content does not match Ion.lock (expected {expected}, found {actual})"
+        ));
+    }
+    for (change, path) in changes {
+        match change {
+            FileChange::Modified => report.modified.push(path),
+            FileChange::Missing => report.missing.push(path),
+            FileChange::Extra => report.extra.push(path),
+        }
+    }
+}
+
+/// Re-hash an installed binary against its locked checksum.
+fn verify_binary(report: &mut SkillVerification, binary_name: &str, version: &str, expected: &str) {
+    let path = binary::binary_path(binary_name, version);
+    if !path.exists() {
+        report.problems.push(format!(
+            "binary {binary_name} {version} is missing from {}",
+            path.display()
+        ));
+        return;
+    }
+    match binary::file_checksum(&path) {
+        Ok(actual) if actual != expected => report.problems.push(format!(
+            "binary {binary_name} {version} does not match Ion.lock (expected {expected}, found {actual})"
+        )),
+        Ok(_) => {}
+        Err(e) => report
+            .problems
+            .push(format!("binary {binary_name} {version} could not be read: {e}")),
+    }
+}
+
+/// Check that every target link resolves to the canonical skill directory,
+/// and that every target copy still matches it.
+fn verify_targets(report: &mut SkillVerification, installer: &SkillInstaller, deployed: &Path) {
+    let canonical = std::fs::canonicalize(deployed).ok();
+    for (target, target_path) in &installer.options().targets {
+        if !installer.options().deploys_to(target, &report.name) {
+            continue;
+        }
+        if let Some(renderer) = installer.options().format_for(target).renderer() {
+            let target_dir = installer.project_dir().join(target_path);
+            verify_rendered(report, target, &target_dir, renderer, deployed);
+            continue;
+        }
+        let link = installer.project_dir().join(target_path).join(&report.name);
+        if link == deployed {
+            continue;
+        }
+        if link.symlink_metadata().is_err() {
+            report.problems.push(format!(
+                "target '{target}' link {} is missing",
+                link.display()
+            ));
+            continue;
+        }
+        if installer.options().deploy_mode_for(target) != DeployMode::Symlink && !link.is_symlink()
+        {
+            let copy = git::checksum_dir(&link).ok();
+            if copy.is_none() || copy != git::checksum_dir(deployed).ok() {
+                report.problems.push(format!(
+                    "target '{target}' copy {} does not match {}",
+                    link.display(),
+                    deployed.display()
+                ));
+            }
+            continue;
+        }
+        if canonical.is_none() || std::fs::canonicalize(&link).ok() != canonical {
+            report.problems.push(format!(
+                "target '{target}' link {} does not resolve to {}",
+                link.display(),
+                deployed.display()
+            ));
+        }
+    }
+}
+
+/// Check that a rendering target holds the files the deployed skill renders to.
+fn verify_rendered(
+    report: &mut SkillVerification,
+    target: &str,
+    target_dir: &Path,
+    renderer: &dyn TargetRenderer,
+    deployed: &Path,
+) {
+    let expected = SkillMetadata::from_file(&deployed.join("SKILL.md"))
+        .map(|(meta, body)| renderer.render(&report.name, &meta, &body))
+        .ok();
+    for path in renderer.paths(&report.name) {
+        let file = target_dir.join(&path);
+        let Ok(actual) = std::fs::read_to_string(&file) else {
+            report.problems.push(format!(
+                "target '{target}' file {} is missing",
+                file.display()
+            ));
+            continue;
+        };
+        let current = expected
+            .iter()
+            .flatten()
+            .find(|rendered| rendered.path == path)
+            .is_none_or(|rendered| rendered.contents == actual);
+        if !current {
+            report.problems.push(format!(
+                "target '{target}' file {} is out of date",
+                file.display()
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::ManifestOptions;
+
+    fn options_with_claude() -> ManifestOptions {
+        let mut options = ManifestOptions::default();
+        options
+            .targets
+            .insert("claude".to_string(), ".claude/skills".to_string());
+        options
+    }
+
+    fn deploy_copy(installer: &SkillInstaller, name: &str, files: &[(&str, &str)]) -> String {
+        let dir = installer.skill_dir(name);
+        std::fs::create_dir_all(&dir).unwrap();
+        for (file, content) in files {
+            std::fs::write(dir.join(file), content).unwrap();
+        }
+        installer.deploy(name, &dir).unwrap();
+        git::checksum_dir(&dir).unwrap()
+    }
+
+    #[test]
+    fn untouched_skill_verifies() {
+        let tmp = tempfile::tempdir().unwrap();
+        let options = options_with_claude();
+        let installer = SkillInstaller::new(tmp.path(), &options);
+        let checksum = deploy_copy(&installer, "demo", &[("SKILL.md", "hello")]);
+
+        let locked =
+            LockedSkill::http("demo", "https://example.com/SKILL.md").with_checksum(checksum);
+        let report = verify_skill(&installer, &locked);
+        assert!(report.is_ok(), "{report:?}");
+    }
+
+    #[test]
+    fn tampered_content_without_git_is_a_checksum_problem() {
+        let tmp = tempfile::tempdir().unwrap();
+        let options = ManifestOptions::default();
+        let installer = SkillInstaller::new(tmp.path(), &options);
+        let checksum = deploy_copy(&installer, "demo", &[("SKILL.md", "hello")]);
+        std::fs::write(installer.skill_dir("demo").join("SKILL.md"), "tampered").unwrap();
+
+        let locked = LockedSkill::git("demo", "https://example.com/r.git", "abc".into(), checksum);
+        let report = verify_skill(&installer, &locked);
+        assert_eq!(report.problems.len(), 1, "{report:?}");
+        assert!(report.problems[0].contains("does not match Ion.lock"));
+    }
+
+    #[test]
+    fn missing_deployment_and_target_link_are_reported() {
+        let tmp = tempfile::tempdir().unwrap();
+        let options = options_with_claude();
+        let installer = SkillInstaller::new(tmp.path(), &options);
+
+        let locked = LockedSkill::git(
+            "gone",
+            "https://example.com/r.git",
+            "abc".into(),
+            String::new(),
+        );
+        let report = verify_skill(&installer, &locked);
+        assert!(report.problems[0].starts_with("not deployed"));
+        assert!(report.problems[1].contains("target 'claude'"));
+    }
+
+    #[test]
+    fn target_pointing_elsewhere_is_reported() {
+        let tmp = tempfile::tempdir().unwrap();
+        let options = options_with_claude();
+        let installer = SkillInstaller::new(tmp.path(), &options);
+        deploy_copy(&installer, "demo", &[("SKILL.md", "hello")]);
+
+        let other = tmp.path().join("other");
+        std::fs::create_dir_all(&other).unwrap();
+        let link = tmp.path().join(".claude/skills/demo");
+        std::fs::remove_file(&link).unwrap();
+        crate::installer::create_skill_symlink(&other, &link).unwrap();
+
+        let report = verify_skill(&installer, &LockedSkill::local("demo"));
+        assert_eq!(report.problems.len(), 1, "{report:?}");
+        assert!(report.problems[0].contains("does not resolve to"));
+    }
+
+    #[test]
+    fn stale_target_copy_is_reported() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut options = options_with_claude();
+        options.deploy = Some(DeployMode::Copy);
+        let installer = SkillInstaller::new(tmp.path(), &options);
+        deploy_copy(&installer, "demo", &[("SKILL.md", "hello")]);
+        assert!(verify_skill(&installer, &LockedSkill::local("demo")).is_ok());
+
+        std::fs::write(tmp.path().join(".claude/skills/demo/SKILL.md"), "edited").unwrap();
+        let report = verify_skill(&installer, &LockedSkill::local("demo"));
+        assert_eq!(report.problems.len(), 1, "{report:?}");
+        assert!(report.problems[0].contains("copy"));
+    }
+
+    #[test]
+    fn rendered_target_files_are_checked() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut options = ManifestOptions::default();
+        options
+            .targets
+            .insert("cursor".to_string(), ".cursor/rules".to_string());
+        options
+            .target_format
+            .insert("cursor".to_string(), crate::render::TargetFormat::Cursor);
+        let installer = SkillInstaller::new(tmp.path(), &options);
+        deploy_copy(
+            &installer,
+            "demo",
+            &[(
+                "SKILL.md",
+                "---\nname: demo\ndescription: Demo.\n---\n\nBody.\n",
+            )],
+        );
+        assert!(verify_skill(&installer, &LockedSkill::local("demo")).is_ok());
+
+        let rule = tmp.path().join(".cursor/rules/demo.mdc");
+        std::fs::write(&rule, "edited").unwrap();
+        let report = verify_skill(&installer, &LockedSkill::local("demo"));
+        assert!(report.problems[0].contains("out of date"), "{report:?}");
+
+        std::fs::remove_file(&rule).unwrap();
+        let report = verify_skill(&installer, &LockedSkill::local("demo"));
+        assert!(report.problems[0].contains("is missing"), "{report:?}");
+    }
+}
@@ -0,0 +1,205 @@
+//! Semver version requirements for git skills — resolve `version = "^1.2"` against repository tags.
+//!
+//! Tags may be repo-wide (`v1.3.0`, `1.3.0`) or prefixed with the skill's
+//! directory name for repos that release skills independently
+//! (`brainstorming-v1.3.0`). When a repo has prefixed tags for a skill, only
+//! those are considered for it.
+
+use std::path::Path;
+
+use semver::{Version, VersionReq};
+
+use crate::{Error, Result, git};
+
+/// A tag selected for a version requirement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedTag {
+    pub tag: String,
+    pub version: Version,
+    pub commit: String,
+}
+
+/// Parse a version requirement from Ion.toml.
+pub fn parse_requirement(req: &str) -> Result<VersionReq> {
+    VersionReq::parse(req)
+        .map_err(|e| Error::Manifest(format!("Invalid version requirement '{req}': {e}")))
+}
+
+/// Parse the version out of `tag`. With `prefix` set, only tags of the form
+/// `{prefix}-v1.2.3` or `{prefix}-1.2.3` match; otherwise `v1.2.3` or `1.2.3`.
+pub fn tag_version(tag: &str, prefix: Option<&str>) -> Option<Version> {
+    let rest = match prefix {
+        Some(prefix) => tag.strip_prefix(prefix)?.strip_prefix('-')?,
+        None => tag,
+    };
+    let rest = rest.strip_prefix('v').unwrap_or(rest);
+    Version::parse(rest).ok()
+}
+
+/// The tag prefix for a skill at `skill_path` within its repo: the last path
+/// component, e.g. `brainstorming` for `skills/brainstorming`.
+fn skill_prefix(skill_path: Option<&str>) -> Option<&str> {
+    skill_path
+        .and_then(|p| Path::new(p).file_name())
+        .and_then(|n| n.to_str())
+}
+
+/// Pick the highest tag in `tags` that satisfies `req`.
+///
+/// Returns `Ok(None)` if no tag carries a version at all, so callers can
+/// fall back to the default branch. Fails if versioned tags exist but none
+/// satisfies `req`.
+pub fn select_tag(
+    tags: &[String],
+    req: &VersionReq,
+    skill_path: Option<&str>,
+) -> Result<Option<(String, Version)>> {
+    let versioned = |prefix: Option<&str>| -> Vec<(String, Version)> {
+        tags.iter()
+            .filter_map(|t| tag_version(t, prefix).map(|v| (t.clone(), v)))
+            .collect()
+    };
+
+    let mut candidates = skill_prefix(skill_path)
+        .map(|prefix| versioned(Some(prefix)))
+        .unwrap_or_default();
+    if candidates.is_empty() {
+        candidates = versioned(None);
+    }
+    if candidates.is_empty() {
+        return Ok(None);
+    }
+
+    candidates.sort_by(|a, b| a.1.cmp(&b.1));
+    match candidates.iter().rev().find(|(_, v)| req.matches(v)) {
+        Some(found) => Ok(Some(found.clone())),
+        None => Err(Error::Source(format!(
+            "No tag satisfies version '{req}' (available: {})",
+            candidates
+                .iter()
+                .map(|(t, _)| t.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))),
+    }
+}
+
+/// Resolve `req` against the tags of the clone at `repo_dir`.
+///
+/// Returns `Ok(None)` if the repo has no version tags for the skill.
+pub fn resolve(
+    repo_dir: &Path,
+    req: &str,
+    skill_path: Option<&str>,
+) -> Result<Option<ResolvedTag>> {
+    let req = parse_requirement(req)?;
+    let tags = git::tags(repo_dir)?;
+    let Some((tag, version)) = select_tag(&tags, &req, skill_path)? else {
+        return Ok(None);
+    };
+    let commit = git::resolve_commit(repo_dir, &tag)?;
+    Ok(Some(ResolvedTag {
+        tag,
+        version,
+        commit,
+    }))
+}
+
+/// Whether a locked `tag` still satisfies the requirement `req`.
+pub fn tag_satisfies(tag: &str, req: &str, skill_path: Option<&str>) -> bool {
+    let Ok(req) = parse_requirement(req) else {
+        return false;
+    };
+    tag_version(tag, skill_prefix(skill_path))
+        .or_else(|| tag_version(tag, None))
+        .is_some_and(|v| req.matches(&v))
+}
+
+/// Whether a SKILL.md `version` satisfies `req`. Falls back to an exact
+/// comparison when either side isn't valid semver.
+pub fn version_satisfies(actual: &str, req: &str) -> bool {
+    match (VersionReq::parse(req), Version::parse(actual)) {
+        (Ok(req), Ok(actual)) => req.matches(&actual),
+        _ => actual == req,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(names: &[&str]) -> Vec<String> {
+        names.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn tag_version_parses_plain_and_prefixed_tags() {
+        assert_eq!(tag_version("v1.2.3", None), Some(Version::new(1, 2, 3)));
+        assert_eq!(tag_version("1.2.3", None), Some(Version::new(1, 2, 3)));
+        assert_eq!(
+            tag_version("brainstorming-v1.3.0", Some("brainstorming")),
+            Some(Version::new(1, 3, 0))
+        );
+        assert_eq!(tag_version("brainstorming-v1.3.0", None), None);
+        assert_eq!(tag_version("other-v1.3.0", Some("brainstorming")), None);
+        assert_eq!(tag_version("release", None), None);
+    }
+
+    #[test]
+    fn select_tag_picks_highest_match() {
+        let req = parse_requirement("^1.2").unwrap();
+        let all = tags(&["v1.1.0", "v1.2.0", "v1.4.1", "v2.0.0", "nightly"]);
+        let (tag, version) = select_tag(&all, &req, None).unwrap().unwrap();
+        assert_eq!(tag, "v1.4.1");
+        assert_eq!(version, Version::new(1, 4, 1));
+    }
+
+    #[test]
+    fn select_tag_prefers_skill_prefixed_tags() {
+        let req = parse_requirement("^1").unwrap();
+        let all = tags(&["v1.9.0", "brainstorming-v1.3.0", "review-v1.5.0"]);
+        let (tag, _) = select_tag(&all, &req, Some("skills/brainstorming"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(tag, "brainstorming-v1.3.0");
+
+        // Skills without their own tags use the repo-wide ones
+        let (tag, _) = select_tag(&all, &req, Some("skills/other"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(tag, "v1.9.0");
+    }
+
+    #[test]
+    fn select_tag_without_version_tags_is_none() {
+        let req = parse_requirement("^1").unwrap();
+        assert!(
+            select_tag(&tags(&["latest"]), &req, None)
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn select_tag_with_no_match_errors() {
+        let req = parse_requirement("^3").unwrap();
+        let err = select_tag(&tags(&["v1.0.0", "v2.0.0"]), &req, None).unwrap_err();
+        assert!(err.to_string().contains("v1.0.0, v2.0.0"), "got: {err}");
+    }
+
+    #[test]
+    fn invalid_requirement_errors() {
+        assert!(parse_requirement("not a version").is_err());
+    }
+
+    #[test]
+    fn tag_satisfies_checks_range() {
+        assert!(tag_satisfies("v1.4.0", "^1.2", None));
+        assert!(!tag_satisfies("v2.0.0", "^1.2", None));
+        assert!(tag_satisfies(
+            "brainstorming-v1.3.0",
+            "~1.3",
+            Some("brainstorming")
+        ));
+    }
+}
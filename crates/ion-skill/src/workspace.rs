@@ -1,6 +1,6 @@
 //! Project workspace context — load manifest and lockfile, resolve effective options and skill paths for a project.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::lockfile::Lockfile;
 use crate::manifest::{Manifest, ManifestOptions};
@@ -11,6 +11,8 @@ pub struct Project {
     pub dir: PathBuf,
     pub manifest_path: PathBuf,
     pub lockfile_path: PathBuf,
+    /// Whether this is the user-global scope rather than a project.
+    pub global: bool,
 }
 
 impl Project {
@@ -21,9 +23,33 @@ impl Project {
             dir,
             manifest_path,
             lockfile_path,
+            global: false,
         }
     }
 
+    /// The user-global scope: Ion.toml and Ion.lock live in Ion's config
+    /// directory, and skills deploy relative to the home directory, so the
+    /// `claude` target `.claude/skills` means `~/.claude/skills`.
+    pub fn global() -> crate::Result<Self> {
+        let home = dirs::home_dir()
+            .ok_or_else(|| crate::Error::Other("cannot determine home directory".to_string()))?;
+        let config_dir = crate::config::GlobalConfig::config_path()
+            .and_then(|path| path.parent().map(Path::to_path_buf))
+            .ok_or_else(|| crate::Error::Other("cannot determine config directory".to_string()))?;
+        Ok(Self {
+            dir: home,
+            manifest_path: config_dir.join("Ion.toml"),
+            lockfile_path: config_dir.join("Ion.lock"),
+            global: true,
+        })
+    }
+
+    /// Directory holding Ion.toml and Ion.lock: the project directory, or
+    /// the config directory for the global scope.
+    pub fn manifest_dir(&self) -> &Path {
+        self.manifest_path.parent().unwrap_or(&self.dir)
+    }
+
     pub fn has_manifest(&self) -> bool {
         self.manifest_path.exists()
     }
@@ -44,17 +70,46 @@ impl Project {
         Lockfile::from_file(&self.lockfile_path)
     }
 
+    /// Skills this project declares that `global` declares too. The
+    /// project's copy wins, hiding the global one.
+    pub fn shadowed_skills(&self, global: &Project) -> crate::Result<Vec<String>> {
+        if self.global || !global.has_manifest() {
+            return Ok(Vec::new());
+        }
+        let global_manifest = global.manifest()?;
+        Ok(self
+            .manifest_or_empty()?
+            .skills
+            .into_keys()
+            .filter(|name| global_manifest.skills.contains_key(name))
+            .collect())
+    }
+
     /// Compute effective options by merging inherited options with this project's local options.
     /// `inherited` comes from the workspace root; local options override inherited ones.
     pub fn effective_options(&self, inherited: &ManifestOptions) -> crate::Result<ManifestOptions> {
         let local = self.manifest_or_empty()?.options;
+        // Skill selection belongs to the targets it was declared with
+        let (targets, target_filters) = if local.targets.is_empty() {
+            (inherited.targets.clone(), inherited.target_filters.clone())
+        } else {
+            (local.targets, local.target_filters)
+        };
         Ok(ManifestOptions {
-            targets: if local.targets.is_empty() {
-                inherited.targets.clone()
+            targets,
+            target_filters,
+            skills_dir: local.skills_dir.or_else(|| inherited.skills_dir.clone()),
+            deploy: local.deploy.or(inherited.deploy),
+            target_deploy: if local.target_deploy.is_empty() {
+                inherited.target_deploy.clone()
             } else {
-                local.targets
+                local.target_deploy
+            },
+            target_format: if local.target_format.is_empty() {
+                inherited.target_format.clone()
+            } else {
+                local.target_format
             },
-            skills_dir: local.skills_dir.or_else(|| inherited.skills_dir.clone()),
         })
     }
 }
@@ -78,4 +133,30 @@ mod tests {
         let project = Project::new(dir);
         assert!(!project.has_manifest());
     }
+
+    #[test]
+    fn shadowed_skills_are_declared_in_both() {
+        let dir = tempfile::tempdir().unwrap();
+        let project_dir = dir.path().join("project");
+        let global_dir = dir.path().join("global");
+        std::fs::create_dir_all(&project_dir).unwrap();
+        std::fs::create_dir_all(&global_dir).unwrap();
+        std::fs::write(
+            project_dir.join("Ion.toml"),
+            "[skills]\nbrainstorming = \"obra/superpowers/brainstorming\"\nlocal = { type = \"local\" }\n",
+        )
+        .unwrap();
+        std::fs::write(
+            global_dir.join("Ion.toml"),
+            "[skills]\nbrainstorming = \"obra/superpowers/brainstorming\"\n",
+        )
+        .unwrap();
+
+        let project = Project::new(project_dir);
+        let mut global = Project::new(global_dir);
+        assert_eq!(project.shadowed_skills(&global).unwrap(), ["brainstorming"]);
+
+        global.global = true;
+        assert!(global.shadowed_skills(&global).unwrap().is_empty());
+    }
 }
@@ -99,16 +99,23 @@ pub struct GitHubAsset {
     pub browser_download_url: String,
 }
 
-pub fn fetch_github_release(repo: &str, tag: Option<&str>) -> Result<GitHubRelease> {
+/// Fetch a release (the latest if `tag` is `None`). `token` is sent as a
+/// bearer token, for private repositories.
+pub fn fetch_github_release(
+    repo: &str,
+    tag: Option<&str>,
+    token: Option<&str>,
+) -> Result<GitHubRelease> {
     let url = match tag {
         Some(t) => format!("https://api.github.com/repos/{}/releases/tags/{}", repo, t),
         None => format!("https://api.github.com/repos/{}/releases/latest", repo),
     };
     let client = reqwest::blocking::Client::new();
-    let resp = client
+    let request = client
         .get(&url)
         .header("User-Agent", "ion-skill-manager")
-        .header("Accept", "application/vnd.github+json")
+        .header("Accept", "application/vnd.github+json");
+    let resp = with_token(request, token)
         .send()
         .map_err(|e| Error::Http(format!("Failed to fetch release: {}", e)))?;
     if !resp.status().is_success() {
@@ -125,13 +132,18 @@ pub fn fetch_github_release(repo: &str, tag: Option<&str>) -> Result<GitHubRelea
 ///
 /// Useful when a repo has multiple crates releasing independently
 /// (e.g. `ion-v*` vs `ion-skill-v*`).
-pub fn fetch_latest_release_by_tag_prefix(repo: &str, prefix: &str) -> Result<GitHubRelease> {
+pub fn fetch_latest_release_by_tag_prefix(
+    repo: &str,
+    prefix: &str,
+    token: Option<&str>,
+) -> Result<GitHubRelease> {
     let url = format!("https://api.github.com/repos/{}/releases?per_page=10", repo);
     let client = reqwest::blocking::Client::new();
-    let resp = client
+    let request = client
         .get(&url)
         .header("User-Agent", "ion-skill-manager")
-        .header("Accept", "application/vnd.github+json")
+        .header("Accept", "application/vnd.github+json");
+    let resp = with_token(request, token)
         .send()
         .map_err(|e| Error::Http(format!("Failed to fetch releases: {}", e)))?;
     if !resp.status().is_success() {
@@ -158,12 +170,11 @@ pub fn parse_version_from_tag(tag: &str) -> &str {
     }
 }
 
-/// Download a file from URL to a local path.
-pub fn download_file(url: &str, dest: &Path) -> Result<()> {
+/// Download a file from URL to a local path, sending `token` as a bearer token.
+pub fn download_file(url: &str, dest: &Path, token: Option<&str>) -> Result<()> {
     let client = reqwest::blocking::Client::new();
-    let resp = client
-        .get(url)
-        .header("User-Agent", "ion-skill-manager")
+    let request = client.get(url).header("User-Agent", "ion-skill-manager");
+    let resp = with_token(request, token)
         .send()
         .map_err(|e| Error::Http(format!("Download failed: {}", e)))?;
     if !resp.status().is_success() {
@@ -179,6 +190,16 @@ pub fn download_file(url: &str, dest: &Path) -> Result<()> {
     Ok(())
 }
 
+fn with_token(
+    request: reqwest::blocking::RequestBuilder,
+    token: Option<&str>,
+) -> reqwest::blocking::RequestBuilder {
+    match token {
+        Some(token) => request.bearer_auth(token),
+        None => request,
+    }
+}
+
 /// Extract a .tar.gz archive to a destination directory.
 pub fn extract_tar_gz(archive_path: &Path, dest_dir: &Path) -> Result<Vec<PathBuf>> {
     let file = fs::File::open(archive_path)?;
@@ -122,7 +122,7 @@ impl SelfManager {
 
     /// Check whether a newer version is available on GitHub Releases.
     pub fn check(&self) -> Result<CheckResult> {
-        let rel = release::fetch_latest_release_by_tag_prefix(&self.repo, &self.tag_prefix)?;
+        let rel = release::fetch_latest_release_by_tag_prefix(&self.repo, &self.tag_prefix, None)?;
         let latest = release::parse_version_from_tag(&rel.tag_name).to_string();
         let update_available = is_newer_version(&self.current_version, &latest);
 
@@ -160,9 +160,11 @@ impl SelfManager {
             Some(v) => {
                 let ver = v.strip_prefix('v').unwrap_or(v);
                 let tag = format!("{}{}", self.tag_prefix, ver);
-                release::fetch_github_release(&self.repo, Some(&tag))?
+                release::fetch_github_release(&self.repo, Some(&tag), None)?
+            }
+            None => {
+                release::fetch_latest_release_by_tag_prefix(&self.repo, &self.tag_prefix, None)?
             }
-            None => release::fetch_latest_release_by_tag_prefix(&self.repo, &self.tag_prefix)?,
         };
         let latest = release::parse_version_from_tag(&rel.tag_name).to_string();
 
@@ -196,7 +198,7 @@ impl SelfManager {
 
         let tmp_dir = tempfile::tempdir()?;
         let archive_path = tmp_dir.path().join(&asset_name);
-        release::download_file(&asset.browser_download_url, &archive_path)?;
+        release::download_file(&asset.browser_download_url, &archive_path, None)?;
 
         let extract_dir = tmp_dir.path().join("extracted");
         release::extract_tar_gz(&archive_path, &extract_dir)?;
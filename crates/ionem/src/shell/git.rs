@@ -12,7 +12,11 @@
 //! }
 //! ```
 
+use std::collections::BTreeMap;
 use std::path::Path;
+use std::process::Command;
+use std::sync::RwLock;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use super::{Cli, CliError, Result};
 
@@ -22,6 +26,53 @@ pub const CLI: Cli = Cli {
     hint: "Install from https://git-scm.com",
 };
 
+static NETWORK_BLOCKED: AtomicBool = AtomicBool::new(false);
+
+/// Block (or allow again) network access for every git command run from
+/// here on, including the on-demand downloads of partial clones.
+pub fn set_network_blocked(blocked: bool) {
+    NETWORK_BLOCKED.store(blocked, Ordering::Relaxed);
+}
+
+static HTTP_HEADERS: RwLock<BTreeMap<String, String>> = RwLock::new(BTreeMap::new());
+
+/// Send `header` with every HTTP request git makes to URLs under `url`, for
+/// every git command run from here on.
+///
+/// The header is passed through the environment (`GIT_CONFIG_COUNT`), so it
+/// never shows up in process listings or in the repository's config.
+pub fn set_http_header(url: &str, header: &str) {
+    HTTP_HEADERS
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(url.to_string(), header.to_string());
+}
+
+fn command() -> Command {
+    let mut cmd = CLI.command();
+    if NETWORK_BLOCKED.load(Ordering::Relaxed) {
+        // Allow no transport at all, so lazy fetches fail instead of connecting
+        cmd.env("GIT_ALLOW_PROTOCOL", "none");
+    }
+    let headers = HTTP_HEADERS.read().unwrap_or_else(|e| e.into_inner());
+    if !headers.is_empty() {
+        // Append to any config the caller already passes this way
+        let base: usize = std::env::var("GIT_CONFIG_COUNT")
+            .ok()
+            .and_then(|count| count.parse().ok())
+            .unwrap_or(0);
+        for (i, (url, header)) in headers.iter().enumerate() {
+            cmd.env(
+                format!("GIT_CONFIG_KEY_{}", base + i),
+                format!("http.{url}.extraHeader"),
+            );
+            cmd.env(format!("GIT_CONFIG_VALUE_{}", base + i), header);
+        }
+        cmd.env("GIT_CONFIG_COUNT", (base + headers.len()).to_string());
+    }
+    cmd
+}
+
 /// Verify `git` is installed and return a handle to run commands.
 pub fn require() -> Result<Git> {
     CLI.require()?;
@@ -55,8 +106,8 @@ impl Git {
 pub fn clone_or_fetch(url: &str, target: &Path) -> Result<()> {
     if target.join(".git").exists() {
         CLI.run_status(
-            CLI.command()
-                .args(["fetch", "--all"])
+            command()
+                .args(["fetch", "--all", "--tags", "--force"])
                 .current_dir(target)
                 .stdout(std::process::Stdio::null())
                 .stderr(std::process::Stdio::null()),
@@ -70,7 +121,7 @@ pub fn clone_or_fetch(url: &str, target: &Path) -> Result<()> {
         }
 
         CLI.run_status(
-            CLI.command()
+            command()
                 .args(["clone", url, &target.display().to_string()])
                 .stdout(std::process::Stdio::null())
                 .stderr(std::process::Stdio::null()),
@@ -78,10 +129,38 @@ pub fn clone_or_fetch(url: &str, target: &Path) -> Result<()> {
     }
 }
 
+/// Like [`clone_or_fetch`], but new clones are partial and sparse: they skip
+/// file contents (`--filter=blob:none`) and check out only top-level files.
+/// Contents are downloaded on demand as they are checked out. Servers without
+/// partial clone support fall back to a regular clone.
+pub fn partial_clone_or_fetch(url: &str, target: &Path) -> Result<()> {
+    if target.join(".git").exists() {
+        return clone_or_fetch(url, target);
+    }
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| CliError::Spawn {
+            cli: CLI.name.to_string(),
+            source: e,
+        })?;
+    }
+    CLI.run_status(
+        command()
+            .args([
+                "clone",
+                "--filter=blob:none",
+                "--sparse",
+                url,
+                &target.display().to_string(),
+            ])
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null()),
+    )
+}
+
 /// Checkout a specific ref (branch, tag, or commit SHA).
 pub fn checkout(repo: &Path, rev: &str) -> Result<()> {
     CLI.run_status(
-        CLI.command()
+        command()
             .args(["checkout", rev])
             .current_dir(repo)
             .stdout(std::process::Stdio::null())
@@ -89,9 +168,25 @@ pub fn checkout(repo: &Path, rev: &str) -> Result<()> {
     )
 }
 
+/// List all tag names in a repository.
+pub fn tag_list(repo: &Path) -> Result<Vec<String>> {
+    let output = CLI.run_command(command().args(["tag", "--list"]).current_dir(repo))?;
+    Ok(output.lines().map(str::to_string).collect())
+}
+
+/// List tag names matching a glob `pattern`, highest version first.
+pub fn tag_list_matching(repo: &Path, pattern: &str) -> Result<Vec<String>> {
+    let output = CLI.run_command(
+        command()
+            .args(["tag", "--list", "--sort=-v:refname", pattern])
+            .current_dir(repo),
+    )?;
+    Ok(output.lines().map(str::to_string).collect())
+}
+
 /// Get the current HEAD commit SHA.
 pub fn head_commit(repo: &Path) -> Result<String> {
-    CLI.run_command(CLI.command().args(["rev-parse", "HEAD"]).current_dir(repo))
+    CLI.run_command(command().args(["rev-parse", "HEAD"]).current_dir(repo))
 }
 
 /// Get the default branch name for a repo by checking `origin/HEAD` or falling back
@@ -99,7 +194,7 @@ pub fn head_commit(repo: &Path) -> Result<String> {
 pub fn default_branch(repo: &Path) -> Result<String> {
     // Try origin/HEAD first (works for cloned repos)
     let origin_result = CLI.run_command(
-        CLI.command()
+        command()
             .args(["symbolic-ref", "refs/remotes/origin/HEAD"])
             .current_dir(repo),
     );
@@ -112,7 +207,7 @@ pub fn default_branch(repo: &Path) -> Result<String> {
 
     // Fallback: local HEAD's branch name
     let local_result = CLI.run_command(
-        CLI.command()
+        command()
             .args(["symbolic-ref", "--short", "HEAD"])
             .current_dir(repo),
     );
@@ -134,7 +229,7 @@ pub fn reset_to_remote_head(repo: &Path) -> Result<()> {
     let remote_ref = format!("origin/{branch}");
 
     CLI.run_status(
-        CLI.command()
+        command()
             .args(["reset", "--hard", &remote_ref])
             .current_dir(repo)
             .stdout(std::process::Stdio::null())
@@ -142,9 +237,165 @@ pub fn reset_to_remote_head(repo: &Path) -> Result<()> {
     )
 }
 
+/// Resolve a ref (branch, tag, or commit SHA) to a full commit SHA.
+pub fn rev_parse(repo: &Path, rev: &str) -> Result<String> {
+    CLI.run_command(
+        command()
+            .args([
+                "rev-parse",
+                "--verify",
+                "--quiet",
+                &format!("{rev}^{{commit}}"),
+            ])
+            .current_dir(repo),
+    )
+}
+
+/// Check out `commit` into a new detached worktree at `path`.
+pub fn worktree_add_detached(repo: &Path, path: &Path, commit: &str) -> Result<()> {
+    CLI.run_status(
+        command()
+            .args([
+                "worktree",
+                "add",
+                "--detach",
+                &path.display().to_string(),
+                commit,
+            ])
+            .current_dir(repo)
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null()),
+    )
+}
+
+/// Forget worktrees whose directories have been deleted.
+pub fn worktree_prune(repo: &Path) -> Result<()> {
+    CLI.run_status(
+        command()
+            .args(["worktree", "prune"])
+            .current_dir(repo)
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null()),
+    )
+}
+
+/// Reconnect worktrees at `paths` after the repository or the worktrees moved.
+pub fn worktree_repair(repo: &Path, paths: &[&Path]) -> Result<()> {
+    let mut cmd = command();
+    cmd.args(["worktree", "repair"])
+        .current_dir(repo)
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null());
+    for path in paths {
+        cmd.arg(path);
+    }
+    CLI.run_status(&mut cmd)
+}
+
+/// The directories a cone-mode sparse checkout includes, or `None` if the
+/// worktree at `dir` is not sparse.
+pub fn sparse_checkout_list(dir: &Path) -> Result<Option<Vec<String>>> {
+    let sparse = CLI
+        .run_command(
+            command()
+                .args(["config", "--bool", "core.sparseCheckout"])
+                .current_dir(dir),
+        )
+        .is_ok_and(|value| value == "true");
+    if !sparse {
+        return Ok(None);
+    }
+    let output = CLI.run_command(command().args(["sparse-checkout", "list"]).current_dir(dir))?;
+    Ok(Some(output.lines().map(str::to_string).collect()))
+}
+
+/// Add directories to the worktree's cone-mode sparse checkout.
+pub fn sparse_checkout_add(dir: &Path, dirs: &[&str]) -> Result<()> {
+    CLI.run_status(
+        command()
+            .args(["sparse-checkout", "add"])
+            .args(dirs)
+            .current_dir(dir)
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null()),
+    )
+}
+
+/// Restrict the worktree to top-level files plus `dirs` (cone mode).
+pub fn sparse_checkout_set(dir: &Path, dirs: &[&str]) -> Result<()> {
+    CLI.run_status(
+        command()
+            .args(["sparse-checkout", "set", "--cone"])
+            .args(dirs)
+            .current_dir(dir)
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null()),
+    )
+}
+
+/// Turn off sparse checkout, checking out every file.
+pub fn sparse_checkout_disable(dir: &Path) -> Result<()> {
+    CLI.run_status(
+        command()
+            .args(["sparse-checkout", "disable"])
+            .current_dir(dir)
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null()),
+    )
+}
+
+/// Count the objects reachable from any ref, and how many of them a partial
+/// clone has not downloaded, as `(missing, total)`.
+pub fn object_counts(repo: &Path) -> Result<(usize, usize)> {
+    let output = CLI.run_command(
+        command()
+            .args(["rev-list", "--objects", "--all", "--missing=print"])
+            .current_dir(repo),
+    )?;
+    let total = output.lines().count();
+    let missing = output.lines().filter(|line| line.starts_with('?')).count();
+    Ok((missing, total))
+}
+
+/// List working-tree changes under `dir`, including untracked and ignored
+/// files, as `(status, path)` pairs from `git status --porcelain`.
+///
+/// `status` is the two-letter porcelain code (e.g. ` M`, ` D`, `??`, `!!`);
+/// `path` is relative to `dir`.
+pub fn status_entries(dir: &Path) -> Result<Vec<(String, String)>> {
+    let prefix = CLI.run_command(
+        command()
+            .args(["rev-parse", "--show-prefix"])
+            .current_dir(dir),
+    )?;
+    let output = CLI.run_command(
+        command()
+            .args([
+                "status",
+                "--porcelain",
+                "-z",
+                "--no-renames",
+                "--untracked-files=all",
+                "--ignored",
+                "--",
+                ".",
+            ])
+            .current_dir(dir),
+    )?;
+    Ok(output
+        .split('\0')
+        .filter(|entry| entry.len() > 3)
+        .map(|entry| {
+            let (code, path) = entry.split_at(2);
+            let path = path[1..].strip_prefix(&prefix).unwrap_or(&path[1..]);
+            (code.to_string(), path.to_string())
+        })
+        .collect())
+}
+
 /// Stage files in a git repository.
 pub fn stage_files(repo: &Path, files: &[&str]) -> Result<()> {
-    let mut cmd = CLI.command();
+    let mut cmd = command();
     cmd.arg("add")
         .current_dir(repo)
         .stdout(std::process::Stdio::null())
@@ -161,7 +412,7 @@ pub fn stage_files(repo: &Path, files: &[&str]) -> Result<()> {
 /// `git diff --cached --quiet` exits with code 1 when there are changes.
 pub fn has_staged_changes(repo: &Path) -> Result<bool> {
     let result = CLI.run_status(
-        CLI.command()
+        command()
             .args(["diff", "--cached", "--quiet"])
             .current_dir(repo)
             .stdout(std::process::Stdio::null())
@@ -178,7 +429,7 @@ pub fn has_staged_changes(repo: &Path) -> Result<bool> {
 /// Create a commit with the given message and return the new HEAD commit SHA.
 pub fn create_commit(repo: &Path, message: &str) -> Result<String> {
     CLI.run_status(
-        CLI.command()
+        command()
             .args(["commit", "-m", message])
             .current_dir(repo)
             .stdout(std::process::Stdio::null())
@@ -191,7 +442,7 @@ pub fn create_commit(repo: &Path, message: &str) -> Result<String> {
 /// Initialize a new git repository at the given path.
 pub fn init(path: &Path) -> Result<()> {
     CLI.run_status(
-        CLI.command()
+        command()
             .args(["init", &path.display().to_string()])
             .stdout(std::process::Stdio::null())
             .stderr(std::process::Stdio::null()),
@@ -234,6 +485,41 @@ impl<'a> Repo<'a> {
         reset_to_remote_head(self.path)
     }
 
+    /// Resolve a ref to a full commit SHA.
+    pub fn rev_parse(&self, rev: &str) -> Result<String> {
+        rev_parse(self.path, rev)
+    }
+
+    /// Check out `commit` into a new detached worktree at `path`.
+    pub fn worktree_add_detached(&self, path: &Path, commit: &str) -> Result<()> {
+        worktree_add_detached(self.path, path, commit)
+    }
+
+    /// Forget worktrees whose directories have been deleted.
+    pub fn worktree_prune(&self) -> Result<()> {
+        worktree_prune(self.path)
+    }
+
+    /// List all tag names.
+    pub fn tag_list(&self) -> Result<Vec<String>> {
+        tag_list(self.path)
+    }
+
+    /// List tag names matching a glob pattern, highest version first.
+    pub fn tag_list_matching(&self, pattern: &str) -> Result<Vec<String>> {
+        tag_list_matching(self.path, pattern)
+    }
+
+    /// List working-tree changes under this directory.
+    pub fn status_entries(&self) -> Result<Vec<(String, String)>> {
+        status_entries(self.path)
+    }
+
+    /// Reconnect worktrees at `paths` after the repository or the worktrees moved.
+    pub fn worktree_repair(&self, paths: &[&Path]) -> Result<()> {
+        worktree_repair(self.path, paths)
+    }
+
     /// Stage files.
     pub fn stage_files(&self, files: &[&str]) -> Result<()> {
         stage_files(self.path, files)
@@ -249,10 +535,35 @@ impl<'a> Repo<'a> {
         create_commit(self.path, message)
     }
 
+    /// The directories a sparse checkout includes, or `None` if not sparse.
+    pub fn sparse_checkout_list(&self) -> Result<Option<Vec<String>>> {
+        sparse_checkout_list(self.path)
+    }
+
+    /// Add directories to the sparse checkout.
+    pub fn sparse_checkout_add(&self, dirs: &[&str]) -> Result<()> {
+        sparse_checkout_add(self.path, dirs)
+    }
+
+    /// Restrict the worktree to top-level files plus `dirs`.
+    pub fn sparse_checkout_set(&self, dirs: &[&str]) -> Result<()> {
+        sparse_checkout_set(self.path, dirs)
+    }
+
+    /// Turn off sparse checkout.
+    pub fn sparse_checkout_disable(&self) -> Result<()> {
+        sparse_checkout_disable(self.path)
+    }
+
+    /// Count reachable objects and those a partial clone hasn't downloaded.
+    pub fn object_counts(&self) -> Result<(usize, usize)> {
+        object_counts(self.path)
+    }
+
     /// Fetch updates from all remotes (requires an existing clone).
     pub fn fetch_all(&self) -> Result<()> {
         CLI.run_status(
-            CLI.command()
+            command()
                 .args(["fetch", "--all"])
                 .current_dir(self.path)
                 .stdout(std::process::Stdio::null())
@@ -7,7 +7,7 @@ use ion_skill::source::SkillSource;
 
 use crate::commands::install_shared::{
     FinalizeOptions, ValidationBuckets, finalize_skill_install, finalize_skill_install_and_write,
-    install_approved_skills, register_in_registry,
+    install_approved_skills, install_dependencies, register_in_registry,
 };
 use crate::commands::validation::{
     confirm_proceed_with_collection, print_validation_summary, select_warned_skills,
@@ -26,9 +26,10 @@ pub fn run(
     json: bool,
     allow_warnings: bool,
     skills_filter: Option<&str>,
+    global: bool,
     project_flags: &[String],
 ) -> anyhow::Result<()> {
-    let ws = WorkspaceContext::load(project_flags)?;
+    let ws = WorkspaceContext::load_scoped(project_flags, global)?;
     let project = ws.single_project()?;
     let p = ws.paint();
 
@@ -115,7 +116,17 @@ pub fn run(
         }
         let installer = ws.installer_for(project, &merged_options);
         let locked = installer.install(&name, &source)?;
-        return finish_single_install(project, &p, &merged_options, &name, &source, locked, json);
+        return finish_single_install(
+            &ws,
+            project,
+            &p,
+            &merged_options,
+            &name,
+            &source,
+            locked,
+            json,
+            allow_warnings,
+        );
     }
 
     // If the source has no path (i.e. points to a whole repo), check if it's
@@ -137,6 +148,7 @@ pub fn run(
         match installer.install(&name, &source) {
             Ok(locked) => {
                 return finish_single_install(
+                    &ws,
                     project,
                     &p,
                     &merged_options,
@@ -144,6 +156,7 @@ pub fn run(
                     &source,
                     locked,
                     json,
+                    allow_warnings,
                 );
             }
             Err(SkillError::ValidationWarning { report, .. }) => {
@@ -163,6 +176,7 @@ pub fn run(
                     },
                 )?;
                 return finish_single_install(
+                    &ws,
                     project,
                     &p,
                     &merged_options,
@@ -170,6 +184,7 @@ pub fn run(
                     &source,
                     locked,
                     json,
+                    allow_warnings,
                 );
             }
             Err(SkillError::InvalidSkill(msg)) if msg.contains("No SKILL.md found") => {
@@ -202,6 +217,7 @@ pub fn run(
                         let installer = ws.installer_for(project, &merged_options);
                         let locked = installer.install(&bin_name, &bin_source)?;
                         return finish_single_install(
+                            &ws,
                             project,
                             &p,
                             &merged_options,
@@ -209,12 +225,14 @@ pub fn run(
                             &bin_source,
                             locked,
                             json,
+                            allow_warnings,
                         );
                     }
                 }
 
                 // Not a single-skill repo — try as a multi-skill collection
                 return install_collection(
+                    &ws,
                     project,
                     &p,
                     &merged_options,
@@ -251,11 +269,22 @@ pub fn run(
         &p,
     )?;
 
-    finish_single_install(project, &p, &merged_options, &name, &source, locked, json)
+    finish_single_install(
+        &ws,
+        project,
+        &p,
+        &merged_options,
+        &name,
+        &source,
+        locked,
+        json,
+        allow_warnings,
+    )
 }
 
 #[allow(clippy::too_many_arguments)]
 fn install_collection(
+    ws: &WorkspaceContext,
     project: &Project,
     p: &Paint,
     merged_options: &ion_skill::manifest::ManifestOptions,
@@ -288,7 +317,7 @@ fn install_collection(
     }
 
     // Phase 1: Validate all skills upfront
-    let installer = ion_skill::installer::SkillInstaller::new(&project.dir, merged_options);
+    let installer = ws.installer_for(project, merged_options);
 
     if !json {
         println!("Validating skills...");
@@ -405,7 +434,7 @@ fn install_collection(
                     "    Installed to {}",
                     p.info(&format!(".agents/skills/{name}/"))
                 );
-                for target_name in merged_options.targets.keys() {
+                for target_name in merged_options.targets_for(name) {
                     println!("    Linked to {}", p.info(target_name));
                 }
             }
@@ -428,10 +457,39 @@ fn install_collection(
         }
     }
 
+    let roots: Vec<String> = buckets
+        .clean
+        .iter()
+        .map(|e| e.name.clone())
+        .chain(
+            buckets
+                .warned
+                .iter()
+                .zip(&warned_selections)
+                .filter(|(_, selected)| **selected)
+                .map(|((e, _), _)| e.name.clone()),
+        )
+        .collect();
+    let dependencies = install_dependencies(
+        &installer,
+        project,
+        merged_options,
+        &roots,
+        &mut lockfile,
+        InstallValidationOptions {
+            skip_validation: false,
+            allow_warnings,
+        },
+        false,
+        p,
+        json,
+    )?;
+
     // Register in global registry (once for the base source)
-    register_in_registry(base_source, &project.dir)?;
+    register_in_registry(base_source, project.manifest_dir())?;
 
     lockfile.write_to(&project.lockfile_path)?;
+    let pruned = crate::commands::prune::prune_project(ws, project, merged_options)?;
 
     if json {
         // Collect names of installed skills
@@ -457,6 +515,8 @@ fn install_collection(
         crate::json::print_success(serde_json::json!({
             "installed": installed_names,
             "skipped": buckets.errored.iter().map(|(n, _)| n.as_str()).collect::<Vec<_>>(),
+            "dependencies": dependencies,
+            "pruned": pruned,
         }));
         return Ok(());
     }
@@ -465,6 +525,7 @@ fn install_collection(
         println!("  Updated {}", p.dim("Ion.toml"));
         println!("  Updated {}", p.dim("Ion.lock"));
     }
+    crate::commands::prune::print_pruned(&pruned, p);
     println!(
         "{}",
         p.success(&format!(
@@ -477,7 +538,9 @@ fn install_collection(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 fn finish_single_install(
+    ws: &WorkspaceContext,
     project: &Project,
     p: &Paint,
     merged_options: &ion_skill::manifest::ManifestOptions,
@@ -485,6 +548,7 @@ fn finish_single_install(
     source: &SkillSource,
     locked: ion_skill::lockfile::LockedSkill,
     json: bool,
+    allow_warnings: bool,
 ) -> anyhow::Result<()> {
     finalize_skill_install_and_write(
         project,
@@ -495,29 +559,66 @@ fn finish_single_install(
         &FinalizeOptions::ADD,
     )?;
 
+    if !json {
+        println!(
+            "  Installed to {}",
+            p.info(&format!(".agents/skills/{name}/"))
+        );
+        for target_name in merged_options.targets_for(name) {
+            println!("  Linked to {}", p.info(target_name));
+        }
+        if crate::context::shadowed_global_skills(project)
+            .iter()
+            .any(|s| s == name)
+        {
+            println!(
+                "  {}",
+                p.dim(&format!(
+                    "Shadows the global skill '{name}' in this project"
+                ))
+            );
+        }
+    }
+
+    let installer = ws.installer_for(project, merged_options);
+    let mut lockfile = project.lockfile()?;
+    let dependencies = install_dependencies(
+        &installer,
+        project,
+        merged_options,
+        &[name.to_string()],
+        &mut lockfile,
+        InstallValidationOptions {
+            skip_validation: false,
+            allow_warnings,
+        },
+        false,
+        p,
+        json,
+    )?;
+    if !dependencies.is_empty() {
+        lockfile.write_to(&project.lockfile_path)?;
+    }
+    let pruned = crate::commands::prune::prune_project(ws, project, merged_options)?;
+
     if json {
         crate::json::print_success(serde_json::json!({
             "name": name,
             "installed_to": format!(".agents/skills/{name}/"),
-            "targets": merged_options.targets.keys().collect::<Vec<_>>(),
+            "targets": merged_options.targets_for(name),
+            "dependencies": dependencies,
+            "pruned": pruned,
         }));
         return Ok(());
     }
 
-    println!(
-        "  Installed to {}",
-        p.info(&format!(".agents/skills/{name}/"))
-    );
-    for target_name in merged_options.targets.keys() {
-        println!("  Linked to {}", p.info(target_name));
-    }
-
-    if !source.is_path() {
+    if !source.is_path() && !project.global {
         println!("  Updated {}", p.dim(".gitignore"));
     }
 
     println!("  Updated {}", p.dim("Ion.toml"));
     println!("  Updated {}", p.dim("Ion.lock"));
+    crate::commands::prune::print_pruned(&pruned, p);
 
     println!("{}", p.success("Done!"));
     prompt_github_star(source, json);
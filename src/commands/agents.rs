@@ -2,6 +2,7 @@ use std::io::{self, Write};
 
 use crate::context::WorkspaceContext;
 use crate::style::Paint;
+use ion_skill::catalog::Catalog;
 use ion_skill::config::GlobalConfig;
 use ion_skill::workspace::Project;
 
@@ -69,13 +70,7 @@ pub fn deploy_agents_update_skill(
     installer.deploy(skill_name, &global_dir)?;
 
     // Gitignore the symlinks
-    let target_paths: Vec<&str> = options.targets.values().map(|s| s.as_str()).collect();
-    ion_skill::gitignore::add_skill_entries(
-        &project.dir,
-        skill_name,
-        &target_paths,
-        options.skills_dir_or_default(),
-    )?;
+    ion_skill::gitignore::add_entries(&project.dir, &installer.gitignore_entries(skill_name))?;
 
     // Register as local skill in Ion.toml if not already present
     let content = std::fs::read_to_string(&project.manifest_path).unwrap_or_default();
@@ -170,8 +165,11 @@ pub(crate) fn apply_template(
     }
 
     // Create agent symlinks (e.g. CLAUDE.md -> AGENTS.md).
-    if let Err(e) = ion_skill::agents::ensure_agent_symlinks(&project.dir, &merged_options.targets)
-    {
+    if let Err(e) = ion_skill::agents::ensure_agent_symlinks(
+        &project.dir,
+        &merged_options.targets,
+        &Catalog::load(global_config),
+    ) {
         log::warn!("Failed to create agent symlinks: {e}");
     }
 
@@ -270,7 +268,7 @@ pub fn update(json: bool, project_flags: &[String]) -> anyhow::Result<()> {
         };
 
         if multi && !json {
-            let label = project_label(project, &ws);
+            let label = ws.project_label(project);
             println!("\n{}:", p.bold(&label));
         }
 
@@ -323,7 +321,7 @@ pub fn update(json: bool, project_flags: &[String]) -> anyhow::Result<()> {
 
         if json {
             json_results.push(serde_json::json!({
-                "project": project_label(project, &ws),
+                "project": ws.project_label(project),
                 "updated": true,
                 "old_rev": old_rev,
                 "new_rev": new_rev,
@@ -452,7 +450,7 @@ pub fn diff(project_flags: &[String]) -> anyhow::Result<()> {
         }
 
         if multi {
-            let label = project_label(project, &ws);
+            let label = ws.project_label(project);
             println!("\n{}:", p.bold(&label));
         }
 
@@ -689,17 +687,3 @@ pub(crate) fn migrate_claude_md(
         }
     }
 }
-
-/// Human-readable label for a project within a workspace.
-fn project_label(project: &Project, ws: &WorkspaceContext) -> String {
-    let root_dir = ws.root_dir();
-    if project.dir == root_dir {
-        ". (root)".to_string()
-    } else {
-        project
-            .dir
-            .strip_prefix(root_dir)
-            .map(|p| p.display().to_string())
-            .unwrap_or_else(|_| project.dir.display().to_string())
-    }
-}
@@ -1,4 +1,5 @@
 use clap::Subcommand;
+use ion_skill::catalog::Catalog;
 use ion_skill::config::GlobalConfig;
 use ion_skill::manifest::Manifest;
 use ion_skill::manifest_writer;
@@ -100,17 +101,22 @@ fn run_set(
         println!("Set {key} = \"{value}\" in {scope} config");
     }
 
-    // Show hint when configuring a codex target
-    if !json
-        && let Some(target_name) = key.strip_prefix("targets.")
-        && target_name.eq_ignore_ascii_case("codex")
-    {
+    // Show a hint when configuring a target for a tool that reads .agents/skills itself
+    if !json && let Some(target_name) = key.strip_prefix("targets.") {
         let config = GlobalConfig::load().unwrap_or_default();
-        let p = crate::style::Paint::new(&config);
-        println!(
-            "  {}: Codex uses the default .agents/ directory — no extra target configuration needed.",
-            p.warn("hint")
-        );
+        let catalog = Catalog::load(&config);
+        let target_name = target_name.to_ascii_lowercase();
+        if catalog
+            .get(&target_name)
+            .is_some_and(|tool| tool.path.is_none())
+        {
+            let p = crate::style::Paint::new(&config);
+            println!(
+                "  {}: {}",
+                p.warn("hint"),
+                crate::commands::init::native_tool_message(catalog.label(&target_name))
+            );
+        }
     }
     Ok(())
 }
@@ -0,0 +1,114 @@
+use ion_skill::doctor::{Problem, Severity, diagnose, repair};
+
+use crate::context::WorkspaceContext;
+use crate::style::Paint;
+
+pub fn run(json: bool, fix: bool, project_flags: &[String]) -> anyhow::Result<()> {
+    let ws = WorkspaceContext::load(project_flags)?;
+    let projects = ws.scoped_projects();
+    let p = ws.paint();
+    let catalog = ws.catalog();
+    let multi = projects.len() > 1;
+
+    let mut found = 0usize;
+    let mut fixed = 0usize;
+    let mut fixable = 0usize;
+    let mut errors = 0usize;
+    let mut json_problems: Vec<serde_json::Value> = Vec::new();
+
+    for project in &projects {
+        if !project.has_manifest() {
+            continue;
+        }
+        let manifest = project.manifest()?;
+        let mut lockfile = project.lockfile()?;
+        let options = ws.merged_options_for(project)?;
+        let installer = ws.installer_for(project, &options);
+        let label = ws.project_label(project);
+
+        let problems = diagnose(&installer, &manifest, &lockfile, &catalog)?;
+        if problems.is_empty() {
+            continue;
+        }
+        if multi && !json {
+            println!("\n{}:", p.bold(&label));
+        }
+
+        let locked_before = lockfile.skills.len();
+        for problem in &problems {
+            found += 1;
+            let repaired = fix && repair(&installer, &mut lockfile, &catalog, problem)?;
+            if repaired {
+                fixed += 1;
+            } else if problem.is_fixable() {
+                fixable += 1;
+            }
+            if !repaired && problem.severity == Severity::Error {
+                errors += 1;
+            }
+
+            if json {
+                let mut value = serde_json::to_value(problem)?;
+                value["fixable"] = serde_json::json!(problem.is_fixable());
+                value["fixed"] = serde_json::json!(repaired);
+                value["project"] = serde_json::json!(label);
+                json_problems.push(value);
+            } else {
+                print_problem(problem, repaired, &p);
+            }
+        }
+        if lockfile.skills.len() != locked_before {
+            lockfile.write_to(&project.lockfile_path)?;
+        }
+    }
+
+    if json {
+        let data = serde_json::json!({
+            "problems": json_problems,
+            "fixed": fixed,
+            "errors": errors,
+        });
+        if errors > 0 {
+            crate::json::print_failure(data);
+        }
+        crate::json::print_success(data);
+        return Ok(());
+    }
+
+    if found == 0 {
+        println!("No problems found.");
+        return Ok(());
+    }
+    if fixed > 0 {
+        println!("\nFixed {fixed} of {found} problem(s).");
+    }
+    if fixable > 0 {
+        println!(
+            "\n{fixable} problem(s) can be fixed with {}.",
+            p.info("ion doctor --fix")
+        );
+    }
+    if errors > 0 {
+        anyhow::bail!("{errors} error(s) found");
+    }
+    Ok(())
+}
+
+fn print_problem(problem: &Problem, fixed: bool, p: &Paint) {
+    if fixed {
+        println!(
+            "  {} {} {}",
+            p.success("✓"),
+            p.dim("fixed:"),
+            problem.message
+        );
+        return;
+    }
+    match problem.severity {
+        Severity::Error => println!("  {} {}", p.warn("✗"), problem.message),
+        Severity::Warning => println!("  {} {}", p.warn("⚠"), problem.message),
+    }
+    if let Some(hint) = &problem.hint {
+        println!("      {}", p.dim(hint));
+    }
+}
@@ -50,10 +50,15 @@ pub fn run(name: &str, json: bool, project_flags: &[String]) -> anyhow::Result<(
         // dest == agents_skill path. Remove the old symlink first, then copy content there.
         if agents_skill.is_symlink() {
             std::fs::remove_file(&agents_skill)?;
-        } else if agents_skill.is_dir() {
-            std::fs::remove_dir_all(&agents_skill)?;
+            copy_dir_recursive(&real_source, &dest)?;
+        } else {
+            // A copy or hardlink deployment already sits at dest; copy it
+            // aside and back so no file stays shared with the cache.
+            let staged = dest.with_file_name(format!(".{name}.ejecting"));
+            std::fs::rename(&agents_skill, &staged)?;
+            copy_dir_recursive(&staged, &dest)?;
+            std::fs::remove_dir_all(&staged)?;
         }
-        copy_dir_recursive(&real_source, &dest)?;
     } else {
         // Custom skills-dir: copy to the custom location
         if dest.exists() {
@@ -106,8 +111,9 @@ pub fn run(name: &str, json: bool, project_flags: &[String]) -> anyhow::Result<(
     // Update lockfile: convert to local kind, preserve checksum
     let mut lockfile = project.lockfile()?;
     if let Some(locked) = lockfile.find(name).cloned() {
-        let mut updated =
-            ion_skill::lockfile::LockedSkill::local(name).with_source(locked.source.clone());
+        let mut updated = ion_skill::lockfile::LockedSkill::local(name)
+            .with_source(locked.source.clone())
+            .with_rendered(locked.rendered.clone());
         if let Some(checksum) = locked.checksum() {
             updated = updated.with_checksum(checksum);
         }
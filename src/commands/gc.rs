@@ -1,4 +1,6 @@
-use ion_skill::installer::data_dir;
+use std::path::{Path, PathBuf};
+
+use ion_skill::installer::{data_dir, store_dir};
 use ion_skill::registry::Registry;
 
 pub fn list(json: bool) -> anyhow::Result<()> {
@@ -21,12 +23,27 @@ pub fn list(json: bool) -> anyhow::Result<()> {
             .map(|(hash, entry)| {
                 let repo_dir = data.join(hash);
                 let size_bytes = dir_size(&repo_dir);
+                let (objects_missing, objects_total) =
+                    ion_skill::git::object_counts(&repo_dir).unwrap_or_default();
+                let snapshots: Vec<serde_json::Value> = snapshot_dirs(hash)
+                    .into_iter()
+                    .map(|(commit, dir)| {
+                        serde_json::json!({
+                            "commit": commit,
+                            "directory": dir.display().to_string(),
+                            "size_bytes": dir_size(&dir),
+                        })
+                    })
+                    .collect();
                 serde_json::json!({
                     "hash": hash,
                     "url": entry.url,
                     "directory": repo_dir.display().to_string(),
                     "exists": repo_dir.exists(),
                     "size_bytes": size_bytes,
+                    "objects_total": objects_total,
+                    "objects_missing": objects_missing,
+                    "snapshots": snapshots,
                     "projects": entry.projects,
                 })
             })
@@ -36,23 +53,120 @@ pub fn list(json: bool) -> anyhow::Result<()> {
     }
 
     println!("{} cached repo(s):", registry.repos.len());
+    let mut partial = false;
     for (hash, entry) in &registry.repos {
         let repo_dir = data.join(hash);
         let size_str = if repo_dir.exists() {
-            format_size(dir_size(&repo_dir))
+            match ion_skill::git::object_counts(&repo_dir) {
+                Ok((missing, total)) if missing > 0 => {
+                    partial = true;
+                    format!(
+                        "{}, {missing} of {total} objects not downloaded",
+                        format_size(dir_size(&repo_dir))
+                    )
+                }
+                _ => format_size(dir_size(&repo_dir)),
+            }
         } else {
             "missing".to_string()
         };
         println!("  {} ({})", entry.url, size_str);
+        let snapshots = snapshot_dirs(hash);
+        if !snapshots.is_empty() {
+            let total: u64 = snapshots.iter().map(|(_, dir)| dir_size(dir)).sum();
+            println!(
+                "    {} snapshot(s) ({})",
+                snapshots.len(),
+                format_size(total)
+            );
+        }
         for project in &entry.projects {
             println!("    used by {project}");
         }
     }
+    if partial {
+        // Trees record only object ids, so a partial clone can't know the
+        // size of blobs it never fetched.
+        println!(
+            "Git doesn't know the size of objects it hasn't downloaded, so the space saved can't be measured."
+        );
+    }
 
     Ok(())
 }
 
-fn dir_size(path: &std::path::Path) -> u64 {
+/// Per-commit snapshots of a cached repo, as `(commit, directory)` pairs.
+fn snapshot_dirs(repo_hash: &str) -> Vec<(String, PathBuf)> {
+    let mut snapshots: Vec<(String, PathBuf)> = std::fs::read_dir(store_dir().join(repo_hash))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .map(|entry| {
+            (
+                entry.file_name().to_string_lossy().to_string(),
+                entry.path(),
+            )
+        })
+        .collect();
+    snapshots.sort();
+    snapshots
+}
+
+/// A snapshot no registered project's Ion.lock refers to.
+struct UnusedSnapshot {
+    hash: String,
+    url: String,
+    commit: String,
+    directory: PathBuf,
+}
+
+/// Snapshots of still-registered repos whose commit is no longer locked anywhere.
+fn unused_snapshots(registry: &Registry) -> Vec<UnusedSnapshot> {
+    let live = registry.live_commits();
+    let mut unused = Vec::new();
+    for (hash, entry) in &registry.repos {
+        // Repos missing from `live` have an unreadable lockfile; keep everything.
+        let Some(commits) = live.get(hash) else {
+            continue;
+        };
+        for (commit, directory) in snapshot_dirs(hash) {
+            if !commits.contains(&commit) {
+                unused.push(UnusedSnapshot {
+                    hash: hash.clone(),
+                    url: entry.url.clone(),
+                    commit,
+                    directory,
+                });
+            }
+        }
+    }
+    unused
+}
+
+/// Delete a repo's clone and all of its snapshots.
+fn remove_repo(hash: &str) -> anyhow::Result<()> {
+    let repo_dir = data_dir().join(hash);
+    if repo_dir.exists() {
+        std::fs::remove_dir_all(&repo_dir)?;
+    }
+    let snapshots = store_dir().join(hash);
+    if snapshots.exists() {
+        std::fs::remove_dir_all(&snapshots)?;
+    }
+    Ok(())
+}
+
+fn remove_snapshot(snapshot: &UnusedSnapshot) -> anyhow::Result<()> {
+    ion_skill::git::remove_snapshot(&data_dir().join(&snapshot.hash), &snapshot.directory)?;
+    Ok(())
+}
+
+fn short_commit(commit: &str) -> &str {
+    commit.get(..7).unwrap_or(commit)
+}
+
+fn dir_size(path: &Path) -> u64 {
     if !path.is_dir() {
         return 0;
     }
@@ -87,16 +201,18 @@ pub fn run(dry_run: bool, json: bool) -> anyhow::Result<()> {
     let mut registry = Registry::load()?;
 
     let removed = registry.cleanup_stale();
+    let snapshots = unused_snapshots(&registry);
 
-    if removed.is_empty() {
+    if removed.is_empty() && snapshots.is_empty() {
         if json {
             crate::json::print_success(serde_json::json!({
                 "dry_run": dry_run,
                 "removed": [],
+                "snapshots": [],
             }));
             return Ok(());
         }
-        println!("No stale repos to clean up.");
+        println!("No stale repos or snapshots to clean up.");
         return Ok(());
     }
 
@@ -115,13 +231,25 @@ pub fn run(dry_run: bool, json: bool) -> anyhow::Result<()> {
                 })
             })
             .collect();
+        let snapshot_entries: Vec<serde_json::Value> = snapshots
+            .iter()
+            .map(|s| {
+                serde_json::json!({
+                    "hash": s.hash,
+                    "url": s.url,
+                    "commit": s.commit,
+                    "directory": s.directory.display().to_string(),
+                    "size_bytes": dir_size(&s.directory),
+                })
+            })
+            .collect();
 
         if !dry_run {
             for (hash, _url) in &removed {
-                let repo_dir = data.join(hash);
-                if repo_dir.exists() {
-                    std::fs::remove_dir_all(&repo_dir)?;
-                }
+                remove_repo(hash)?;
+            }
+            for snapshot in &snapshots {
+                remove_snapshot(snapshot)?;
             }
             registry.save()?;
         }
@@ -129,6 +257,7 @@ pub fn run(dry_run: bool, json: bool) -> anyhow::Result<()> {
         crate::json::print_success(serde_json::json!({
             "dry_run": dry_run,
             "removed": entries,
+            "snapshots": snapshot_entries,
         }));
         return Ok(());
     }
@@ -142,18 +271,39 @@ pub fn run(dry_run: bool, json: bool) -> anyhow::Result<()> {
             }
         } else {
             println!("Removing: {url} ({hash})");
-            if repo_dir.exists() {
-                std::fs::remove_dir_all(&repo_dir)?;
+            let existed = repo_dir.exists();
+            remove_repo(hash)?;
+            if existed {
                 println!("  Deleted {}", repo_dir.display());
             }
         }
     }
 
+    for snapshot in &snapshots {
+        let label = format!("{} @ {}", snapshot.url, short_commit(&snapshot.commit));
+        if dry_run {
+            println!("Would remove snapshot: {label}");
+            println!("  Directory: {}", snapshot.directory.display());
+        } else {
+            println!("Removing snapshot: {label}");
+            remove_snapshot(snapshot)?;
+            println!("  Deleted {}", snapshot.directory.display());
+        }
+    }
+
     if !dry_run {
         registry.save()?;
-        println!("Cleaned up {} stale repo(s).", removed.len());
+        println!(
+            "Cleaned up {} stale repo(s) and {} unused snapshot(s).",
+            removed.len(),
+            snapshots.len()
+        );
     } else {
-        println!("{} repo(s) would be cleaned up.", removed.len());
+        println!(
+            "{} repo(s) and {} snapshot(s) would be cleaned up.",
+            removed.len(),
+            snapshots.len()
+        );
     }
 
     Ok(())
@@ -30,6 +30,9 @@ pub fn run(skill_str: &str, json: bool, project_flags: &[String]) -> anyhow::Res
         if let Ok(url) = source.git_url() {
             data["git_url"] = serde_json::json!(url);
         }
+        if let Some(url) = source.web_url() {
+            data["web_url"] = serde_json::json!(url);
+        }
         crate::json::print_success(data);
         return Ok(());
     }
@@ -43,6 +46,9 @@ pub fn run(skill_str: &str, json: bool, project_flags: &[String]) -> anyhow::Res
     if let Ok(url) = source.git_url() {
         println!("  Git URL: {url}");
     }
+    if let Some(url) = source.web_url() {
+        println!("  Web: {url}");
+    }
     Ok(())
 }
 
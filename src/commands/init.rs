@@ -3,42 +3,36 @@ use std::io::IsTerminal;
 use std::path::Path;
 
 use crate::context::WorkspaceContext;
+use ion_skill::catalog::Catalog;
 use ion_skill::manifest_writer;
+use ion_skill::render::TargetFormat;
 
-/// Known agent tool targets and their default skill directories.
-const KNOWN_TARGETS: &[(&str, &str, &str)] = &[
-    ("claude", ".claude", ".claude/skills"),
-    ("cursor", ".cursor", ".cursor/skills"),
-    ("windsurf", ".windsurf", ".windsurf/skills"),
-];
-
-/// Print a hint when the user tries to configure a target for Codex.
-fn print_codex_hint(p: &crate::style::Paint) {
-    println!(
-        "  {}: Codex uses the default .agents/ directory — no extra target configuration needed.",
-        p.warn("hint")
-    );
+/// Message for tools in the catalog that read `.agents/skills` themselves.
+pub fn native_tool_message(label: &str) -> String {
+    format!("{label} uses the default .agents/ directory — no extra target configuration needed.")
 }
 
-/// Parse a --target flag value. Accepts "name" (uses lookup) or "name:path".
-fn parse_target_flag(flag: &str) -> anyhow::Result<(String, String)> {
+/// Parse a --target flag value. Accepts "name" (looked up in the catalog) or "name:path".
+fn parse_target_flag(flag: &str, catalog: &Catalog) -> anyhow::Result<(String, String)> {
     if let Some((name, path)) = flag.split_once(':') {
         if Path::new(path).is_absolute() {
             anyhow::bail!("Target paths must be relative to the project directory: {path}");
         }
-        Ok((name.to_string(), path.to_string()))
-    } else if flag.eq_ignore_ascii_case("codex") {
-        anyhow::bail!(
-            "Codex uses the default .agents/ directory — no extra target configuration needed."
-        )
-    } else {
-        let known = KNOWN_TARGETS.iter().find(|(n, _, _)| *n == flag);
-        match known {
-            Some((name, _, path)) => Ok((name.to_string(), path.to_string())),
-            None => anyhow::bail!(
-                "Unknown target '{flag}'. Known targets: claude, cursor, windsurf. \
-                 Use 'name:path' for custom targets."
-            ),
+        return Ok((name.to_string(), path.to_string()));
+    }
+    let name = flag.to_ascii_lowercase();
+    match catalog.get(&name) {
+        Some(tool) => match &tool.path {
+            Some(path) => Ok((name, path.clone())),
+            None => anyhow::bail!(native_tool_message(catalog.label(&name))),
+        },
+        None => {
+            let known: Vec<&str> = catalog.targets().map(|(name, _, _)| name).collect();
+            anyhow::bail!(
+                "Unknown target '{flag}'. Known targets: {}. \
+                 Use 'name:path' for custom targets.",
+                known.join(", ")
+            )
         }
     }
 }
@@ -79,10 +73,11 @@ fn rename_legacy_files(project_dir: &Path) -> anyhow::Result<()> {
 
 fn select_targets_interactive(
     project_dir: &Path,
+    catalog: &Catalog,
 ) -> anyhow::Result<Option<BTreeMap<String, String>>> {
     use crate::tui::init_select::run_init_select;
 
-    run_init_select(project_dir)
+    run_init_select(project_dir, catalog)
 }
 
 /// Print a hint if no targets are configured, suggesting `ion init`.
@@ -271,9 +266,11 @@ fn ensure_agents_md(
         None => {
             // No CLAUDE.md file to migrate.
             if agents_md.exists() {
-                if let Err(e) =
-                    ion_skill::agents::ensure_agent_symlinks(&project.dir, &merged_options.targets)
-                {
+                if let Err(e) = ion_skill::agents::ensure_agent_symlinks(
+                    &project.dir,
+                    &merged_options.targets,
+                    &Catalog::load(global_config),
+                ) {
                     log::warn!("Failed to create agent symlinks: {e}");
                 }
                 Ok(AgentsMdOutcome::Existing)
@@ -356,19 +353,24 @@ pub fn run(
     }
 
     // Resolve targets: flags take priority, otherwise interactive
+    let catalog = ws.catalog();
     let resolved: BTreeMap<String, String> = if !targets.is_empty() {
         let mut map = BTreeMap::new();
         for flag in targets {
-            let (name, path) = parse_target_flag(flag)?;
+            let (name, path) = parse_target_flag(flag, &catalog)?;
             map.insert(name, path);
         }
         map
     } else if json {
-        let detected: Vec<_> = KNOWN_TARGETS
-            .iter()
-            .map(|(name, dir, path)| {
-                let exists = project.dir.join(dir).exists();
-                serde_json::json!({"name": name, "path": path, "detected": exists})
+        let detected: Vec<_> = catalog
+            .targets()
+            .map(|(name, tool, path)| {
+                serde_json::json!({
+                    "name": name,
+                    "label": catalog.label(name),
+                    "path": path,
+                    "detected": tool.is_detected(&project.dir),
+                })
             })
             .collect();
         crate::json::print_action_required(
@@ -392,8 +394,8 @@ pub fn run(
         // mirrors the `detected` flag the --json channel exposes, so a human
         // reading the plain-text list gets the same signal an agent does.
         let mut first_detected: Option<&str> = None;
-        for (name, dir, path) in KNOWN_TARGETS {
-            if project.dir.join(dir).exists() {
+        for (name, tool, path) in catalog.targets() {
+            if tool.is_detected(&project.dir) {
                 if first_detected.is_none() {
                     first_detected = Some(name);
                 }
@@ -409,14 +411,28 @@ pub fn run(
         println!("Re-run with --target <name> to select targets (e.g. --target {example}).");
         anyhow::bail!("no targets selected; re-run with --target <name>");
     } else {
-        match select_targets_interactive(&project.dir)? {
+        match select_targets_interactive(&project.dir, &catalog)? {
             Some(targets) => targets,
             None => return Ok(()),
         }
     };
 
-    // Write targets to Ion.toml
+    // Write targets to Ion.toml, along with the native format of tools
+    // that don't read skill directories
     manifest_writer::write_targets(&project.manifest_path, &resolved)?;
+    let configured = project.manifest_or_empty()?.options;
+    for name in resolved.keys() {
+        if let Some(tool) = catalog.get(name)
+            && tool.format != TargetFormat::default()
+            && !configured.target_format.contains_key(name)
+        {
+            manifest_writer::set_option(
+                &project.manifest_path,
+                &format!("target-format.{name}"),
+                tool.format.as_str(),
+            )?;
+        }
+    }
 
     // Install the built-in ion-cli skill so agents can discover Ion's JSON interface
     let manifest = project.manifest_or_empty()?;
@@ -430,7 +446,7 @@ pub fn run(
     // `deploy()` only creates missing symlinks, so this is a cheap no-op for
     // skills that are already fully linked.
     if !resolved.is_empty() {
-        let installer = ion_skill::installer::SkillInstaller::new(&project.dir, &merged_options);
+        let installer = ws.installer_for(&project, &merged_options);
         for name in manifest.skills.keys() {
             let skill_dir = installer.skill_dir(name);
             if skill_dir.exists() {
@@ -517,9 +533,15 @@ pub fn run(
 
     print_agents_outcome(&p, &agents_outcome);
 
-    // Show hint if any resolved target looks like codex
-    if resolved.keys().any(|k| k.eq_ignore_ascii_case("codex")) {
-        print_codex_hint(&p);
+    // Show a hint for tools that read .agents/skills themselves
+    for name in resolved.keys() {
+        if catalog.get(name).is_some_and(|tool| tool.path.is_none()) {
+            println!(
+                "  {}: {}",
+                p.warn("hint"),
+                native_tool_message(catalog.label(name))
+            );
+        }
     }
 
     print_next_steps(&p, &next);
@@ -574,31 +596,32 @@ mod tests {
 
     #[test]
     fn parse_known_target() {
-        let (name, path) = parse_target_flag("claude").unwrap();
+        let (name, path) = parse_target_flag("claude", &Catalog::builtin()).unwrap();
         assert_eq!(name, "claude");
         assert_eq!(path, ".claude/skills");
     }
 
     #[test]
     fn parse_custom_target() {
-        let (name, path) = parse_target_flag("claude:.claude/commands/skills").unwrap();
+        let (name, path) =
+            parse_target_flag("claude:.claude/commands/skills", &Catalog::builtin()).unwrap();
         assert_eq!(name, "claude");
         assert_eq!(path, ".claude/commands/skills");
     }
 
     #[test]
     fn parse_unknown_target_is_error() {
-        assert!(parse_target_flag("unknown").is_err());
+        assert!(parse_target_flag("unknown", &Catalog::builtin()).is_err());
     }
 
     #[test]
     fn parse_absolute_path_is_error() {
-        assert!(parse_target_flag("foo:/absolute/path").is_err());
+        assert!(parse_target_flag("foo:/absolute/path", &Catalog::builtin()).is_err());
     }
 
     #[test]
     fn parse_codex_target_shows_hint() {
-        let err = parse_target_flag("codex").unwrap_err();
+        let err = parse_target_flag("codex", &Catalog::builtin()).unwrap_err();
         assert!(
             err.to_string().contains(".agents/"),
             "should mention .agents/"
@@ -607,13 +630,13 @@ mod tests {
 
     #[test]
     fn parse_codex_case_insensitive() {
-        assert!(parse_target_flag("Codex").is_err());
-        assert!(parse_target_flag("CODEX").is_err());
+        assert!(parse_target_flag("Codex", &Catalog::builtin()).is_err());
+        assert!(parse_target_flag("CODEX", &Catalog::builtin()).is_err());
     }
 
     #[test]
     fn parse_codex_with_custom_path_still_works() {
-        let (name, path) = parse_target_flag("codex:custom/path").unwrap();
+        let (name, path) = parse_target_flag("codex:custom/path", &Catalog::builtin()).unwrap();
         assert_eq!(name, "codex");
         assert_eq!(path, "custom/path");
     }
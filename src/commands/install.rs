@@ -1,19 +1,38 @@
-use ion_skill::lockfile::LockedSkill;
+use ion_skill::installer::InstallValidationOptions;
+use ion_skill::lockfile::{LockDrift, LockedSkill};
 
 use crate::commands::install_shared::{
     FinalizeOptions, ValidationBuckets, finalize_skill_install, install_approved_skills,
+    install_dependencies,
 };
 use crate::commands::validation::{print_validation_summary, select_warned_skills};
 use crate::context::WorkspaceContext;
 
-pub fn run(json: bool, allow_warnings: bool, project_flags: &[String]) -> anyhow::Result<()> {
-    let ws = WorkspaceContext::load(project_flags)?;
+/// Install every skill declared in Ion.toml.
+///
+/// Unpinned git skills that Ion.lock already records are installed at their
+/// locked commit rather than the latest upstream; `ion update` moves them.
+/// With `locked`, every declared skill must be in Ion.lock as Ion.toml
+/// declares it, and is installed at its locked commit and checksum. `frozen`
+/// implies `locked` and additionally refuses stale lock entries; the
+/// lockfile is never rewritten in that mode.
+pub fn run(
+    json: bool,
+    allow_warnings: bool,
+    locked: bool,
+    frozen: bool,
+    global: bool,
+    project_flags: &[String],
+) -> anyhow::Result<()> {
+    let locked = locked || frozen;
+    let ws = WorkspaceContext::load_scoped(project_flags, global)?;
     let projects = ws.scoped_projects();
     let p = ws.paint();
     let multi = projects.len() > 1;
 
     let mut all_json_installed: Vec<serde_json::Value> = Vec::new();
     let mut all_json_skipped: Vec<serde_json::Value> = Vec::new();
+    let mut all_json_pruned: Vec<serde_json::Value> = Vec::new();
 
     for project in &projects {
         if !project.has_manifest() {
@@ -26,8 +45,12 @@ pub fn run(json: bool, allow_warnings: bool, project_flags: &[String]) -> anyhow
         ws.ensure_builtin_skill(project, &merged_options);
 
         // Create agent file symlinks (e.g. CLAUDE.md -> AGENTS.md)
-        if let Err(e) =
-            ion_skill::agents::ensure_agent_symlinks(&project.dir, &merged_options.targets)
+        if !project.global
+            && let Err(e) = ion_skill::agents::ensure_agent_symlinks(
+                &project.dir,
+                &merged_options.targets,
+                &ws.catalog(),
+            )
         {
             log::warn!("Failed to create agent symlinks: {e}");
         }
@@ -46,12 +69,37 @@ pub fn run(json: bool, allow_warnings: bool, project_flags: &[String]) -> anyhow
 
         let mut lockfile = project.lockfile()?;
 
+        // The built-in ion-cli skill is managed by Ion, not by the lock.
+        let drift: Vec<_> = lockfile
+            .drift(&manifest.skills)?
+            .into_iter()
+            .filter(|d| d.name() != crate::builtin_skill::SKILL_NAME)
+            .collect();
+        // `--locked` never installs a skill the lock doesn't pin as declared;
+        // only `--frozen` also refuses entries Ion.toml no longer declares.
+        let refused: Vec<_> = drift
+            .iter()
+            .filter(|d| locked && (frozen || !matches!(d, LockDrift::Stale { .. })))
+            .collect();
+        if !refused.is_empty() {
+            let details: Vec<String> = refused.iter().map(|d| format!("  - {d}")).collect();
+            anyhow::bail!(
+                "Ion.lock is out of date with Ion.toml in {}:\n{}\n\
+                 Run `ion add` without --{} to update it.",
+                project.dir.display(),
+                details.join("\n"),
+                if frozen { "frozen" } else { "locked" }
+            );
+        }
+        // Snapshot of the lock as committed, for drift checks after install.
+        let original_lock = lockfile.clone();
+
         if manifest.skills.is_empty() {
             continue;
         }
 
         if multi && !json {
-            let label = project_label(project, &ws);
+            let label = ws.project_label(project);
             println!("\n{}:", p.bold(&label));
         }
 
@@ -100,8 +148,9 @@ pub fn run(json: bool, allow_warnings: bool, project_flags: &[String]) -> anyhow
                 }
                 installer.deploy(name, &local_skill_dir)?;
 
-                let mut locked_local =
-                    LockedSkill::local(name.clone()).with_source(source.source.clone());
+                let mut locked_local = LockedSkill::local(name.clone())
+                    .with_source(source.source.clone())
+                    .with_rendered(installer.rendered_files(name));
                 if let Ok(checksum) = ion_skill::git::checksum_dir(&local_skill_dir) {
                     locked_local = locked_local.with_checksum(checksum);
                 }
@@ -111,6 +160,48 @@ pub fn run(json: bool, allow_warnings: bool, project_flags: &[String]) -> anyhow
                 continue;
             }
 
+            // In locked mode, check out the locked commit and verify its
+            // checksum before anything is validated or deployed; drift was
+            // refused above, so the entry matches Ion.toml. Otherwise
+            // reuse the locked commit unless Ion.toml pins its own `rev`, has
+            // a version requirement the lock didn't resolve to a tag, or the
+            // entry no longer matches the lock. HTTP skills can't be pinned,
+            // so new content they serve is skipped until `ion update` accepts it.
+            let source = match original_lock.find(name) {
+                Some(entry) if locked => installer.verify_locked(&source, entry)?,
+                Some(entry) if source.is_http() && !drift.iter().any(|d| d.name() == name) => {
+                    match installer.verify_locked(&source, entry) {
+                        Ok(source) => source,
+                        Err(ion_skill::Error::LockMismatch(msg)) => {
+                            log::debug!("{msg}");
+                            if !json {
+                                println!(
+                                    "  {} {} — content changed since it was locked, skipping (run `ion update {name}` to accept it)",
+                                    p.warn("⚠"),
+                                    p.bold(name),
+                                );
+                            }
+                            json_local_skipped.push(serde_json::json!({
+                                "name": name,
+                                "reason": "content_changed",
+                            }));
+                            continue;
+                        }
+                        Err(e) => return Err(e.into()),
+                    }
+                }
+                Some(entry)
+                    if source.rev.is_none()
+                        && (source.version.is_none()
+                            || source.channel.is_some()
+                            || entry.tag().is_some())
+                        && !drift.iter().any(|d| d.name() == name) =>
+                {
+                    entry.pin(&source)
+                }
+                _ => source,
+            };
+
             non_local_skills.push((name.clone(), source));
         }
 
@@ -175,19 +266,57 @@ pub fn run(json: bool, allow_warnings: bool, project_flags: &[String]) -> anyhow
             &warned_selections,
             &p,
             json,
-            |name, source, locked| {
+            |name, source, installed| {
+                if locked {
+                    check_binary_checksum(original_lock.find(name), &installed)?;
+                }
+                let installed = keep_locked_tag(original_lock.find(name), installed);
                 finalize_skill_install(
                     project,
                     &merged_options,
                     name,
                     source,
-                    locked,
+                    installed,
                     &mut lockfile,
                     &FinalizeOptions::INSTALL,
                 )
             },
         )?;
 
+        // Phase 4: Install what the declared skills require
+        let roots: Vec<String> = manifest
+            .skills
+            .keys()
+            .filter(|name| name.as_str() != crate::builtin_skill::SKILL_NAME)
+            .cloned()
+            .collect();
+        let dependencies = install_dependencies(
+            &installer,
+            project,
+            &merged_options,
+            &roots,
+            &mut lockfile,
+            InstallValidationOptions {
+                skip_validation: false,
+                allow_warnings,
+            },
+            locked,
+            &p,
+            json,
+        )?;
+
+        if frozen
+            && let Some(missing) = dependencies
+                .iter()
+                .find(|name| original_lock.find(name).is_none())
+        {
+            anyhow::bail!(
+                "Ion.lock is out of date with Ion.toml in {}:\n  - dependency '{missing}' is not in Ion.lock\n\
+                 Run `ion add` without --frozen to update it.",
+                project.dir.display()
+            );
+        }
+
         // Log skipped errored skills
         let mut json_skipped: Vec<serde_json::Value> = json_local_skipped;
         for (name, _) in &buckets.errored {
@@ -197,7 +326,15 @@ pub fn run(json: bool, allow_warnings: bool, project_flags: &[String]) -> anyhow
             json_skipped.push(serde_json::json!({ "name": name, "reason": "validation_errors" }));
         }
 
-        lockfile.write_to(&project.lockfile_path)?;
+        if !frozen {
+            lockfile.write_to(&project.lockfile_path)?;
+        }
+        // --locked and --frozen promise to change nothing beyond Ion.lock's state
+        let pruned = if locked || frozen {
+            Vec::new()
+        } else {
+            crate::commands::prune::prune_project(&ws, project, &merged_options)?
+        };
 
         if json {
             let mut json_installed: Vec<serde_json::Value> = json_local_installed;
@@ -215,10 +352,21 @@ pub fn run(json: bool, allow_warnings: bool, project_flags: &[String]) -> anyhow
                         .push(serde_json::json!({ "name": entry.name, "reason": "deselected" }));
                 }
             }
+            json_installed.extend(
+                dependencies
+                    .iter()
+                    .map(|name| serde_json::json!({ "name": name, "dependency": true })),
+            );
             all_json_installed.extend(json_installed);
             all_json_skipped.extend(json_skipped);
+            for orphan in &pruned {
+                all_json_pruned.push(serde_json::to_value(orphan)?);
+            }
         } else {
-            println!("Updated {}", p.dim("Ion.lock"));
+            if !frozen {
+                println!("Updated {}", p.dim("Ion.lock"));
+            }
+            crate::commands::prune::print_pruned(&pruned, &p);
             println!("{}", p.success("Done!"));
         }
     }
@@ -227,6 +375,7 @@ pub fn run(json: bool, allow_warnings: bool, project_flags: &[String]) -> anyhow
         crate::json::print_success(serde_json::json!({
             "installed": all_json_installed,
             "skipped": all_json_skipped,
+            "pruned": all_json_pruned,
         }));
     } else if !projects.iter().any(|p| p.has_manifest()) {
         println!("No Ion.toml found. Run `ion init` to set up a project.");
@@ -235,16 +384,34 @@ pub fn run(json: bool, allow_warnings: bool, project_flags: &[String]) -> anyhow
     Ok(())
 }
 
-/// Human-readable label for a project within a workspace.
-fn project_label(project: &ion_skill::workspace::Project, ws: &WorkspaceContext) -> String {
-    let root_dir = ws.root_dir();
-    if project.dir == root_dir {
-        ". (root)".to_string()
-    } else {
-        project
-            .dir
-            .strip_prefix(root_dir)
-            .map(|p| p.display().to_string())
-            .unwrap_or_else(|_| project.dir.display().to_string())
+/// In locked mode, a reinstalled binary must hash to the locked checksum.
+fn check_binary_checksum(
+    previous: Option<&LockedSkill>,
+    installed: &LockedSkill,
+) -> anyhow::Result<()> {
+    if let Some(previous) = previous
+        && previous.is_binary()
+        && let (Some(expected), Some(actual)) = (previous.checksum(), installed.checksum())
+        && expected != actual
+    {
+        return Err(ion_skill::Error::LockMismatch(format!(
+            "binary for '{}' does not match Ion.lock (expected {expected}, found {actual})",
+            installed.name
+        ))
+        .into());
+    }
+    Ok(())
+}
+
+/// A skill reinstalled at its locked commit keeps the tag its version
+/// requirement resolved to, since the pinned source no longer carries it.
+fn keep_locked_tag(previous: Option<&LockedSkill>, installed: LockedSkill) -> LockedSkill {
+    if installed.tag().is_none()
+        && let Some(previous) = previous
+        && previous.commit() == installed.commit()
+        && let Some(tag) = previous.tag()
+    {
+        return installed.with_tag(tag);
     }
+    installed
 }
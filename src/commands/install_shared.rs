@@ -1,5 +1,6 @@
 use ion_skill::Error as SkillError;
-use ion_skill::installer::{InstallValidationOptions, SkillInstaller, hash_simple};
+use ion_skill::deps::DependencyResolver;
+use ion_skill::installer::{InstallValidationOptions, SkillInstaller, cache_key};
 use ion_skill::lockfile::{LockedSkill, Lockfile};
 use ion_skill::manifest::ManifestOptions;
 use ion_skill::manifest_writer;
@@ -25,7 +26,7 @@ pub fn register_in_registry(
     if source.is_git_based()
         && let Ok(url) = source.git_url()
     {
-        let repo_hash = format!("{:x}", hash_simple(&url));
+        let repo_hash = cache_key(&url);
         let project_str = project_dir.display().to_string();
         let mut registry = Registry::load()?;
         registry.register(&repo_hash, &url, &project_str);
@@ -42,7 +43,7 @@ pub fn unregister_from_registry(
     if source.is_git_based()
         && let Ok(url) = source.git_url()
     {
-        let repo_hash = format!("{:x}", hash_simple(&url));
+        let repo_hash = cache_key(&url);
         let project_str = project_dir.display().to_string();
         let mut registry = Registry::load()?;
         registry.unregister(&repo_hash, &project_str);
@@ -127,17 +128,8 @@ pub fn add_gitignore_entries(
     merged_options: &ManifestOptions,
 ) -> anyhow::Result<()> {
     if source.is_remote_installable() {
-        let target_paths: Vec<&str> = merged_options
-            .targets
-            .values()
-            .map(|s| s.as_str())
-            .collect();
-        ion_skill::gitignore::add_skill_entries(
-            project_dir,
-            name,
-            &target_paths,
-            merged_options.skills_dir_or_default(),
-        )?;
+        let installer = ion_skill::installer::SkillInstaller::new(project_dir, merged_options);
+        ion_skill::gitignore::add_entries(project_dir, &installer.gitignore_entries(name))?;
     }
     Ok(())
 }
@@ -182,9 +174,12 @@ pub fn finalize_skill_install(
     lockfile: &mut Lockfile,
     opts: &FinalizeOptions,
 ) -> anyhow::Result<()> {
-    add_gitignore_entries(&project.dir, name, source, merged_options)?;
+    // The global scope deploys into the home directory, which is no repository
+    if !project.global {
+        add_gitignore_entries(&project.dir, name, source, merged_options)?;
+    }
     if opts.register_in_registry {
-        register_in_registry(source, &project.dir)?;
+        register_in_registry(source, project.manifest_dir())?;
     }
     if opts.write_manifest {
         manifest_writer::add_skill(&project.manifest_path, name, source)?;
@@ -216,6 +211,72 @@ pub fn finalize_skill_install_and_write(
     Ok(())
 }
 
+// ---------------------------------------------------------------------------
+// Dependencies
+// ---------------------------------------------------------------------------
+
+/// Install the `requires:` dependencies of the deployed skills `roots`,
+/// transitively, and record them in `lockfile`. Skills Ion.toml declares
+/// directly are not installed again. Returns the installed dependency names.
+#[allow(clippy::too_many_arguments)]
+pub fn install_dependencies(
+    installer: &SkillInstaller,
+    project: &Project,
+    merged_options: &ManifestOptions,
+    roots: &[String],
+    lockfile: &mut Lockfile,
+    validation: InstallValidationOptions,
+    locked: bool,
+    p: &Paint,
+    json: bool,
+) -> anyhow::Result<Vec<String>> {
+    let manifest = project.manifest()?;
+    let declared = manifest
+        .skills
+        .iter()
+        .filter_map(|(name, entry)| entry.resolve().ok().map(|source| (name.clone(), source)))
+        .collect();
+    let previous = lockfile.clone();
+    let mut resolver = DependencyResolver::new(installer, &previous, declared)
+        .with_validation(validation)
+        .with_locked(locked);
+    for root in roots {
+        match resolver.install_for(root) {
+            Ok(()) => {}
+            Err(SkillError::ValidationWarning { .. }) => anyhow::bail!(
+                "A dependency of '{root}' has validation warnings. \
+                 Re-run with --allow-warnings to install it."
+            ),
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    let mut names = Vec::new();
+    for dep in resolver.into_resolved() {
+        if !json {
+            println!(
+                "  Installed dependency {} {}",
+                p.bold(&format!("'{}'", dep.name)),
+                p.dim(&format!(
+                    "(required by {})",
+                    dep.locked.required_by.join(", ")
+                ))
+            );
+        }
+        finalize_skill_install(
+            project,
+            merged_options,
+            &dep.name,
+            &dep.source,
+            dep.locked,
+            lockfile,
+            &FinalizeOptions::INSTALL,
+        )?;
+        names.push(dep.name);
+    }
+    Ok(names)
+}
+
 // ---------------------------------------------------------------------------
 // Validation buckets & batch install
 // ---------------------------------------------------------------------------
@@ -1,11 +1,14 @@
 use std::path::PathBuf;
 
+use ion_skill::installer::SkillInstaller;
 use ion_skill::source::SkillSource;
+use ion_skill::workspace::Project;
 
 use crate::commands::install_shared::{FinalizeOptions, finalize_skill_install_and_write};
+use crate::commands::link_watch::{self, DevBinary, WatchedSkill};
 use crate::context::WorkspaceContext;
 
-pub fn run(path: &str, json: bool, project_flags: &[String]) -> anyhow::Result<()> {
+pub fn run(path: &str, watch: bool, json: bool, project_flags: &[String]) -> anyhow::Result<()> {
     let ws = WorkspaceContext::load(project_flags)?;
     let project = ws.single_project()?;
     let p = ws.paint();
@@ -17,6 +20,13 @@ pub fn run(path: &str, json: bool, project_flags: &[String]) -> anyhow::Result<(
     };
 
     if !skill_path.exists() {
+        // `--watch <name>` watches a skill Ion.toml already declares
+        if watch && project.manifest()?.skills.contains_key(path) {
+            let merged_options = ws.merged_options_for(project)?;
+            let installer = ws.installer_for(project, &merged_options);
+            let skill = watched_entry(project, &installer, path)?;
+            return link_watch::run(project, &installer, &skill, &p, json);
+        }
         anyhow::bail!("Path does not exist: {}", skill_path.display());
     }
 
@@ -56,7 +66,7 @@ pub fn run(path: &str, json: bool, project_flags: &[String]) -> anyhow::Result<(
                 merged_options.skills_dir_or_default()
             ))
         );
-        for target_name in merged_options.targets.keys() {
+        for target_name in merged_options.targets_for(&name) {
             println!("  Linked to {}", p.info(target_name));
         }
     }
@@ -77,16 +87,69 @@ pub fn run(path: &str, json: bool, project_flags: &[String]) -> anyhow::Result<(
     }
 
     if json {
-        let targets: Vec<&str> = merged_options.targets.keys().map(|s| s.as_str()).collect();
+        let targets = merged_options.targets_for(&name);
         crate::json::print_success(serde_json::json!({
             "name": name,
             "path": path,
             "targets": targets,
         }));
-        return Ok(());
+    } else {
+        println!("{}", p.success("Done!"));
+        crate::commands::init::print_no_targets_hint(&merged_options, &p, json);
     }
 
-    println!("{}", p.success("Done!"));
-    crate::commands::init::print_no_targets_hint(&merged_options, &p, json);
+    if watch {
+        let skill = WatchedSkill {
+            name,
+            dir: skill_path,
+            dev_binary: None,
+        };
+        return link_watch::run(project, &installer, &skill, &p, json);
+    }
     Ok(())
 }
+
+/// The directory (and dev binary) behind the Ion.toml entry `name`.
+/// Only skills edited in place can be watched: local, path and dev binary skills.
+fn watched_entry(
+    project: &Project,
+    installer: &SkillInstaller,
+    name: &str,
+) -> anyhow::Result<WatchedSkill> {
+    let manifest = project.manifest()?;
+    let source = manifest.skills[name].resolve()?;
+
+    let (dir, dev_binary) = if source.is_local() {
+        // Use explicit path from Ion.toml if set, otherwise fall back to skills-dir
+        let dir = match source.path {
+            Some(ref path) => project.dir.join(path),
+            None => installer.skill_dir(name),
+        };
+        (dir, None)
+    } else if source.is_path() {
+        let base = project.dir.join(&source.source);
+        let dir = ion_skill::installer::resolve_skill_dir(&base, source.path.as_deref())?;
+        (dir, None)
+    } else {
+        let lockfile = project.lockfile()?;
+        let Some(locked) = lockfile.find(name).filter(|l| l.is_dev()) else {
+            anyhow::bail!(
+                "'{name}' is not edited in place; only local, path and dev binary skills can be watched"
+            );
+        };
+        let dev = DevBinary {
+            project: locked.source.clone(),
+            binary_name: locked.binary_name().unwrap_or(name).to_string(),
+        };
+        (installer.skill_dir(name), Some(dev))
+    };
+
+    if !dir.join("SKILL.md").exists() {
+        anyhow::bail!("No SKILL.md found at {}", dir.display());
+    }
+    Ok(WatchedSkill {
+        name: name.to_string(),
+        dir,
+        dev_binary,
+    })
+}
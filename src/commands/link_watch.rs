@@ -0,0 +1,271 @@
+//! Watch mode for `ion skill link --watch`: re-validate a local skill and
+//! refresh its lock entry whenever its files change.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use ion_skill::installer::SkillInstaller;
+use ion_skill::skill::SkillMetadata;
+use ion_skill::validate::{self, ValidationReport};
+use ion_skill::workspace::Project;
+
+use crate::style::Paint;
+
+/// How often the skill directory is checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+
+/// A skill being watched.
+pub struct WatchedSkill {
+    pub name: String,
+    /// Directory holding the skill's SKILL.md.
+    pub dir: PathBuf,
+    /// Debug binary of a dev binary skill, whose rebuilds regenerate SKILL.md.
+    pub dev_binary: Option<DevBinary>,
+}
+
+/// The cargo project and binary name of a dev binary skill.
+pub struct DevBinary {
+    pub project: String,
+    pub binary_name: String,
+}
+
+/// Modification time and size of every file under a directory.
+type Fingerprint = BTreeMap<PathBuf, (Option<SystemTime>, u64)>;
+
+fn fingerprint(dir: &Path) -> Fingerprint {
+    ion_skill::git::list_files(dir)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|file| {
+            let meta = std::fs::metadata(dir.join(&file)).ok();
+            let modified = meta.as_ref().and_then(|m| m.modified().ok());
+            let len = meta.map(|m| m.len()).unwrap_or(0);
+            (file, (modified, len))
+        })
+        .collect()
+}
+
+/// Files added, removed or modified between two fingerprints.
+fn changed_files(old: &Fingerprint, new: &Fingerprint) -> Vec<PathBuf> {
+    let mut changed: Vec<PathBuf> = new
+        .iter()
+        .filter(|(file, stamp)| old.get(*file) != Some(stamp))
+        .map(|(file, _)| file.clone())
+        .collect();
+    changed.extend(old.keys().filter(|f| !new.contains_key(*f)).cloned());
+    changed.sort();
+    changed
+}
+
+/// Identity of a finding, used to report what appeared and what was fixed.
+fn finding_keys(report: &ValidationReport) -> Vec<String> {
+    report
+        .findings
+        .iter()
+        .map(|f| format!("{} [{}] {}", f.severity, f.checker, f.message))
+        .collect()
+}
+
+/// Watch `skill` until interrupted, re-validating on every change.
+pub fn run(
+    project: &Project,
+    installer: &SkillInstaller,
+    skill: &WatchedSkill,
+    p: &Paint,
+    json: bool,
+) -> anyhow::Result<()> {
+    if !json {
+        println!(
+            "Watching {} in {} (Ctrl-C to stop)...",
+            p.bold(&format!("'{}'", skill.name)),
+            p.info(&skill.dir.display().to_string())
+        );
+    }
+
+    // Fingerprint before the first check so edits made while it runs are seen
+    let mut files = fingerprint(&skill.dir);
+    let mut binary_stamp = skill.dev_binary.as_ref().and_then(binary_modified);
+    let mut previous = check(project, installer, skill, &[], None, p, json)?;
+
+    loop {
+        std::thread::sleep(POLL_INTERVAL);
+
+        if let Some(ref dev) = skill.dev_binary {
+            let stamp = binary_modified(dev);
+            if stamp.is_some() && stamp != binary_stamp {
+                binary_stamp = stamp;
+                let skill_md = skill.dir.join("SKILL.md");
+                if crate::commands::run::try_refresh_dev_skill(
+                    &dev.project,
+                    &dev.binary_name,
+                    &skill_md,
+                ) && !json
+                {
+                    println!(
+                        "  {} Regenerated SKILL.md from the rebuilt binary",
+                        p.info("↻")
+                    );
+                }
+            }
+        }
+
+        let current = fingerprint(&skill.dir);
+        let changed = changed_files(&files, &current);
+        if changed.is_empty() {
+            continue;
+        }
+        files = current;
+        previous = check(
+            project,
+            installer,
+            skill,
+            &changed,
+            previous.as_ref(),
+            p,
+            json,
+        )?;
+    }
+}
+
+fn binary_modified(dev: &DevBinary) -> Option<SystemTime> {
+    let bin_name = if cfg!(windows) {
+        format!("{}.exe", dev.binary_name)
+    } else {
+        dev.binary_name.clone()
+    };
+    Path::new(&dev.project)
+        .join("target")
+        .join("debug")
+        .join(bin_name)
+        .metadata()
+        .and_then(|m| m.modified())
+        .ok()
+}
+
+/// Validate the skill, print what changed since `previous`, and refresh its
+/// deployment and lock entry. Returns the new report, or `None` if SKILL.md
+/// couldn't be read.
+fn check(
+    project: &Project,
+    installer: &SkillInstaller,
+    skill: &WatchedSkill,
+    changed: &[PathBuf],
+    previous: Option<&ValidationReport>,
+    p: &Paint,
+    json: bool,
+) -> anyhow::Result<Option<ValidationReport>> {
+    let changed_names: Vec<String> = changed.iter().map(|f| f.display().to_string()).collect();
+    if !json && !changed.is_empty() {
+        println!();
+        println!("Changed: {}", p.dim(&changed_names.join(", ")));
+    }
+
+    let (meta, body) = match SkillMetadata::from_file(&skill.dir.join("SKILL.md")) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            if json {
+                crate::json::print_success(serde_json::json!({
+                    "name": skill.name,
+                    "changed": changed_names,
+                    "error": e.to_string(),
+                }));
+            } else {
+                println!("  {} {e}", p.warn("✗"));
+            }
+            return Ok(None);
+        }
+    };
+    let report = validate::validate_skill_dir(&skill.dir, &meta, &body);
+
+    // Copies and generated targets only pick up changes on redeploy
+    installer.deploy(&skill.name, &skill.dir)?;
+    let checksum = refresh_checksum(project, &skill.name, &skill.dir)?;
+
+    let old_keys = previous.map(finding_keys).unwrap_or_default();
+    let new_keys = finding_keys(&report);
+    let fixed: Vec<&String> = old_keys.iter().filter(|k| !new_keys.contains(k)).collect();
+
+    if json {
+        crate::json::print_success(serde_json::json!({
+            "name": skill.name,
+            "changed": changed_names,
+            "findings": report.findings,
+            "fixed": fixed,
+            "error_count": report.error_count,
+            "warning_count": report.warning_count,
+            "info_count": report.info_count,
+            "checksum": checksum,
+        }));
+        return Ok(Some(report));
+    }
+
+    for finding in &report.findings {
+        let key = format!(
+            "{} [{}] {}",
+            finding.severity, finding.checker, finding.message
+        );
+        if previous.is_some() && old_keys.contains(&key) {
+            continue;
+        }
+        println!("  {} {key}", p.warn("+"));
+        if let Some(ref detail) = finding.detail {
+            println!("      {detail}");
+        }
+    }
+    for key in &fixed {
+        println!("  {} {}", p.success("-"), p.dim(key));
+    }
+    let summary = format!(
+        "{} error(s), {} warning(s), {} info",
+        report.error_count, report.warning_count, report.info_count
+    );
+    if report.findings.is_empty() {
+        println!("  {} Valid ({summary})", p.success("✓"));
+    } else {
+        println!("  Found: {summary}");
+    }
+    Ok(Some(report))
+}
+
+/// Record the skill's current checksum in Ion.lock, if it has an entry.
+fn refresh_checksum(project: &Project, name: &str, dir: &Path) -> anyhow::Result<Option<String>> {
+    let checksum = ion_skill::git::checksum_dir(dir)?;
+    let mut lockfile = project.lockfile()?;
+    // Dev binary skills lock their binary's checksum, not the directory's
+    let Some(locked) = lockfile.find(name).filter(|l| l.binary_name().is_none()) else {
+        return Ok(None);
+    };
+    if locked.checksum() != Some(checksum.as_str()) {
+        let updated = locked.clone().with_checksum(checksum.clone());
+        lockfile.upsert(updated);
+        lockfile.write_to(&project.lockfile_path)?;
+    }
+    Ok(Some(checksum))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changed_files_reports_edits_additions_and_removals() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("SKILL.md"), "one").unwrap();
+        std::fs::write(dir.path().join("old.md"), "old").unwrap();
+        let before = fingerprint(dir.path());
+        assert!(changed_files(&before, &fingerprint(dir.path())).is_empty());
+
+        std::fs::write(dir.path().join("SKILL.md"), "one more").unwrap();
+        std::fs::remove_file(dir.path().join("old.md")).unwrap();
+        std::fs::write(dir.path().join("new.md"), "new").unwrap();
+        assert_eq!(
+            changed_files(&before, &fingerprint(dir.path())),
+            vec![
+                PathBuf::from("SKILL.md"),
+                PathBuf::from("new.md"),
+                PathBuf::from("old.md")
+            ]
+        );
+    }
+}
@@ -1,8 +1,10 @@
 use crate::context::WorkspaceContext;
+use crate::style::Paint;
+use ion_skill::manifest::ManifestOptions;
 use ion_skill::workspace::Project;
 
-pub fn run(json: bool, project_flags: &[String]) -> anyhow::Result<()> {
-    let ws = WorkspaceContext::load(project_flags)?;
+pub fn run(json: bool, global: bool, project_flags: &[String]) -> anyhow::Result<()> {
+    let ws = WorkspaceContext::load_scoped(project_flags, global)?;
     let projects = ws.scoped_projects();
     let p = ws.paint();
     let multi = projects.len() > 1;
@@ -13,7 +15,7 @@ pub fn run(json: bool, project_flags: &[String]) -> anyhow::Result<()> {
             if !project.has_manifest() {
                 continue;
             }
-            let project_label = project_label(project, &ws);
+            let project_label = ws.project_label(project);
             let skills = json_skills_for_project(project, &ws)?;
             for mut skill in skills {
                 skill
@@ -39,12 +41,13 @@ pub fn run(json: bool, project_flags: &[String]) -> anyhow::Result<()> {
         any_skills = true;
 
         if multi {
-            let label = project_label(project, &ws);
+            let label = ws.project_label(project);
             println!("\n{}:", p.bold(&label));
         }
 
         let merged_options = ws.merged_options_for(project)?;
         let lockfile = project.lockfile()?;
+        let shadowed = crate::context::shadowed_global_skills(project);
 
         for (name, entry) in &manifest.skills {
             let source = match entry.resolve() {
@@ -72,6 +75,8 @@ pub fn run(json: bool, project_flags: &[String]) -> anyhow::Result<()> {
             if source.is_local() {
                 println!("  {} {} [{}]", p.bold(name), p.dim("(local)"), status);
                 println!("    source: {}", p.info("local"));
+                print_targets(&merged_options, name, &p);
+                print_shadowed(&shadowed, name, &p);
                 continue;
             }
 
@@ -110,6 +115,8 @@ pub fn run(json: bool, project_flags: &[String]) -> anyhow::Result<()> {
                 status
             );
             println!("    source: {}", p.info(&source.source));
+            print_targets(&merged_options, name, &p);
+            print_shadowed(&shadowed, name, &p);
         }
     }
 
@@ -128,6 +135,7 @@ fn json_skills_for_project(
     let manifest = project.manifest()?;
     let merged_options = ws.merged_options_for(project)?;
     let lockfile = project.lockfile()?;
+    let shadowed = crate::context::shadowed_global_skills(project);
 
     let skills: Vec<serde_json::Value> = manifest
         .skills
@@ -162,22 +170,30 @@ fn json_skills_for_project(
                 "commit": commit,
                 "binary": is_binary,
                 "installed": installed,
+                "targets": merged_options.targets_for(name),
+                "shadows_global": shadowed.contains(name),
             }))
         })
         .collect();
     Ok(skills)
 }
 
-/// Human-readable label for a project within a workspace.
-fn project_label(project: &Project, ws: &WorkspaceContext) -> String {
-    let root_dir = ws.root_dir();
-    if project.dir == root_dir {
-        ". (root)".to_string()
+/// Print the targets a skill is deployed to, when any are configured.
+fn print_targets(options: &ManifestOptions, name: &str, p: &Paint) {
+    if options.targets.is_empty() {
+        return;
+    }
+    let targets = options.targets_for(name);
+    if targets.is_empty() {
+        println!("    targets: {}", p.dim("none"));
     } else {
-        project
-            .dir
-            .strip_prefix(root_dir)
-            .map(|p| p.display().to_string())
-            .unwrap_or_else(|_| project.dir.display().to_string())
+        println!("    targets: {}", p.info(&targets.join(", ")));
+    }
+}
+
+/// Note that a project skill hides the global skill of the same name.
+fn print_shadowed(shadowed: &[String], name: &str, p: &Paint) {
+    if shadowed.iter().any(|s| s == name) {
+        println!("    {}", p.dim("shadows the global skill of the same name"));
     }
 }
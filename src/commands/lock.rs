@@ -0,0 +1,256 @@
+use ion_skill::lockfile::{LockDrift, LockedSkill};
+use ion_skill::source::SkillSource;
+use ion_skill::workspace::Project;
+
+use crate::context::WorkspaceContext;
+
+/// Resolve every Ion.toml entry into Ion.lock without deploying anything.
+///
+/// Entries that already agree with Ion.toml are kept as they are, missing or
+/// drifted ones are resolved to a commit and checksum, and stale ones are
+/// dropped. Binary skills are skipped with a note: they are only locked by
+/// installing them. With `check`, nothing is written and the command fails if
+/// Ion.lock is out of date.
+pub fn run(check: bool, json: bool, project_flags: &[String]) -> anyhow::Result<()> {
+    let ws = WorkspaceContext::load(project_flags)?;
+    let projects = ws.scoped_projects();
+    let p = ws.paint();
+    let multi = projects.len() > 1;
+
+    let mut drift_count = 0usize;
+    let mut locked_count = 0usize;
+    let mut removed_count = 0usize;
+    let mut failed_count = 0usize;
+    let mut skipped_count = 0usize;
+    let mut json_drift: Vec<serde_json::Value> = Vec::new();
+    let mut json_locked: Vec<serde_json::Value> = Vec::new();
+    let mut json_removed: Vec<serde_json::Value> = Vec::new();
+    let mut json_failed: Vec<serde_json::Value> = Vec::new();
+    let mut json_skipped: Vec<serde_json::Value> = Vec::new();
+
+    for project in &projects {
+        if !project.has_manifest() {
+            continue;
+        }
+        let manifest = project.manifest()?;
+        let mut lockfile = project.lockfile()?;
+        let label = ws.project_label(project);
+
+        // The built-in ion-cli skill is managed by Ion, not by the lock.
+        let drift: Vec<_> = lockfile
+            .drift(&manifest.skills)?
+            .into_iter()
+            .filter(|d| d.name() != crate::builtin_skill::SKILL_NAME)
+            .collect();
+        drift_count += drift.len();
+
+        if multi && !json && !drift.is_empty() {
+            println!("\n{}:", p.bold(&label));
+        }
+
+        if check {
+            for d in &drift {
+                if json {
+                    json_drift.push(serde_json::json!({
+                        "name": d.name(),
+                        "project": label,
+                        "problem": d.to_string(),
+                    }));
+                } else {
+                    println!("  {} {d}", p.warn("✗"));
+                }
+            }
+            continue;
+        }
+        if drift.is_empty() {
+            continue;
+        }
+
+        let options = ws.merged_options_for(project)?;
+        let installer = ws.installer_for(project, &options);
+        let mut changed = false;
+
+        for d in &drift {
+            let name = d.name();
+            if let LockDrift::Stale { .. } = d {
+                lockfile.remove(name);
+                removed_count += 1;
+                changed = true;
+                if json {
+                    json_removed.push(serde_json::json!({ "name": name, "project": label }));
+                } else {
+                    println!(
+                        "  {} {}  {}",
+                        p.dim("-"),
+                        p.bold(name),
+                        p.dim("removed (not in Ion.toml)")
+                    );
+                }
+                continue;
+            }
+
+            let source = manifest
+                .skills
+                .get(name)
+                .ok_or_else(|| anyhow::anyhow!("'{name}' is not in Ion.toml"))
+                .and_then(|entry| Ok(entry.resolve()?));
+            // A binary's checksum only exists once its release asset is
+            // downloaded and unpacked, which is what `ion add` does.
+            if source.as_ref().is_ok_and(|s| s.is_binary()) {
+                skipped_count += 1;
+                if json {
+                    json_skipped.push(serde_json::json!({
+                        "name": name,
+                        "project": label,
+                        "reason": "binary",
+                    }));
+                } else {
+                    println!(
+                        "  {} {}  {}",
+                        p.dim("-"),
+                        p.bold(name),
+                        p.dim("skipped (binary skills are locked by `ion add`)")
+                    );
+                }
+                continue;
+            }
+            let result = source.and_then(|source| lock_skill(&installer, project, name, &source));
+            match result {
+                Ok(locked) => {
+                    let resolved = describe_locked(&locked);
+                    if json {
+                        json_locked.push(serde_json::json!({
+                            "name": name,
+                            "project": label,
+                            "commit": locked.commit(),
+                            "tag": locked.tag(),
+                            "checksum": locked.checksum(),
+                        }));
+                    } else {
+                        println!(
+                            "  {} {}  {}",
+                            p.success("✓"),
+                            p.bold(name),
+                            p.info(&resolved)
+                        );
+                    }
+                    lockfile.upsert(locked);
+                    locked_count += 1;
+                    changed = true;
+                }
+                Err(e) => {
+                    if json {
+                        json_failed.push(serde_json::json!({
+                            "name": name,
+                            "project": label,
+                            "error": e.to_string(),
+                        }));
+                    } else {
+                        println!(
+                            "  {} {}  {}",
+                            p.warn("✗"),
+                            p.bold(name),
+                            p.warn(&e.to_string())
+                        );
+                    }
+                    failed_count += 1;
+                }
+            }
+        }
+
+        if changed {
+            lockfile.write_to(&project.lockfile_path)?;
+        }
+    }
+
+    if json {
+        if check {
+            let data = serde_json::json!({
+                "up_to_date": drift_count == 0,
+                "drift": json_drift,
+            });
+            if drift_count > 0 {
+                crate::json::print_failure(data);
+            }
+            crate::json::print_success(data);
+        } else {
+            let data = serde_json::json!({
+                "locked": json_locked,
+                "removed": json_removed,
+                "skipped": json_skipped,
+                "failed": json_failed,
+            });
+            if failed_count > 0 {
+                crate::json::print_failure(data);
+            }
+            crate::json::print_success(data);
+        }
+        return Ok(());
+    }
+
+    if check {
+        if drift_count > 0 {
+            anyhow::bail!("Ion.lock is out of date with Ion.toml; run `ion lock` to update it");
+        }
+        println!("Ion.lock is up to date.");
+        return Ok(());
+    }
+
+    if locked_count + removed_count > 0 {
+        println!("Updated {}", p.dim("Ion.lock"));
+    }
+    if failed_count > 0 {
+        anyhow::bail!("{failed_count} skill(s) could not be locked");
+    }
+    if skipped_count > 0 {
+        println!(
+            "Run {} to lock the skipped binary skill(s).",
+            p.info("ion add")
+        );
+    }
+    if drift_count == 0 {
+        println!("Ion.lock is up to date.");
+    }
+    Ok(())
+}
+
+/// Resolve one skill to its lock entry. Local skills are hashed in place;
+/// everything else goes through the installer without deploying.
+fn lock_skill(
+    installer: &ion_skill::installer::SkillInstaller<'_>,
+    project: &Project,
+    name: &str,
+    source: &SkillSource,
+) -> anyhow::Result<LockedSkill> {
+    if !source.is_local() {
+        return Ok(installer.lock(name, source)?);
+    }
+
+    // Use explicit path from Ion.toml if set, otherwise fall back to skills-dir
+    let local_skill_dir = match source.path {
+        Some(ref path) => project.dir.join(path),
+        None => installer.skill_dir(name),
+    };
+    if !local_skill_dir.exists() {
+        anyhow::bail!(
+            "local skill directory not found: {}",
+            local_skill_dir.display()
+        );
+    }
+    let checksum = ion_skill::git::checksum_dir(&local_skill_dir)?;
+    Ok(LockedSkill::local(name)
+        .with_source(source.source.clone())
+        .with_checksum(checksum)
+        .with_rendered(installer.rendered_files(name)))
+}
+
+/// Short description of what a lock entry was resolved to.
+fn describe_locked(locked: &LockedSkill) -> String {
+    match (locked.tag(), locked.commit()) {
+        (Some(tag), _) => format!("locked to {tag}"),
+        (None, Some(commit)) if !commit.is_empty() => {
+            format!("locked to {}", commit.get(..7).unwrap_or(commit))
+        }
+        _ => "locked".to_string(),
+    }
+}
@@ -184,20 +184,11 @@ pub fn run(
     let locked = ion_skill::migrate::migrate(project_dir, &resolved, &options)?;
 
     // ── Phase 5: Gitignore + registry ─────────────────────────────────────
-    let target_paths: Vec<&str> = merged_options
-        .targets
-        .values()
-        .map(|s| s.as_str())
-        .collect();
+    let installer = ws.installer_for(project, &merged_options);
 
     for entry in &locked {
         // Add per-skill gitignore entries
-        ion_skill::gitignore::add_skill_entries(
-            project_dir,
-            &entry.name,
-            &target_paths,
-            merged_options.skills_dir_or_default(),
-        )?;
+        ion_skill::gitignore::add_entries(project_dir, &installer.gitignore_entries(&entry.name))?;
 
         // Register in global registry
         if let Some(resolved_skill) = resolved.iter().find(|r| r.name == entry.name) {
@@ -314,11 +305,9 @@ pub fn run(
                                         &leftover.name,
                                         &source,
                                     )?;
-                                    ion_skill::gitignore::add_skill_entries(
+                                    ion_skill::gitignore::add_entries(
                                         project_dir,
-                                        &leftover.name,
-                                        &target_paths,
-                                        merged_options.skills_dir_or_default(),
+                                        &installer.gitignore_entries(&leftover.name),
                                     )?;
                                     register_in_registry(&source, project_dir)?;
                                     lockfile.upsert(entry);
@@ -3,6 +3,7 @@ pub mod agents;
 pub mod ci;
 pub mod completion;
 pub mod config;
+pub mod doctor;
 pub mod eject;
 pub mod gc;
 pub mod info;
@@ -10,14 +11,21 @@ pub mod init;
 pub mod install;
 pub mod install_shared;
 pub mod link;
+pub mod link_watch;
 pub mod list;
+pub mod lock;
 pub mod migrate;
 pub mod new;
+pub mod outdated;
+pub mod prune;
+pub mod push;
 pub mod remove;
 pub mod run;
 pub mod search;
 pub mod self_cmd;
 pub mod update;
+pub mod update_preview;
 pub mod validate;
 pub mod validation;
+pub mod verify;
 pub mod workspace;
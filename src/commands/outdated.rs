@@ -0,0 +1,243 @@
+use ion_skill::lockfile::LockedSkill;
+use ion_skill::source::SkillSource;
+use ion_skill::update::{UpdateCheck, short_digest};
+
+use crate::commands::update::{placeholder_lock, updater_for};
+use crate::context::WorkspaceContext;
+use crate::style::Paint;
+
+/// Update status of one git or binary skill.
+struct Outdated {
+    name: String,
+    current: Option<String>,
+    /// `None` when the latest version couldn't be determined.
+    latest: Option<String>,
+    pinned: bool,
+    constraint: String,
+    error: Option<String>,
+}
+
+impl Outdated {
+    fn has_update(&self) -> bool {
+        self.latest.is_some() && self.latest != self.current
+    }
+}
+
+pub fn run(json: bool, project_flags: &[String]) -> anyhow::Result<()> {
+    let ws = WorkspaceContext::load(project_flags)?;
+    let projects = ws.scoped_projects();
+    let p = ws.paint();
+    let multi = projects.len() > 1;
+
+    let mut total = 0usize;
+    let mut outdated = 0usize;
+    let mut failed = 0usize;
+    let mut json_skills: Vec<serde_json::Value> = Vec::new();
+
+    for project in &projects {
+        if !project.has_manifest() {
+            continue;
+        }
+        let manifest = project.manifest()?;
+        let lockfile = project.lockfile()?;
+        let label = ws.project_label(project);
+
+        let mut rows = Vec::new();
+        for (name, entry) in &manifest.skills {
+            let source = match entry.resolve() {
+                Ok(source) => source,
+                Err(e) => {
+                    eprintln!("warning: skipping '{}': {}", name, e);
+                    continue;
+                }
+            };
+            if updater_for(&source).is_none() {
+                continue;
+            }
+            rows.push(check_skill(name, &source, lockfile.find(name)));
+        }
+        if rows.is_empty() {
+            continue;
+        }
+
+        total += rows.len();
+        outdated += rows.iter().filter(|r| r.has_update()).count();
+        failed += rows.iter().filter(|r| r.error.is_some()).count();
+
+        if json {
+            json_skills.extend(rows.iter().map(|row| {
+                serde_json::json!({
+                    "name": row.name,
+                    "project": label,
+                    "current": row.current,
+                    "latest": row.latest,
+                    "pinned": row.pinned,
+                    "constraint": row.constraint,
+                    "outdated": row.has_update(),
+                    "error": row.error,
+                })
+            }));
+        } else {
+            if multi {
+                println!("\n{}:", p.bold(&label));
+            }
+            print_table(&rows, &p);
+        }
+    }
+
+    if json {
+        let data = serde_json::json!({
+            "skills": json_skills,
+            "outdated": outdated,
+            "failed": failed,
+        });
+        if outdated > 0 || failed > 0 {
+            crate::json::print_failure(data);
+        }
+        crate::json::print_success(data);
+        return Ok(());
+    }
+
+    if total == 0 {
+        println!("No git or binary skills to check.");
+    } else if outdated > 0 {
+        anyhow::bail!("{outdated} skill(s) have updates available; run `ion update` to apply them");
+    } else if failed > 0 {
+        anyhow::bail!("{failed} skill(s) could not be checked");
+    } else {
+        println!("\nAll {total} skill(s) are up to date.");
+    }
+    Ok(())
+}
+
+/// Run the update check for one skill without applying anything.
+fn check_skill(name: &str, source: &SkillSource, locked: Option<&LockedSkill>) -> Outdated {
+    let current = locked.and_then(installed_version);
+    let pinned = source.rev.is_some();
+    let mut row = Outdated {
+        name: name.to_string(),
+        current: current.clone(),
+        latest: None,
+        pinned,
+        constraint: describe_constraint(source),
+        error: None,
+    };
+
+    // `ion update` never moves git skills pinned to a rev.
+    if pinned && !source.is_binary() {
+        row.latest = current;
+        return row;
+    }
+
+    let Some(updater) = updater_for(source) else {
+        return row;
+    };
+    let locked = locked
+        .cloned()
+        .unwrap_or_else(|| placeholder_lock(name, source));
+    match updater.check(&locked, source) {
+        Ok(UpdateCheck::UpToDate) => row.latest = current,
+        Ok(UpdateCheck::Available(info)) => row.latest = Some(info.new_version),
+        Ok(UpdateCheck::Unknown) => {}
+        Err(e) => row.error = Some(e.to_string()),
+    }
+    row
+}
+
+/// The installed version as `ion update` reports it: the binary version,
+/// the resolved tag, a short commit, a short manifest digest for OCI skills,
+/// or a short checksum for HTTP skills.
+fn installed_version(locked: &LockedSkill) -> Option<String> {
+    if locked.is_binary() {
+        return locked.binary_version().map(str::to_string);
+    }
+    if let Some(tag) = locked.tag() {
+        return Some(tag.to_string());
+    }
+    if let Some(digest) = locked.digest().filter(|d| !d.is_empty()) {
+        return Some(short_digest(digest));
+    }
+    if let Some(commit) = locked.commit().filter(|c| !c.is_empty()) {
+        return Some(commit.get(..7).unwrap_or(commit).to_string());
+    }
+    locked
+        .checksum()
+        .filter(|c| !c.is_empty())
+        .map(short_digest)
+}
+
+/// What an update is allowed to move to, per Ion.toml.
+fn describe_constraint(source: &SkillSource) -> String {
+    if let Some(ref rev) = source.rev {
+        return format!("pinned to {rev}");
+    }
+    if let Some(ref channel) = source.channel {
+        let (key, value) = channel.manifest_field();
+        return format!("{key} {value}");
+    }
+    if let Some(ref version) = source.version {
+        return format!("version {version}");
+    }
+    if source.is_binary() {
+        "latest release".to_string()
+    } else if source.is_http() {
+        "served content".to_string()
+    } else if let Ok(reference) = ion_skill::oci::Reference::parse(&source.source) {
+        format!("tag {}", reference.reference)
+    } else {
+        "default branch".to_string()
+    }
+}
+
+fn print_table(rows: &[Outdated], p: &Paint) {
+    let latest_text = |row: &Outdated| match (&row.latest, &row.error) {
+        (_, Some(_)) => "check failed".to_string(),
+        (Some(latest), None) => latest.clone(),
+        (None, None) => "unknown".to_string(),
+    };
+    let current_text = |row: &Outdated| row.current.clone().unwrap_or_else(|| "-".to_string());
+
+    let name_w = rows.iter().map(|r| r.name.len()).max().unwrap_or(0).max(5);
+    let current_w = rows
+        .iter()
+        .map(|r| current_text(r).len())
+        .max()
+        .unwrap_or(0)
+        .max(7);
+    let latest_w = rows
+        .iter()
+        .map(|r| latest_text(r).len())
+        .max()
+        .unwrap_or(0)
+        .max(6);
+
+    println!(
+        "  {}",
+        p.dim(&format!(
+            "{:<name_w$}  {:<current_w$}  {:<latest_w$}  Allows",
+            "Skill", "Current", "Latest"
+        ))
+    );
+    for row in rows {
+        let latest = format!("{:<latest_w$}", latest_text(row));
+        let latest = if row.error.is_some() {
+            p.warn(&latest)
+        } else if row.has_update() {
+            p.info(&latest)
+        } else {
+            p.dim(&latest)
+        };
+        println!(
+            "  {}  {:<current_w$}  {}  {}",
+            p.bold(&format!("{:<name_w$}", row.name)),
+            current_text(row),
+            latest,
+            p.dim(&row.constraint)
+        );
+    }
+    for row in rows {
+        if let Some(ref error) = row.error {
+            println!("  {} {}: {}", p.warn("⚠"), p.bold(&row.name), p.warn(error));
+        }
+    }
+}
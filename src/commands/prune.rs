@@ -0,0 +1,97 @@
+use ion_skill::manifest::ManifestOptions;
+use ion_skill::prune::{Orphan, OrphanKind, declared_skills, find_orphans, remove_orphans};
+use ion_skill::workspace::Project;
+
+use crate::context::WorkspaceContext;
+use crate::style::Paint;
+
+pub fn run(dry_run: bool, json: bool, project_flags: &[String]) -> anyhow::Result<()> {
+    let ws = WorkspaceContext::load(project_flags)?;
+    let projects = ws.scoped_projects();
+    let p = ws.paint();
+    let multi = projects.len() > 1;
+
+    let mut total = 0usize;
+    let mut json_orphans: Vec<serde_json::Value> = Vec::new();
+
+    for project in &projects {
+        if !project.has_manifest() {
+            continue;
+        }
+        let options = ws.merged_options_for(project)?;
+        let installer = ws.installer_for(project, &options);
+        let lockfile = project.lockfile()?;
+        let declared = declared_skills(&project.manifest()?, &lockfile);
+        let orphans = find_orphans(&installer, &declared, &lockfile)?;
+        if orphans.is_empty() {
+            continue;
+        }
+        total += orphans.len();
+        if !dry_run {
+            remove_orphans(&installer, &orphans)?;
+        }
+
+        let label = ws.project_label(project);
+        if json {
+            for orphan in &orphans {
+                let mut value = serde_json::to_value(orphan)?;
+                value["project"] = serde_json::json!(label);
+                json_orphans.push(value);
+            }
+            continue;
+        }
+        if multi {
+            println!("\n{}:", p.bold(&label));
+        }
+        for orphan in &orphans {
+            let verb = if dry_run { "Would remove" } else { "Removed" };
+            println!("  {verb} {}", describe(orphan, &p));
+        }
+    }
+
+    if json {
+        crate::json::print_success(serde_json::json!({
+            "orphans": json_orphans,
+            "dry_run": dry_run,
+        }));
+        return Ok(());
+    }
+
+    if total == 0 {
+        println!("Nothing to prune.");
+    } else if dry_run {
+        println!("\nRun {} to remove them.", p.info("ion prune"));
+    }
+    Ok(())
+}
+
+/// Remove the orphaned links, copies and `.gitignore` lines of `project` and return
+/// them. Run at the end of `ion add`, which sees the manifest after any
+/// hand edits.
+pub fn prune_project(
+    ws: &WorkspaceContext,
+    project: &Project,
+    options: &ManifestOptions,
+) -> anyhow::Result<Vec<Orphan>> {
+    let installer = ws.installer_for(project, options);
+    let lockfile = project.lockfile()?;
+    let declared = declared_skills(&project.manifest()?, &lockfile);
+    let orphans = find_orphans(&installer, &declared, &lockfile)?;
+    remove_orphans(&installer, &orphans)?;
+    Ok(orphans)
+}
+
+/// Print one line per pruned orphan.
+pub fn print_pruned(orphans: &[Orphan], p: &Paint) {
+    for orphan in orphans {
+        println!("  Pruned {}", describe(orphan, p));
+    }
+}
+
+fn describe(orphan: &Orphan, p: &Paint) -> String {
+    match orphan.kind {
+        OrphanKind::Link => format!("link {}", p.info(&orphan.path)),
+        OrphanKind::Copy => format!("copy {}", p.info(&orphan.path)),
+        OrphanKind::Gitignore => format!("{} entry {}", p.dim(".gitignore"), orphan.path),
+    }
+}
@@ -0,0 +1,48 @@
+use std::path::PathBuf;
+
+use ion_skill::oci::Reference;
+use ion_skill::skill::SkillMetadata;
+
+pub fn run(path: &str, reference: &str, json: bool) -> anyhow::Result<()> {
+    let config = ion_skill::config::GlobalConfig::load()?;
+    let p = crate::style::Paint::new(&config);
+
+    let skill_path = PathBuf::from(path);
+    let skill_md = skill_path.join("SKILL.md");
+    if !skill_md.exists() {
+        anyhow::bail!(
+            "No SKILL.md found at {}. Is this a skill directory?",
+            skill_path.display()
+        );
+    }
+    let reference = Reference::parse(reference)?;
+    let (meta, _body) = SkillMetadata::from_file(&skill_md)?;
+
+    if !json {
+        println!(
+            "Pushing {} to {}...",
+            p.bold(&format!("'{}'", meta.name)),
+            p.info(&reference.to_string())
+        );
+    }
+
+    let digest = ion_skill::oci::push(&skill_path, &reference).map_err(|e| match e {
+        ion_skill::Error::ValidationFailed { .. } => {
+            anyhow::anyhow!("{e}. Run `ion validate {path}` for details")
+        }
+        e => e.into(),
+    })?;
+
+    if json {
+        crate::json::print_success(serde_json::json!({
+            "name": meta.name,
+            "reference": reference.to_string(),
+            "digest": digest,
+        }));
+        return Ok(());
+    }
+
+    println!("  {} Pushed {}", p.success("✓"), p.dim(&digest));
+    println!("  Install with: ion add {reference}");
+    Ok(())
+}
@@ -1,9 +1,16 @@
 use ion_skill::manifest_writer;
+use ion_skill::source::SkillSource;
 
 use crate::context::WorkspaceContext;
 
-pub fn run(name: &str, yes: bool, json: bool, project_flags: &[String]) -> anyhow::Result<()> {
-    let ws = WorkspaceContext::load(project_flags)?;
+pub fn run(
+    name: &str,
+    yes: bool,
+    json: bool,
+    global: bool,
+    project_flags: &[String],
+) -> anyhow::Result<()> {
+    let ws = WorkspaceContext::load_scoped(project_flags, global)?;
     let project = ws.single_project()?;
     let p = ws.paint();
     let manifest = project.manifest()?;
@@ -25,6 +32,12 @@ pub fn run(name: &str, yes: bool, json: bool, project_flags: &[String]) -> anyho
         matches
     };
 
+    // Dependencies that only the removed skills required
+    let mut lockfile = project.lockfile()?;
+    let mut remaining = manifest.skills.clone();
+    remaining.retain(|skill_name, _| !skills_to_remove.contains(skill_name));
+    let unused_dependencies = lockfile.orphans(&remaining);
+
     // Confirm before removing
     if !json {
         println!(
@@ -41,6 +54,7 @@ pub fn run(name: &str, yes: bool, json: bool, project_flags: &[String]) -> anyho
             "confirm_removal",
             serde_json::json!({
                 "skills": skills_to_remove,
+                "unused_dependencies": unused_dependencies,
             }),
         );
     }
@@ -56,8 +70,11 @@ pub fn run(name: &str, yes: bool, json: bool, project_flags: &[String]) -> anyho
         }
     }
 
+    // Offer to prune dependencies nothing else uses; --yes accepts
+    let prune =
+        !unused_dependencies.is_empty() && (yes || confirm_prune(&unused_dependencies, &p)?);
+
     let merged_options = ws.merged_options_for(project)?;
-    let mut lockfile = project.lockfile()?;
 
     for skill_name in &skills_to_remove {
         let entry = &manifest.skills[skill_name];
@@ -67,26 +84,22 @@ pub fn run(name: &str, yes: bool, json: bool, project_flags: &[String]) -> anyho
             println!("Removing skill {}...", p.bold(&format!("'{skill_name}'")));
         }
 
-        // For local skills, only remove symlinks — preserve the actual skill directory
+        // For local skills, only remove deployments — preserve the actual skill directory
         if let Ok(ref source) = entry_source
             && source.is_local()
         {
-            for target_path in merged_options.targets.values() {
-                let target_dir = project.dir.join(target_path).join(skill_name);
-                if target_dir.is_symlink() {
-                    std::fs::remove_file(&target_dir)?;
-                }
-            }
-            // Remove .agents symlink only if it IS a symlink (custom skills-dir)
-            let agents_dir = project
+            let skills_dir = project
                 .dir
                 .join(merged_options.skills_dir_or_default())
                 .join(skill_name);
-            if agents_dir.is_symlink() {
-                std::fs::remove_file(&agents_dir)?;
-            }
+            let local_skill_dir = match source.path {
+                Some(ref path) => project.dir.join(path),
+                None => skills_dir,
+            };
+            ws.installer_for(project, &merged_options)
+                .undeploy(skill_name, &local_skill_dir)?;
             if !json {
-                println!("  Removed symlinks for {}", p.info(skill_name));
+                println!("  Removed deployments of {}", p.info(skill_name));
                 println!("  {}: local skill directory preserved", p.dim("note"));
             }
         } else {
@@ -104,7 +117,8 @@ pub fn run(name: &str, yes: bool, json: bool, project_flags: &[String]) -> anyho
         }
 
         // Skip gitignore removal for local skills (they were never gitignored)
-        if !matches!(entry_source.as_ref().map(|s| s.is_local()), Ok(true)) {
+        // and for the global scope, which never writes .gitignore
+        if !project.global && !matches!(entry_source.as_ref().map(|s| s.is_local()), Ok(true)) {
             ion_skill::gitignore::remove_skill_entries(&project.dir, skill_name)?;
             if !json {
                 println!("  Updated {}", p.dim(".gitignore"));
@@ -113,7 +127,10 @@ pub fn run(name: &str, yes: bool, json: bool, project_flags: &[String]) -> anyho
 
         // Unregister from global registry for git-based sources
         if let Ok(ref source) = entry_source {
-            crate::commands::install_shared::unregister_from_registry(source, &project.dir)?;
+            crate::commands::install_shared::unregister_from_registry(
+                source,
+                project.manifest_dir(),
+            )?;
         }
 
         // Clean up binary files if this is a binary skill
@@ -135,11 +152,40 @@ pub fn run(name: &str, yes: bool, json: bool, project_flags: &[String]) -> anyho
         lockfile.remove(skill_name);
     }
 
+    let mut pruned = Vec::new();
+    if prune {
+        let installer = ws.installer_for(project, &merged_options);
+        for dep_name in &unused_dependencies {
+            if !json {
+                println!(
+                    "Removing unused dependency {}...",
+                    p.bold(&format!("'{dep_name}'"))
+                );
+            }
+            installer.uninstall(dep_name)?;
+            if !project.global {
+                ion_skill::gitignore::remove_skill_entries(&project.dir, dep_name)?;
+            }
+            if let Some(locked) = lockfile.find(dep_name)
+                && let Ok(mut source) = SkillSource::infer(&locked.source)
+            {
+                source.path = locked.path.clone();
+                crate::commands::install_shared::unregister_from_registry(
+                    &source,
+                    project.manifest_dir(),
+                )?;
+            }
+            lockfile.remove(dep_name);
+            pruned.push(dep_name.clone());
+        }
+    }
+
     lockfile.write_to(&project.lockfile_path)?;
 
     if json {
         crate::json::print_success(serde_json::json!({
             "removed": skills_to_remove,
+            "pruned": pruned,
         }));
         return Ok(());
     }
@@ -150,6 +196,23 @@ pub fn run(name: &str, yes: bool, json: bool, project_flags: &[String]) -> anyho
     Ok(())
 }
 
+/// Ask whether to also remove dependencies that only the removed skills required.
+fn confirm_prune(unused: &[String], p: &crate::style::Paint) -> anyhow::Result<bool> {
+    use std::io::Write;
+    println!(
+        "{} dependenc{} no longer required by any skill: {}",
+        p.bold(&unused.len().to_string()),
+        if unused.len() == 1 { "y is" } else { "ies are" },
+        unused.join(", ")
+    );
+    print!("Remove them too? [Y/n] ");
+    std::io::stdout().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    let answer = answer.trim();
+    Ok(answer.is_empty() || answer.eq_ignore_ascii_case("y") || answer.eq_ignore_ascii_case("yes"))
+}
+
 /// Check if a skill matches the query by name or source.
 /// Matches if the query appears anywhere in the skill name or source string.
 fn skill_matches(skill_name: &str, entry: &ion_skill::manifest::SkillEntry, query: &str) -> bool {
@@ -107,8 +107,13 @@ pub fn run(
 }
 
 /// Refresh the skill file from the last-built dev binary if it is newer than the
-/// current SKILL.md. Best-effort: all errors are silently ignored.
-fn try_refresh_dev_skill(source_path: &str, binary_name: &str, skill_md_path: &Path) {
+/// current SKILL.md. Best-effort: all errors are silently ignored. Returns
+/// whether SKILL.md was rewritten.
+pub(crate) fn try_refresh_dev_skill(
+    source_path: &str,
+    binary_name: &str,
+    skill_md_path: &Path,
+) -> bool {
     let bin_name = if cfg!(windows) {
         format!("{binary_name}.exe")
     } else {
@@ -120,7 +125,7 @@ fn try_refresh_dev_skill(source_path: &str, binary_name: &str, skill_md_path: &P
         .join(&bin_name);
 
     if !debug_bin.exists() {
-        return;
+        return false;
     }
 
     // Skip if the skill file is already up to date (binary not newer).
@@ -132,16 +137,14 @@ fn try_refresh_dev_skill(source_path: &str, binary_name: &str, skill_md_path: &P
         _ => true,
     };
     if !should_refresh {
-        return;
+        return false;
     }
 
     let Ok(content) = binary::generate_skill_md(&debug_bin) else {
-        return;
+        return false;
     };
     let current = std::fs::read_to_string(skill_md_path).unwrap_or_default();
-    if current != content {
-        let _ = std::fs::write(skill_md_path, content);
-    }
+    current != content && std::fs::write(skill_md_path, content).is_ok()
 }
 
 /// Run a dev-mode binary skill by forwarding to `cargo run` in the project directory.
@@ -6,6 +6,7 @@ use ion_skill::search::{
     AgentSource, GitHubSource, RegistrySource, SearchCache, SearchResult, SearchSource,
     SkillsShSource, enrich_results, owner_repo_of, parallel_search, skill_dir_name,
 };
+use ion_skill::source::SkillSource;
 
 pub fn run(
     query: &str,
@@ -154,6 +155,11 @@ fn search_single_source(
     query: &str,
     limit: usize,
 ) -> anyhow::Result<Vec<SearchResult>> {
+    if ion_skill::offline::is_offline() {
+        log::debug!("offline: serving {name} from the search cache");
+        let cached = SearchCache::new().and_then(|c| c.get(name, query, u64::MAX));
+        return Ok(cached.unwrap_or_default());
+    }
     if name == "github" {
         log::debug!("searching GitHub for: {query:?}");
         return Ok(GitHubSource.search(query, limit)?);
@@ -268,7 +274,10 @@ fn group_by_owner_repo_refs<'a>(
 fn source_url(registry: &str, source: &str) -> String {
     match registry {
         "skills.sh" | "skills-sh" => format!("https://skills.sh/{source}"),
-        _ => format!("https://github.com/{source}"),
+        _ => match SkillSource::infer(source) {
+            Ok(s) if !s.is_github() => s.web_url().unwrap_or_else(|| source.to_string()),
+            _ => format!("https://github.com/{source}"),
+        },
     }
 }
 
@@ -505,7 +514,18 @@ fn pick_and_install(results: &[SearchResult]) -> anyhow::Result<()> {
         && let Some(source) = app.selected_install_source()
     {
         log::debug!("user selected install source: {source}");
-        crate::commands::add::run(source, None, false, false, None, false, false, None, &[])?;
+        crate::commands::add::run(
+            source,
+            None,
+            false,
+            false,
+            None,
+            false,
+            false,
+            None,
+            false,
+            &[],
+        )?;
     }
 
     Ok(())
@@ -1,79 +1,191 @@
+use std::collections::HashMap;
 use std::io::IsTerminal;
 
 use indicatif::{ProgressBar, ProgressStyle};
+use ion_skill::installer::InstallValidationOptions;
 use ion_skill::lockfile::LockedSkill;
-use ion_skill::source::SkillSourceKind;
-use ion_skill::update::Updater;
+use ion_skill::source::{SkillSource, SkillSourceKind};
 use ion_skill::update::binary::BinaryUpdater;
 use ion_skill::update::git::GitUpdater;
+use ion_skill::update::http::HttpUpdater;
+use ion_skill::update::oci::OciUpdater;
+use ion_skill::update::{Candidate, UpdateCheck, UpdateInfo, Updater, apply_candidate};
 use ion_skill::workspace::Project;
 
+use crate::commands::install_shared::install_dependencies;
+use crate::commands::update_preview::{
+    preview_git_update, preview_http_update, preview_oci_update, print_preview,
+};
+use crate::commands::validation::{SelectItem, select_skills};
 use crate::context::WorkspaceContext;
 use crate::style::Paint;
 
-pub fn run(name: Option<&str>, json: bool, project_flags: &[String]) -> anyhow::Result<()> {
-    let ws = WorkspaceContext::load(project_flags)?;
+/// Per-project results of an update pass, merged across the workspace.
+#[derive(Default)]
+struct UpdateSummary {
+    updated: u32,
+    available: u32,
+    skipped: u32,
+    failed: u32,
+    up_to_date: u32,
+    json_updated: Vec<serde_json::Value>,
+    json_available: Vec<serde_json::Value>,
+    json_skipped: Vec<serde_json::Value>,
+    json_failed: Vec<serde_json::Value>,
+    json_up_to_date: Vec<serde_json::Value>,
+    /// Updates a dry run found.
+    pending: Vec<Pending>,
+    /// Skills this pass updated or repaired.
+    applied: Vec<String>,
+    /// Dependencies installed for the applied skills.
+    dependencies: Vec<String>,
+}
+
+/// An update a dry run found.
+struct Pending {
+    name: String,
+    /// The version change, e.g. `"old → new"`.
+    change: String,
+    previewed: Option<Previewed>,
+}
+
+/// An update as it was previewed, so interactive mode applies exactly what
+/// the user saw rather than whatever the source serves by then.
+struct Previewed {
+    info: UpdateInfo,
+    candidate: Candidate,
+}
+
+/// Skills the user picked in interactive mode, with what each was previewed at.
+type Selection = HashMap<String, Option<Previewed>>;
+
+impl UpdateSummary {
+    fn merge(&mut self, other: UpdateSummary) {
+        self.updated += other.updated;
+        self.available += other.available;
+        self.skipped += other.skipped;
+        self.failed += other.failed;
+        self.up_to_date += other.up_to_date;
+        self.json_updated.extend(other.json_updated);
+        self.json_available.extend(other.json_available);
+        self.json_skipped.extend(other.json_skipped);
+        self.json_failed.extend(other.json_failed);
+        self.json_up_to_date.extend(other.json_up_to_date);
+        self.dependencies.extend(other.dependencies);
+    }
+}
+
+pub fn run(
+    name: Option<&str>,
+    dry_run: bool,
+    interactive: bool,
+    json: bool,
+    global: bool,
+    project_flags: &[String],
+) -> anyhow::Result<()> {
+    if interactive && json {
+        anyhow::bail!("--interactive cannot be combined with --json");
+    }
+
+    let ws = WorkspaceContext::load_scoped(project_flags, global)?;
     let projects = ws.scoped_projects();
     let p = ws.paint();
     let multi = projects.len() > 1;
 
-    let mut total_updated = 0u32;
-    let mut total_skipped = 0u32;
-    let mut total_failed = 0u32;
-    let mut total_up_to_date = 0u32;
+    // Interactive mode previews every update first, then applies only the
+    // ones the user keeps ticked.
+    let mut selection = if interactive {
+        match select_updates(name, &ws, &projects, &p)? {
+            Some(selection) => Some(selection),
+            None => return Ok(()),
+        }
+    } else {
+        None
+    };
+    let dry_run = dry_run && selection.is_none();
 
-    let mut all_json_updated: Vec<serde_json::Value> = Vec::new();
-    let mut all_json_skipped: Vec<serde_json::Value> = Vec::new();
-    let mut all_json_failed: Vec<serde_json::Value> = Vec::new();
-    let mut all_json_up_to_date: Vec<serde_json::Value> = Vec::new();
+    let mut total = UpdateSummary::default();
 
-    for project in &projects {
+    for (index, project) in projects.iter().enumerate() {
         if !project.has_manifest() {
             continue;
         }
+        let only = match selection {
+            Some(ref mut selection) => match selection.remove(&index) {
+                Some(names) => Some(names),
+                None => continue,
+            },
+            None => None,
+        };
+        let whole_project = only.is_none();
         let manifest = project.manifest()?;
         let mut lockfile = project.lockfile()?;
 
         let options = ws.merged_options_for(project)?;
         let installer = ws.installer_for(project, &options);
 
-        // Ensure built-in skill and agent symlinks are up to date (non-fatal)
-        ws.ensure_builtin_skill(project, &options);
-        if let Err(e) = ion_skill::agents::ensure_agent_symlinks(&project.dir, &options.targets) {
-            log::warn!("Failed to create agent symlinks: {e}");
+        if !dry_run {
+            // Ensure built-in skill and agent symlinks are up to date (non-fatal)
+            ws.ensure_builtin_skill(project, &options);
+            if !project.global
+                && let Err(e) = ion_skill::agents::ensure_agent_symlinks(
+                    &project.dir,
+                    &options.targets,
+                    &ws.catalog(),
+                )
+            {
+                log::warn!("Failed to create agent symlinks: {e}");
+            }
         }
 
         if multi && !json {
-            let label = project_label(project, &ws);
+            let label = ws.project_label(project);
             println!("\n{}:", p.bold(&label));
         }
 
-        let (updated, skipped, failed, up_to_date, ju, js, jf, jut) = update_project_skills(
+        let mut summary = update_project_skills(
             name,
+            only,
+            dry_run,
             json,
             &manifest,
             &mut lockfile,
             &installer,
             &p,
-            project,
         )?;
+        let updated = summary.updated;
 
-        total_updated += updated;
-        total_skipped += skipped;
-        total_failed += failed;
-        total_up_to_date += up_to_date;
-        all_json_updated.extend(ju);
-        all_json_skipped.extend(js);
-        all_json_failed.extend(jf);
-        all_json_up_to_date.extend(jut);
+        // A new version may require skills the old one didn't
+        if !summary.applied.is_empty() {
+            summary.dependencies = install_dependencies(
+                &installer,
+                project,
+                &options,
+                &summary.applied,
+                &mut lockfile,
+                InstallValidationOptions {
+                    skip_validation: false,
+                    allow_warnings: true,
+                },
+                false,
+                &p,
+                json,
+            )?;
+        }
+        total.merge(summary);
+
+        if dry_run {
+            continue;
+        }
 
         // Check for agents template update (non-fatal)
         let mut agents_updated = false;
-        if manifest
-            .agents
-            .as_ref()
-            .and_then(|a| a.template.as_ref())
-            .is_some()
+        if whole_project
+            && manifest
+                .agents
+                .as_ref()
+                .and_then(|a| a.template.as_ref())
+                .is_some()
         {
             match crate::commands::agents::update_template_non_fatal(
                 project,
@@ -101,31 +213,48 @@ pub fn run(name: Option<&str>, json: bool, project_flags: &[String]) -> anyhow::
     }
 
     if json {
-        crate::json::print_success(serde_json::json!({
-            "updated": all_json_updated,
-            "skipped": all_json_skipped,
-            "failed": all_json_failed,
-            "up_to_date": all_json_up_to_date,
-        }));
+        if dry_run {
+            crate::json::print_success(serde_json::json!({
+                "dry_run": true,
+                "available": total.json_available,
+                "skipped": total.json_skipped,
+                "failed": total.json_failed,
+                "up_to_date": total.json_up_to_date,
+            }));
+        } else {
+            crate::json::print_success(serde_json::json!({
+                "updated": total.json_updated,
+                "dependencies": total.dependencies,
+                "skipped": total.json_skipped,
+                "failed": total.json_failed,
+                "up_to_date": total.json_up_to_date,
+            }));
+        }
         return Ok(());
     }
 
     // Print summary
     let mut parts = Vec::new();
-    if total_updated > 0 {
-        parts.push(format!("{} updated", total_updated));
+    if total.updated > 0 {
+        parts.push(format!("{} updated", total.updated));
     }
-    if total_skipped > 0 {
-        parts.push(format!("{} skipped", total_skipped));
+    if total.available > 0 {
+        parts.push(format!("{} available", total.available));
     }
-    if total_failed > 0 {
-        parts.push(format!("{} failed", total_failed));
+    if total.skipped > 0 {
+        parts.push(format!("{} skipped", total.skipped));
     }
-    if total_up_to_date > 0 {
-        parts.push(format!("{} up to date", total_up_to_date));
+    if total.failed > 0 {
+        parts.push(format!("{} failed", total.failed));
+    }
+    if total.up_to_date > 0 {
+        parts.push(format!("{} up to date", total.up_to_date));
     }
     if !parts.is_empty() {
         println!("\n{}", parts.join(", "));
+        if total.available > 0 {
+            println!("Dry run: nothing was changed. Run `ion update` to apply.");
+        }
     } else if name.is_some() {
         // Only bail if targeting a specific skill and it wasn't found in any project
         let n = name.unwrap();
@@ -137,31 +266,110 @@ pub fn run(name: Option<&str>, json: bool, project_flags: &[String]) -> anyhow::
     Ok(())
 }
 
-/// Update skills within a single project. Returns counts and JSON arrays.
+/// Preview every available update, then let the user pick which to apply.
+///
+/// Returns the selected skills keyed by project index, or `None` when
+/// there is nothing to apply (no updates, or the user cancelled).
+fn select_updates(
+    name: Option<&str>,
+    ws: &WorkspaceContext,
+    projects: &[&Project],
+    p: &Paint,
+) -> anyhow::Result<Option<HashMap<usize, Selection>>> {
+    let multi = projects.len() > 1;
+    let mut candidates: Vec<(usize, Pending)> = Vec::new();
+    let mut items: Vec<SelectItem> = Vec::new();
+
+    for (index, project) in projects.iter().enumerate() {
+        if !project.has_manifest() {
+            continue;
+        }
+        let manifest = project.manifest()?;
+        let mut lockfile = project.lockfile()?;
+        let options = ws.merged_options_for(project)?;
+        let installer = ws.installer_for(project, &options);
+        let label = ws.project_label(project);
+
+        if multi {
+            println!("\n{}:", p.bold(&label));
+        }
+        let summary = update_project_skills(
+            name,
+            None,
+            true,
+            false,
+            &manifest,
+            &mut lockfile,
+            &installer,
+            p,
+        )?;
+        for pending in summary.pending {
+            let (skill_name, change) = (&pending.name, &pending.change);
+            let label = if multi {
+                format!("{skill_name} {change} ({label})")
+            } else {
+                format!("{skill_name} {change}")
+            };
+            items.push(SelectItem {
+                prompt: format!("Update {label}?"),
+                label,
+            });
+            candidates.push((index, pending));
+        }
+    }
+
+    if items.is_empty() {
+        println!("\nNo updates available.");
+        return Ok(None);
+    }
+
+    println!();
+    let Some(selected) = select_skills("Select which skills to update", &items)? else {
+        println!("Update cancelled.");
+        return Ok(None);
+    };
+
+    let mut selection: HashMap<usize, Selection> = HashMap::new();
+    for ((index, pending), keep) in candidates.into_iter().zip(selected) {
+        if keep {
+            selection
+                .entry(index)
+                .or_default()
+                .insert(pending.name, pending.previewed);
+        }
+    }
+    if selection.is_empty() {
+        println!("No skills selected.");
+        return Ok(None);
+    }
+    println!();
+    Ok(Some(selection))
+}
+
+/// Update skills within a single project.
+///
+/// `only` restricts the pass to the given skills, applying any previewed
+/// candidate as-is. With `dry_run`, updates are checked and previewed but
+/// nothing is installed.
 #[allow(clippy::too_many_arguments)]
-#[allow(clippy::type_complexity)]
 fn update_project_skills(
     name: Option<&str>,
+    mut only: Option<Selection>,
+    dry_run: bool,
     json: bool,
     manifest: &ion_skill::manifest::Manifest,
     lockfile: &mut ion_skill::lockfile::Lockfile,
     installer: &ion_skill::installer::SkillInstaller<'_>,
     p: &Paint,
-    project: &Project,
-) -> anyhow::Result<(
-    u32,
-    u32,
-    u32,
-    u32,
-    Vec<serde_json::Value>,
-    Vec<serde_json::Value>,
-    Vec<serde_json::Value>,
-    Vec<serde_json::Value>,
-)> {
+) -> anyhow::Result<UpdateSummary> {
     let skills_to_check: Vec<(String, _)> = manifest
         .skills
         .iter()
         .filter(|(skill_name, _)| name.is_none() || name == Some(skill_name.as_str()))
+        .filter(|(skill_name, _)| {
+            only.as_ref()
+                .is_none_or(|only| only.contains_key(skill_name.as_str()))
+        })
         .filter_map(|(skill_name, entry)| match entry.resolve() {
             Ok(source) => Some((skill_name.clone(), source)),
             Err(e) => {
@@ -171,35 +379,27 @@ fn update_project_skills(
         })
         .collect();
 
+    let mut summary = UpdateSummary::default();
     if skills_to_check.is_empty() {
-        return Ok((0, 0, 0, 0, Vec::new(), Vec::new(), Vec::new(), Vec::new()));
+        return Ok(summary);
     }
 
-    // Count updatable skills (skip path/http/local which are silently ignored)
+    // Count updatable skills (skip path/local which are silently ignored)
     let updatable_count = skills_to_check
         .iter()
-        .filter(|(_, s)| !s.is_path() && !s.is_http() && !s.is_local())
+        .filter(|(_, s)| !s.is_path() && !s.is_local())
         .count() as u64;
 
-    let pb = if !json && updatable_count > 0 {
+    // Previews print multi-line diffs, which don't mix with a progress bar
+    let pb = if !json && !dry_run && updatable_count > 0 {
         Some(make_progress_bar(p, updatable_count))
     } else {
         None
     };
 
-    let mut updated_count = 0u32;
-    let mut skipped_count = 0u32;
-    let mut failed_count = 0u32;
-    let mut up_to_date_count = 0u32;
-
-    let mut json_updated: Vec<serde_json::Value> = Vec::new();
-    let mut json_skipped: Vec<serde_json::Value> = Vec::new();
-    let mut json_failed: Vec<serde_json::Value> = Vec::new();
-    let mut json_up_to_date: Vec<serde_json::Value> = Vec::new();
-
     for (skill_name, source) in &skills_to_check {
-        // Skip Path and Http source types silently
-        if source.is_path() || source.is_http() || source.is_local() {
+        // Skip Path and local source types silently
+        if source.is_path() || source.is_local() {
             continue;
         }
 
@@ -207,6 +407,29 @@ fn update_project_skills(
             pb.set_message(format!("checking {}", skill_name));
         }
 
+        // An archive URL names one release; there is nothing newer to look up
+        if source.is_archive() {
+            if !json {
+                pb_println(
+                    &pb,
+                    format!(
+                        "  {} {}  {}",
+                        p.dim("-"),
+                        p.bold(skill_name),
+                        p.dim("skipped (archive: point its source at a new release in Ion.toml)")
+                    ),
+                );
+            }
+            summary
+                .json_skipped
+                .push(serde_json::json!({ "name": skill_name, "reason": "archive" }));
+            summary.skipped += 1;
+            if let Some(ref pb) = pb {
+                pb.inc(1);
+            }
+            continue;
+        }
+
         // Skip non-binary skills with rev set (pinned)
         if !source.is_binary()
             && let Some(ref rev) = source.rev
@@ -222,10 +445,10 @@ fn update_project_skills(
                     ),
                 );
             }
-            json_skipped.push(
+            summary.json_skipped.push(
                 serde_json::json!({ "name": skill_name, "reason": format!("pinned to {}", rev) }),
             );
-            skipped_count += 1;
+            summary.skipped += 1;
             if let Some(ref pb) = pb {
                 pb.inc(1);
             }
@@ -233,51 +456,52 @@ fn update_project_skills(
         }
 
         // Select updater based on source type
-        let updater: Box<dyn Updater> = match &source.kind {
-            SkillSourceKind::Binary { .. } => Box::new(BinaryUpdater),
-            SkillSourceKind::Github | SkillSourceKind::Git => Box::new(GitUpdater),
-            _ => {
-                if let Some(ref pb) = pb {
-                    pb.inc(1);
-                }
-                continue;
+        let Some(updater) = updater_for(source) else {
+            if let Some(ref pb) = pb {
+                pb.inc(1);
             }
+            continue;
         };
 
         // Get or create locked skill
-        let locked = lockfile.find(skill_name).cloned().unwrap_or_else(|| {
-            let mut fallback = if source.is_binary() {
-                let binary_name = match &source.kind {
-                    SkillSourceKind::Binary { binary_name, .. } if !binary_name.is_empty() => {
-                        binary_name.as_str()
-                    }
-                    _ => skill_name.as_str(),
-                };
-                LockedSkill::binary(
-                    skill_name.clone(),
-                    source.source.clone(),
-                    binary_name,
-                    None,
-                    None,
-                )
-            } else {
-                LockedSkill::git(
-                    skill_name.clone(),
-                    source.source.clone(),
-                    String::new(),
-                    String::new(),
-                )
-            };
-            if let Some(ref path) = source.path {
-                fallback = fallback.with_path(path.clone());
+        let locked = lockfile
+            .find(skill_name)
+            .cloned()
+            .unwrap_or_else(|| placeholder_lock(skill_name, source));
+
+        // Check for update; a previewed one is applied as it was shown
+        let previewed = only
+            .as_mut()
+            .and_then(|only| only.remove(skill_name))
+            .flatten();
+        let check = match previewed {
+            Some(ref previewed) => Ok(UpdateCheck::Available(previewed.info.clone())),
+            None => updater.check(&locked, source),
+        };
+        let update_info = match check {
+            Ok(UpdateCheck::Available(info)) => Some(info),
+            Ok(UpdateCheck::Unknown) => {
+                if !json {
+                    pb_println(
+                        &pb,
+                        format!(
+                            "  {} {}  {}",
+                            p.dim("?"),
+                            p.bold(skill_name),
+                            p.dim("unknown (offline)")
+                        ),
+                    );
+                }
+                summary
+                    .json_skipped
+                    .push(serde_json::json!({ "name": skill_name, "reason": "offline" }));
+                summary.skipped += 1;
+                if let Some(ref pb) = pb {
+                    pb.inc(1);
+                }
+                continue;
             }
-            fallback
-        });
-
-        // Check for update
-        let update_info = match updater.check(&locked, source) {
-            Ok(Some(info)) => Some(info),
-            Ok(None) => {
+            Ok(UpdateCheck::UpToDate) => {
                 // Remote is up to date — verify local deployment is intact
                 if installer.is_deployed(skill_name) {
                     if !json {
@@ -291,8 +515,10 @@ fn update_project_skills(
                             ),
                         );
                     }
-                    json_up_to_date.push(serde_json::json!({ "name": skill_name }));
-                    up_to_date_count += 1;
+                    summary
+                        .json_up_to_date
+                        .push(serde_json::json!({ "name": skill_name }));
+                    summary.up_to_date += 1;
                     if let Some(ref pb) = pb {
                         pb.inc(1);
                     }
@@ -313,8 +539,10 @@ fn update_project_skills(
                         ),
                     );
                 }
-                json_failed.push(serde_json::json!({ "name": skill_name, "error": e.to_string() }));
-                failed_count += 1;
+                summary
+                    .json_failed
+                    .push(serde_json::json!({ "name": skill_name, "error": e.to_string() }));
+                summary.failed += 1;
                 if let Some(ref pb) = pb {
                     pb.inc(1);
                 }
@@ -322,6 +550,19 @@ fn update_project_skills(
             }
         };
 
+        if dry_run {
+            preview_update(
+                skill_name,
+                source,
+                &locked,
+                update_info.as_ref(),
+                json,
+                p,
+                &mut summary,
+            );
+            continue;
+        }
+
         // Apply update or repair
         if let Some(ref pb) = pb {
             let action = if update_info.is_some() {
@@ -332,7 +573,11 @@ fn update_project_skills(
             pb.set_message(format!("{} {}", action, skill_name));
         }
 
-        match updater.apply(&locked, source, installer) {
+        let applied = match previewed {
+            Some(previewed) => apply_candidate(&locked, source, previewed.candidate, installer),
+            None => updater.apply(&locked, source, installer),
+        };
+        match applied {
             Ok(new_locked) => {
                 if !json {
                     if let Some(ref info) = update_info {
@@ -361,20 +606,21 @@ fn update_project_skills(
                     }
                 }
                 if let Some(ref info) = update_info {
-                    json_updated.push(serde_json::json!({
+                    summary.json_updated.push(serde_json::json!({
                         "name": skill_name,
                         "old_version": info.old_version,
                         "new_version": info.new_version,
                         "binary": source.is_binary(),
                     }));
                 } else {
-                    json_updated.push(serde_json::json!({
+                    summary.json_updated.push(serde_json::json!({
                         "name": skill_name,
                         "repaired": true,
                     }));
                 }
                 lockfile.upsert(new_locked);
-                updated_count += 1;
+                summary.applied.push(skill_name.clone());
+                summary.updated += 1;
             }
             Err(e) => {
                 if !json {
@@ -388,8 +634,10 @@ fn update_project_skills(
                         ),
                     );
                 }
-                json_failed.push(serde_json::json!({ "name": skill_name, "error": e.to_string() }));
-                failed_count += 1;
+                summary
+                    .json_failed
+                    .push(serde_json::json!({ "name": skill_name, "error": e.to_string() }));
+                summary.failed += 1;
             }
         }
 
@@ -403,32 +651,144 @@ fn update_project_skills(
         pb.finish_and_clear();
     }
 
-    let _ = project; // used for scope clarity
-
-    Ok((
-        updated_count,
-        skipped_count,
-        failed_count,
-        up_to_date_count,
-        json_updated,
-        json_skipped,
-        json_failed,
-        json_up_to_date,
-    ))
+    Ok(summary)
+}
+
+/// Report what `ion update` would do for one skill without applying it.
+/// Git, HTTP and OCI updates show a diff of the skill directory and any new
+/// validation findings; binary updates show only the version change.
+fn preview_update(
+    skill_name: &str,
+    source: &SkillSource,
+    locked: &LockedSkill,
+    update_info: Option<&UpdateInfo>,
+    json: bool,
+    p: &Paint,
+    summary: &mut UpdateSummary,
+) {
+    let Some(info) = update_info else {
+        if !json {
+            println!(
+                "  {} {}  {}",
+                p.info("~"),
+                p.bold(skill_name),
+                p.info("would repair")
+            );
+        }
+        summary
+            .json_available
+            .push(serde_json::json!({ "name": skill_name, "repair": true }));
+        summary.pending.push(Pending {
+            name: skill_name.to_string(),
+            change: "(repair)".to_string(),
+            previewed: None,
+        });
+        summary.available += 1;
+        return;
+    };
+
+    let preview = if source.is_binary() {
+        None
+    } else {
+        let preview = if source.is_http() {
+            preview_http_update(source)
+        } else if source.is_oci() {
+            preview_oci_update(locked, source)
+        } else {
+            preview_git_update(locked, source)
+        };
+        match preview {
+            Ok(preview) => Some(preview),
+            Err(e) => {
+                if !json {
+                    println!(
+                        "  {} {}  {}",
+                        p.warn("✗"),
+                        p.bold(skill_name),
+                        p.warn(&format!("preview failed: {e}"))
+                    );
+                }
+                summary
+                    .json_failed
+                    .push(serde_json::json!({ "name": skill_name, "error": e.to_string() }));
+                summary.failed += 1;
+                return;
+            }
+        }
+    };
+
+    if json {
+        summary.json_available.push(serde_json::json!({
+            "name": skill_name,
+            "old_version": info.old_version,
+            "new_version": info.new_version,
+            "binary": source.is_binary(),
+            "diff": preview.as_ref().map(|preview| preview.diff.as_str()),
+            "new_findings": preview.as_ref().map(|preview| &preview.new_findings),
+        }));
+    } else {
+        let binary_suffix = if source.is_binary() { " (binary)" } else { "" };
+        println!(
+            "  {} {}  {} → {}{}",
+            p.info("↑"),
+            p.bold(skill_name),
+            info.old_version,
+            p.info(&info.new_version),
+            binary_suffix
+        );
+        if let Some(ref preview) = preview {
+            print_preview(preview, p);
+        }
+    }
+    summary.pending.push(Pending {
+        name: skill_name.to_string(),
+        change: format!("{} → {}", info.old_version, info.new_version),
+        previewed: preview.map(|preview| Previewed {
+            info: info.clone(),
+            candidate: preview.candidate,
+        }),
+    });
+    summary.available += 1;
+}
+
+/// The updater for a source, or `None` for sources that aren't updated.
+pub(crate) fn updater_for(source: &SkillSource) -> Option<Box<dyn Updater>> {
+    match &source.kind {
+        SkillSourceKind::Binary { .. } => Some(Box::new(BinaryUpdater)),
+        SkillSourceKind::Github | SkillSourceKind::Git => Some(Box::new(GitUpdater)),
+        SkillSourceKind::Http => Some(Box::new(HttpUpdater)),
+        SkillSourceKind::Oci => Some(Box::new(OciUpdater)),
+        _ => None,
+    }
 }
 
-/// Human-readable label for a project within a workspace.
-fn project_label(project: &Project, ws: &WorkspaceContext) -> String {
-    let root_dir = ws.root_dir();
-    if project.dir == root_dir {
-        ". (root)".to_string()
+/// Lock entry standing in for a skill that isn't in Ion.lock yet, so its
+/// update check compares against nothing installed.
+pub(crate) fn placeholder_lock(skill_name: &str, source: &SkillSource) -> LockedSkill {
+    let mut fallback = if source.is_binary() {
+        let binary_name = match &source.kind {
+            SkillSourceKind::Binary { binary_name, .. } if !binary_name.is_empty() => {
+                binary_name.as_str()
+            }
+            _ => skill_name,
+        };
+        LockedSkill::binary(skill_name, source.source.clone(), binary_name, None, None)
+    } else if source.is_http() {
+        LockedSkill::http(skill_name, source.source.clone())
+    } else if source.is_oci() {
+        LockedSkill::oci(skill_name, source.source.clone(), String::new())
     } else {
-        project
-            .dir
-            .strip_prefix(root_dir)
-            .map(|p| p.display().to_string())
-            .unwrap_or_else(|_| project.dir.display().to_string())
+        LockedSkill::git(
+            skill_name,
+            source.source.clone(),
+            String::new(),
+            String::new(),
+        )
+    };
+    if let Some(ref path) = source.path {
+        fallback = fallback.with_path(path.clone());
     }
+    fallback
 }
 
 /// Create a progress bar styled for the update command.
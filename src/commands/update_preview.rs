@@ -0,0 +1,204 @@
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use ion_skill::installer::resolve_skill_dir;
+use ion_skill::lockfile::LockedSkill;
+use ion_skill::skill::SkillMetadata;
+use ion_skill::source::SkillSource;
+use ion_skill::update::Candidate;
+use ion_skill::update::git::GitUpdater;
+use ion_skill::validate::{self, Finding};
+use ion_skill::{http_cache, oci};
+use similar::TextDiff;
+
+use crate::style::Paint;
+
+/// What an available git, HTTP or OCI update would change, computed without applying it.
+pub struct UpdatePreview {
+    /// Unified diff of the skill directory from the locked content to the candidate.
+    pub diff: String,
+    /// Validation findings for the candidate that the locked version doesn't have.
+    pub new_findings: Vec<Finding>,
+    /// The previewed version, for applying exactly what was shown.
+    pub candidate: Candidate,
+}
+
+/// Snapshot the commit `ion update` would move to, diff it against the
+/// locked commit and revalidate it. Nothing is deployed.
+pub fn preview_git_update(
+    locked: &LockedSkill,
+    source: &SkillSource,
+) -> anyhow::Result<UpdatePreview> {
+    let updater = GitUpdater;
+    let candidate = updater.candidate(source)?;
+    let old_dir = updater.locked_dir(locked, source)?;
+    preview_dirs(old_dir.as_deref(), Candidate::Git(candidate))
+}
+
+/// Download what an HTTP skill's URL serves now and diff it against the
+/// cached copy. Nothing is deployed and the cache is left untouched.
+pub fn preview_http_update(source: &SkillSource) -> anyhow::Result<UpdatePreview> {
+    let old_dir = http_cache::cached_dir(source)?;
+    let candidate = http_cache::candidate(source)?;
+    preview_dirs(old_dir.as_deref(), Candidate::Http(candidate))
+}
+
+/// Pull the manifest an OCI skill's tag points at now and diff it against
+/// the locked digest. Nothing is deployed.
+pub fn preview_oci_update(
+    locked: &LockedSkill,
+    source: &SkillSource,
+) -> anyhow::Result<UpdatePreview> {
+    let skill_dir = |digest: &str| -> anyhow::Result<PathBuf> {
+        let base = oci::fetch(&source.clone().with_rev(digest))?;
+        Ok(resolve_skill_dir(&base, source.path.as_deref())?)
+    };
+    let old_dir = match locked.digest().filter(|d| !d.is_empty()) {
+        Some(digest) => Some(skill_dir(digest)?),
+        None => None,
+    };
+    let digest = oci::resolve_digest(source)?;
+    let candidate = Candidate::Oci {
+        skill_dir: skill_dir(&digest)?,
+        digest,
+    };
+    preview_dirs(old_dir.as_deref(), candidate)
+}
+
+fn preview_dirs(old_dir: Option<&Path>, candidate: Candidate) -> anyhow::Result<UpdatePreview> {
+    let new_dir = candidate.skill_dir();
+    let diff = diff_dirs(old_dir, new_dir)?;
+    let old_findings = match old_dir {
+        Some(dir) => findings(dir)?,
+        None => Vec::new(),
+    };
+    let new_findings = findings(new_dir)?
+        .into_iter()
+        .filter(|f| {
+            !old_findings
+                .iter()
+                .any(|o| o.checker == f.checker && o.message == f.message)
+        })
+        .collect();
+
+    Ok(UpdatePreview {
+        diff,
+        new_findings,
+        candidate,
+    })
+}
+
+/// Print a preview below the skill's update line.
+pub fn print_preview(preview: &UpdatePreview, p: &Paint) {
+    for line in preview.diff.lines() {
+        let painted = if line.starts_with("+++") || line.starts_with("---") {
+            p.bold(line)
+        } else if line.starts_with('+') {
+            p.success(line)
+        } else if line.starts_with('-') {
+            p.warn(line)
+        } else if line.starts_with("@@") {
+            p.info(line)
+        } else {
+            line.to_string()
+        };
+        println!("    {painted}");
+    }
+    for finding in &preview.new_findings {
+        println!(
+            "    {} [{}] {} {}",
+            p.warn("new finding:"),
+            finding.severity,
+            finding.checker,
+            finding.message
+        );
+    }
+}
+
+fn findings(dir: &Path) -> anyhow::Result<Vec<Finding>> {
+    let skill_md = dir.join("SKILL.md");
+    if !skill_md.exists() {
+        return Ok(Vec::new());
+    }
+    let (meta, body) = SkillMetadata::from_file(&skill_md)?;
+    Ok(validate::validate_skill_dir(dir, &meta, &body).findings)
+}
+
+/// Unified diff of every file that differs between `old` and `new`.
+/// A missing `old` diffs against an empty directory.
+fn diff_dirs(old: Option<&Path>, new: &Path) -> anyhow::Result<String> {
+    let old_files = match old {
+        Some(dir) => ion_skill::git::list_files(dir)?,
+        None => Vec::new(),
+    };
+    let new_files = ion_skill::git::list_files(new)?;
+    let all: BTreeSet<&PathBuf> = old_files.iter().chain(&new_files).collect();
+
+    let mut out = String::new();
+    for rel in all {
+        let read = |dir: Option<&Path>| -> std::io::Result<Option<Vec<u8>>> {
+            match dir.map(|d| d.join(rel)).filter(|path| path.exists()) {
+                Some(path) => std::fs::read(path).map(Some),
+                None => Ok(None),
+            }
+        };
+        let before = read(old)?;
+        let after = read(Some(new))?;
+        if before == after {
+            continue;
+        }
+
+        let name = rel.to_string_lossy().replace('\\', "/");
+        let old_label = match before {
+            Some(_) => format!("a/{name}"),
+            None => "/dev/null".to_string(),
+        };
+        let new_label = match after {
+            Some(_) => format!("b/{name}"),
+            None => "/dev/null".to_string(),
+        };
+        let text = |bytes: &Option<Vec<u8>>| match bytes {
+            Some(bytes) => String::from_utf8(bytes.clone()).ok(),
+            None => Some(String::new()),
+        };
+        match (text(&before), text(&after)) {
+            (Some(old_text), Some(new_text)) => {
+                let diff = TextDiff::from_lines(&old_text, &new_text);
+                out.push_str(
+                    &diff
+                        .unified_diff()
+                        .header(&old_label, &new_label)
+                        .to_string(),
+                );
+            }
+            _ => out.push_str(&format!(
+                "Binary files {old_label} and {new_label} differ\n"
+            )),
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_dirs_reports_changed_added_and_removed_files() {
+        let old = tempfile::tempdir().unwrap();
+        let new = tempfile::tempdir().unwrap();
+        std::fs::write(old.path().join("SKILL.md"), "one\ntwo\n").unwrap();
+        std::fs::write(new.path().join("SKILL.md"), "one\nthree\n").unwrap();
+        std::fs::write(old.path().join("gone.md"), "bye\n").unwrap();
+        std::fs::write(new.path().join("same.md"), "x\n").unwrap();
+        std::fs::write(old.path().join("same.md"), "x\n").unwrap();
+        std::fs::write(new.path().join("logo.png"), [0xff, 0xfe, 0x00]).unwrap();
+
+        let diff = diff_dirs(Some(old.path()), new.path()).unwrap();
+        assert!(diff.contains("--- a/SKILL.md\n+++ b/SKILL.md"), "{diff}");
+        assert!(diff.contains("-two\n+three"), "{diff}");
+        assert!(diff.contains("--- a/gone.md\n+++ /dev/null"), "{diff}");
+        assert!(diff.contains("Binary files /dev/null and b/logo.png differ"));
+        assert!(!diff.contains("same.md"));
+    }
+}
@@ -56,23 +56,50 @@ pub fn confirm_install_on_warnings() -> anyhow::Result<bool> {
 ///
 /// Falls back to sequential y/N prompts if stdin is not a terminal.
 pub fn select_warned_skills(skills: &[(String, usize)]) -> anyhow::Result<Option<Vec<bool>>> {
-    if skills.is_empty() {
+    let items: Vec<SelectItem> = skills
+        .iter()
+        .map(|(name, count)| {
+            let warning_label = if *count == 1 { "warning" } else { "warnings" };
+            SelectItem {
+                label: format!("{name} ({count} {warning_label})"),
+                prompt: format!("Install '{name}' with {count} warning(s)?"),
+            }
+        })
+        .collect();
+    select_skills("Select which warned skills to install", &items)
+}
+
+/// One entry in a [`select_skills`] list.
+pub struct SelectItem {
+    /// Line shown in the interactive list.
+    pub label: String,
+    /// Question asked in the y/N fallback when there's no TTY.
+    pub prompt: String,
+}
+
+/// Interactive multi-select over `items`, all selected by default.
+/// Returns `Some(Vec<bool>)` indicating which items the user approved,
+/// or `None` if the user cancelled (q/Esc).
+///
+/// Falls back to sequential y/N prompts if stdin is not a terminal.
+pub fn select_skills(header: &str, items: &[SelectItem]) -> anyhow::Result<Option<Vec<bool>>> {
+    if items.is_empty() {
         return Ok(Some(vec![]));
     }
 
     // Non-interactive fallback
     if !io::stdin().is_terminal() {
-        return fallback_select(skills).map(Some);
+        return fallback_select(items).map(Some);
     }
 
-    interactive_select(skills)
+    interactive_select(header, items)
 }
 
-/// Fallback: sequential y/N for each skill when there's no TTY.
-fn fallback_select(skills: &[(String, usize)]) -> anyhow::Result<Vec<bool>> {
-    let mut selected = Vec::with_capacity(skills.len());
-    for (name, count) in skills {
-        print!("  Install '{}' with {} warning(s)? [Y/n] ", name, count);
+/// Fallback: sequential y/N for each item when there's no TTY.
+fn fallback_select(items: &[SelectItem]) -> anyhow::Result<Vec<bool>> {
+    let mut selected = Vec::with_capacity(items.len());
+    for item in items {
+        print!("  {} [Y/n] ", item.prompt);
         io::stdout().flush()?;
         let mut answer = String::new();
         io::stdin().read_line(&mut answer)?;
@@ -89,8 +116,8 @@ fn fallback_select(skills: &[(String, usize)]) -> anyhow::Result<Vec<bool>> {
 
 /// Interactive crossterm-based multi-select.
 /// Returns `None` if the user quit (q/Esc/Ctrl+C).
-fn interactive_select(skills: &[(String, usize)]) -> anyhow::Result<Option<Vec<bool>>> {
-    let mut selected = vec![true; skills.len()];
+fn interactive_select(header: &str, items: &[SelectItem]) -> anyhow::Result<Option<Vec<bool>>> {
+    let mut selected = vec![true; items.len()];
     let mut cursor_pos: usize = 0;
 
     let mut stdout = io::stdout();
@@ -102,7 +129,7 @@ fn interactive_select(skills: &[(String, usize)]) -> anyhow::Result<Option<Vec<b
     let start_row = cursor::position()?.1;
 
     // Draw initial state
-    let result = run_select_loop(&mut stdout, skills, &mut selected, &mut cursor_pos);
+    let result = run_select_loop(&mut stdout, header, items, &mut selected, &mut cursor_pos);
 
     // Always restore terminal
     terminal::disable_raw_mode()?;
@@ -124,7 +151,8 @@ fn interactive_select(skills: &[(String, usize)]) -> anyhow::Result<Option<Vec<b
 
 fn render_select(
     stdout: &mut io::Stdout,
-    skills: &[(String, usize)],
+    header: &str,
+    items: &[SelectItem],
     selected: &[bool],
     cursor_pos: usize,
     line_count: usize,
@@ -138,17 +166,13 @@ fn render_select(
     // Header
     write!(
         stdout,
-        "Select which warned skills to install (↑↓ move, space toggle, enter confirm, a toggle all, q/esc quit):\r\n"
+        "{header} (↑↓ move, space toggle, enter confirm, a toggle all, q/esc quit):\r\n"
     )?;
 
-    for (i, (name, count)) in skills.iter().enumerate() {
+    for (i, item) in items.iter().enumerate() {
         let marker = if cursor_pos == i { ">" } else { " " };
         let check = if selected[i] { "x" } else { " " };
-        let warning_label = if *count == 1 { "warning" } else { "warnings" };
-        write!(
-            stdout,
-            "  {marker} [{check}] {name} ({count} {warning_label})\r\n"
-        )?;
+        write!(stdout, "  {marker} [{check}] {}\r\n", item.label)?;
     }
 
     stdout.flush()?;
@@ -158,14 +182,15 @@ fn render_select(
 /// Returns `true` if user confirmed (Enter), `false` if cancelled (q/Esc/Ctrl+C).
 fn run_select_loop(
     stdout: &mut io::Stdout,
-    skills: &[(String, usize)],
+    header: &str,
+    items: &[SelectItem],
     selected: &mut [bool],
     cursor_pos: &mut usize,
 ) -> anyhow::Result<bool> {
-    let total_lines = skills.len() + 1; // header + items
+    let total_lines = items.len() + 1; // header + items
 
     // Initial render
-    render_select(stdout, skills, selected, *cursor_pos, 0)?;
+    render_select(stdout, header, items, selected, *cursor_pos, 0)?;
 
     loop {
         if let Event::Key(KeyEvent {
@@ -184,7 +209,7 @@ fn run_select_loop(
                 KeyCode::Up | KeyCode::Char('k') if *cursor_pos > 0 => {
                     *cursor_pos -= 1;
                 }
-                KeyCode::Down | KeyCode::Char('j') if *cursor_pos + 1 < skills.len() => {
+                KeyCode::Down | KeyCode::Char('j') if *cursor_pos + 1 < items.len() => {
                     *cursor_pos += 1;
                 }
                 KeyCode::Char(' ') => {
@@ -202,7 +227,7 @@ fn run_select_loop(
                 _ => {}
             }
 
-            render_select(stdout, skills, selected, *cursor_pos, total_lines)?;
+            render_select(stdout, header, items, selected, *cursor_pos, total_lines)?;
         }
     }
 }
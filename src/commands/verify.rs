@@ -0,0 +1,91 @@
+use ion_skill::verify::{SkillVerification, verify_skill};
+
+use crate::context::WorkspaceContext;
+use crate::style::Paint;
+
+pub fn run(json: bool, global: bool, project_flags: &[String]) -> anyhow::Result<()> {
+    let ws = WorkspaceContext::load_scoped(project_flags, global)?;
+    let projects = ws.scoped_projects();
+    let p = ws.paint();
+    let multi = projects.len() > 1;
+
+    let mut total = 0usize;
+    let mut failed = 0usize;
+    let mut json_skills: Vec<serde_json::Value> = Vec::new();
+
+    for project in &projects {
+        if !project.has_manifest() {
+            continue;
+        }
+        let lockfile = project.lockfile()?;
+        if lockfile.skills.is_empty() {
+            continue;
+        }
+        let options = ws.merged_options_for(project)?;
+        let installer = ws.installer_for(project, &options);
+        let label = ws.project_label(project);
+
+        if multi && !json {
+            println!("\n{}:", p.bold(&label));
+        }
+
+        for locked in &lockfile.skills {
+            let report = verify_skill(&installer, locked);
+            total += 1;
+            if !report.is_ok() {
+                failed += 1;
+            }
+
+            if json {
+                let mut value = serde_json::to_value(&report)?;
+                value["ok"] = serde_json::json!(report.is_ok());
+                value["project"] = serde_json::json!(label);
+                json_skills.push(value);
+            } else {
+                print_report(&report, &p);
+            }
+        }
+    }
+
+    if json {
+        let data = serde_json::json!({
+            "skills": json_skills,
+            "verified": total - failed,
+            "failed": failed,
+        });
+        if failed > 0 {
+            crate::json::print_failure(data);
+        }
+        crate::json::print_success(data);
+        return Ok(());
+    }
+
+    if total == 0 {
+        println!("No skills in Ion.lock to verify.");
+    } else if failed > 0 {
+        anyhow::bail!("{failed} of {total} skill(s) do not match Ion.lock");
+    } else {
+        println!("\nAll {total} skill(s) match Ion.lock.");
+    }
+    Ok(())
+}
+
+fn print_report(report: &SkillVerification, p: &Paint) {
+    if report.is_ok() {
+        println!("  {} {}", p.success("✓"), p.bold(&report.name));
+        return;
+    }
+    println!("  {} {}", p.warn("✗"), p.bold(&report.name));
+    for file in &report.modified {
+        println!("      {} {file}", p.warn("modified:"));
+    }
+    for file in &report.missing {
+        println!("      {} {file}", p.warn("missing: "));
+    }
+    for file in &report.extra {
+        println!("      {} {file}", p.warn("extra:   "));
+    }
+    for problem in &report.problems {
+        println!("      {}", p.warn(problem));
+    }
+}
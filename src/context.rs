@@ -1,5 +1,6 @@
 use std::path::{Path, PathBuf};
 
+use ion_skill::catalog::Catalog;
 use ion_skill::config::GlobalConfig;
 use ion_skill::manifest::ManifestOptions;
 use ion_skill::workspace::Project;
@@ -88,6 +89,31 @@ impl WorkspaceContext {
         })
     }
 
+    /// Load the user-global scope as a workspace-of-one. Its Ion.toml and
+    /// Ion.lock live next to the global config.
+    pub fn load_global() -> anyhow::Result<Self> {
+        let global_config = GlobalConfig::load()?;
+        let project = Project::global()?;
+        std::fs::create_dir_all(project.manifest_dir())?;
+        Ok(Self {
+            projects: vec![project],
+            scope: Scope::Projects(vec![0]),
+            global_config,
+            has_workspace_section: false,
+        })
+    }
+
+    /// Load the global scope when `global` is set, otherwise the workspace.
+    pub fn load_scoped(project_flags: &[String], global: bool) -> anyhow::Result<Self> {
+        if !global {
+            return Self::load(project_flags);
+        }
+        if !project_flags.is_empty() {
+            anyhow::bail!("--global cannot be combined with --project");
+        }
+        Self::load_global()
+    }
+
     /// Projects that are in scope for the current command.
     pub fn scoped_projects(&self) -> Vec<&Project> {
         match &self.scope {
@@ -122,6 +148,22 @@ impl WorkspaceContext {
         &self.projects[0].dir
     }
 
+    /// Human-readable label for a project within the workspace.
+    pub fn project_label(&self, project: &Project) -> String {
+        let root_dir = self.root_dir();
+        if project.global {
+            "global".to_string()
+        } else if project.dir == root_dir {
+            ". (root)".to_string()
+        } else {
+            project
+                .dir
+                .strip_prefix(root_dir)
+                .map(|p| p.display().to_string())
+                .unwrap_or_else(|_| project.dir.display().to_string())
+        }
+    }
+
     /// Whether the root project declares a `[workspace]` section.
     ///
     /// Returns `true` even for a workspace with zero members (e.g. before
@@ -152,7 +194,11 @@ impl WorkspaceContext {
         let merged_targets = self.global_config.resolve_targets(&effective);
         Ok(ManifestOptions {
             targets: merged_targets,
+            target_filters: effective.target_filters,
             skills_dir: effective.skills_dir,
+            deploy: effective.deploy,
+            target_deploy: effective.target_deploy,
+            target_format: effective.target_format,
         })
     }
 
@@ -161,6 +207,11 @@ impl WorkspaceContext {
         crate::style::Paint::new(&self.global_config)
     }
 
+    /// The agent tool catalog, with `[tools]` from the global config applied.
+    pub fn catalog(&self) -> Catalog {
+        Catalog::load(&self.global_config)
+    }
+
     /// Create a `SkillInstaller` for a specific project.
     pub fn installer_for<'a>(
         &'a self,
@@ -168,10 +219,15 @@ impl WorkspaceContext {
         options: &'a ManifestOptions,
     ) -> ion_skill::installer::SkillInstaller<'a> {
         ion_skill::installer::SkillInstaller::new(&project.dir, options)
+            .with_lockfile(&project.lockfile_path)
     }
 
     /// Ensure the built-in ion-cli skill is deployed for a project.
     pub fn ensure_builtin_skill(&self, project: &Project, merged_options: &ManifestOptions) {
+        // The built-in skill is per project and writes .gitignore entries
+        if project.global {
+            return;
+        }
         if let Err(e) = crate::builtin_skill::ensure_installed(
             &project.dir,
             &project.manifest_path,
@@ -266,3 +322,11 @@ fn resolve_project_flags(
 /// Type alias for backward compatibility during migration.
 #[allow(dead_code)]
 pub type ProjectContext = WorkspaceContext;
+
+/// Skills `project` declares that the user-global Ion.toml declares too.
+/// Empty when there is no global manifest or it cannot be read.
+pub fn shadowed_global_skills(project: &Project) -> Vec<String> {
+    Project::global()
+        .and_then(|global| project.shadowed_skills(&global))
+        .unwrap_or_default()
+}
@@ -22,6 +22,10 @@ struct Cli {
     #[arg(long, global = true)]
     project: Vec<String>,
 
+    /// Use only cached repositories and search results; never access the network
+    #[arg(long, global = true)]
+    offline: bool,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -76,6 +80,15 @@ enum Commands {
         /// Comma-separated list of skills to install from a collection
         #[arg(long)]
         skills: Option<String>,
+        /// Install the exact commits recorded in Ion.lock and fail if they or their content drifted
+        #[arg(long, conflicts_with = "source")]
+        locked: bool,
+        /// Like --locked, but also fail on stale Ion.lock entries and never rewrite Ion.lock
+        #[arg(long, conflicts_with = "source")]
+        frozen: bool,
+        /// Install into the user-global scope (~/.claude/skills and the like) instead of the project
+        #[arg(long, short = 'g')]
+        global: bool,
     },
     /// Remove a skill from the project
     Remove {
@@ -84,6 +97,15 @@ enum Commands {
         /// Skip confirmation prompt
         #[arg(long, short = 'y')]
         yes: bool,
+        /// Use the user-global Ion.toml instead of the project's
+        #[arg(long, short = 'g')]
+        global: bool,
+    },
+    /// Resolve Ion.toml into Ion.lock without deploying any skills
+    Lock {
+        /// Don't write anything; fail if Ion.lock is missing entries, has stale ones or disagrees with Ion.toml
+        #[arg(long)]
+        check: bool,
     },
     /// Search for skills across registries and GitHub
     Search {
@@ -102,10 +124,21 @@ enum Commands {
         #[arg(long, short)]
         verbose: bool,
     },
+    /// Report available skill updates without applying them
+    Outdated,
     /// Update skills to their latest versions
     Update {
         /// Update only a specific skill (default: update all)
         name: Option<String>,
+        /// Show what would change, with a diff of each skill, without applying it
+        #[arg(long)]
+        dry_run: bool,
+        /// Preview updates, then choose which ones to apply
+        #[arg(long, short)]
+        interactive: bool,
+        /// Use the user-global Ion.toml instead of the project's
+        #[arg(long, short = 'g')]
+        global: bool,
     },
     /// Manage AGENTS.md templates
     Agents {
@@ -159,12 +192,34 @@ enum Commands {
     },
     /// List installed skills
     #[command(alias = "ls")]
-    List,
+    List {
+        /// Use the user-global Ion.toml instead of the project's
+        #[arg(long, short = 'g')]
+        global: bool,
+    },
     /// Validate local skill definitions
     Validate {
         /// Optional path to a SKILL.md file or skill/workspace directory
         path: Option<String>,
     },
+    /// Check deployed skills and target links against Ion.lock
+    Verify {
+        /// Use the user-global Ion.toml instead of the project's
+        #[arg(long, short = 'g')]
+        global: bool,
+    },
+    /// Remove links, copies and .gitignore entries left by skills no longer in Ion.toml
+    Prune {
+        /// Show what would be removed without removing it
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Diagnose broken links, stale entries and missing binaries
+    Doctor {
+        /// Repair the problems that can be fixed safely
+        #[arg(long)]
+        fix: bool,
+    },
     /// Manage the skill cache
     Cache {
         #[command(subcommand)]
@@ -205,13 +260,24 @@ enum SkillCommands {
     /// Link a local skill directory into the project
     Link {
         /// Path to the local skill directory containing SKILL.md
+        /// (with --watch, also the name of a local, path or dev binary skill)
         path: String,
+        /// Keep running: re-validate the skill and refresh Ion.lock on every change
+        #[arg(long)]
+        watch: bool,
     },
     /// Eject a remote skill into an editable local copy
     Eject {
         /// Name of the skill to eject
         name: String,
     },
+    /// Push a skill directory to an OCI registry
+    Push {
+        /// Path to the skill directory containing SKILL.md
+        path: String,
+        /// Destination, e.g. oci://ghcr.io/acme/review:1.0
+        reference: String,
+    },
     // Kept for backward compatibility — use the top-level `ion new/list/validate` instead
     #[command(hide = true)]
     New {
@@ -225,7 +291,10 @@ enum SkillCommands {
         force: bool,
     },
     #[command(hide = true)]
-    List,
+    List {
+        #[arg(long, short = 'g')]
+        global: bool,
+    },
     #[command(hide = true)]
     Validate { path: Option<String> },
 }
@@ -314,10 +383,16 @@ fn main() {
     let json = cli.json;
     let project_flags = cli.project;
     crate::json::set_pretty(cli.pretty);
-    let skip_update_check = matches!(
-        cli.command,
-        Commands::Self_ { .. } | Commands::Completion { .. }
-    );
+    ion_skill::offline::set_offline(cli.offline);
+    if let Ok(config) = ion_skill::config::GlobalConfig::load() {
+        ion_skill::hosts::set_hosts(config.hosts);
+        ion_skill::credentials::set_credentials(config.credentials);
+    }
+    let skip_update_check = cli.offline
+        || matches!(
+            cli.command,
+            Commands::Self_ { .. } | Commands::Completion { .. }
+        );
 
     let result = match cli.command {
         Commands::Init {
@@ -347,6 +422,9 @@ fn main() {
             name,
             allow_warnings,
             skills,
+            locked,
+            frozen,
+            global,
         } => match source {
             Some(src) => commands::add::run(
                 &src,
@@ -357,11 +435,17 @@ fn main() {
                 json,
                 allow_warnings,
                 skills.as_deref(),
+                global,
                 &project_flags,
             ),
-            None => commands::install::run(json, allow_warnings, &project_flags),
+            None => {
+                commands::install::run(json, allow_warnings, locked, frozen, global, &project_flags)
+            }
         },
-        Commands::Remove { name, yes } => commands::remove::run(&name, yes, json, &project_flags),
+        Commands::Remove { name, yes, global } => {
+            commands::remove::run(&name, yes, json, global, &project_flags)
+        }
+        Commands::Lock { check } => commands::lock::run(check, json, &project_flags),
         Commands::Search {
             query,
             agent,
@@ -376,15 +460,31 @@ fn main() {
             }
             commands::search::run(&query, agent, json, source.as_deref(), limit)
         }
-        Commands::Update { name } => commands::update::run(name.as_deref(), json, &project_flags),
+        Commands::Outdated => commands::outdated::run(json, &project_flags),
+        Commands::Update {
+            name,
+            dry_run,
+            interactive,
+            global,
+        } => commands::update::run(
+            name.as_deref(),
+            dry_run,
+            interactive,
+            json,
+            global,
+            &project_flags,
+        ),
         Commands::New {
             path,
             dir,
             collection,
             force,
         } => commands::new::run(path.as_deref(), dir.as_deref(), collection, force, json),
-        Commands::List => commands::list::run(json, &project_flags),
+        Commands::List { global } => commands::list::run(json, global, &project_flags),
         Commands::Validate { path } => commands::validate::run(path.as_deref(), json),
+        Commands::Verify { global } => commands::verify::run(json, global, &project_flags),
+        Commands::Doctor { fix } => commands::doctor::run(json, fix, &project_flags),
+        Commands::Prune { dry_run } => commands::prune::run(dry_run, json, &project_flags),
         Commands::Agents { action } => match action {
             AgentsCommands::Init {
                 source,
@@ -424,9 +524,12 @@ fn main() {
             } => commands::new::run(path.as_deref(), dir.as_deref(), collection, force, json),
             SkillCommands::Validate { path } => commands::validate::run(path.as_deref(), json),
             SkillCommands::Info { skill } => commands::info::run(&skill, json, &project_flags),
-            SkillCommands::List => commands::list::run(json, &project_flags),
-            SkillCommands::Link { path } => commands::link::run(&path, json, &project_flags),
+            SkillCommands::List { global } => commands::list::run(json, global, &project_flags),
+            SkillCommands::Link { path, watch } => {
+                commands::link::run(&path, watch, json, &project_flags)
+            }
             SkillCommands::Eject { name } => commands::eject::run(&name, json, &project_flags),
+            SkillCommands::Push { path, reference } => commands::push::run(&path, &reference, json),
         },
         Commands::Migrate { from, dry_run, yes } => {
             commands::migrate::run(from.as_deref(), dry_run, json, yes, &project_flags)
@@ -469,7 +572,7 @@ fn main() {
         }
     }
 
-    // Hint about available updates (silent on failure, skipped for --json and `self` commands)
+    // Hint about available updates (silent on failure, skipped for --json, --offline and `self` commands)
     if !json && !skip_update_check {
         commands::self_cmd::check_for_update_hint();
     }
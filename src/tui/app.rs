@@ -147,13 +147,19 @@ impl App {
             },
             ConfigSection {
                 name: "options".to_string(),
-                entries: vec![match &manifest.options.skills_dir {
-                    Some(v) => ConfigEntry::new("skills-dir", v),
-                    None => ConfigEntry::with_default(
-                        "skills-dir",
-                        ion_skill::manifest::DEFAULT_SKILLS_DIR,
-                    ),
-                }],
+                entries: vec![
+                    match &manifest.options.skills_dir {
+                        Some(v) => ConfigEntry::new("skills-dir", v),
+                        None => ConfigEntry::with_default(
+                            "skills-dir",
+                            ion_skill::manifest::DEFAULT_SKILLS_DIR,
+                        ),
+                    },
+                    match manifest.options.deploy {
+                        Some(mode) => ConfigEntry::new("deploy", mode.as_str()),
+                        None => ConfigEntry::with_default("deploy", "symlink"),
+                    },
+                ],
             },
         ]
     }
@@ -302,14 +308,17 @@ impl App {
             .as_table_mut()
             .ok_or_else(|| anyhow::anyhow!("[options] is not a table"))?;
 
-        // Save targets
-        options["targets"] = Item::Table(Table::new());
-        if let Some(section) = self.project_sections.iter().find(|s| s.name == "targets") {
-            let targets_table = options["targets"].as_table_mut().unwrap();
-            for e in &section.entries {
-                targets_table[e.key.as_str()] = toml_edit::value(e.value.as_str());
-            }
-        }
+        // Save targets, keeping table-form entries' skill selection
+        let targets = self
+            .project_sections
+            .iter()
+            .find(|s| s.name == "targets")
+            .map(|section| section.entries.as_slice())
+            .unwrap_or_default();
+        ion_skill::manifest_writer::replace_targets(
+            options,
+            targets.iter().map(|e| (e.key.as_str(), e.value.as_str())),
+        );
 
         // Save top-level options (skills-dir, etc.)
         // Don't write values that are still at their default
@@ -10,16 +10,7 @@ use ratatui::text::{Line, Span};
 use ratatui::widgets::Paragraph;
 use ratatui::{Terminal, TerminalOptions, Viewport};
 
-/// Known agent tool targets: (name, config_dir, default_skills_path).
-const KNOWN_TARGETS: &[(&str, &str, &str)] = &[
-    ("claude", ".claude", ".claude/skills"),
-    ("cursor", ".cursor", ".cursor/skills"),
-    ("windsurf", ".windsurf", ".windsurf/skills"),
-];
-
-/// Total lines the inline widget occupies:
-/// 1 header + known targets + 1 custom row + 1 footer = KNOWN_TARGETS.len() + 3
-const WIDGET_HEIGHT: u16 = KNOWN_TARGETS.len() as u16 + 3;
+use ion_skill::catalog::Catalog;
 
 /// An item in the multi-select list.
 #[cfg_attr(test, derive(Debug))]
@@ -43,17 +34,11 @@ struct InitSelect {
 }
 
 impl InitSelect {
-    fn new(project_dir: &Path) -> Self {
-        let detected: Vec<&str> = KNOWN_TARGETS
-            .iter()
-            .filter(|(_, dir, _)| project_dir.join(dir).is_dir())
-            .map(|(name, _, _)| *name)
-            .collect();
-
-        let items = KNOWN_TARGETS
-            .iter()
-            .map(|(name, _, path)| {
-                let is_detected = detected.contains(name);
+    fn new(project_dir: &Path, catalog: &Catalog) -> Self {
+        let items = catalog
+            .targets()
+            .map(|(name, tool, path)| {
+                let is_detected = tool.is_detected(project_dir);
                 SelectItem {
                     name: name.to_string(),
                     path: path.to_string(),
@@ -73,6 +58,12 @@ impl InitSelect {
         }
     }
 
+    /// Total lines the inline widget occupies: 1 header + one row per
+    /// target + 1 custom row + 1 footer.
+    fn height(&self) -> u16 {
+        self.items.len() as u16 + 3
+    }
+
     fn on_custom_row(&self) -> bool {
         self.cursor == self.items.len()
     }
@@ -299,13 +290,16 @@ fn build_lines<'a>(app: &'a InitSelect) -> Vec<Line<'a>> {
 
 /// Run the interactive multi-select inline in the terminal.
 /// Returns `Ok(None)` if the user cancelled, or `Ok(Some(targets))` on confirm.
-pub fn run_init_select(project_dir: &Path) -> anyhow::Result<Option<BTreeMap<String, String>>> {
-    let mut app = InitSelect::new(project_dir);
+pub fn run_init_select(
+    project_dir: &Path,
+    catalog: &Catalog,
+) -> anyhow::Result<Option<BTreeMap<String, String>>> {
+    let mut app = InitSelect::new(project_dir, catalog);
 
     enable_raw_mode()?;
     let backend = CrosstermBackend::new(io::stdout());
     let options = TerminalOptions {
-        viewport: Viewport::Inline(WIDGET_HEIGHT),
+        viewport: Viewport::Inline(app.height()),
     };
     let mut terminal = Terminal::with_options(backend, options)?;
     // Capture viewport start row (after any scrolling done by ratatui)
@@ -362,12 +356,22 @@ mod tests {
         }
     }
 
+    /// The built-in catalog narrowed to claude, cursor and windsurf, so row
+    /// indices stay stable as tools are added.
+    fn test_catalog() -> Catalog {
+        Catalog::builtin()
+            .iter()
+            .filter(|(name, _)| ["claude", "cursor", "windsurf"].contains(name))
+            .map(|(name, tool)| (name.to_string(), tool.clone()))
+            .collect()
+    }
+
     fn make_app() -> InitSelect {
         let dir = tempfile::tempdir().unwrap();
         std::fs::create_dir(dir.path().join(".claude")).unwrap();
         // We need the tempdir to live long enough, but InitSelect only reads
         // directory contents in new(), so we can construct and return.
-        let app = InitSelect::new(dir.path());
+        let app = InitSelect::new(dir.path(), &test_catalog());
         // Verify detected state
         assert!(
             app.items[0].selected,
@@ -716,7 +720,7 @@ mod tests {
         let dir = tempfile::tempdir().unwrap();
         std::fs::create_dir(dir.path().join(".claude")).unwrap();
         std::fs::create_dir(dir.path().join(".cursor")).unwrap();
-        let app = InitSelect::new(dir.path());
+        let app = InitSelect::new(dir.path(), &test_catalog());
         assert!(app.items[0].selected); // claude
         assert!(app.items[0].detected);
         assert!(app.items[1].selected); // cursor
@@ -728,12 +732,25 @@ mod tests {
     #[test]
     fn no_dirs_means_nothing_preselected() {
         let dir = tempfile::tempdir().unwrap();
-        let app = InitSelect::new(dir.path());
+        let app = InitSelect::new(dir.path(), &test_catalog());
         assert!(!app.items[0].selected);
         assert!(!app.items[1].selected);
         assert!(!app.items[2].selected);
     }
 
+    #[test]
+    fn builtin_catalog_drives_the_list() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join(".gemini")).unwrap();
+        let app = InitSelect::new(dir.path(), &Catalog::builtin());
+        let gemini = app.items.iter().find(|i| i.name == "gemini").unwrap();
+        assert!(gemini.detected && gemini.selected);
+        assert!(app.items.iter().any(|i| i.name == "opencode"));
+        // Tools that read .agents/skills themselves need no target
+        assert!(app.items.iter().all(|i| i.name != "codex"));
+        assert_eq!(app.height() as usize, app.items.len() + 3);
+    }
+
     // --- Up arrow on custom row returns to item list ---
 
     #[test]
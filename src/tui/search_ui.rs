@@ -5,6 +5,7 @@ use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph};
 
 use ion_skill::search::skill_dir_name;
+use ion_skill::source::SkillSource;
 
 use super::search_app::{Hyperlink, ListRow, SearchApp};
 use super::util::wrap_text;
@@ -20,7 +21,10 @@ const DIM_STYLE: Style = Style::new().fg(Color::DarkGray);
 fn source_url(registry: &str, source: &str) -> String {
     match registry {
         "skills.sh" | "skills-sh" => format!("https://skills.sh/{source}"),
-        _ => format!("https://github.com/{source}"),
+        _ => match SkillSource::infer(source) {
+            Ok(s) if !s.is_github() => s.web_url().unwrap_or_else(|| source.to_string()),
+            _ => format!("https://github.com/{source}"),
+        },
     }
 }
 
@@ -222,6 +222,10 @@ fn hint_for_entry(app: &App) -> Option<(&'static str, &'static str)> {
             "Directory where local skills are stored. Skills live at <skills-dir>/<name>/.",
             "e.g. .agents/skills, skills",
         )),
+        (Tab::Project, "options", "deploy") => Some((
+            "How skills are placed into the skills and target directories.",
+            "e.g. symlink, copy, hardlink",
+        )),
         _ => None,
     }
 }
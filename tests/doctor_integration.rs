@@ -0,0 +1,114 @@
+use std::process::Command;
+
+fn ion_cmd() -> Command {
+    Command::new(env!("CARGO_BIN_EXE_ion"))
+}
+
+fn doctor_json(dir: &std::path::Path, args: &[&str]) -> (bool, serde_json::Value) {
+    let output = ion_cmd()
+        .args(["--json", "doctor"])
+        .args(args)
+        .current_dir(dir)
+        .env("XDG_DATA_HOME", dir.join(".data"))
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap_or_else(|_| {
+        panic!(
+            "invalid JSON: {}\nstderr: {}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        )
+    });
+    (output.status.success(), json)
+}
+
+/// A workspace whose `docs` member has a stale .gitignore entry, a stale
+/// Ion.lock entry, a dangling target link and a missing binary.
+fn setup_workspace(dir: &std::path::Path) -> std::path::PathBuf {
+    std::fs::write(dir.join("Ion.toml"), "[workspace]\nmembers = [\"docs\"]\n").unwrap();
+    let docs = dir.join("docs");
+    std::fs::create_dir_all(docs.join(".claude/skills")).unwrap();
+    std::fs::write(
+        docs.join("Ion.toml"),
+        "[options.targets]\nclaude = \".claude/skills\"\n\n[skills]\nmytool = { type = \"binary\", source = \"org/mytool\", binary = \"mytool\" }\n",
+    )
+    .unwrap();
+    std::fs::write(
+        docs.join("Ion.lock"),
+        r#"[[skill]]
+name = "mytool"
+source = "org/mytool"
+kind = "binary"
+binary_name = "mytool"
+binary_version = "1.0.0"
+
+[[skill]]
+name = "removed"
+source = "org/skills"
+kind = "local"
+"#,
+    )
+    .unwrap();
+    std::fs::write(
+        docs.join(".gitignore"),
+        "# Managed by ion\n.agents/skills/gone\n.claude/skills/gone\n",
+    )
+    .unwrap();
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(
+        "../../.agents/skills/gone",
+        docs.join(".claude/skills/gone"),
+    )
+    .unwrap();
+    docs
+}
+
+#[test]
+fn doctor_reports_problems_across_members() {
+    let tmp = tempfile::tempdir().unwrap();
+    setup_workspace(tmp.path());
+
+    let (ok, json) = doctor_json(tmp.path(), &[]);
+    assert!(!ok, "a missing binary is an error: {json}");
+    let problems = json["data"]["problems"].as_array().unwrap();
+    let checks: Vec<&str> = problems
+        .iter()
+        .map(|p| p["check"].as_str().unwrap())
+        .collect();
+    assert!(checks.contains(&"stale-gitignore"), "{checks:?}");
+    assert!(checks.contains(&"stale-lock-entry"), "{checks:?}");
+    assert!(checks.contains(&"missing-binary"), "{checks:?}");
+    #[cfg(unix)]
+    assert!(checks.contains(&"dangling-link"), "{checks:?}");
+    assert!(problems.iter().all(|p| p["project"] == "docs"));
+    assert!(problems.iter().all(|p| p["fixed"] == false));
+
+    let binary = problems
+        .iter()
+        .find(|p| p["check"] == "missing-binary")
+        .unwrap();
+    assert_eq!(binary["severity"], "error");
+    assert_eq!(binary["fixable"], false);
+}
+
+#[test]
+fn doctor_fix_repairs_what_it_can() {
+    let tmp = tempfile::tempdir().unwrap();
+    let docs = setup_workspace(tmp.path());
+
+    let (ok, json) = doctor_json(tmp.path(), &["--fix"]);
+    assert!(!ok, "the missing binary stays: {json}");
+    assert_eq!(json["data"]["errors"], 1);
+
+    let gitignore = std::fs::read_to_string(docs.join(".gitignore")).unwrap();
+    assert!(!gitignore.contains("gone"), "{gitignore}");
+    let lock = std::fs::read_to_string(docs.join("Ion.lock")).unwrap();
+    assert!(!lock.contains("removed"), "{lock}");
+    assert!(lock.contains("mytool"), "{lock}");
+    assert!(!docs.join(".claude/skills/gone").is_symlink());
+
+    let (_, json) = doctor_json(tmp.path(), &[]);
+    let problems = json["data"]["problems"].as_array().unwrap();
+    assert_eq!(problems.len(), 1, "{problems:?}");
+    assert_eq!(problems[0]["check"], "missing-binary");
+}
@@ -71,6 +71,85 @@ fn add_and_remove_local_skill() {
     assert!(!project.path().join(".agents/skills/test-skill").exists());
 }
 
+#[test]
+fn add_installs_required_skills_and_remove_prunes_them() {
+    let project = tempfile::tempdir().unwrap();
+    let skill_base = tempfile::tempdir().unwrap();
+    let write_skill = |name: &str, requires: &str| {
+        let dir = skill_base.path().join(name);
+        std::fs::create_dir(&dir).unwrap();
+        std::fs::write(
+            dir.join("SKILL.md"),
+            format!("---\nname: {name}\ndescription: Dependency test skill.\n{requires}---\n\n# Test\n\nDo things.\n"),
+        )
+        .unwrap();
+        dir
+    };
+    let helper = write_skill("helper", "");
+    let main = write_skill(
+        "main-skill",
+        &format!("requires:\n  - {}\n", helper.display()),
+    );
+
+    let output = ion_cmd()
+        .args(["--json", "add", &main.display().to_string()])
+        .current_dir(project.path())
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "add failed: {stdout}");
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json["data"]["dependencies"], serde_json::json!(["helper"]));
+    assert!(
+        project
+            .path()
+            .join(".agents/skills/helper/SKILL.md")
+            .exists()
+    );
+
+    // The dependency is locked but not declared
+    let manifest = std::fs::read_to_string(project.path().join("Ion.toml")).unwrap();
+    assert!(!manifest.contains("helper"), "{manifest}");
+    let lock = std::fs::read_to_string(project.path().join("Ion.lock")).unwrap();
+    assert!(lock.contains("required_by = [\"main-skill\"]"), "{lock}");
+
+    let output = ion_cmd()
+        .args(["lock", "--check"])
+        .current_dir(project.path())
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+
+    let output = ion_cmd()
+        .args(["--json", "remove", "main-skill"])
+        .current_dir(project.path())
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(
+        json["data"]["unused_dependencies"],
+        serde_json::json!(["helper"])
+    );
+
+    let output = ion_cmd()
+        .args(["--json", "remove", "main-skill", "--yes"])
+        .current_dir(project.path())
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "remove failed: {stdout}");
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json["data"]["pruned"], serde_json::json!(["helper"]));
+    assert!(!project.path().join(".agents/skills/helper").exists());
+    let lock = std::fs::read_to_string(project.path().join("Ion.lock")).unwrap();
+    assert!(!lock.contains("helper"), "{lock}");
+}
+
 #[test]
 fn link_local_skill_with_relative_path() {
     // Regression: `ion skill link <relative-path>` used to fail with
@@ -115,6 +194,64 @@ fn link_local_skill_with_relative_path() {
     );
 }
 
+#[test]
+fn link_watch_revalidates_and_refreshes_lock() {
+    use std::io::{BufRead, BufReader};
+
+    let project = tempfile::tempdir().unwrap();
+    let skill_path = project.path().join("skills/my-skill");
+    std::fs::create_dir_all(&skill_path).unwrap();
+    std::fs::write(
+        skill_path.join("SKILL.md"),
+        "---\nname: my-skill\ndescription: Watch mode test skill.\n---\n\n# Body\n",
+    )
+    .unwrap();
+    let output = ion_cmd()
+        .args(["skill", "link", "skills/my-skill"])
+        .current_dir(project.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    // Watch the Ion.toml entry by name
+    let mut child = ion_cmd()
+        .args(["--json", "skill", "link", "--watch", "my-skill"])
+        .current_dir(project.path())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let (tx, rx) = std::sync::mpsc::channel();
+    let stdout = child.stdout.take().unwrap();
+    std::thread::spawn(move || {
+        for line in BufReader::new(stdout).lines() {
+            let Ok(line) = line else { break };
+            let _ = tx.send(line);
+        }
+    });
+    let next_event = || {
+        let line = rx
+            .recv_timeout(std::time::Duration::from_secs(20))
+            .expect("watch produced no output");
+        serde_json::from_str::<serde_json::Value>(&line).unwrap()
+    };
+
+    let initial = next_event();
+    assert_eq!(initial["data"]["name"], "my-skill");
+    assert_eq!(initial["data"]["error_count"], 0);
+    let checksum = initial["data"]["checksum"].as_str().unwrap().to_string();
+
+    std::fs::write(skill_path.join("notes.md"), "More notes.\n").unwrap();
+    let changed = next_event();
+    child.kill().unwrap();
+    child.wait().unwrap();
+
+    assert_eq!(changed["data"]["changed"], serde_json::json!(["notes.md"]));
+    let new_checksum = changed["data"]["checksum"].as_str().unwrap();
+    assert_ne!(new_checksum, checksum);
+    let lock = std::fs::read_to_string(project.path().join("Ion.lock")).unwrap();
+    assert!(lock.contains(new_checksum), "{lock}");
+}
+
 #[test]
 fn install_from_manifest() {
     let project = tempfile::tempdir().unwrap();
@@ -953,3 +1090,913 @@ fn link_shows_hint_when_no_targets_configured() {
         "should show hint about ion init when no targets configured. stdout: {stdout}"
     );
 }
+
+#[test]
+fn global_skills_deploy_under_home() {
+    let home = tempfile::tempdir().unwrap();
+    let config_home = tempfile::tempdir().unwrap();
+    let project = tempfile::tempdir().unwrap();
+    let skill_base = tempfile::tempdir().unwrap();
+    let skill_path = skill_base.path().join("test-skill");
+    std::fs::create_dir(&skill_path).unwrap();
+    std::fs::write(
+        skill_path.join("SKILL.md"),
+        "---\nname: test-skill\ndescription: Integration test skill.\n---\n\n# Test\n\nDo things.\n",
+    )
+    .unwrap();
+    std::fs::create_dir_all(config_home.path().join("ion")).unwrap();
+    std::fs::write(
+        config_home.path().join("ion/Ion.toml"),
+        "[options.targets]\nclaude = \".claude/skills\"\n",
+    )
+    .unwrap();
+    let run = |args: &[&str]| {
+        let output = ion_cmd()
+            .args(args)
+            .env("HOME", home.path())
+            .env("XDG_CONFIG_HOME", config_home.path())
+            .current_dir(project.path())
+            .output()
+            .unwrap();
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(
+            output.status.success(),
+            "{args:?} failed: stdout={stdout}\nstderr={stderr}"
+        );
+        stdout
+    };
+    let skill = skill_path.display().to_string();
+
+    run(&["add", "--global", &skill]);
+    let manifest = std::fs::read_to_string(config_home.path().join("ion/Ion.toml")).unwrap();
+    assert!(
+        manifest.contains("test-skill"),
+        "global Ion.toml: {manifest}"
+    );
+    assert!(config_home.path().join("ion/Ion.lock").exists());
+    assert!(
+        home.path()
+            .join(".claude/skills/test-skill/SKILL.md")
+            .exists()
+    );
+    assert!(!project.path().join("Ion.toml").exists());
+    assert!(!home.path().join(".gitignore").exists());
+
+    // A project skill of the same name shadows the global one
+    let stdout = run(&["add", &skill]);
+    assert!(
+        stdout.contains("Shadows the global skill"),
+        "stdout: {stdout}"
+    );
+    let stdout = run(&["--json", "list"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let listed = json["data"].as_array().unwrap();
+    let shadows = |name: &str| {
+        listed
+            .iter()
+            .find(|s| s["name"] == name)
+            .map(|s| s["shadows_global"].clone())
+            .unwrap()
+    };
+    assert_eq!(shadows("test-skill"), true);
+    assert_eq!(shadows("ion-cli"), false);
+    let stdout = run(&["--json", "list", "--global"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json["data"][0]["name"], "test-skill");
+    assert_eq!(json["data"][0]["shadows_global"], false);
+
+    run(&["remove", "--global", "test-skill", "-y"]);
+    assert!(!home.path().join(".claude/skills/test-skill").exists());
+    assert!(project.path().join(".agents/skills/test-skill").exists());
+}
+
+#[test]
+fn skill_info_resolves_configured_host_shorthand() {
+    let project = tempfile::tempdir().unwrap();
+    let config_home = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(config_home.path().join("ion")).unwrap();
+    std::fs::write(
+        config_home.path().join("ion/config.toml"),
+        "[hosts.work]\nurl = \"https://git.example.com\"\nprotocol = \"ssh\"\n",
+    )
+    .unwrap();
+
+    let output = ion_cmd()
+        .args(["--json", "skill", "info", "work:team/skills//review"])
+        .env("XDG_CONFIG_HOME", config_home.path())
+        .current_dir(project.path())
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "info failed: {stdout}");
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json["data"]["source"], "work:team/skills");
+    assert_eq!(json["data"]["path"], "review");
+    assert_eq!(
+        json["data"]["git_url"],
+        "git@git.example.com:team/skills.git"
+    );
+    assert_eq!(
+        json["data"]["web_url"],
+        "https://git.example.com/team/skills"
+    );
+}
+
+/// A request received by [`serve`].
+struct Request {
+    method: String,
+    /// Path and query string.
+    target: String,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl Request {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// A response for [`serve`] to send.
+struct Response {
+    status: &'static str,
+    headers: Vec<(&'static str, String)>,
+    body: Vec<u8>,
+}
+
+impl Response {
+    fn new(status: &'static str) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: Vec::new(),
+        }
+    }
+
+    fn header(mut self, name: &'static str, value: impl Into<String>) -> Self {
+        self.headers.push((name, value.into()));
+        self
+    }
+
+    fn body(mut self, body: impl Into<Vec<u8>>) -> Self {
+        self.body = body.into();
+        self
+    }
+}
+
+/// Answer HTTP requests on a local port with `handle` for the rest of the
+/// test process and return the server's `host:port`.
+fn serve(mut handle: impl FnMut(Request) -> Response + Send + 'static) -> String {
+    use std::io::{BufRead, BufReader, Read};
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else { continue };
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            if reader.read_line(&mut request_line).is_err() {
+                continue;
+            }
+            let mut parts = request_line.split_whitespace();
+            let method = parts.next().unwrap_or_default().to_string();
+            let target = parts.next().unwrap_or_default().to_string();
+            let mut headers = Vec::new();
+            let mut line = String::new();
+            while reader.read_line(&mut line).is_ok_and(|n| n > 2) {
+                if let Some((name, value)) = line.split_once(':') {
+                    headers.push((name.trim().to_string(), value.trim().to_string()));
+                }
+                line.clear();
+            }
+            let mut request = Request {
+                method,
+                target,
+                headers,
+                body: Vec::new(),
+            };
+            if let Some(length) = request.header("content-length") {
+                request.body = vec![0; length.parse().unwrap()];
+                reader.read_exact(&mut request.body).unwrap();
+            }
+
+            let head_only = request.method == "HEAD";
+            let response = handle(request);
+            let mut head = format!("HTTP/1.1 {}\r\n", response.status);
+            for (name, value) in &response.headers {
+                head.push_str(&format!("{name}: {value}\r\n"));
+            }
+            head.push_str(&format!(
+                "Content-Length: {}\r\nConnection: close\r\n\r\n",
+                response.body.len()
+            ));
+            let _ = stream.write_all(head.as_bytes());
+            if !head_only {
+                let _ = stream.write_all(&response.body);
+            }
+        }
+    });
+    addr.to_string()
+}
+
+/// Serve `body` over HTTP on a local port for the rest of the test process.
+fn serve_bytes(body: Vec<u8>) -> String {
+    serve_shared(std::sync::Arc::new(std::sync::Mutex::new(body)))
+}
+
+/// Like [`serve_bytes`], but serves whatever `body` holds at request time.
+/// Responses carry an `ETag`, and a matching `If-None-Match` gets a 304.
+fn serve_shared(body: std::sync::Arc<std::sync::Mutex<Vec<u8>>>) -> String {
+    use std::hash::{Hash, Hasher};
+
+    let addr = serve(move |request| {
+        let body = body.lock().unwrap().clone();
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        body.hash(&mut hasher);
+        let etag = format!("\"{:x}\"", hasher.finish());
+        if request.header("if-none-match") == Some(etag.as_str()) {
+            return Response::new("304 Not Modified").header("ETag", etag);
+        }
+        Response::new("200 OK").header("ETag", etag).body(body)
+    });
+    format!("http://{addr}")
+}
+
+#[test]
+fn http_skill_content_change_requires_update() {
+    let project = tempfile::tempdir().unwrap();
+    let data = tempfile::tempdir().unwrap();
+    let skill = |description: &str| {
+        format!("---\nname: notes\ndescription: {description}\n---\n\n# Notes\n").into_bytes()
+    };
+    let body = std::sync::Arc::new(std::sync::Mutex::new(skill("Take notes.")));
+    let url = format!("{}/notes/SKILL.md", serve_shared(body.clone()));
+    std::fs::write(
+        project.path().join("Ion.toml"),
+        format!("[skills]\nnotes = {{ type = \"http\", source = \"{url}\" }}\n"),
+    )
+    .unwrap();
+
+    let ion = |args: &[&str]| {
+        ion_cmd()
+            .args(args)
+            .env("XDG_DATA_HOME", data.path())
+            .current_dir(project.path())
+            .output()
+            .unwrap()
+    };
+    let deployed =
+        || std::fs::read_to_string(project.path().join(".agents/skills/notes/SKILL.md")).unwrap();
+
+    let output = ion(&["add"]);
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let locked_checksum = || {
+        let lock = std::fs::read_to_string(project.path().join("Ion.lock")).unwrap();
+        let (_, notes) = lock.split_once("name = \"notes\"").unwrap();
+        notes
+            .lines()
+            .find_map(|line| line.strip_prefix("checksum = "))
+            .unwrap()
+            .to_string()
+    };
+    let checksum = locked_checksum();
+
+    // Unchanged content revalidates and reinstalls cleanly
+    let output = ion(&["add"]);
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // New content is refused until `ion update` accepts it
+    *body.lock().unwrap() = skill("Take better notes.");
+    let output = ion(&["add"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success());
+    assert!(stdout.contains("content changed"), "stdout: {stdout}");
+    assert!(deployed().contains("Take notes."));
+    assert_eq!(locked_checksum(), checksum);
+
+    let output = ion(&["add", "--locked"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(stderr.contains("ion update notes"), "stderr: {stderr}");
+
+    let output = ion(&["update", "notes"]);
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(deployed().contains("Take better notes."));
+    assert_ne!(locked_checksum(), checksum);
+
+    let output = ion(&["add"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success());
+    assert!(!stdout.contains("content changed"), "stdout: {stdout}");
+}
+
+#[test]
+fn http_skill_update_leaves_other_projects_alone() {
+    let first = tempfile::tempdir().unwrap();
+    let second = tempfile::tempdir().unwrap();
+    let data = tempfile::tempdir().unwrap();
+    let skill = |description: &str| {
+        format!("---\nname: notes\ndescription: {description}\n---\n\n# Notes\n").into_bytes()
+    };
+    let body = std::sync::Arc::new(std::sync::Mutex::new(skill("Take notes.")));
+    let url = format!("{}/notes/SKILL.md", serve_shared(body.clone()));
+
+    let ion = |project: &std::path::Path, args: &[&str]| {
+        let output = ion_cmd()
+            .args(args)
+            .env("XDG_DATA_HOME", data.path())
+            .current_dir(project)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "{args:?}: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    };
+    let deployed = |project: &std::path::Path| {
+        std::fs::read_to_string(project.join(".agents/skills/notes/SKILL.md")).unwrap()
+    };
+    for project in [first.path(), second.path()] {
+        ion(project, &["add", &url]);
+    }
+
+    *body.lock().unwrap() = skill("Take better notes.");
+    ion(first.path(), &["update", "notes"]);
+    assert!(deployed(first.path()).contains("Take better notes."));
+    assert!(deployed(second.path()).contains("Take notes."));
+
+    // The second project still installs what it locked
+    ion(second.path(), &["add"]);
+    assert!(deployed(second.path()).contains("Take notes."));
+}
+
+#[test]
+fn http_skill_uses_configured_credentials() {
+    let project = tempfile::tempdir().unwrap();
+    let data = tempfile::tempdir().unwrap();
+    let config_home = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(config_home.path().join("ion")).unwrap();
+    std::fs::write(
+        config_home.path().join("ion/config.toml"),
+        "[credentials.\"127.0.0.1\"]\nenv = \"ION_TEST_TOKEN\"\n",
+    )
+    .unwrap();
+
+    // Only serve the skill to requests carrying the token
+    let addr = serve(|request| {
+        if request.header("authorization") != Some("Bearer s3cret-token") {
+            return Response::new("401 Unauthorized");
+        }
+        Response::new("200 OK")
+            .body("---\nname: private\ndescription: A private skill.\n---\n\n# Private\n")
+    });
+    let url = format!("http://{addr}/private/SKILL.md");
+
+    let ion = |token: Option<&str>| {
+        let mut cmd = ion_cmd();
+        cmd.args(["--json", "add", &url])
+            .env("XDG_DATA_HOME", data.path())
+            .env("XDG_CONFIG_HOME", config_home.path())
+            .env_remove("ION_TEST_TOKEN")
+            .current_dir(project.path());
+        if let Some(token) = token {
+            cmd.env("ION_TEST_TOKEN", token);
+        }
+        cmd.output().unwrap()
+    };
+
+    // Without the variable the request goes out anonymously
+    let output = ion(None);
+    assert!(!output.status.success(), "add should fail without a token");
+    assert!(
+        String::from_utf8_lossy(&output.stdout).contains("401"),
+        "stdout: {}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+
+    let output = ion(Some("s3cret-token"));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "stdout: {stdout}\nstderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(!stdout.contains("s3cret-token"));
+    for file in ["Ion.toml", "Ion.lock"] {
+        let content = std::fs::read_to_string(project.path().join(file)).unwrap();
+        assert!(!content.contains("s3cret-token"), "{file} leaks the token");
+    }
+    assert!(
+        project
+            .path()
+            .join(".agents/skills/private/SKILL.md")
+            .exists()
+    );
+}
+
+/// Write a global config with a `[credentials]` entry for the loopback host
+/// reading its token from `ION_TEST_TOKEN`, and return its config home.
+fn loopback_credentials() -> tempfile::TempDir {
+    let config_home = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(config_home.path().join("ion")).unwrap();
+    std::fs::write(
+        config_home.path().join("ion/config.toml"),
+        "[credentials.\"127.0.0.1\"]\nenv = \"ION_TEST_TOKEN\"\nusername = \"me\"\n",
+    )
+    .unwrap();
+    config_home
+}
+
+#[test]
+fn git_fetches_send_configured_credentials() {
+    let project = tempfile::tempdir().unwrap();
+    let data = tempfile::tempdir().unwrap();
+    let config_home = loopback_credentials();
+    let seen = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let addr = serve({
+        let seen = seen.clone();
+        move |request| {
+            seen.lock()
+                .unwrap()
+                .push(request.header("authorization").map(str::to_string));
+            Response::new("404 Not Found")
+        }
+    });
+
+    let output = ion_cmd()
+        .args(["add", &format!("http://{addr}/team/private.git")])
+        .env("XDG_DATA_HOME", data.path())
+        .env("XDG_CONFIG_HOME", config_home.path())
+        .env("ION_TEST_TOKEN", "s3cret-token")
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .current_dir(project.path())
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+
+    // base64 of "me:s3cret-token"
+    let expected = "Basic bWU6czNjcmV0LXRva2Vu";
+    let seen = seen.lock().unwrap();
+    assert!(!seen.is_empty(), "git never contacted the server");
+    assert!(
+        seen.iter()
+            .all(|header| header.as_deref() == Some(expected)),
+        "{seen:?}"
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!stderr.contains("s3cret-token"), "stderr leaks the token");
+}
+
+#[test]
+fn add_archive_skill_verifies_sha256() {
+    let project = tempfile::tempdir().unwrap();
+    let data = tempfile::tempdir().unwrap();
+    let build = tempfile::tempdir().unwrap();
+    let skill_dir = build.path().join("review-1.0/review");
+    std::fs::create_dir_all(&skill_dir).unwrap();
+    std::fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: review\ndescription: Review code from an archive.\n---\n\n# Review\n",
+    )
+    .unwrap();
+    let status = Command::new("tar")
+        .args(["czf", "review.tar.gz", "review-1.0"])
+        .current_dir(build.path())
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let bytes = std::fs::read(build.path().join("review.tar.gz")).unwrap();
+    let digest = String::from_utf8(
+        Command::new("sha256sum")
+            .arg(build.path().join("review.tar.gz"))
+            .output()
+            .unwrap()
+            .stdout,
+    )
+    .unwrap()
+    .split_whitespace()
+    .next()
+    .unwrap()
+    .to_string();
+    let url = format!("{}/review.tar.gz", serve_bytes(bytes));
+
+    let write_manifest = |sha256: &str| {
+        std::fs::write(
+            project.path().join("Ion.toml"),
+            format!(
+                "[skills]\nreview = {{ type = \"archive\", source = \"{url}\", sha256 = \"{sha256}\", strip-components = 1, path = \"review\" }}\n"
+            ),
+        )
+        .unwrap();
+    };
+
+    // A mismatched digest aborts the install
+    write_manifest(&"0".repeat(64));
+    let output = ion_cmd()
+        .args(["add"])
+        .env("XDG_DATA_HOME", data.path())
+        .current_dir(project.path())
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(stderr.contains("sha256 mismatch"), "stderr: {stderr}");
+    assert!(!project.path().join(".agents/skills/review").exists());
+
+    write_manifest(&digest);
+    let output = ion_cmd()
+        .args(["add"])
+        .env("XDG_DATA_HOME", data.path())
+        .current_dir(project.path())
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {stderr}");
+    assert!(
+        project
+            .path()
+            .join(".agents/skills/review/SKILL.md")
+            .exists()
+    );
+    let lock = std::fs::read_to_string(project.path().join("Ion.lock")).unwrap();
+    assert!(lock.contains("kind = \"archive\""), "{lock}");
+    assert!(lock.contains(&format!("sha256 = \"{digest}\"")), "{lock}");
+
+    // An archive has no newer release to find, so update says so
+    let output = ion_cmd()
+        .args(["--json", "update"])
+        .env("XDG_DATA_HOME", data.path())
+        .current_dir(project.path())
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "stdout: {stdout}");
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json["data"]["skipped"][0]["name"], "review");
+    assert_eq!(json["data"]["skipped"][0]["reason"], "archive");
+}
+
+/// Run a minimal in-memory OCI registry on a local port for the rest of the
+/// test process and return its `host:port`. It supports what `ion skill push`
+/// and pulls need: blob uploads, blob and manifest fetches, and manifest puts.
+fn serve_registry() -> String {
+    serve_registry_with(|_| None)
+}
+
+/// Like [`serve_registry`], but every request is first passed to
+/// `challenge`; when it returns one, the request gets a 401 with that
+/// `WWW-Authenticate` challenge.
+fn serve_registry_with(challenge: impl Fn(&Request) -> Option<String> + Send + 'static) -> String {
+    use std::collections::HashMap;
+
+    let mut blobs: HashMap<String, Vec<u8>> = HashMap::new();
+    let mut manifests: HashMap<String, Vec<u8>> = HashMap::new();
+    let mut uploads = 0;
+    serve(move |request| {
+        if let Some(challenge) = challenge(&request) {
+            return Response::new("401 Unauthorized").header("WWW-Authenticate", challenge);
+        }
+        let (path, query) = request
+            .target
+            .split_once('?')
+            .unwrap_or((&request.target, ""));
+        let digest_of = |bytes: &[u8]| {
+            format!(
+                "sha256:{}",
+                ion_skill::installer::cache_key(std::str::from_utf8(bytes).unwrap())
+            )
+        };
+        match (request.method.as_str(), path) {
+            ("POST", p) if p.ends_with("/blobs/uploads/") => {
+                uploads += 1;
+                Response::new("202 Accepted").header("Location", format!("/upload/{uploads}"))
+            }
+            ("PUT", p) if p.starts_with("/upload/") => {
+                let digest = query.strip_prefix("digest=").unwrap().replace("%3A", ":");
+                blobs.insert(digest, request.body.clone());
+                Response::new("201 Created")
+            }
+            (method, p) if p.contains("/blobs/") => {
+                let digest = p.rsplit('/').next().unwrap();
+                match blobs.get(digest) {
+                    Some(blob) if method == "GET" => Response::new("200 OK").body(blob.clone()),
+                    Some(_) => Response::new("200 OK"),
+                    None => Response::new("404 Not Found"),
+                }
+            }
+            ("PUT", p) if p.contains("/manifests/") => {
+                manifests.insert(digest_of(&request.body), request.body.clone());
+                manifests.insert(p.to_string(), request.body.clone());
+                Response::new("201 Created")
+            }
+            ("GET", p) if p.contains("/manifests/") => {
+                let reference = p.rsplit('/').next().unwrap();
+                match manifests.get(p).or_else(|| manifests.get(reference)) {
+                    Some(manifest) => Response::new("200 OK")
+                        .header("Content-Type", "application/vnd.oci.image.manifest.v1+json")
+                        .body(manifest.clone()),
+                    None => Response::new("404 Not Found"),
+                }
+            }
+            _ => Response::new("404 Not Found"),
+        }
+    })
+}
+
+#[test]
+fn oci_registries_accept_configured_credentials() {
+    let config_home = loopback_credentials();
+    // base64 of "me:s3cret-token"
+    let basic = "Basic bWU6czNjcmV0LXRva2Vu";
+    let token_service = serve(move |request| {
+        if request.header("authorization") == Some(basic) {
+            Response::new("200 OK").body(r#"{"token":"registry-token"}"#)
+        } else {
+            Response::new("401 Unauthorized")
+        }
+    });
+    let registries = [
+        (basic.to_string(), r#"Basic realm="registry""#.to_string()),
+        (
+            "Bearer registry-token".to_string(),
+            format!(r#"Bearer realm="http://{token_service}/token",service="registry""#),
+        ),
+    ];
+
+    for auth in registries {
+        let scheme = auth.1.split(' ').next().unwrap().to_string();
+        let (authorization, challenge) = auth;
+        let registry = serve_registry_with(move |request| {
+            (request.header("authorization") != Some(authorization.as_str()))
+                .then(|| challenge.clone())
+        });
+        let reference = format!("oci://{registry}/team/review:1.0");
+        let project = tempfile::tempdir().unwrap();
+        let data = tempfile::tempdir().unwrap();
+        let skill = tempfile::tempdir().unwrap();
+        std::fs::write(
+            skill.path().join("SKILL.md"),
+            "---\nname: review\ndescription: Review code from a private registry.\n---\n\n# Review\n",
+        )
+        .unwrap();
+
+        let ion = |dir: &std::path::Path, args: &[&str]| {
+            ion_cmd()
+                .args(args)
+                .env("XDG_DATA_HOME", data.path())
+                .env("XDG_CONFIG_HOME", config_home.path())
+                .env("ION_TEST_TOKEN", "s3cret-token")
+                .current_dir(dir)
+                .output()
+                .unwrap()
+        };
+        let output = ion(skill.path(), &["skill", "push", ".", &reference]);
+        assert!(
+            output.status.success(),
+            "{scheme} push: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let output = ion(project.path(), &["add", &reference]);
+        assert!(
+            output.status.success(),
+            "{scheme} add: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        assert!(
+            project
+                .path()
+                .join(".agents/skills/review/SKILL.md")
+                .is_file(),
+            "{scheme}"
+        );
+    }
+}
+
+#[test]
+fn oci_push_fetches_a_token_for_each_scope() {
+    // The token service issues one token per scope, and the registry only
+    // lets writes through with the `pull,push` one.
+    let token_service = serve(|request| {
+        let scope = request
+            .target
+            .split(['?', '&'])
+            .find_map(|param| param.strip_prefix("scope="))
+            .unwrap_or_default()
+            .replace("%3A", ":")
+            .replace("%2C", ",")
+            .replace("%2F", "/");
+        Response::new("200 OK").body(format!(r#"{{"token":"{scope}"}}"#))
+    });
+    let registry = serve_registry_with(move |request| {
+        let scope = match request.method.as_str() {
+            "GET" | "HEAD" => "repository:team/review:pull",
+            _ => "repository:team/review:pull,push",
+        };
+        let granted = request
+            .header("authorization")
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .is_some_and(|token| token == scope || scope.ends_with(":pull"));
+        (!granted).then(|| {
+            format!(
+                r#"Bearer realm="http://{token_service}/token",service="registry",scope="{scope}""#
+            )
+        })
+    });
+    let reference = format!("oci://{registry}/team/review:1.0");
+    let project = tempfile::tempdir().unwrap();
+    let data = tempfile::tempdir().unwrap();
+    let skill = tempfile::tempdir().unwrap();
+    std::fs::write(
+        skill.path().join("SKILL.md"),
+        "---\nname: review\ndescription: Review code from a scoped registry.\n---\n\n# Review\n",
+    )
+    .unwrap();
+
+    let ion = |dir: &std::path::Path, args: &[&str]| {
+        ion_cmd()
+            .args(args)
+            .env("XDG_DATA_HOME", data.path())
+            .current_dir(dir)
+            .output()
+            .unwrap()
+    };
+    let output = ion(skill.path(), &["skill", "push", ".", &reference]);
+    assert!(
+        output.status.success(),
+        "push: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let output = ion(project.path(), &["add", &reference]);
+    assert!(
+        output.status.success(),
+        "add: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(
+        project
+            .path()
+            .join(".agents/skills/review/SKILL.md")
+            .is_file()
+    );
+}
+
+#[test]
+fn push_and_install_oci_skill() {
+    let project = tempfile::tempdir().unwrap();
+    let data = tempfile::tempdir().unwrap();
+    let skill = tempfile::tempdir().unwrap();
+    let write_skill = |description: &str| {
+        std::fs::write(
+            skill.path().join("SKILL.md"),
+            format!("---\nname: review\ndescription: {description}\n---\n\n# Review\n"),
+        )
+        .unwrap();
+    };
+    write_skill("Review code from a registry.");
+    std::fs::create_dir(skill.path().join("scripts")).unwrap();
+    std::fs::write(skill.path().join("scripts/run.sh"), "echo hi\n").unwrap();
+    let reference = format!("oci://{}/team/review:1.0", serve_registry());
+
+    let ion = |dir: &std::path::Path, args: &[&str]| {
+        ion_cmd()
+            .args(args)
+            .env("XDG_DATA_HOME", data.path())
+            .current_dir(dir)
+            .output()
+            .unwrap()
+    };
+    let push = || {
+        let output = ion(
+            skill.path(),
+            &["--json", "skill", "push", ".", reference.as_str()],
+        );
+        assert!(
+            output.status.success(),
+            "stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        json["data"]["digest"].as_str().unwrap().to_string()
+    };
+    let locked_digest = || {
+        let lock = std::fs::read_to_string(project.path().join("Ion.lock")).unwrap();
+        let (_, review) = lock.split_once("name = \"review\"").unwrap();
+        assert!(review.contains("kind = \"oci\""), "lock: {lock}");
+        review
+            .lines()
+            .find_map(|line| line.strip_prefix("digest = "))
+            .unwrap()
+            .trim_matches('"')
+            .to_string()
+    };
+    let deployed =
+        || std::fs::read_to_string(project.path().join(".agents/skills/review/SKILL.md")).unwrap();
+
+    let first = push();
+    let output = ion(project.path(), &["add", &reference]);
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(
+        project
+            .path()
+            .join(".agents/skills/review/scripts/run.sh")
+            .is_file()
+    );
+    assert_eq!(locked_digest(), first);
+    let manifest = std::fs::read_to_string(project.path().join("Ion.toml")).unwrap();
+    assert!(
+        manifest.contains(&format!("review = \"{reference}\"")),
+        "Ion.toml: {manifest}"
+    );
+
+    // Moving the tag doesn't change what the lock installs
+    write_skill("Review code from a registry, carefully.");
+    let second = push();
+    assert_ne!(first, second);
+    let output = ion(project.path(), &["add"]);
+    assert!(output.status.success());
+    assert!(deployed().contains("from a registry."));
+
+    let output = ion(project.path(), &["update", "review"]);
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(deployed().contains("carefully"));
+    assert_eq!(locked_digest(), second);
+}
+
+#[test]
+fn add_renders_skills_for_native_format_targets() {
+    let project = tempfile::tempdir().unwrap();
+    let skill_base = tempfile::tempdir().unwrap();
+    let skill_path = skill_base.path().join("rendered-skill");
+    std::fs::create_dir(&skill_path).unwrap();
+    std::fs::write(
+        skill_path.join("SKILL.md"),
+        "---\nname: rendered-skill\ndescription: Rendered for Cursor.\nmetadata:\n  globs: \"*.rs\"\n---\n\n# Rules\n\nBe precise.\n",
+    )
+    .unwrap();
+    std::fs::write(
+        project.path().join("Ion.toml"),
+        "[skills]\n\n[options.targets]\ncursor = \".cursor/rules\"\n\n[options.target-format]\ncursor = \"cursor\"\n",
+    )
+    .unwrap();
+
+    let output = ion_cmd()
+        .args(["add", &skill_path.display().to_string()])
+        .current_dir(project.path())
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "add failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let rule = project.path().join(".cursor/rules/rendered-skill.mdc");
+    let contents = std::fs::read_to_string(&rule).unwrap();
+    assert!(contents.starts_with("---\ndescription: \"Rendered for Cursor.\"\nglobs: *.rs\n"));
+    assert!(contents.ends_with("Be precise.\n"));
+    assert!(!project.path().join(".cursor/rules/rendered-skill").exists());
+
+    let lock = std::fs::read_to_string(project.path().join("Ion.lock")).unwrap();
+    assert!(
+        lock.contains("rendered = [\".cursor/rules/rendered-skill.mdc\"]"),
+        "{lock}"
+    );
+
+    let output = ion_cmd()
+        .args(["remove", "rendered-skill", "--yes"])
+        .current_dir(project.path())
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "remove failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(!rule.exists());
+}
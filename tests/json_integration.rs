@@ -165,6 +165,44 @@ fn json_skill_list_empty_project() {
     assert_eq!(parsed["success"], true);
 }
 
+#[test]
+fn json_skill_list_reports_selected_targets() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("Ion.toml"),
+        r#"[skills]
+review = { type = "local" }
+deploy-prod = { type = "local" }
+
+[options.targets]
+claude = ".claude/skills"
+
+[options.targets.cursor]
+path = ".cursor/skills"
+exclude = ["deploy-prod"]
+"#,
+    )
+    .unwrap();
+    let output = ion()
+        .args(["--json", "skill", "list"])
+        .current_dir(dir.path())
+        .env("XDG_CONFIG_HOME", dir.path().join("config"))
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let parsed: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid JSON");
+    let skills = parsed["data"].as_array().unwrap();
+    let targets = |name: &str| {
+        skills
+            .iter()
+            .find(|s| s["name"] == name)
+            .map(|s| s["targets"].clone())
+            .unwrap()
+    };
+    assert_eq!(targets("review"), serde_json::json!(["claude", "cursor"]));
+    assert_eq!(targets("deploy-prod"), serde_json::json!(["claude"]));
+}
+
 #[test]
 fn json_gc_dry_run() {
     let output = ion()
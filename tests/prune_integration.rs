@@ -0,0 +1,189 @@
+use std::process::Command;
+
+fn ion_cmd() -> Command {
+    Command::new(env!("CARGO_BIN_EXE_ion"))
+}
+
+/// Create a git repo at `path` with a valid SKILL.md.
+fn create_upstream_repo(path: &std::path::Path, skill_name: &str) {
+    std::fs::create_dir_all(path).unwrap();
+    std::fs::write(
+        path.join("SKILL.md"),
+        format!("---\nname: {skill_name}\ndescription: A test skill for prune.\n---\n\n# Test\n"),
+    )
+    .unwrap();
+
+    for args in [
+        &["init"][..],
+        &["config", "user.email", "test@test.com"][..],
+        &["config", "user.name", "Test"][..],
+        &["add", "."][..],
+        &["commit", "-m", "initial"][..],
+    ] {
+        Command::new("git")
+            .args(args)
+            .current_dir(path)
+            .output()
+            .unwrap();
+    }
+}
+
+fn run_ion(project: &std::path::Path, args: &[&str]) -> String {
+    let output = ion_cmd().args(args).current_dir(project).output().unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    assert!(
+        output.status.success(),
+        "{args:?} failed: stdout={stdout}\nstderr={}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    stdout
+}
+
+/// Install `kept` and `dropped` into a new project, then drop `dropped`
+/// from Ion.toml by hand. `options` is prepended to Ion.toml. Returns the
+/// project dir.
+fn setup_project(tmp: &std::path::Path, options: &str) -> std::path::PathBuf {
+    let project = tmp.join("project");
+    std::fs::create_dir_all(&project).unwrap();
+    let mut skills = String::new();
+    for name in ["kept", "dropped"] {
+        let upstream = tmp.join(name);
+        create_upstream_repo(&upstream, name);
+        skills.push_str(&format!(
+            "{name} = {{ type = \"git\", source = \"{}\" }}\n",
+            upstream.display()
+        ));
+    }
+    std::fs::write(
+        project.join("Ion.toml"),
+        format!("{options}[options.targets]\nclaude = \".claude/skills\"\n\n[skills]\n{skills}"),
+    )
+    .unwrap();
+    run_ion(&project, &["add"]);
+    assert!(project.join(".claude/skills/dropped").exists());
+
+    let manifest = std::fs::read_to_string(project.join("Ion.toml")).unwrap();
+    let edited: String = manifest
+        .lines()
+        .filter(|line| !line.starts_with("dropped"))
+        .map(|line| format!("{line}\n"))
+        .collect();
+    std::fs::write(project.join("Ion.toml"), edited).unwrap();
+    project
+}
+
+#[test]
+fn prune_dry_run_then_remove() {
+    let tmp = tempfile::tempdir().unwrap();
+    let project = setup_project(tmp.path(), "");
+    // The user's own link is never pruned
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(tmp.path().join("kept"), project.join(".claude/skills/mine"))
+        .unwrap();
+    // Nor is a hand-made link to a skills-dir entry Ion doesn't manage
+    std::fs::create_dir_all(project.join(".agents/skills/handmade")).unwrap();
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(
+        "../../.agents/skills/handmade",
+        project.join(".claude/skills/handmade"),
+    )
+    .unwrap();
+
+    let stdout = run_ion(&project, &["--json", "prune", "--dry-run"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json["data"]["dry_run"], true);
+    let orphans = json["data"]["orphans"].as_array().unwrap();
+    assert!(
+        orphans.iter().all(|o| o["skill"] == "dropped"),
+        "{orphans:?}"
+    );
+    let paths: Vec<(&str, &str)> = orphans
+        .iter()
+        .map(|o| (o["kind"].as_str().unwrap(), o["path"].as_str().unwrap()))
+        .collect();
+    assert!(
+        paths.contains(&("link", ".agents/skills/dropped")),
+        "{paths:?}"
+    );
+    assert!(
+        paths.contains(&("link", ".claude/skills/dropped")),
+        "{paths:?}"
+    );
+    assert!(
+        paths.contains(&("gitignore", ".claude/skills/dropped")),
+        "{paths:?}"
+    );
+    assert!(project.join(".claude/skills/dropped").exists());
+
+    let stdout = run_ion(&project, &["prune"]);
+    assert!(stdout.contains("Removed"), "{stdout}");
+    assert!(!project.join(".claude/skills/dropped").is_symlink());
+    assert!(!project.join(".agents/skills/dropped").is_symlink());
+    assert!(project.join(".claude/skills/kept").exists());
+    #[cfg(unix)]
+    assert!(project.join(".claude/skills/mine").is_symlink());
+    #[cfg(unix)]
+    assert!(project.join(".claude/skills/handmade").is_symlink());
+    let gitignore = std::fs::read_to_string(project.join(".gitignore")).unwrap();
+    assert!(!gitignore.contains("dropped"), "{gitignore}");
+    assert!(gitignore.contains(".claude/skills/kept"), "{gitignore}");
+
+    let stdout = run_ion(&project, &["prune"]);
+    assert!(stdout.contains("Nothing to prune."), "{stdout}");
+}
+
+#[test]
+fn add_prunes_hand_removed_skills() {
+    let tmp = tempfile::tempdir().unwrap();
+    let project = setup_project(tmp.path(), "");
+    let extra = tmp.path().join("extra");
+    create_upstream_repo(&extra, "extra");
+
+    let stdout = run_ion(&project, &["--json", "add", &extra.display().to_string()]);
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let pruned = json["data"]["pruned"].as_array().unwrap();
+    assert!(!pruned.is_empty());
+    assert!(pruned.iter().all(|o| o["skill"] == "dropped"), "{pruned:?}");
+    assert!(!project.join(".claude/skills/dropped").is_symlink());
+    assert!(project.join(".claude/skills/extra").exists());
+}
+
+#[test]
+fn prune_removes_copies() {
+    let tmp = tempfile::tempdir().unwrap();
+    let project = setup_project(tmp.path(), "[options]\ndeploy = \"copy\"\n\n");
+    assert!(!project.join(".claude/skills/dropped").is_symlink());
+    // A copy the managed section doesn't list, but whose content still
+    // matches the lock, is Ion's too
+    let mut gitignore = std::fs::read_to_string(project.join(".gitignore")).unwrap();
+    assert!(
+        gitignore.contains(".agents/skills/dropped\n"),
+        "{gitignore}"
+    );
+    gitignore = gitignore.replace(".agents/skills/dropped\n", "");
+    std::fs::write(project.join(".gitignore"), gitignore).unwrap();
+    // A directory of the user's own is never pruned
+    std::fs::create_dir_all(project.join(".claude/skills/handmade")).unwrap();
+
+    let stdout = run_ion(&project, &["--json", "prune"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let orphans = json["data"]["orphans"].as_array().unwrap();
+    let paths: Vec<(&str, &str)> = orphans
+        .iter()
+        .map(|o| (o["kind"].as_str().unwrap(), o["path"].as_str().unwrap()))
+        .collect();
+    for expected in [
+        ("copy", ".agents/skills/dropped"),
+        ("copy", ".claude/skills/dropped"),
+        ("gitignore", ".claude/skills/dropped"),
+    ] {
+        assert!(paths.contains(&expected), "{paths:?}");
+    }
+    assert!(!project.join(".agents/skills/dropped").exists());
+    assert!(!project.join(".claude/skills/dropped").exists());
+    assert!(project.join(".claude/skills/kept/SKILL.md").is_file());
+    assert!(project.join(".claude/skills/handmade").is_dir());
+    let gitignore = std::fs::read_to_string(project.join(".gitignore")).unwrap();
+    assert!(!gitignore.contains("dropped"), "{gitignore}");
+    assert!(gitignore.contains(".claude/skills/kept"), "{gitignore}");
+}
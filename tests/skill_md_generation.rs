@@ -113,6 +113,7 @@ fn json_remove_yes_structure() {
 
     assert_eq!(out["success"], true);
     assert!(out["data"]["removed"].is_array());
+    assert!(out["data"]["pruned"].is_array());
 }
 
 #[test]
@@ -154,6 +155,44 @@ fn json_validate_structure() {
     assert!(out["data"]["total_infos"].is_number());
 }
 
+#[test]
+fn json_verify_structure() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("Ion.toml"), "[skills]\n").unwrap();
+
+    let out = parse(&capture_json(&["--json", "verify"], dir.path()));
+
+    assert_eq!(out["success"], true);
+    assert!(out["data"]["skills"].is_array());
+    assert!(out["data"]["verified"].is_number());
+    assert!(out["data"]["failed"].is_number());
+}
+
+#[test]
+fn json_outdated_structure() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("Ion.toml"), "[skills]\n").unwrap();
+
+    let out = parse(&capture_json(&["--json", "outdated"], dir.path()));
+
+    assert_eq!(out["success"], true);
+    assert!(out["data"]["skills"].is_array());
+    assert!(out["data"]["outdated"].is_number());
+    assert!(out["data"]["failed"].is_number());
+}
+
+#[test]
+fn json_lock_check_structure() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("Ion.toml"), "[skills]\n").unwrap();
+
+    let out = parse(&capture_json(&["--json", "lock", "--check"], dir.path()));
+
+    assert_eq!(out["success"], true);
+    assert_eq!(out["data"]["up_to_date"], true);
+    assert!(out["data"]["drift"].is_array());
+}
+
 #[test]
 fn json_config_list_structure() {
     let dir = tempfile::tempdir().unwrap();
@@ -228,6 +267,7 @@ fn json_gc_dry_run_structure() {
     assert_eq!(out["success"], true);
     assert_eq!(out["data"]["dry_run"], true);
     assert!(out["data"]["removed"].is_array());
+    assert!(out["data"]["snapshots"].is_array());
 }
 
 #[test]
